@@ -1,24 +1,29 @@
 use crate::{
     implementation::{
         data_representation_builtin_endpoints::parameter_id_values::{
-            PID_DEADLINE, PID_DESTINATION_ORDER, PID_DURABILITY, PID_ENDPOINT_GUID, PID_GROUP_DATA,
-            PID_HISTORY, PID_LATENCY_BUDGET, PID_LIFESPAN, PID_LIVELINESS, PID_OWNERSHIP,
-            PID_PARTICIPANT_GUID, PID_PARTITION, PID_PRESENTATION, PID_RELIABILITY,
-            PID_RESOURCE_LIMITS, PID_TIME_BASED_FILTER, PID_TOPIC_DATA, PID_TOPIC_NAME,
-            PID_TRANSPORT_PRIORITY, PID_TYPE_NAME, PID_USER_DATA,
+            PID_DATA_REPRESENTATION, PID_DEADLINE, PID_DESTINATION_ORDER, PID_DURABILITY,
+            PID_ENDPOINT_GUID, PID_GROUP_DATA, PID_HISTORY, PID_LATENCY_BUDGET, PID_LIFESPAN,
+            PID_LIVELINESS, PID_OWNERSHIP, PID_PARTICIPANT_GUID, PID_PARTITION, PID_PRESENTATION,
+            PID_RELIABILITY, PID_RESOURCE_LIMITS, PID_TIME_BASED_FILTER, PID_TOPIC_DATA,
+            PID_TOPIC_NAME, PID_TRANSPORT_PRIORITY, PID_TYPE_CONSISTENCY_ENFORCEMENT,
+            PID_TYPE_INFORMATION, PID_TYPE_NAME, PID_USER_DATA,
         },
         parameter_list_serde::parameter::{Parameter, ParameterWithDefault},
     },
     infrastructure::qos_policy::{
-        DeadlineQosPolicy, DestinationOrderQosPolicy, DurabilityQosPolicy, GroupDataQosPolicy,
-        HistoryQosPolicy, LatencyBudgetQosPolicy, LifespanQosPolicy, LivelinessQosPolicy,
-        OwnershipQosPolicy, PartitionQosPolicy, PresentationQosPolicy, ReliabilityQosPolicy,
-        ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, TopicDataQosPolicy,
-        TransportPriorityQosPolicy, UserDataQosPolicy,
+        DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
+        DurabilityQosPolicy, GroupDataQosPolicy, HistoryQosPolicy, LatencyBudgetQosPolicy,
+        LifespanQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy, PartitionQosPolicy,
+        PresentationQosPolicy, ReliabilityQosPolicy, ResourceLimitsQosPolicy,
+        TimeBasedFilterQosPolicy, TopicDataQosPolicy, TransportPriorityQosPolicy,
+        TypeConsistencyEnforcementQosPolicy, UserDataQosPolicy,
         DEFAULT_RELIABILITY_QOS_POLICY_DATA_READER_AND_TOPICS,
         DEFAULT_RELIABILITY_QOS_POLICY_DATA_WRITER,
     },
-    topic_definition::type_support::{DdsHasKey, DdsRepresentation, Representation},
+    topic_definition::{
+        type_object::{TypeInformation, XTypesParameterValue},
+        type_support::{DdsHasKey, DdsRepresentation, Representation},
+    },
 };
 
 #[derive(
@@ -103,6 +108,7 @@ pub struct TopicBuiltinTopicData {
     resource_limits: ParameterWithDefault<PID_RESOURCE_LIMITS, ResourceLimitsQosPolicy>,
     ownership: ParameterWithDefault<PID_OWNERSHIP, OwnershipQosPolicy>,
     topic_data: ParameterWithDefault<PID_TOPIC_DATA, TopicDataQosPolicy>,
+    representation: ParameterWithDefault<PID_DATA_REPRESENTATION, DataRepresentationQosPolicy>,
 }
 
 impl TopicBuiltinTopicData {
@@ -123,6 +129,7 @@ impl TopicBuiltinTopicData {
         resource_limits: ResourceLimitsQosPolicy,
         ownership: OwnershipQosPolicy,
         topic_data: TopicDataQosPolicy,
+        representation: DataRepresentationQosPolicy,
     ) -> Self {
         Self {
             key: key.into(),
@@ -140,6 +147,7 @@ impl TopicBuiltinTopicData {
             resource_limits: resource_limits.into(),
             ownership: ownership.into(),
             topic_data: topic_data.into(),
+            representation: representation.into(),
         }
     }
 
@@ -202,6 +210,10 @@ impl TopicBuiltinTopicData {
     pub fn topic_data(&self) -> &TopicDataQosPolicy {
         self.topic_data.as_ref()
     }
+
+    pub fn representation(&self) -> &DataRepresentationQosPolicy {
+        self.representation.as_ref()
+    }
 }
 
 impl DdsHasKey for TopicBuiltinTopicData {
@@ -266,6 +278,9 @@ pub struct PublicationBuiltinTopicData {
     partition: ParameterWithDefault<PID_PARTITION, PartitionQosPolicy>,
     topic_data: ParameterWithDefault<PID_TOPIC_DATA, TopicDataQosPolicy>,
     group_data: ParameterWithDefault<PID_GROUP_DATA, GroupDataQosPolicy>,
+    representation: ParameterWithDefault<PID_DATA_REPRESENTATION, DataRepresentationQosPolicy>,
+    type_information:
+        ParameterWithDefault<PID_TYPE_INFORMATION, XTypesParameterValue<TypeInformation>>,
 }
 
 impl PublicationBuiltinTopicData {
@@ -288,6 +303,8 @@ impl PublicationBuiltinTopicData {
         partition: PartitionQosPolicy,
         topic_data: TopicDataQosPolicy,
         group_data: GroupDataQosPolicy,
+        representation: DataRepresentationQosPolicy,
+        type_information: Option<TypeInformation>,
     ) -> Self {
         Self {
             key: key.into(),
@@ -307,6 +324,8 @@ impl PublicationBuiltinTopicData {
             partition: partition.into(),
             topic_data: topic_data.into(),
             group_data: group_data.into(),
+            representation: representation.into(),
+            type_information: XTypesParameterValue::new(type_information).into(),
         }
     }
 
@@ -377,6 +396,14 @@ impl PublicationBuiltinTopicData {
     pub fn group_data(&self) -> &GroupDataQosPolicy {
         self.group_data.as_ref()
     }
+
+    pub fn representation(&self) -> &DataRepresentationQosPolicy {
+        self.representation.as_ref()
+    }
+
+    pub fn type_information(&self) -> Option<&TypeInformation> {
+        self.type_information.as_ref().value()
+    }
 }
 
 impl DdsHasKey for PublicationBuiltinTopicData {
@@ -409,6 +436,11 @@ pub struct SubscriptionBuiltinTopicData {
     partition: ParameterWithDefault<PID_PARTITION, PartitionQosPolicy>,
     topic_data: ParameterWithDefault<PID_TOPIC_DATA, TopicDataQosPolicy>,
     group_data: ParameterWithDefault<PID_GROUP_DATA, GroupDataQosPolicy>,
+    representation: ParameterWithDefault<PID_DATA_REPRESENTATION, DataRepresentationQosPolicy>,
+    type_consistency:
+        ParameterWithDefault<PID_TYPE_CONSISTENCY_ENFORCEMENT, TypeConsistencyEnforcementQosPolicy>,
+    type_information:
+        ParameterWithDefault<PID_TYPE_INFORMATION, XTypesParameterValue<TypeInformation>>,
 }
 
 impl SubscriptionBuiltinTopicData {
@@ -431,6 +463,9 @@ impl SubscriptionBuiltinTopicData {
         partition: PartitionQosPolicy,
         topic_data: TopicDataQosPolicy,
         group_data: GroupDataQosPolicy,
+        representation: DataRepresentationQosPolicy,
+        type_consistency: TypeConsistencyEnforcementQosPolicy,
+        type_information: Option<TypeInformation>,
     ) -> Self {
         Self {
             key: key.into(),
@@ -450,6 +485,9 @@ impl SubscriptionBuiltinTopicData {
             partition: partition.into(),
             topic_data: topic_data.into(),
             group_data: group_data.into(),
+            representation: representation.into(),
+            type_consistency: type_consistency.into(),
+            type_information: XTypesParameterValue::new(type_information).into(),
         }
     }
 
@@ -520,6 +558,18 @@ impl SubscriptionBuiltinTopicData {
     pub fn group_data(&self) -> &GroupDataQosPolicy {
         self.group_data.as_ref()
     }
+
+    pub fn representation(&self) -> &DataRepresentationQosPolicy {
+        self.representation.as_ref()
+    }
+
+    pub fn type_consistency(&self) -> &TypeConsistencyEnforcementQosPolicy {
+        self.type_consistency.as_ref()
+    }
+
+    pub fn type_information(&self) -> Option<&TypeInformation> {
+        self.type_information.as_ref().value()
+    }
 }
 
 impl DdsHasKey for SubscriptionBuiltinTopicData {
@@ -1,20 +1,22 @@
-use std::time::Instant;
+use std::{any::TypeId, time::Instant};
 
 use crate::{
     builtin_topics::{ParticipantBuiltinTopicData, TopicBuiltinTopicData},
     implementation::{
         dds::{
             dds_domain_participant::DdsDomainParticipant,
+            dds_domain_participant_listener::DdsDomainParticipantListener,
             dds_publisher::DdsPublisher,
             dds_publisher_listener::DdsPublisherListener,
             dds_subscriber::DdsSubscriber,
             dds_subscriber_listener::DdsSubscriberListener,
             dds_topic::DdsTopic,
+            multitopic_join::SubscriptionExpression,
             nodes::{PublisherNode, SubscriberNode, SubscriberNodeKind, TopicNode, TopicNodeKind},
         },
         rtps::{
             group::RtpsGroup,
-            messages::overall_structure::RtpsMessageHeader,
+            messages::{overall_structure::RtpsMessageHeader, submessage_elements::Data},
             types::{
                 EntityId, Guid, USER_DEFINED_READER_GROUP, USER_DEFINED_TOPIC,
                 USER_DEFINED_WRITER_GROUP,
@@ -33,9 +35,12 @@ use crate::{
     publication::{publisher::Publisher, publisher_listener::PublisherListener},
     subscription::{subscriber::Subscriber, subscriber_listener::SubscriberListener},
     topic_definition::{
+        dynamic_data::DynamicData,
+        dynamic_type::DynamicType,
+        multitopic::{MultiTopic, MultiTopicSources},
         topic::Topic,
         topic_listener::TopicListener,
-        type_support::{dds_serialize_key, dds_serialize_to_bytes},
+        type_support::{dds_key_hash, dds_serialize_key, dds_serialize_to_bytes, TypeSupport},
     },
 };
 
@@ -69,6 +74,23 @@ impl DomainParticipant {
     pub(crate) fn new(address: ActorAddress<DdsDomainParticipant>) -> Self {
         Self(address)
     }
+
+    pub(crate) fn register_type<Foo>(&self, type_name: &str) -> DdsResult<()>
+    where
+        Foo: 'static,
+    {
+        self.0
+            .register_type(type_name.to_string(), TypeId::of::<Foo>())?
+    }
+
+    pub(crate) fn register_dynamic_type(
+        &self,
+        type_name: &str,
+        dynamic_type: DynamicType,
+    ) -> DdsResult<()> {
+        self.0
+            .register_dynamic_type(type_name.to_string(), dynamic_type)?
+    }
 }
 
 impl Drop for DomainParticipant {
@@ -123,20 +145,7 @@ impl DomainParticipant {
     /// If [`DomainParticipant::delete_publisher()`] is called on a different [`DomainParticipant`], the operation will have no effect and it will return
     /// a PreconditionNotMet error.
     pub fn delete_publisher(&self, a_publisher: &Publisher) -> DdsResult<()> {
-        if self.0.get_guid()?.prefix() != a_publisher.node().address().guid()?.prefix() {
-            return Err(DdsError::PreconditionNotMet(
-                "Publisher can only be deleted from its parent participant".to_string(),
-            ));
-        }
-
-        if !a_publisher.node().address().data_writer_list()?.is_empty() {
-            return Err(DdsError::PreconditionNotMet(
-                "Publisher still contains data writers".to_string(),
-            ));
-        }
-
-        self.0
-            .delete_user_defined_publisher(a_publisher.get_instance_handle()?)
+        delete_publisher(&self.0, a_publisher)
     }
 
     /// This operation creates a [`Subscriber`] with the desired QoS policies and attaches to it the specified [`SubscriberListener`].
@@ -187,25 +196,7 @@ impl DomainParticipant {
     /// it is called on a different [`DomainParticipant`], the operation will have no effect and it will return
     /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
     pub fn delete_subscriber(&self, a_subscriber: &Subscriber) -> DdsResult<()> {
-        match a_subscriber.node() {
-            SubscriberNodeKind::Builtin(_) | SubscriberNodeKind::Listener(_) => Ok(()),
-            SubscriberNodeKind::UserDefined(s) => {
-                if self.0.get_guid()?.prefix() != s.address().guid()?.prefix() {
-                    return Err(DdsError::PreconditionNotMet(
-                        "Subscriber can only be deleted from its parent participant".to_string(),
-                    ));
-                }
-
-                if !s.address().data_reader_list()?.is_empty() {
-                    return Err(DdsError::PreconditionNotMet(
-                        "Subscriber still contains data readers".to_string(),
-                    ));
-                }
-
-                self.0
-                    .delete_user_defined_subscriber(s.address().get_instance_handle()?)
-            }
-        }
+        delete_subscriber(&self.0, a_subscriber)
     }
 
     /// This operation creates a [`Topic`] with the desired QoS policies and attaches to it the specified [`TopicListener`].
@@ -216,13 +207,45 @@ impl DomainParticipant {
     /// The created [`Topic`] belongs to the [`DomainParticipant`] that is its factory.
     /// In case of failure, the operation will return an error and no [`Topic`] will be created.
     pub fn create_topic(
+        &self,
+        topic_name: &str,
+        type_name: &str,
+        qos: QosKind<TopicQos>,
+        a_listener: Option<Box<dyn TopicListener + Send + Sync>>,
+        mask: &[StatusKind],
+    ) -> DdsResult<Topic> {
+        self.create_topic_with_type_name(topic_name, type_name, qos, a_listener, mask)
+    }
+
+    /// This operation creates a [`Topic`] for the type `Foo` in the same way as [`DomainParticipant::create_topic()`]. The type
+    /// name of the [`Topic`] is the one returned by [`TypeSupport::get_type_name()`] and the type `Foo` is registered with it, so that
+    /// creating a [`DataReader`](crate::subscription::data_reader::DataReader) or [`DataWriter`](crate::publication::data_writer::DataWriter)
+    /// of a different type on a [`Topic`] with the same type name fails.
+    /// If the type name is already registered for a different type the operation will fail and return
+    /// [`DdsError::PreconditionNotMet`].
+    pub fn create_typed_topic<Foo>(
+        &self,
+        topic_name: &str,
+        qos: QosKind<TopicQos>,
+        a_listener: Option<Box<dyn TopicListener + Send + Sync>>,
+        mask: &[StatusKind],
+    ) -> DdsResult<Topic<Foo>>
+    where
+        Foo: TypeSupport + 'static,
+    {
+        let type_name = Foo::get_type_name();
+        Foo::register_type(self, type_name)?;
+        self.create_topic_with_type_name(topic_name, type_name, qos, a_listener, mask)
+    }
+
+    fn create_topic_with_type_name<Foo>(
         &self,
         topic_name: &str,
         type_name: &str,
         qos: QosKind<TopicQos>,
         _a_listener: Option<Box<dyn TopicListener + Send + Sync>>,
         _mask: &[StatusKind],
-    ) -> DdsResult<Topic> {
+    ) -> DdsResult<Topic<Foo>> {
         let qos = match qos {
             QosKind::Default => self.0.default_topic_qos()?,
             QosKind::Specific(q) => q,
@@ -254,40 +277,8 @@ impl DomainParticipant {
     /// it, it will return [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
     /// The [`DomainParticipant::delete_topic()`] operation must be called on the same [`DomainParticipant`] object used to create the [`Topic`]. If [`DomainParticipant::delete_topic()`] is
     /// called on a different [`DomainParticipant`], the operation will have no effect and it will return [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
-    pub fn delete_topic(&self, a_topic: &Topic) -> DdsResult<()> {
-        match &a_topic.node() {
-            TopicNodeKind::UserDefined(t) => {
-                if self.0.get_guid()?.prefix() != t.address().guid()?.prefix() {
-                    return Err(DdsError::PreconditionNotMet(
-                        "Topic can only be deleted from its parent participant".to_string(),
-                    ));
-                }
-
-                for publisher in self.0.get_user_defined_publisher_list()? {
-                    if publisher.data_writer_list()?.iter().any(|w| {
-                        w.get_type_name() == t.address().get_type_name()
-                            && w.get_topic_name() == t.address().get_name()
-                    }) {
-                        return Err(DdsError::PreconditionNotMet(
-                            "Topic still attached to some data writer".to_string(),
-                        ));
-                    }
-                }
-
-                for subscriber in self.0.get_user_defined_subscriber_list()? {
-                    if subscriber.data_reader_list()?.iter().any(|r| {
-                        r.get_type_name() == t.address().get_type_name()
-                            && r.get_topic_name() == t.address().get_name()
-                    }) {
-                        return Err(DdsError::PreconditionNotMet(
-                            "Topic still attached to some data reader".to_string(),
-                        ));
-                    }
-                }
-
-                self.0.delete_topic(t.address().get_instance_handle()?)
-            }
-        }
+    pub fn delete_topic<Foo>(&self, a_topic: &Topic<Foo>) -> DdsResult<()> {
+        delete_topic(&self.0, a_topic)
     }
 
     /// This operation gives access to an existing (or ready to exist) enabled [`Topic`], based on its name. The operation takes
@@ -332,6 +323,7 @@ impl DomainParticipant {
                             transport_priority: discovered_topic_data.transport_priority().clone(),
                             lifespan: discovered_topic_data.lifespan().clone(),
                             ownership: discovered_topic_data.ownership().clone(),
+                            representation: discovered_topic_data.representation().clone(),
                         };
                         let topic = self.create_topic(
                             topic_name,
@@ -349,6 +341,105 @@ impl DomainParticipant {
         Err(DdsError::Timeout)
     }
 
+    /// This operation creates a [`MultiTopic`]. A [`MultiTopic`] can be used to subscribe to multiple topics and combine/filter
+    /// the received data into a resulting type. In particular, [`MultiTopic`] provides a content-based subscription mechanism.
+    /// The resulting type is specified by the `type_name` argument and the types of the related topics by the generic type
+    /// argument `Sources`, a tuple with one type for each of the topics in the same order as they appear in the `subscription_expression`.
+    /// The `subscription_expression` selects the fields of the related topics and the topics whose samples are combined. Only
+    /// the natural join of the related topics is supported, a `WHERE` clause results in a [`DdsError::Unsupported`] error.
+    /// Since the parameters can only be referenced by a `WHERE` clause, `expression_parameters` must be empty, otherwise
+    /// [`DdsError::BadParameter`] is returned.
+    /// All the related topics must have been created locally on this [`DomainParticipant`] and the name of the [`MultiTopic`]
+    /// must be different from the name of any existing [`Topic`] or [`MultiTopic`], otherwise [`DdsError::PreconditionNotMet`] is returned.
+    pub fn create_multitopic<Sources>(
+        &self,
+        name: &str,
+        type_name: &str,
+        subscription_expression: &str,
+        expression_parameters: &[String],
+    ) -> DdsResult<MultiTopic>
+    where
+        Sources: MultiTopicSources,
+    {
+        let parsed_subscription_expression =
+            SubscriptionExpression::parse(subscription_expression)?;
+        if parsed_subscription_expression.related_topic_names().len()
+            != Sources::NUMBER_OF_RELATED_TOPICS
+            || !expression_parameters.is_empty()
+        {
+            return Err(DdsError::BadParameter);
+        }
+
+        if self.lookup_topicdescription(name)?.is_some()
+            || self.0.has_multitopic(name.to_string())?
+        {
+            return Err(DdsError::PreconditionNotMet(format!(
+                "Topic with name {} already exists",
+                name
+            )));
+        }
+
+        let mut related_topic_list = Vec::new();
+        for related_topic_name in parsed_subscription_expression.related_topic_names() {
+            let related_topic = self
+                .lookup_topicdescription(related_topic_name)?
+                .ok_or_else(|| {
+                    DdsError::PreconditionNotMet(format!(
+                        "Related topic {} does not exist",
+                        related_topic_name
+                    ))
+                })?;
+            related_topic_list.push(related_topic);
+        }
+
+        self.0.add_multitopic(name.to_string())?;
+
+        Ok(MultiTopic::new(
+            name.to_string(),
+            type_name.to_string(),
+            subscription_expression.to_string(),
+            expression_parameters.to_vec(),
+            parsed_subscription_expression,
+            related_topic_list,
+            Sources::create_source_readers,
+            self.0.clone(),
+        ))
+    }
+
+    /// This operation deletes a [`MultiTopic`].
+    /// The deletion of a [`MultiTopic`] is not allowed if there are any existing [`DataReader`](crate::subscription::data_reader::DataReader)
+    /// objects that are using the [`MultiTopic`]. If the [`DomainParticipant::delete_multitopic()`] operation is called on a [`MultiTopic`] with
+    /// existing [`DataReader`](crate::subscription::data_reader::DataReader) objects attached to it, it will return [`DdsError::PreconditionNotMet`].
+    /// The [`DomainParticipant::delete_multitopic()`] operation must be called on the same [`DomainParticipant`] object used to create the [`MultiTopic`].
+    /// If [`DomainParticipant::delete_multitopic()`] is called on a different [`DomainParticipant`], the operation will have no effect and it will
+    /// return [`DdsError::PreconditionNotMet`].
+    pub fn delete_multitopic(&self, a_multitopic: &MultiTopic) -> DdsResult<()> {
+        if a_multitopic.participant_address() != &self.0 {
+            return Err(DdsError::PreconditionNotMet(
+                "MultiTopic can only be deleted from its parent participant".to_string(),
+            ));
+        }
+
+        for subscriber in self.0.get_user_defined_subscriber_list()? {
+            for data_reader in subscriber.data_reader_list()? {
+                if data_reader.get_topic_name()? == a_multitopic.get_name()?
+                    && data_reader.get_type_name()? == a_multitopic.get_type_name()?
+                    && !data_reader.get_multitopic_source_readers()?.is_empty()
+                {
+                    return Err(DdsError::PreconditionNotMet(
+                        "MultiTopic still attached to some data reader".to_string(),
+                    ));
+                }
+            }
+        }
+
+        if self.0.remove_multitopic(a_multitopic.get_name()?)? {
+            Ok(())
+        } else {
+            Err(DdsError::AlreadyDeleted)
+        }
+    }
+
     /// This operation gives access to an existing locally-created [`Topic`], based on its name and type. The
     /// operation takes as argument the name of the [`Topic`] and the type as a generic type argument `Foo`.
     /// If a [`Topic`] of the same name already exists, it gives access to it, otherwise it returns a [`None`] value. The operation
@@ -359,18 +450,16 @@ impl DomainParticipant {
     /// deletion. It is still possible to delete the [`Topic`] returned by [`DomainParticipant::lookup_topicdescription()`], provided it has no readers or
     /// writers, but then it is really deleted and subsequent lookups will fail.
     /// If the operation fails to locate a [`Topic`], the operation succeeds and a [`None`] value is returned.
-    pub fn lookup_topicdescription(&self, _topic_name: &str) -> DdsResult<Option<Topic>> {
-        todo!()
-        // self.call_participant_method(|dp| {
-        //     Ok(
-        //         crate::implementation::behavior::domain_participant::lookup_topicdescription(
-        //             dp,
-        //             topic_name,
-        //             Foo,
-        //         )?
-        //         .map(|x| Topic::new(TopicNodeKind::UserDefined(x))),
-        //     )
-        // })
+    pub fn lookup_topicdescription(&self, topic_name: &str) -> DdsResult<Option<Topic>> {
+        for topic in self.0.get_user_defined_topic_list()? {
+            if topic.get_name()? == topic_name {
+                return Ok(Some(Topic::new(TopicNodeKind::UserDefined(
+                    TopicNode::new(topic, self.0.clone()),
+                ))));
+            }
+        }
+
+        Ok(None)
     }
 
     /// This operation allows access to the built-in [`Subscriber`]. Each [`DomainParticipant`] contains several built-in [`Topic`] objects as
@@ -472,10 +561,11 @@ impl DomainParticipant {
     /// NOTE: Writing data via the write operation on a  [`DataWriter`](crate::publication::data_writer::DataWriter) asserts liveliness on the DataWriter itself and its
     /// [`DomainParticipant`]. Consequently the use of this operation is only needed if the application is not writing data regularly.
     pub fn assert_liveliness(&self) -> DdsResult<()> {
-        todo!()
-        // self.call_participant_method(|dp| {
-        //     crate::implementation::behavior::domain_participant::assert_liveliness(dp)
-        // })
+        if !self.0.is_enabled()? {
+            return Err(DdsError::NotEnabled);
+        }
+
+        self.0.assert_liveliness(self.0.get_current_time()?)
     }
 
     /// This operation sets a default value of the Publisher QoS policies which will be used for newly created [`Publisher`] entities in the
@@ -562,15 +652,16 @@ impl DomainParticipant {
     /// Use the operation [`DomainParticipant::get_discovered_participants()`] to find the DomainParticipants that are currently discovered.
     pub fn get_discovered_participant_data(
         &self,
-        _participant_handle: InstanceHandle,
+        participant_handle: InstanceHandle,
     ) -> DdsResult<ParticipantBuiltinTopicData> {
-        todo!()
-        // self.call_participant_method(|dp| {
-        //     crate::implementation::behavior::domain_participant::get_discovered_participant_data(
-        //         dp,
-        //         participant_handle,
-        //     )
-        // })
+        match self.0.discovered_participant_get(participant_handle)? {
+            Some(discovered_participant_data) => {
+                Ok(discovered_participant_data.dds_participant_data().clone())
+            }
+            None => Err(DdsError::PreconditionNotMet(
+                "Participant with this instance handle not discovered".to_owned(),
+            )),
+        }
     }
 
     /// This operation retrieves the list of Topics that have been discovered in the domain and that the application has not indicated
@@ -598,11 +689,42 @@ impl DomainParticipant {
     /// so forth.
     /// The instance handle for an Entity may be obtained from built-in topic data, from various statuses, or from the Entity operation
     /// `get_instance_handle`.
-    pub fn contains_entity(&self, _a_handle: InstanceHandle) -> DdsResult<bool> {
-        todo!()
-        // self.call_participant_method(|dp| {
-        //     crate::implementation::behavior::domain_participant::contains_entity(dp, a_handle)
-        // })
+    pub fn contains_entity(&self, a_handle: InstanceHandle) -> DdsResult<bool> {
+        for publisher in self.0.get_user_defined_publisher_list()? {
+            if publisher.get_instance_handle()? == a_handle {
+                return Ok(true);
+            }
+            for data_writer in publisher.data_writer_list()? {
+                if data_writer.get_instance_handle()? == a_handle {
+                    return Ok(true);
+                }
+            }
+        }
+
+        let builtin_subscriber = self.0.get_builtin_subscriber()?;
+        for subscriber in self
+            .0
+            .get_user_defined_subscriber_list()?
+            .into_iter()
+            .chain(std::iter::once(builtin_subscriber))
+        {
+            if subscriber.get_instance_handle()? == a_handle {
+                return Ok(true);
+            }
+            for data_reader in subscriber.data_reader_list()? {
+                if data_reader.get_instance_handle()? == a_handle {
+                    return Ok(true);
+                }
+            }
+        }
+
+        for topic in self.0.get_user_defined_topic_list()? {
+            if topic.get_instance_handle()? == a_handle {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
     }
 
     /// This operation returns the current value of the time that the service uses to time-stamp data-writes and to set the reception timestamp
@@ -648,25 +770,20 @@ impl DomainParticipant {
     /// will be removed.
     pub fn set_listener(
         &self,
-        _a_listener: Option<Box<dyn DomainParticipantListener + Send + Sync>>,
-        _mask: &[StatusKind],
+        a_listener: Option<Box<dyn DomainParticipantListener + Send + Sync>>,
+        mask: &[StatusKind],
     ) -> DdsResult<()> {
-        todo!()
+        self.0.set_listener(
+            a_listener.map(|l| spawn_actor(DdsDomainParticipantListener::new(l))),
+            mask.to_vec(),
+        )
     }
 
     /// This operation allows access to the [`StatusCondition`] associated with the Entity. The returned
     /// condition can then be added to a [`WaitSet`](crate::infrastructure::wait_set::WaitSet) so that the application can wait for specific status changes
     /// that affect the Entity.
     pub fn get_statuscondition(&self) -> DdsResult<StatusCondition> {
-        todo!()
-        // THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_domain_participant_listener(
-        //     &self.0.guid(),
-        //     |domain_participant_listener| {
-        //         Ok(domain_participant_listener
-        //             .ok_or(DdsError::AlreadyDeleted)?
-        //             .get_status_condition())
-        //     },
-        // )
+        self.0.get_statuscondition().map(StatusCondition::new)
     }
 
     /// This operation retrieves the list of communication statuses in the Entity that are ‘triggered.’ That is, the list of statuses whose
@@ -676,15 +793,11 @@ impl DomainParticipant {
     /// The list of statuses returned by the [`Self::get_status_changes`] operation refers to the status that are triggered on the Entity itself
     /// and does not include statuses that apply to contained entities.
     pub fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
-        // THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_domain_participant_listener(
-        //     &self.0.guid(),
-        //     |domain_participant_listener| {
-        //         Ok(domain_participant_listener
-        //             .ok_or(DdsError::AlreadyDeleted)?
-        //             .get_status_changes())
-        //     },
-        // )
+        Ok(self
+            .0
+            .get_statuscondition()?
+            .read_lock()
+            .get_status_changes())
     }
 
     /// This operation enables the Entity. Entity objects can be created either enabled or disabled. This is controlled by the value of
@@ -746,8 +859,9 @@ impl DomainParticipant {
                                 dds_serialize_to_bytes(&spdp_discovered_participant_data)?;
                             let timestamp = domain_participant_address.get_current_time()?;
                             participant_announcer.write_w_timestamp(
-                                serialized_data,
+                                Data::new(serialized_data),
                                 dds_serialize_key(&spdp_discovered_participant_data).unwrap(),
+                                dds_key_hash(&spdp_discovered_participant_data).unwrap(),
                                 None,
                                 timestamp,
                             )??;
@@ -774,7 +888,7 @@ impl DomainParticipant {
                 }
             });
 
-            // Spawn the task that regularly checks for deadline missed
+            // Spawn the task that regularly checks for deadline missed and liveliness lost
             let domain_participant_address = self.0.clone();
             THE_RUNTIME.spawn(async move {
                 let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(50));
@@ -795,6 +909,22 @@ impl DomainParticipant {
                             }
                         }
 
+                        let participant_liveliness_assertion =
+                            domain_participant_address.get_last_liveliness_assertion()?;
+                        for publisher in
+                            domain_participant_address.get_user_defined_publisher_list()?
+                        {
+                            for data_writer in publisher.data_writer_list()? {
+                                data_writer.update_communication_status(
+                                    now,
+                                    participant_liveliness_assertion,
+                                    data_writer.clone(),
+                                    publisher.clone(),
+                                    domain_participant_address.clone(),
+                                )?;
+                            }
+                        }
+
                         Ok(())
                     });
 
@@ -814,3 +944,132 @@ impl DomainParticipant {
         self.0.get_instance_handle()
     }
 }
+
+pub(crate) fn check_registered_type<Foo>(
+    domain_participant: &ActorAddress<DdsDomainParticipant>,
+    type_name: &str,
+) -> DdsResult<()>
+where
+    Foo: 'static,
+{
+    if TypeId::of::<Foo>() == TypeId::of::<DynamicData>() {
+        match domain_participant.get_registered_type(type_name.to_string())? {
+            Some(type_id) if type_id == TypeId::of::<DynamicData>() => Ok(()),
+            _ => Err(DdsError::PreconditionNotMet(format!(
+                "No DynamicType is registered with type name {}",
+                type_name
+            ))),
+        }
+    } else {
+        // A type name which was never registered is bound to the type of the first reader or
+        // writer created with it
+        domain_participant
+            .register_type(type_name.to_string(), TypeId::of::<Foo>())?
+            .map_err(|_| {
+                DdsError::PreconditionNotMet(format!(
+                    "Type {} does not match the type registered with type name {}",
+                    std::any::type_name::<Foo>(),
+                    type_name
+                ))
+            })
+    }
+}
+
+pub(crate) fn check_type_of_type_name<Foo>(
+    domain_participant: &ActorAddress<DdsDomainParticipant>,
+    type_name: &str,
+) -> DdsResult<()>
+where
+    Foo: 'static,
+{
+    match domain_participant.get_registered_type(type_name.to_string())? {
+        Some(type_id) if type_id == TypeId::of::<Foo>() => Ok(()),
+        _ => Err(DdsError::PreconditionNotMet(format!(
+            "Type {} does not match the type registered with type name {}",
+            std::any::type_name::<Foo>(),
+            type_name
+        ))),
+    }
+}
+
+pub(crate) fn delete_publisher(
+    domain_participant: &ActorAddress<DdsDomainParticipant>,
+    a_publisher: &Publisher,
+) -> DdsResult<()> {
+    if domain_participant.get_guid()?.prefix() != a_publisher.node().address().guid()?.prefix() {
+        return Err(DdsError::PreconditionNotMet(
+            "Publisher can only be deleted from its parent participant".to_string(),
+        ));
+    }
+
+    if !a_publisher.node().address().data_writer_list()?.is_empty() {
+        return Err(DdsError::PreconditionNotMet(
+            "Publisher still contains data writers".to_string(),
+        ));
+    }
+
+    domain_participant.delete_user_defined_publisher(a_publisher.get_instance_handle()?)
+}
+
+pub(crate) fn delete_subscriber(
+    domain_participant: &ActorAddress<DdsDomainParticipant>,
+    a_subscriber: &Subscriber,
+) -> DdsResult<()> {
+    match a_subscriber.node() {
+        SubscriberNodeKind::Builtin(_) => Ok(()),
+        SubscriberNodeKind::UserDefined(s) | SubscriberNodeKind::Listener(s) => {
+            if domain_participant.get_guid()?.prefix() != s.address().guid()?.prefix() {
+                return Err(DdsError::PreconditionNotMet(
+                    "Subscriber can only be deleted from its parent participant".to_string(),
+                ));
+            }
+
+            if !s.address().data_reader_list()?.is_empty() {
+                return Err(DdsError::PreconditionNotMet(
+                    "Subscriber still contains data readers".to_string(),
+                ));
+            }
+
+            domain_participant.delete_user_defined_subscriber(s.address().get_instance_handle()?)
+        }
+    }
+}
+
+pub(crate) fn delete_topic<Foo>(
+    domain_participant: &ActorAddress<DdsDomainParticipant>,
+    a_topic: &Topic<Foo>,
+) -> DdsResult<()> {
+    match &a_topic.node() {
+        TopicNodeKind::UserDefined(t) => {
+            if domain_participant.get_guid()?.prefix() != t.address().guid()?.prefix() {
+                return Err(DdsError::PreconditionNotMet(
+                    "Topic can only be deleted from its parent participant".to_string(),
+                ));
+            }
+
+            for publisher in domain_participant.get_user_defined_publisher_list()? {
+                if publisher.data_writer_list()?.iter().any(|w| {
+                    w.get_type_name() == t.address().get_type_name()
+                        && w.get_topic_name() == t.address().get_name()
+                }) {
+                    return Err(DdsError::PreconditionNotMet(
+                        "Topic still attached to some data writer".to_string(),
+                    ));
+                }
+            }
+
+            for subscriber in domain_participant.get_user_defined_subscriber_list()? {
+                if subscriber.data_reader_list()?.iter().any(|r| {
+                    r.get_type_name() == t.address().get_type_name()
+                        && r.get_topic_name() == t.address().get_name()
+                }) {
+                    return Err(DdsError::PreconditionNotMet(
+                        "Topic still attached to some data reader".to_string(),
+                    ));
+                }
+            }
+
+            domain_participant.delete_topic(t.address().get_instance_handle()?)
+        }
+    }
+}
@@ -42,14 +42,8 @@ use crate::{
         qos::{DomainParticipantFactoryQos, DomainParticipantQos, QosKind},
         status::StatusKind,
     },
-    subscription::{
-        data_reader::Sample,
-        sample_info::{
-            InstanceStateKind, SampleStateKind, ANY_INSTANCE_STATE, ANY_SAMPLE_STATE,
-            ANY_VIEW_STATE,
-        },
-    },
-    topic_definition::type_support::dds_serialize_key,
+    subscription::{data_reader::Sample, sample_info::InstanceStateKind},
+    topic_definition::type_support::{dds_serialize_key, dds_serialize_key_to_bytes},
 };
 use fnmatch_regex::glob_to_regex;
 use jsonschema::JSONSchema;
@@ -300,6 +294,11 @@ impl DomainParticipantFactory {
             .ok_or(DdsError::BadParameter)?;
 
         if participant.is_empty()? {
+            // The participant is only announced once enabled so its deletion is only announced
+            // if it is enabled
+            if participant.is_enabled()? {
+                announce_deleted_participant(participant)?;
+            }
             self.0.address().delete_participant(handle)?;
             Ok(())
         } else {
@@ -378,6 +377,46 @@ impl DomainParticipantFactory {
     }
 }
 
+fn announce_deleted_participant(
+    participant_address: &ActorAddress<DdsDomainParticipant>,
+) -> DdsResult<()> {
+    if let Some(participant_announcer) = participant_address
+        .get_builtin_publisher()?
+        .data_writer_list()?
+        .iter()
+        .find(|dw| {
+            if let Ok(name) = dw.get_type_name() {
+                name == "SpdpDiscoveredParticipantData"
+            } else {
+                false
+            }
+        })
+    {
+        let spdp_discovered_participant_data =
+            participant_address.as_spdp_discovered_participant_data()?;
+        let timestamp = participant_address.get_current_time()?;
+        participant_announcer.dispose_w_timestamp(
+            dds_serialize_key_to_bytes(&spdp_discovered_participant_data)?
+                .as_ref()
+                .to_vec(),
+            participant_address.get_instance_handle()?,
+            timestamp,
+        )??;
+
+        participant_announcer.send_message(
+            RtpsMessageHeader::new(
+                participant_address.get_protocol_version()?,
+                participant_address.get_vendor_id()?,
+                participant_address.get_guid()?.prefix(),
+            ),
+            participant_address.get_udp_transport_write()?,
+            timestamp,
+        )?;
+    }
+
+    Ok(())
+}
+
 fn lookup_data_writer_by_topic_name(
     writer_list: &[ActorAddress<DdsDataWriter>],
     topic_name: &str,
@@ -669,28 +708,38 @@ fn process_spdp_metatraffic(
             false
         }
     }) {
-        // Receive the data on the builtin spdp reader
-        spdp_data_reader.process_rtps_message(
-            message,
-            participant_address.get_current_time()?,
-            spdp_data_reader.clone(),
-            builtin_subscriber,
-            participant_address.clone(),
-        )?;
-
-        // Read data from each of the readers
-        while let Ok(spdp_data_sample_list) = spdp_data_reader
-            .read::<SpdpDiscoveredParticipantData>(
-                1,
-                &[SampleStateKind::NotRead],
-                ANY_VIEW_STATE,
-                ANY_INSTANCE_STATE,
-                None,
+        // Receive the data on the builtin spdp reader. Only the samples added by this message
+        // are returned and they remain available to the application.
+        if let Ok(spdp_data_sample_list) = spdp_data_reader
+            .process_rtps_message_and_get_added_samples::<SpdpDiscoveredParticipantData>(
+                message,
+                participant_address.get_current_time()?,
+                spdp_data_reader.clone(),
+                builtin_subscriber,
+                participant_address.clone(),
             )
         {
             for spdp_data_sample in spdp_data_sample_list {
-                let discovered_participant_data =
-                    spdp_data_sample.data.expect("Should contain data");
+                let discovered_participant_data = match spdp_data_sample.sample_info.instance_state
+                {
+                    InstanceStateKind::Alive => spdp_data_sample.data.expect("Should contain data"),
+                    InstanceStateKind::NotAliveDisposed | InstanceStateKind::NotAliveNoWriters => {
+                        remove_discovered_participant(
+                            participant_address,
+                            spdp_data_sample.sample_info.instance_handle,
+                        )?;
+                        continue;
+                    }
+                };
+
+                // A participant which was already discovered with the same data needs no further processing
+                if participant_address
+                    .discovered_participant_get(spdp_data_sample.sample_info.instance_handle)?
+                    .as_ref()
+                    == Some(&discovered_participant_data)
+                {
+                    continue;
+                }
 
                 // Check that the domainId of the discovered participant equals the local one.
                 // If it is not equal then there the local endpoints are not configured to
@@ -817,6 +866,125 @@ fn process_spdp_metatraffic(
     Ok(())
 }
 
+fn remove_discovered_participant(
+    participant_address: &ActorAddress<DdsDomainParticipant>,
+    participant_handle: InstanceHandle,
+) -> DdsResult<()> {
+    if let Some(discovered_participant_data) =
+        participant_address.discovered_participant_get(participant_handle)?
+    {
+        let guid_prefix = discovered_participant_data
+            .participant_proxy()
+            .guid_prefix();
+
+        let builtin_data_writer_list = participant_address
+            .get_builtin_publisher()?
+            .data_writer_list()?;
+        for (topic_name, remote_reader_entity_id) in [
+            (
+                DCPS_PUBLICATION,
+                ENTITYID_SEDP_BUILTIN_PUBLICATIONS_DETECTOR,
+            ),
+            (
+                DCPS_SUBSCRIPTION,
+                ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_DETECTOR,
+            ),
+            (DCPS_TOPIC, ENTITYID_SEDP_BUILTIN_TOPICS_DETECTOR),
+        ]
+        .iter()
+        {
+            if let Some(sedp_announcer) =
+                lookup_data_writer_by_topic_name(&builtin_data_writer_list, topic_name)
+            {
+                sedp_announcer
+                    .matched_reader_remove(Guid::new(guid_prefix, *remote_reader_entity_id))?;
+            }
+        }
+
+        let builtin_data_reader_list = participant_address
+            .get_builtin_subscriber()?
+            .data_reader_list()?;
+        for (topic_name, remote_writer_entity_id) in [
+            (
+                DCPS_PUBLICATION,
+                ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER,
+            ),
+            (
+                DCPS_SUBSCRIPTION,
+                ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER,
+            ),
+            (DCPS_TOPIC, ENTITYID_SEDP_BUILTIN_TOPICS_ANNOUNCER),
+        ]
+        .iter()
+        {
+            if let Some(sedp_detector) =
+                lookup_data_reader_by_topic_name(&builtin_data_reader_list, topic_name)
+            {
+                sedp_detector
+                    .matched_writer_remove(Guid::new(guid_prefix, *remote_writer_entity_id))?;
+            }
+        }
+
+        for publisher in participant_address.get_user_defined_publisher_list()? {
+            for data_writer in publisher.data_writer_list()? {
+                for subscription_handle in data_writer.get_matched_subscriptions()? {
+                    if Guid::from(<[u8; 16]>::from(subscription_handle)).prefix() == guid_prefix {
+                        data_writer.remove_matched_reader(
+                            subscription_handle,
+                            data_writer.clone(),
+                            publisher.clone(),
+                            participant_address.clone(),
+                        )?;
+                    }
+                }
+            }
+        }
+
+        for subscriber in participant_address.get_user_defined_subscriber_list()? {
+            for data_reader in subscriber.data_reader_list()? {
+                for publication_handle in data_reader.get_matched_publications()? {
+                    if Guid::from(<[u8; 16]>::from(publication_handle)).prefix() == guid_prefix {
+                        data_reader.remove_matched_writer(
+                            publication_handle,
+                            data_reader.clone(),
+                            subscriber.clone(),
+                            participant_address.clone(),
+                        )?;
+                    }
+                }
+            }
+        }
+
+        for discovered_writer_data in participant_address.discovered_writer_data_list()? {
+            if discovered_writer_data
+                .writer_proxy()
+                .remote_writer_guid()
+                .prefix()
+                == guid_prefix
+            {
+                participant_address
+                    .discovered_writer_remove(dds_serialize_key(&discovered_writer_data)?.into())?;
+            }
+        }
+
+        for discovered_reader_data in participant_address.discovered_reader_data_list()? {
+            if discovered_reader_data
+                .reader_proxy()
+                .remote_reader_guid()
+                .prefix()
+                == guid_prefix
+            {
+                participant_address
+                    .discovered_reader_remove(dds_serialize_key(&discovered_reader_data)?.into())?;
+            }
+        }
+
+        participant_address.discovered_participant_remove(participant_handle)?;
+    }
+
+    Ok(())
+}
+
 fn process_sedp_metatraffic(
     participant_address: &ActorAddress<DdsDomainParticipant>,
     message: RtpsMessageRead,
@@ -838,13 +1006,49 @@ fn process_sedp_metatraffic(
     }
 
     for stateful_builtin_reader in builtin_subscriber.data_reader_list()? {
-        stateful_builtin_reader.process_rtps_message(
-            message.clone(),
-            participant_address.get_current_time()?,
-            stateful_builtin_reader.clone(),
-            builtin_subscriber.clone(),
-            participant_address.clone(),
-        )?;
+        // The discovered endpoints are collected while processing the message so that none is
+        // missed if the application takes the samples of the builtin readers in the meantime.
+        // Only the samples added by this message are returned to avoid going over the whole
+        // cache of the builtin readers every time.
+        match stateful_builtin_reader.get_topic_name()?.as_str() {
+            DCPS_PUBLICATION => {
+                if let Ok(discovered_writer_sample_list) = stateful_builtin_reader
+                    .process_rtps_message_and_get_added_samples::<DiscoveredWriterData>(
+                    message.clone(),
+                    participant_address.get_current_time()?,
+                    stateful_builtin_reader.clone(),
+                    builtin_subscriber.clone(),
+                    participant_address.clone(),
+                ) {
+                    update_discovered_writer_list(
+                        participant_address,
+                        discovered_writer_sample_list,
+                    )?;
+                }
+            }
+            DCPS_SUBSCRIPTION => {
+                if let Ok(discovered_reader_sample_list) = stateful_builtin_reader
+                    .process_rtps_message_and_get_added_samples::<DiscoveredReaderData>(
+                    message.clone(),
+                    participant_address.get_current_time()?,
+                    stateful_builtin_reader.clone(),
+                    builtin_subscriber.clone(),
+                    participant_address.clone(),
+                ) {
+                    update_discovered_reader_list(
+                        participant_address,
+                        discovered_reader_sample_list,
+                    )?;
+                }
+            }
+            _ => stateful_builtin_reader.process_rtps_message(
+                message.clone(),
+                participant_address.get_current_time()?,
+                stateful_builtin_reader.clone(),
+                builtin_subscriber.clone(),
+                participant_address.clone(),
+            )?,
+        }
         stateful_builtin_reader.send_message(
             RtpsMessageHeader::new(
                 participant_address.get_protocol_version()?,
@@ -858,58 +1062,55 @@ fn process_sedp_metatraffic(
     Ok(())
 }
 
-fn process_sedp_discovery(
+pub(crate) fn process_sedp_discovery(
     participant_address: &ActorAddress<DdsDomainParticipant>,
 ) -> DdsResult<()> {
     let builtin_subscriber = participant_address.get_builtin_subscriber()?;
 
-    for stateful_builtin_reader in builtin_subscriber.data_reader_list()? {
-        match stateful_builtin_reader.get_topic_name()?.as_str() {
-            DCPS_PUBLICATION => {
-                if let Ok(mut discovered_writer_sample_list) = stateful_builtin_reader
-                    .read::<DiscoveredWriterData>(
-                    i32::MAX,
-                    ANY_SAMPLE_STATE,
-                    ANY_VIEW_STATE,
-                    ANY_INSTANCE_STATE,
-                    None,
-                ) {
-                    for discovered_writer_sample in discovered_writer_sample_list.drain(..) {
-                        discover_matched_writers(participant_address, &discovered_writer_sample)?;
-                    }
-                }
+    // The builtin topics reader is only peeked so that it remains usable by the application
+    if let Some(sedp_topics_detector) =
+        lookup_data_reader_by_topic_name(&builtin_subscriber.data_reader_list()?, DCPS_TOPIC)
+    {
+        if let Ok(discovered_topic_sample_list) = sedp_topics_detector.peek::<DiscoveredTopicData>()
+        {
+            for discovered_topic_sample in discovered_topic_sample_list {
+                discover_matched_topics(participant_address, &discovered_topic_sample)?;
             }
-            DCPS_SUBSCRIPTION => {
-                if let Ok(mut discovered_reader_sample_list) = stateful_builtin_reader
-                    .read::<DiscoveredReaderData>(
-                    i32::MAX,
-                    ANY_SAMPLE_STATE,
-                    ANY_VIEW_STATE,
-                    ANY_INSTANCE_STATE,
-                    None,
-                ) {
-                    for discovered_reader_sample in discovered_reader_sample_list.drain(..) {
-                        discover_matched_readers(participant_address, &discovered_reader_sample)?;
-                    }
+        }
+    }
+
+    for discovered_writer_data in participant_address.discovered_writer_data_list()? {
+        discover_matched_writers(participant_address, &discovered_writer_data)?;
+    }
+
+    for discovered_reader_data in participant_address.discovered_reader_data_list()? {
+        discover_matched_readers(participant_address, &discovered_reader_data)?;
+    }
+
+    Ok(())
+}
+
+fn update_discovered_writer_list(
+    participant_address: &ActorAddress<DdsDomainParticipant>,
+    discovered_writer_sample_list: Vec<Sample<DiscoveredWriterData>>,
+) -> DdsResult<()> {
+    for discovered_writer_sample in discovered_writer_sample_list {
+        match discovered_writer_sample.sample_info.instance_state {
+            InstanceStateKind::Alive => {
+                if let Some(discovered_writer_data) = discovered_writer_sample.data {
+                    participant_address.discovered_writer_add(
+                        discovered_writer_sample.sample_info.instance_handle,
+                        discovered_writer_data,
+                    )?;
                 }
             }
-            DCPS_TOPIC => {
-                if let Ok(discovered_topic_sample_list) = stateful_builtin_reader
-                    .read::<DiscoveredTopicData>(
-                        i32::MAX,
-                        ANY_SAMPLE_STATE,
-                        ANY_VIEW_STATE,
-                        ANY_INSTANCE_STATE,
-                        None,
-                    )
-                {
-                    for discovered_topic_sample in discovered_topic_sample_list {
-                        discover_matched_topics(participant_address, &discovered_topic_sample)?;
-                    }
-                }
+            InstanceStateKind::NotAliveDisposed | InstanceStateKind::NotAliveNoWriters => {
+                remove_discovered_writer(
+                    participant_address,
+                    discovered_writer_sample.sample_info.instance_handle,
+                )?
             }
-            _ => (),
-        };
+        }
     }
 
     Ok(())
@@ -917,121 +1118,154 @@ fn process_sedp_discovery(
 
 fn discover_matched_writers(
     participant_address: &ActorAddress<DdsDomainParticipant>,
-    discovered_writer_sample: &Sample<DiscoveredWriterData>,
+    discovered_writer_data: &DiscoveredWriterData,
 ) -> DdsResult<()> {
-    match discovered_writer_sample.sample_info.instance_state {
-        InstanceStateKind::Alive => {
-            if let Some(discovered_writer_data) = &discovered_writer_sample.data {
-                if !participant_address.is_publication_ignored(
+    let discovered_writer_handle = dds_serialize_key(discovered_writer_data)?.into();
+    if !participant_address.is_publication_ignored(
+        discovered_writer_data
+            .writer_proxy()
+            .remote_writer_guid()
+            .into(),
+    )? {
+        let remote_writer_guid_prefix = discovered_writer_data
+            .writer_proxy()
+            .remote_writer_guid()
+            .prefix();
+        let writer_parent_participant_guid =
+            Guid::new(remote_writer_guid_prefix, ENTITYID_PARTICIPANT);
+
+        if let Some(spdp_discovered_participant_data) = participant_address
+            .discovered_participant_get(InstanceHandle::from(writer_parent_participant_guid))?
+        {
+            let discovered_writer_data = &discovered_writer_data
+                .clone()
+                .filter_vendor_specific_parameters(
+                    spdp_discovered_participant_data
+                        .participant_proxy()
+                        .vendor_id(),
+                );
+            let default_unicast_locator_list = spdp_discovered_participant_data
+                .participant_proxy()
+                .default_unicast_locator_list()
+                .to_vec();
+            let default_multicast_locator_list = spdp_discovered_participant_data
+                .participant_proxy()
+                .default_multicast_locator_list()
+                .to_vec();
+            for user_defined_subscriber_address in
+                participant_address.get_user_defined_subscriber_list()?
+            {
+                let is_discovered_writer_regex_matched_to_subscriber = if let Ok(d) = glob_to_regex(
                     discovered_writer_data
-                        .writer_proxy()
-                        .remote_writer_guid()
-                        .into(),
-                )? {
-                    let remote_writer_guid_prefix = discovered_writer_data
-                        .writer_proxy()
-                        .remote_writer_guid()
-                        .prefix();
-                    let writer_parent_participant_guid =
-                        Guid::new(remote_writer_guid_prefix, ENTITYID_PARTICIPANT);
-
-                    if let Some(spdp_discovered_participant_data) = participant_address
-                        .discovered_participant_get(InstanceHandle::from(
-                            writer_parent_participant_guid,
-                        ))?
-                    {
-                        let default_unicast_locator_list = spdp_discovered_participant_data
-                            .participant_proxy()
-                            .default_unicast_locator_list()
-                            .to_vec();
-                        let default_multicast_locator_list = spdp_discovered_participant_data
-                            .participant_proxy()
-                            .default_multicast_locator_list()
-                            .to_vec();
-                        for user_defined_subscriber_address in
-                            participant_address.get_user_defined_subscriber_list()?
-                        {
-                            let is_discovered_writer_regex_matched_to_subscriber = if let Ok(d) =
-                                glob_to_regex(
-                                    discovered_writer_data
-                                        .clone()
-                                        .dds_publication_data()
-                                        .partition()
-                                        .name
-                                        .as_str(),
-                                ) {
-                                d.is_match(
-                                    &user_defined_subscriber_address.get_qos()?.partition.name,
-                                )
-                            } else {
-                                false
-                            };
-
-                            let is_subscriber_regex_matched_to_discovered_writer = if let Ok(d) =
-                                glob_to_regex(
-                                    &user_defined_subscriber_address.get_qos()?.partition.name,
-                                ) {
-                                d.is_match(
-                                    &discovered_writer_data
-                                        .clone()
-                                        .dds_publication_data()
-                                        .partition()
-                                        .name,
-                                )
-                            } else {
-                                false
-                            };
-
-                            let is_partition_string_matched = discovered_writer_data
-                                .clone()
-                                .dds_publication_data()
-                                .partition()
-                                .name
-                                == user_defined_subscriber_address.get_qos()?.partition.name;
-
-                            if is_discovered_writer_regex_matched_to_subscriber
-                                || is_subscriber_regex_matched_to_discovered_writer
-                                || is_partition_string_matched
-                            {
-                                for data_reader_address in
-                                    user_defined_subscriber_address.data_reader_list()?
-                                {
-                                    data_reader_address.add_matched_writer(
-                                        discovered_writer_data.clone(),
-                                        default_unicast_locator_list.clone(),
-                                        default_multicast_locator_list.clone(),
-                                        data_reader_address.clone(),
-                                        user_defined_subscriber_address.clone(),
-                                        participant_address.clone(),
-                                    )?;
-                                    data_reader_address.send_message(
-                                        RtpsMessageHeader::new(
-                                            participant_address.get_protocol_version()?,
-                                            participant_address.get_vendor_id()?,
-                                            participant_address.get_guid()?.prefix(),
-                                        ),
-                                        participant_address.get_udp_transport_write()?,
-                                    )?;
-                                }
-                            }
-                        }
+                        .clone()
+                        .dds_publication_data()
+                        .partition()
+                        .name
+                        .as_str(),
+                ) {
+                    d.is_match(&user_defined_subscriber_address.get_qos()?.partition.name)
+                } else {
+                    false
+                };
+
+                let is_subscriber_regex_matched_to_discovered_writer = if let Ok(d) =
+                    glob_to_regex(&user_defined_subscriber_address.get_qos()?.partition.name)
+                {
+                    d.is_match(
+                        &discovered_writer_data
+                            .clone()
+                            .dds_publication_data()
+                            .partition()
+                            .name,
+                    )
+                } else {
+                    false
+                };
+
+                let is_partition_string_matched = discovered_writer_data
+                    .clone()
+                    .dds_publication_data()
+                    .partition()
+                    .name
+                    == user_defined_subscriber_address.get_qos()?.partition.name;
+
+                if is_discovered_writer_regex_matched_to_subscriber
+                    || is_subscriber_regex_matched_to_discovered_writer
+                    || is_partition_string_matched
+                {
+                    for data_reader_address in user_defined_subscriber_address.data_reader_list()? {
+                        data_reader_address.add_matched_writer(
+                            discovered_writer_data.clone(),
+                            default_unicast_locator_list.clone(),
+                            default_multicast_locator_list.clone(),
+                            data_reader_address.clone(),
+                            user_defined_subscriber_address.clone(),
+                            participant_address.clone(),
+                        )?;
+                        data_reader_address.send_message(
+                            RtpsMessageHeader::new(
+                                participant_address.get_protocol_version()?,
+                                participant_address.get_vendor_id()?,
+                                participant_address.get_guid()?.prefix(),
+                            ),
+                            participant_address.get_udp_transport_write()?,
+                        )?;
+                    }
+                } else {
+                    for data_reader_address in user_defined_subscriber_address.data_reader_list()? {
+                        data_reader_address.remove_matched_writer(
+                            discovered_writer_handle,
+                            data_reader_address.clone(),
+                            user_defined_subscriber_address.clone(),
+                            participant_address.clone(),
+                        )?;
                     }
                 }
             }
         }
-        InstanceStateKind::NotAliveDisposed => {
-            for subscriber in participant_address.get_user_defined_subscriber_list()? {
-                for data_reader in subscriber.data_reader_list()? {
-                    data_reader.remove_matched_writer(
-                        discovered_writer_sample.sample_info.instance_handle,
-                        data_reader.clone(),
-                        subscriber.clone(),
-                        participant_address.clone(),
+    }
+    Ok(())
+}
+
+fn remove_discovered_writer(
+    participant_address: &ActorAddress<DdsDomainParticipant>,
+    discovered_writer_handle: InstanceHandle,
+) -> DdsResult<()> {
+    for subscriber in participant_address.get_user_defined_subscriber_list()? {
+        for data_reader in subscriber.data_reader_list()? {
+            data_reader.remove_matched_writer(
+                discovered_writer_handle,
+                data_reader.clone(),
+                subscriber.clone(),
+                participant_address.clone(),
+            )?;
+        }
+    }
+
+    participant_address.discovered_writer_remove(discovered_writer_handle)
+}
+
+fn update_discovered_reader_list(
+    participant_address: &ActorAddress<DdsDomainParticipant>,
+    discovered_reader_sample_list: Vec<Sample<DiscoveredReaderData>>,
+) -> DdsResult<()> {
+    for discovered_reader_sample in discovered_reader_sample_list {
+        match discovered_reader_sample.sample_info.instance_state {
+            InstanceStateKind::Alive => {
+                if let Some(discovered_reader_data) = discovered_reader_sample.data {
+                    participant_address.discovered_reader_add(
+                        discovered_reader_sample.sample_info.instance_handle,
+                        discovered_reader_data,
                     )?;
                 }
             }
+            InstanceStateKind::NotAliveDisposed | InstanceStateKind::NotAliveNoWriters => {
+                remove_discovered_reader(
+                    participant_address,
+                    discovered_reader_sample.sample_info.instance_handle,
+                )?
+            }
         }
-        InstanceStateKind::NotAliveNoWriters => todo!(),
     }
 
     Ok(())
@@ -1039,119 +1273,128 @@ fn discover_matched_writers(
 
 pub fn discover_matched_readers(
     participant_address: &ActorAddress<DdsDomainParticipant>,
-    discovered_reader_sample: &Sample<DiscoveredReaderData>,
+    discovered_reader_data: &DiscoveredReaderData,
 ) -> DdsResult<()> {
-    match discovered_reader_sample.sample_info.instance_state {
-        InstanceStateKind::Alive => {
-            if let Some(discovered_reader_data) = &discovered_reader_sample.data {
-                if !participant_address.is_subscription_ignored(
-                    discovered_reader_data
-                        .reader_proxy()
-                        .remote_reader_guid()
-                        .into(),
-                )? {
-                    let remote_reader_guid_prefix = discovered_reader_data
-                        .reader_proxy()
-                        .remote_reader_guid()
-                        .prefix();
-                    let reader_parent_participant_guid =
-                        Guid::new(remote_reader_guid_prefix, ENTITYID_PARTICIPANT);
-
-                    if let Some(spdp_discovered_participant_data) = participant_address
-                        .discovered_participant_get(InstanceHandle::from(
-                            reader_parent_participant_guid,
-                        ))?
-                    {
-                        let default_unicast_locator_list = spdp_discovered_participant_data
-                            .participant_proxy()
-                            .default_unicast_locator_list()
-                            .to_vec();
-                        let default_multicast_locator_list = spdp_discovered_participant_data
-                            .participant_proxy()
-                            .default_multicast_locator_list()
-                            .to_vec();
-                        for user_defined_publisher_address in
-                            participant_address.get_user_defined_publisher_list()?
-                        {
-                            let publisher_qos = user_defined_publisher_address.get_qos()?;
-                            let is_discovered_reader_regex_matched_to_publisher = if let Ok(d) =
-                                glob_to_regex(
-                                    &discovered_reader_data
-                                        .subscription_builtin_topic_data()
-                                        .partition()
-                                        .name,
-                                ) {
-                                d.is_match(&publisher_qos.partition.name)
-                            } else {
-                                false
-                            };
-
-                            let is_publisher_regex_matched_to_discovered_reader =
-                                if let Ok(d) = glob_to_regex(&publisher_qos.partition.name) {
-                                    d.is_match(
-                                        &discovered_reader_data
-                                            .subscription_builtin_topic_data()
-                                            .partition()
-                                            .name,
-                                    )
-                                } else {
-                                    false
-                                };
-
-                            let is_partition_string_matched = discovered_reader_data
+    let discovered_reader_handle = dds_serialize_key(discovered_reader_data)?.into();
+    if !participant_address.is_subscription_ignored(
+        discovered_reader_data
+            .reader_proxy()
+            .remote_reader_guid()
+            .into(),
+    )? {
+        let remote_reader_guid_prefix = discovered_reader_data
+            .reader_proxy()
+            .remote_reader_guid()
+            .prefix();
+        let reader_parent_participant_guid =
+            Guid::new(remote_reader_guid_prefix, ENTITYID_PARTICIPANT);
+
+        if let Some(spdp_discovered_participant_data) = participant_address
+            .discovered_participant_get(InstanceHandle::from(reader_parent_participant_guid))?
+        {
+            let discovered_reader_data = &discovered_reader_data
+                .clone()
+                .filter_vendor_specific_parameters(
+                    spdp_discovered_participant_data
+                        .participant_proxy()
+                        .vendor_id(),
+                );
+            let default_unicast_locator_list = spdp_discovered_participant_data
+                .participant_proxy()
+                .default_unicast_locator_list()
+                .to_vec();
+            let default_multicast_locator_list = spdp_discovered_participant_data
+                .participant_proxy()
+                .default_multicast_locator_list()
+                .to_vec();
+            for user_defined_publisher_address in
+                participant_address.get_user_defined_publisher_list()?
+            {
+                let publisher_qos = user_defined_publisher_address.get_qos()?;
+                let is_discovered_reader_regex_matched_to_publisher = if let Ok(d) = glob_to_regex(
+                    &discovered_reader_data
+                        .subscription_builtin_topic_data()
+                        .partition()
+                        .name,
+                ) {
+                    d.is_match(&publisher_qos.partition.name)
+                } else {
+                    false
+                };
+
+                let is_publisher_regex_matched_to_discovered_reader =
+                    if let Ok(d) = glob_to_regex(&publisher_qos.partition.name) {
+                        d.is_match(
+                            &discovered_reader_data
                                 .subscription_builtin_topic_data()
                                 .partition()
-                                .name
-                                == publisher_qos.partition.name;
-
-                            if is_discovered_reader_regex_matched_to_publisher
-                                || is_publisher_regex_matched_to_discovered_reader
-                                || is_partition_string_matched
-                            {
-                                for data_writer in
-                                    user_defined_publisher_address.data_writer_list()?
-                                {
-                                    data_writer.add_matched_reader(
-                                        discovered_reader_data.clone(),
-                                        default_unicast_locator_list.clone(),
-                                        default_multicast_locator_list.clone(),
-                                        data_writer.clone(),
-                                        user_defined_publisher_address.clone(),
-                                        participant_address.clone(),
-                                    )?;
-                                    data_writer.send_message(
-                                        RtpsMessageHeader::new(
-                                            participant_address.get_protocol_version()?,
-                                            participant_address.get_vendor_id()?,
-                                            participant_address.get_guid()?.prefix(),
-                                        ),
-                                        participant_address.get_udp_transport_write()?,
-                                        participant_address.get_current_time()?,
-                                    )?;
-                                }
-                            }
-                        }
+                                .name,
+                        )
+                    } else {
+                        false
+                    };
+
+                let is_partition_string_matched = discovered_reader_data
+                    .subscription_builtin_topic_data()
+                    .partition()
+                    .name
+                    == publisher_qos.partition.name;
+
+                if is_discovered_reader_regex_matched_to_publisher
+                    || is_publisher_regex_matched_to_discovered_reader
+                    || is_partition_string_matched
+                {
+                    for data_writer in user_defined_publisher_address.data_writer_list()? {
+                        data_writer.add_matched_reader(
+                            discovered_reader_data.clone(),
+                            default_unicast_locator_list.clone(),
+                            default_multicast_locator_list.clone(),
+                            data_writer.clone(),
+                            user_defined_publisher_address.clone(),
+                            participant_address.clone(),
+                        )?;
+                        data_writer.send_message(
+                            RtpsMessageHeader::new(
+                                participant_address.get_protocol_version()?,
+                                participant_address.get_vendor_id()?,
+                                participant_address.get_guid()?.prefix(),
+                            ),
+                            participant_address.get_udp_transport_write()?,
+                            participant_address.get_current_time()?,
+                        )?;
+                    }
+                } else {
+                    for data_writer in user_defined_publisher_address.data_writer_list()? {
+                        data_writer.remove_matched_reader(
+                            discovered_reader_handle,
+                            data_writer.clone(),
+                            user_defined_publisher_address.clone(),
+                            participant_address.clone(),
+                        )?;
                     }
                 }
             }
         }
-        InstanceStateKind::NotAliveDisposed => {
-            for publisher in participant_address.get_user_defined_publisher_list()? {
-                for data_writer in publisher.data_writer_list()? {
-                    data_writer.remove_matched_reader(
-                        discovered_reader_sample.sample_info.instance_handle,
-                        data_writer.clone(),
-                        publisher.clone(),
-                        participant_address.clone(),
-                    )?;
-                }
-            }
-        }
+    }
+    Ok(())
+}
 
-        InstanceStateKind::NotAliveNoWriters => todo!(),
+fn remove_discovered_reader(
+    participant_address: &ActorAddress<DdsDomainParticipant>,
+    discovered_reader_handle: InstanceHandle,
+) -> DdsResult<()> {
+    for publisher in participant_address.get_user_defined_publisher_list()? {
+        for data_writer in publisher.data_writer_list()? {
+            data_writer.remove_matched_reader(
+                discovered_reader_handle,
+                data_writer.clone(),
+                publisher.clone(),
+                participant_address.clone(),
+            )?;
+        }
     }
 
-    Ok(())
+    participant_address.discovered_reader_remove(discovered_reader_handle)
 }
 
 fn discover_matched_topics(
@@ -1160,6 +1403,12 @@ fn discover_matched_topics(
 ) -> DdsResult<()> {
     match discovered_topic_sample.sample_info.instance_state {
         InstanceStateKind::Alive => {
+            if participant_address
+                .is_topic_ignored(discovered_topic_sample.sample_info.instance_handle)?
+            {
+                return Ok(());
+            }
+
             if let Some(topic_data) = discovered_topic_sample.data.as_ref() {
                 for topic in participant_address.get_user_defined_topic_list()? {
                     topic.process_discovered_topic(topic_data.clone())?;
@@ -1171,8 +1420,10 @@ fn discover_matched_topics(
                 )?;
             }
         }
-        InstanceStateKind::NotAliveDisposed => todo!(),
-        InstanceStateKind::NotAliveNoWriters => todo!(),
+        InstanceStateKind::NotAliveDisposed | InstanceStateKind::NotAliveNoWriters => {
+            participant_address
+                .discovered_topic_remove(discovered_topic_sample.sample_info.instance_handle)?;
+        }
     }
 
     Ok(())
@@ -7,6 +7,9 @@ pub mod error;
 /// Classes related to the instance handle that identifies the entities.
 pub mod instance;
 
+/// Classes related to the handles which delete the entities when dropped.
+pub mod owned;
+
 /// Classes related to the qos policies for the different entities.
 pub mod qos;
 
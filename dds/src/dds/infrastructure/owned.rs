@@ -0,0 +1,76 @@
+use std::{
+    ops::Deref,
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
+};
+
+use super::error::DdsResult;
+
+/// This trait is implemented by the entities which can be held by an [`Owned`] handle, that is the
+/// [`Publisher`](crate::publication::publisher::Publisher), [`Subscriber`](crate::subscription::subscriber::Subscriber),
+/// [`Topic`](crate::topic_definition::topic::Topic), [`DataWriter`](crate::publication::data_writer::DataWriter) and
+/// [`DataReader`](crate::subscription::data_reader::DataReader).
+pub trait OwnedEntity {
+    #[doc(hidden)]
+    fn delete_entity(&self) -> DdsResult<()>;
+}
+
+/// [`Owned`] is an opt-in handle which deletes the entity it holds when the last clone of the handle is dropped. The entity is deleted
+/// in the same way as by the delete operation of its factory, e.g. [`Publisher::delete_datawriter()`](crate::publication::publisher::Publisher::delete_datawriter),
+/// and in particular the deletion of a [`DataWriter`](crate::publication::data_writer::DataWriter) or
+/// [`DataReader`](crate::subscription::data_reader::DataReader) is announced to the remote participants.
+/// Errors during the deletion on drop are ignored. This means that an entity which still contains other entities, or a
+/// [`Topic`](crate::topic_definition::topic::Topic) still in use by a [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// or [`DataReader`](crate::subscription::data_reader::DataReader), is not deleted when its handle is dropped so the handles
+/// of the contained entities must be dropped first. Use [`Owned::delete()`] to delete the entity and get the error instead.
+/// Other handles to the same entity, for example the one returned by
+/// [`DataWriter::get_publisher()`](crate::publication::data_writer::DataWriter::get_publisher), don't keep the entity alive.
+pub struct Owned<E: OwnedEntity>(Arc<OwnedEntityHolder<E>>);
+
+impl<E: OwnedEntity> Owned<E> {
+    /// Create a new [`Owned`] handle which deletes the `entity` when it is dropped.
+    pub fn new(entity: E) -> Self {
+        Self(Arc::new(OwnedEntityHolder {
+            entity,
+            is_deleted: AtomicBool::new(false),
+        }))
+    }
+
+    /// Delete the entity right away returning the error of the deletion, if any. If the deletion succeeds the entity is not deleted
+    /// again when the handle is dropped, otherwise dropping the last clone of the handle tries to delete it once more.
+    pub fn delete(&self) -> DdsResult<()> {
+        self.0.entity.delete_entity()?;
+        self.0.is_deleted.store(true, atomic::Ordering::Release);
+        Ok(())
+    }
+}
+
+impl<E: OwnedEntity> Clone for Owned<E> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<E: OwnedEntity> Deref for Owned<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.entity
+    }
+}
+
+struct OwnedEntityHolder<E: OwnedEntity> {
+    entity: E,
+    is_deleted: AtomicBool,
+}
+
+impl<E: OwnedEntity> Drop for OwnedEntityHolder<E> {
+    fn drop(&mut self) {
+        if !self.is_deleted.load(atomic::Ordering::Acquire) {
+            // Errors can not be returned from drop. Owned::delete() is available to handle them
+            self.entity.delete_entity().ok();
+        }
+    }
+}
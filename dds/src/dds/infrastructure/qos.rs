@@ -5,12 +5,13 @@ use crate::infrastructure::{
 
 use super::{
     qos_policy::{
-        DeadlineQosPolicy, DestinationOrderQosPolicy, DurabilityQosPolicy, EntityFactoryQosPolicy,
-        GroupDataQosPolicy, HistoryQosPolicy, HistoryQosPolicyKind, LatencyBudgetQosPolicy,
-        LifespanQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy, PartitionQosPolicy,
-        PresentationQosPolicy, ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy,
-        ReliabilityQosPolicyKind, ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy,
-        TopicDataQosPolicy, TransportPriorityQosPolicy, UserDataQosPolicy,
+        DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
+        DurabilityQosPolicy, EntityFactoryQosPolicy, GroupDataQosPolicy, HistoryQosPolicy,
+        HistoryQosPolicyKind, LatencyBudgetQosPolicy, LifespanQosPolicy, LivelinessQosPolicy,
+        OwnershipQosPolicy, PartitionQosPolicy, PresentationQosPolicy,
+        ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+        ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, TopicDataQosPolicy,
+        TransportPriorityQosPolicy, TypeConsistencyEnforcementQosPolicy, UserDataQosPolicy,
         WriterDataLifecycleQosPolicy,
     },
     time::DurationKind,
@@ -70,6 +71,7 @@ pub struct DataWriterQos {
     pub user_data: UserDataQosPolicy,
     pub ownership: OwnershipQosPolicy,
     pub writer_data_lifecycle: WriterDataLifecycleQosPolicy,
+    pub representation: DataRepresentationQosPolicy,
 }
 
 impl Default for DataWriterQos {
@@ -94,6 +96,7 @@ impl Default for DataWriterQos {
             lifespan: LifespanQosPolicy::default(),
             transport_priority: TransportPriorityQosPolicy::default(),
             writer_data_lifecycle: WriterDataLifecycleQosPolicy::default(),
+            representation: DataRepresentationQosPolicy::default(),
         }
     }
 }
@@ -128,6 +131,7 @@ impl DataWriterQos {
             || self.history != other.history
             || self.resource_limits != other.resource_limits
             || self.ownership != other.ownership
+            || self.representation != other.representation
         {
             Err(DdsError::ImmutablePolicy)
         } else {
@@ -170,6 +174,8 @@ pub struct DataReaderQos {
     pub ownership: OwnershipQosPolicy,
     pub time_based_filter: TimeBasedFilterQosPolicy,
     pub reader_data_lifecycle: ReaderDataLifecycleQosPolicy,
+    pub representation: DataRepresentationQosPolicy,
+    pub type_consistency: TypeConsistencyEnforcementQosPolicy,
 }
 
 impl Default for DataReaderQos {
@@ -193,6 +199,8 @@ impl Default for DataReaderQos {
             ownership: OwnershipQosPolicy::default(),
             time_based_filter: TimeBasedFilterQosPolicy::default(),
             reader_data_lifecycle: ReaderDataLifecycleQosPolicy::default(),
+            representation: DataRepresentationQosPolicy::default(),
+            type_consistency: TypeConsistencyEnforcementQosPolicy::default(),
         }
     }
 }
@@ -233,6 +241,8 @@ impl DataReaderQos {
             || self.history != other.history
             || self.resource_limits != other.resource_limits
             || self.ownership != other.ownership
+            || self.representation != other.representation
+            || self.type_consistency != other.type_consistency
         {
             Err(DdsError::ImmutablePolicy)
         } else {
@@ -256,6 +266,7 @@ pub struct TopicQos {
     pub transport_priority: TransportPriorityQosPolicy,
     pub lifespan: LifespanQosPolicy,
     pub ownership: OwnershipQosPolicy,
+    pub representation: DataRepresentationQosPolicy,
 }
 
 impl Default for TopicQos {
@@ -279,6 +290,7 @@ impl Default for TopicQos {
             transport_priority: TransportPriorityQosPolicy::default(),
             lifespan: LifespanQosPolicy::default(),
             ownership: OwnershipQosPolicy::default(),
+            representation: DataRepresentationQosPolicy::default(),
         }
     }
 }
@@ -313,6 +325,7 @@ impl TopicQos {
             || self.history != other.history
             || self.resource_limits != other.resource_limits
             || self.ownership != other.ownership
+            || self.representation != other.representation
         {
             Err(DdsError::ImmutablePolicy)
         } else {
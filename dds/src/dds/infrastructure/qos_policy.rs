@@ -101,6 +101,8 @@ const TOPICDATA_QOS_POLICY_NAME: &str = "TopicData";
 const TRANSPORTPRIORITY_QOS_POLICY_NAME: &str = "TransportPriority";
 const GROUPDATA_QOS_POLICY_NAME: &str = "GroupData";
 const LIFESPAN_QOS_POLICY_NAME: &str = "Lifespan";
+const DATAREPRESENTATION_QOS_POLICY_NAME: &str = "DataRepresentation";
+const TYPECONSISTENCYENFORCEMENT_QOS_POLICY_NAME: &str = "TypeConsistencyEnforcement";
 
 pub const INVALID_QOS_POLICY_ID: QosPolicyId = 0;
 pub const USERDATA_QOS_POLICY_ID: QosPolicyId = 1;
@@ -124,6 +126,8 @@ pub const GROUPDATA_QOS_POLICY_ID: QosPolicyId = 19;
 pub const TRANSPORTPRIORITY_QOS_POLICY_ID: QosPolicyId = 20;
 pub const LIFESPAN_QOS_POLICY_ID: QosPolicyId = 21;
 pub const DURABILITYSERVICE_QOS_POLICY_ID: QosPolicyId = 22;
+pub const DATAREPRESENTATION_QOS_POLICY_ID: QosPolicyId = 23;
+pub const TYPECONSISTENCYENFORCEMENT_QOS_POLICY_ID: QosPolicyId = 24;
 
 /// This policy allows the application to attach additional information to the created Entity objects such that when
 /// a remote application discovers their existence it can access that information and use it for its own purposes.
@@ -277,6 +281,7 @@ impl Default for DurabilityQosPolicy {
 pub enum PresentationQosPolicyAccessScopeKind {
     Instance,
     Topic,
+    Group,
 }
 
 impl PartialOrd for PresentationQosPolicyAccessScopeKind {
@@ -284,11 +289,18 @@ impl PartialOrd for PresentationQosPolicyAccessScopeKind {
         match self {
             PresentationQosPolicyAccessScopeKind::Instance => match other {
                 PresentationQosPolicyAccessScopeKind::Instance => Some(Ordering::Equal),
-                PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Less),
+                PresentationQosPolicyAccessScopeKind::Topic
+                | PresentationQosPolicyAccessScopeKind::Group => Some(Ordering::Less),
             },
             PresentationQosPolicyAccessScopeKind::Topic => match other {
                 PresentationQosPolicyAccessScopeKind::Instance => Some(Ordering::Greater),
                 PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Equal),
+                PresentationQosPolicyAccessScopeKind::Group => Some(Ordering::Less),
+            },
+            PresentationQosPolicyAccessScopeKind::Group => match other {
+                PresentationQosPolicyAccessScopeKind::Instance
+                | PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Greater),
+                PresentationQosPolicyAccessScopeKind::Group => Some(Ordering::Equal),
             },
         }
     }
@@ -969,6 +981,153 @@ impl Default for ReaderDataLifecycleQosPolicy {
     }
 }
 
+pub type DataRepresentationId = i16;
+pub const XCDR_DATA_REPRESENTATION: DataRepresentationId = 0;
+pub const XML_DATA_REPRESENTATION: DataRepresentationId = 1;
+pub const XCDR2_DATA_REPRESENTATION: DataRepresentationId = 2;
+
+/// This policy controls the data representations used to serialize the samples of the [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// and accepted by the [`DataReader`](crate::subscription::data_reader::DataReader).
+///
+/// A [`DataWriter`](crate::publication::data_writer::DataWriter) serializes its samples using the first element of the value and a
+/// [`DataReader`](crate::subscription::data_reader::DataReader) accepts any of the representations in the value. An empty value selects
+/// the default for the type: the [`DataWriter`](crate::publication::data_writer::DataWriter) uses the representation of the type and the
+/// [`DataReader`](crate::subscription::data_reader::DataReader) accepts both [`XCDR_DATA_REPRESENTATION`] and [`XCDR2_DATA_REPRESENTATION`].
+/// A remote entity announcing an empty value is interpreted as using [`XCDR_DATA_REPRESENTATION`].
+/// The [`DataWriter`](crate::publication::data_writer::DataWriter) and [`DataReader`](crate::subscription::data_reader::DataReader) only match if the
+/// representation of the [`DataWriter`](crate::publication::data_writer::DataWriter) is accepted by the [`DataReader`](crate::subscription::data_reader::DataReader).
+#[derive(Debug, Default, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DataRepresentationQosPolicy {
+    pub value: Vec<DataRepresentationId>,
+}
+
+impl QosPolicy for DataRepresentationQosPolicy {
+    fn name(&self) -> &str {
+        DATAREPRESENTATION_QOS_POLICY_NAME
+    }
+}
+
+pub(crate) fn is_data_representation_compatible(
+    writer_data_representation: &DataRepresentationQosPolicy,
+    reader_data_representation: &DataRepresentationQosPolicy,
+) -> bool {
+    let writer_data_representation_id = writer_data_representation
+        .value
+        .first()
+        .unwrap_or(&XCDR_DATA_REPRESENTATION);
+    if reader_data_representation.value.is_empty() {
+        writer_data_representation_id == &XCDR_DATA_REPRESENTATION
+    } else {
+        reader_data_representation
+            .value
+            .contains(writer_data_representation_id)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TypeConsistencyKind {
+    DisallowTypeCoercion,
+    AllowTypeCoercion,
+}
+
+const DISALLOW_TYPE_COERCION: u16 = 0;
+const ALLOW_TYPE_COERCION: u16 = 1;
+
+impl serde::Serialize for TypeConsistencyKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(
+            &match self {
+                TypeConsistencyKind::DisallowTypeCoercion => DISALLOW_TYPE_COERCION,
+                TypeConsistencyKind::AllowTypeCoercion => ALLOW_TYPE_COERCION,
+            },
+            serializer,
+        )
+    }
+}
+
+struct TypeConsistencyKindVisitor;
+
+impl<'de> serde::de::Visitor<'de> for TypeConsistencyKindVisitor {
+    type Value = TypeConsistencyKind;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str(&format!(
+            "value `{:}` or `{:}`",
+            DISALLOW_TYPE_COERCION, ALLOW_TYPE_COERCION
+        ))
+    }
+
+    fn visit_u16<E>(self, value: u16) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(match value {
+            DISALLOW_TYPE_COERCION => TypeConsistencyKind::DisallowTypeCoercion,
+            ALLOW_TYPE_COERCION => TypeConsistencyKind::AllowTypeCoercion,
+            _ => {
+                return Err(serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Unsigned(value as u64),
+                    &self,
+                ))
+            }
+        })
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TypeConsistencyKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_u16(TypeConsistencyKindVisitor)
+    }
+}
+
+/// This policy controls the rules of the DDS-XTypes standard used to decide whether the type of a
+/// [`DataWriter`](crate::publication::data_writer::DataWriter) is consistent with the type of a
+/// [`DataReader`](crate::subscription::data_reader::DataReader) with the same topic name.
+///
+/// The types are compared using the type information announced by the entities, which is available for the types
+/// deriving [`DdsType`](crate::topic_definition::type_support::DdsType) with an extensibility attribute. With
+/// [`TypeConsistencyKind::DisallowTypeCoercion`] the types must be the same, with [`TypeConsistencyKind::AllowTypeCoercion`]
+/// the type of the [`DataReader`](crate::subscription::data_reader::DataReader) must be assignable from the type of the
+/// [`DataWriter`](crate::publication::data_writer::DataWriter), for example a mutable type to which optional members have been added.
+/// The `ignore_*` flags relax the comparison of the bounds and member names, `prevent_type_widening` rejects a writer type with
+/// members unknown to the reader type and `force_type_validation` rejects the entities which do not announce their type information.
+/// When the types are not consistent the entities do not match and the [`InconsistentTopicStatus`](crate::infrastructure::status::InconsistentTopicStatus)
+/// of the local [`Topic`](crate::topic_definition::topic::Topic) is changed.
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TypeConsistencyEnforcementQosPolicy {
+    pub kind: TypeConsistencyKind,
+    pub ignore_sequence_bounds: bool,
+    pub ignore_string_bounds: bool,
+    pub ignore_member_names: bool,
+    pub prevent_type_widening: bool,
+    pub force_type_validation: bool,
+}
+
+impl QosPolicy for TypeConsistencyEnforcementQosPolicy {
+    fn name(&self) -> &str {
+        TYPECONSISTENCYENFORCEMENT_QOS_POLICY_NAME
+    }
+}
+
+impl Default for TypeConsistencyEnforcementQosPolicy {
+    fn default() -> Self {
+        Self {
+            kind: TypeConsistencyKind::AllowTypeCoercion,
+            ignore_sequence_bounds: true,
+            ignore_string_bounds: true,
+            ignore_member_names: false,
+            prevent_type_widening: false,
+            force_type_validation: false,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,19 +2,25 @@ use std::{marker::PhantomData, time::Instant};
 
 use crate::{
     builtin_topics::SubscriptionBuiltinTopicData,
+    domain::domain_participant_factory::process_sedp_discovery,
     implementation::{
         data_representation_builtin_endpoints::discovered_writer_data::DiscoveredWriterData,
         dds::{
+            dds_data_writer_listener::DdsDataWriterListener,
             dds_domain_participant::DdsDomainParticipant,
-            nodes::{DataWriterNodeKind, PublisherNode},
+            nodes::{DataWriterNodeKind, PublisherNode, TopicNode, TopicNodeKind},
         },
-        rtps::messages::overall_structure::RtpsMessageHeader,
-        utils::actor::ActorAddress,
+        rtps::{
+            messages::{overall_structure::RtpsMessageHeader, submessage_elements::Data},
+            types::USER_DEFINED_WRITER_WITH_KEY,
+        },
+        utils::actor::{spawn_actor, ActorAddress},
     },
     infrastructure::{
         condition::StatusCondition,
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
+        owned::OwnedEntity,
         qos::{DataWriterQos, QosKind, TopicQos},
         status::{
             LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
@@ -26,8 +32,9 @@ use crate::{
     topic_definition::{
         topic::Topic,
         type_support::{
-            dds_serialize_key, dds_serialize_key_to_bytes, dds_serialize_to_bytes, DdsGetKey,
-            DdsHasKey, DdsRepresentation,
+            dds_key_hash, dds_serialize_key, dds_serialize_key_to_bytes, dds_serialize_to_bytes,
+            dds_set_key_fields_from_serialized_key, DdsGetKey, DdsHasKey, DdsSerialize,
+            DdsSetKeyFields,
         },
     },
 };
@@ -67,9 +74,15 @@ impl<Foo> DataWriter<Foo> {
 //     }
 // }
 
+impl<Foo> OwnedEntity for DataWriter<Foo> {
+    fn delete_entity(&self) -> DdsResult<()> {
+        self.get_publisher()?.delete_datawriter(self)
+    }
+}
+
 impl<Foo> DataWriter<Foo>
 where
-    Foo: DdsGetKey + DdsHasKey + DdsRepresentation + serde::Serialize,
+    Foo: DdsGetKey + DdsHasKey + DdsSerialize,
 {
     /// This operation informs the Service that the application will be modifying a particular instance.
     /// It gives an opportunity to the Service to pre-configure itself to improve performance. It takes
@@ -100,12 +113,17 @@ where
     /// For details see [`DestinationOrderQosPolicy`](crate::infrastructure::qos_policy::DestinationOrderQosPolicy).
     pub fn register_instance_w_timestamp(
         &self,
-        _instance: &Foo,
-        _timestamp: Time,
+        instance: &Foo,
+        timestamp: Time,
     ) -> DdsResult<Option<InstanceHandle>> {
         match &self.0 {
-            DataWriterNodeKind::UserDefined(_) => todo!(),
-            DataWriterNodeKind::Listener(_) => todo!(),
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
+                dw.address().register_instance_w_timestamp(
+                    dds_serialize_key(instance)?,
+                    dds_key_hash(instance)?,
+                    timestamp,
+                )?
+            }
         }
     }
 
@@ -161,7 +179,14 @@ where
         handle: Option<InstanceHandle>,
         timestamp: Time,
     ) -> DdsResult<()> {
-        if Foo::HAS_KEY {
+        // The kind of the writer is used since the keyedness of some types, like DynamicData,
+        // is only known at runtime
+        let has_key = match &self.0 {
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
+                dw.address().guid()?.entity_id().entity_kind() == USER_DEFINED_WRITER_WITH_KEY
+            }
+        };
+        if has_key {
             let instance_handle = match handle {
                 Some(h) => {
                     if let Some(stored_handle) = self.lookup_instance(instance)? {
@@ -207,10 +232,15 @@ where
     /// fields that form the key inside the `key_holder` instance.
     /// This operation returns [`DdsError::BadParameter`](crate::infrastructure::error::DdsError) if the `handle` does not
     /// correspond to an existing data object known to the [`DataWriter`].
-    pub fn get_key_value(&self, _key_holder: &mut Foo, _handle: InstanceHandle) -> DdsResult<()> {
+    pub fn get_key_value(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()>
+    where
+        Foo: DdsSetKeyFields,
+    {
         match &self.0 {
-            DataWriterNodeKind::UserDefined(_) => todo!(),
-            DataWriterNodeKind::Listener(_) => todo!(),
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
+                let serialized_key = dw.address().get_key_value(handle)??;
+                dds_set_key_fields_from_serialized_key(key_holder, serialized_key.as_ref())
+            }
         }
     }
 
@@ -222,7 +252,7 @@ where
     pub fn lookup_instance(&self, instance: &Foo) -> DdsResult<Option<InstanceHandle>> {
         match &self.0 {
             DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
-                dw.address().lookup_instance(dds_serialize_key(instance)?)?
+                dw.address().lookup_instance(dds_key_hash(instance)?)?
             }
         }
     }
@@ -279,17 +309,26 @@ where
         handle: Option<InstanceHandle>,
         timestamp: Time,
     ) -> DdsResult<()> {
-        let serialized_data = dds_serialize_to_bytes(data)?;
-
         match &self.0 {
             DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
+                let representation = Foo::REPRESENTATION
+                    .with_data_representation(&dw.address().get_qos()?.representation)?;
+                let serialized_data =
+                    Data::from_writer(|writer| data.serialize_data(&representation, writer))?;
+                let instance_handle = dds_key_hash(data)?;
+
                 dw.address().write_w_timestamp(
                     serialized_data,
                     dds_serialize_key(data)?,
+                    instance_handle,
                     handle,
                     timestamp,
                 )??;
 
+                let now = dw.parent_participant().get_current_time()?;
+                dw.address().on_instance_written(instance_handle, now)?;
+                dw.parent_participant().assert_liveliness(now)?;
+
                 dw.address().send_message(
                     RtpsMessageHeader::new(
                         dw.parent_participant().get_protocol_version()?,
@@ -297,7 +336,7 @@ where
                         dw.parent_participant().get_guid()?.prefix(),
                     ),
                     dw.parent_participant().get_udp_transport_write()?,
-                    dw.parent_participant().get_current_time()?,
+                    now,
                 )?;
 
                 Ok(())
@@ -387,7 +426,7 @@ impl<Foo> DataWriter<Foo> {
     /// Otherwise the operation will return immediately with [`Ok`].
     pub fn wait_for_acknowledgments(&self, max_wait: Duration) -> DdsResult<()> {
         match &self.0 {
-            DataWriterNodeKind::UserDefined(dw) => {
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
                 let start_time = Instant::now();
                 while start_time.elapsed() < std::time::Duration::from(max_wait) {
                     if dw.address().are_all_changes_acknowledge()? {
@@ -397,80 +436,34 @@ impl<Foo> DataWriter<Foo> {
 
                 Err(DdsError::Timeout)
             }
-            DataWriterNodeKind::Listener(_) => todo!(),
         }
     }
 
     /// This operation allows access to the [`LivelinessLostStatus`].
     pub fn get_liveliness_lost_status(&self) -> DdsResult<LivelinessLostStatus> {
-        todo!()
-        // match &self.0 {
-        //     DataWriterNodeKind::UserDefined(w) => {
-        //         let status = THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //             .get_participant_mut(&w.guid().prefix(), |dp| {
-        //                 crate::implementation::behavior::user_defined_data_writer::get_liveliness_lost_status(dp.ok_or(DdsError::AlreadyDeleted)?,w.guid(),
-        //                 w.parent_publisher(),)
-        //             })?;
-        //         THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_data_writer_listener(
-        //             &w.guid(),
-        //             |data_writer_listener| {
-        //                 if let Some(l) = data_writer_listener {
-        //                     l.remove_communication_state(StatusKind::LivelinessLost);
-        //                 }
-        //             },
-        //         );
-        //         Ok(status)
-        //     }
-        //     DataWriterNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.0 {
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
+                dw.address().get_liveliness_lost_status()
+            }
+        }
     }
 
     /// This operation allows access to the [`OfferedDeadlineMissedStatus`].
     pub fn get_offered_deadline_missed_status(&self) -> DdsResult<OfferedDeadlineMissedStatus> {
-        todo!()
-        // match &self.0 {
-        //     DataWriterNodeKind::UserDefined(w) => {
-        //         let status = THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //             .get_participant_mut(&w.guid().prefix(), |dp| {
-        //                 crate::implementation::behavior::user_defined_data_writer::get_offered_deadline_missed_status(dp.ok_or(DdsError::AlreadyDeleted)?, w.guid(),
-        //                 w.parent_publisher(),)
-        //             })?;
-        //         THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_data_writer_listener(
-        //             &w.guid(),
-        //             |data_writer_listener| {
-        //                 if let Some(l) = data_writer_listener {
-        //                     l.remove_communication_state(StatusKind::OfferedDeadlineMissed);
-        //                 }
-        //             },
-        //         );
-        //         Ok(status)
-        //     }
-        //     DataWriterNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.0 {
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
+                dw.address().get_offered_deadline_missed_status()
+            }
+        }
     }
 
     /// This operation allows access to the [`OfferedIncompatibleQosStatus`].
     pub fn get_offered_incompatible_qos_status(&self) -> DdsResult<OfferedIncompatibleQosStatus> {
-        todo!()
-        // match &self.0 {
-        //     DataWriterNodeKind::UserDefined(w) => {
-        //         let status = THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //             .get_participant_mut(&w.guid().prefix(), |dp| {
-        //                 crate::implementation::behavior::user_defined_data_writer::get_offered_incompatible_qos_status(dp.ok_or(DdsError::AlreadyDeleted)?, w.guid(),
-        //                 w.parent_publisher(),)
-        //             })?;
-        //         THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_data_writer_listener(
-        //             &w.guid(),
-        //             |data_writer_listener| {
-        //                 if let Some(l) = data_writer_listener {
-        //                     l.remove_communication_state(StatusKind::OfferedIncompatibleQos);
-        //                 }
-        //             },
-        //         );
-        //         Ok(status)
-        //     }
-        //     DataWriterNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.0 {
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
+                dw.address().get_offered_incompatible_qos_status()
+            }
+        }
     }
 
     /// This operation allows access to the [`PublicationMatchedStatus`].
@@ -485,8 +478,12 @@ impl<Foo> DataWriter<Foo> {
     /// This operation returns the [`Topic`] associated with the [`DataWriter`]. This is the same [`Topic`] that was used to create the [`DataWriter`].
     pub fn get_topic(&self) -> DdsResult<Topic> {
         match &self.0 {
-            DataWriterNodeKind::UserDefined(_) => todo!(),
-            DataWriterNodeKind::Listener(_) => todo!(),
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
+                Ok(Topic::new(TopicNodeKind::UserDefined(TopicNode::new(
+                    dw.topic_address()?,
+                    dw.parent_participant().clone(),
+                ))))
+            }
         }
     }
 
@@ -512,8 +509,15 @@ impl<Foo> DataWriter<Foo> {
     /// if the application is not writing data regularly.
     pub fn assert_liveliness(&self) -> DdsResult<()> {
         match &self.0 {
-            DataWriterNodeKind::UserDefined(_) => todo!(),
-            DataWriterNodeKind::Listener(_) => todo!(),
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
+                if !dw.address().is_enabled()? {
+                    return Err(DdsError::NotEnabled);
+                }
+
+                let now = dw.parent_participant().get_current_time()?;
+                dw.address().assert_liveliness(now)?;
+                dw.parent_participant().assert_liveliness(now)
+            }
         }
     }
 
@@ -553,7 +557,7 @@ impl<Foo> DataWriter<Foo> {
 /// This implementation block contains the Entity operations for the [`DataWriter`].
 impl<Foo> DataWriter<Foo>
 where
-    Foo: DdsHasKey + serde::Serialize + 'static,
+    Foo: DdsHasKey + DdsSerialize + 'static,
 {
     /// This operation is used to set the QoS policies of the Entity and replacing the values of any policies previously set.
     /// Certain policies are “immutable;” they can only be set at Entity creation time, or before the entity is made enabled.
@@ -570,14 +574,18 @@ where
     pub fn set_qos(&self, qos: QosKind<DataWriterQos>) -> DdsResult<()> {
         match &self.0 {
             DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
-                let q = match qos {
+                let mut q = match qos {
                     QosKind::Default => dw.parent_publisher().get_default_datawriter_qos()?,
                     QosKind::Specific(q) => {
                         q.is_consistent()?;
                         q
                     }
                 };
-                dw.address().set_qos(q)?;
+                if q.representation.value.is_empty() {
+                    q.representation.value = vec![Foo::REPRESENTATION.data_representation_id()];
+                }
+                Foo::REPRESENTATION.with_data_representation(&q.representation)?;
+                dw.address().set_qos(q)??;
 
                 if dw.address().is_enabled()? {
                     announce_data_writer(
@@ -590,6 +598,8 @@ where
                                 .get_default_multicast_locator_list()?,
                         )?,
                     )?;
+
+                    process_sedp_discovery(dw.parent_participant())?;
                 }
 
                 Ok(())
@@ -614,22 +624,17 @@ where
     /// will be removed.
     pub fn set_listener(
         &self,
-        _a_listener: Option<Box<dyn DataWriterListener<Foo = Foo> + Send + Sync>>,
-        _mask: &[StatusKind],
+        a_listener: Option<Box<dyn DataWriterListener<Foo = Foo> + Send + Sync>>,
+        mask: &[StatusKind],
     ) -> DdsResult<()> {
-        todo!()
-        // match &self.0 {
-        //     DataWriterNodeKind::UserDefined(w) =>
-        //     {
-        //         #[allow(clippy::redundant_closure)]
-        //         w.set_listener(
-        //             a_listener
-        //                 .map::<Box<dyn AnyDataWriterListener + Send + Sync>, _>(|l| Box::new(l)),
-        //             mask,
-        //         )
-        //     }
-        //     DataWriterNodeKind::Listener(_) => Err(DdsError::IllegalOperation),
-        // }
+        match &self.0 {
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
+                dw.address().set_listener(
+                    a_listener.map(|l| spawn_actor(DdsDataWriterListener::new(Box::new(l)))),
+                    mask.to_vec(),
+                )
+            }
+        }
     }
 
     /// This operation allows access to the [`StatusCondition`] associated with the Entity. The returned
@@ -637,10 +642,9 @@ where
     /// that affect the Entity.
     pub fn get_statuscondition(&self) -> DdsResult<StatusCondition> {
         match &self.0 {
-            DataWriterNodeKind::UserDefined(dw) => {
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
                 dw.address().get_statuscondition().map(StatusCondition::new)
             }
-            DataWriterNodeKind::Listener(_) => todo!(),
         }
     }
 
@@ -651,16 +655,13 @@ where
     /// The list of statuses returned by the [`Self::get_status_changes`] operation refers to the status that are triggered on the Entity itself
     /// and does not include statuses that apply to contained entities.
     pub fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
-        // match &self.0 {
-        //     DataWriterNodeKind::UserDefined(w) => THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //         .get_data_writer_listener(&w.guid(), |data_writer_listener| {
-        //             Ok(data_writer_listener
-        //                 .ok_or(DdsError::AlreadyDeleted)?
-        //                 .get_status_changes())
-        //         }),
-        //     DataWriterNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.0 {
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => Ok(dw
+                .address()
+                .get_statuscondition()?
+                .read_lock()
+                .get_status_changes()),
+        }
     }
 
     /// This operation enables the Entity. Entity objects can be created either enabled or disabled. This is controlled by the value of
@@ -685,7 +686,7 @@ where
     /// enabled are “inactive,” that is, the operation [`StatusCondition::get_trigger_value()`] will always return `false`.
     pub fn enable(&self) -> DdsResult<()> {
         match &self.0 {
-            DataWriterNodeKind::UserDefined(w) => {
+            DataWriterNodeKind::UserDefined(w) | DataWriterNodeKind::Listener(w) => {
                 if !w.address().is_enabled()? {
                     w.address().enable()?;
 
@@ -702,8 +703,6 @@ where
                 }
                 Ok(())
             }
-
-            DataWriterNodeKind::Listener(_) => Err(DdsError::IllegalOperation),
         }
     }
 
@@ -719,7 +718,7 @@ where
 
 pub trait AnyDataWriter {}
 
-fn announce_data_writer(
+pub(crate) fn announce_data_writer(
     domain_participant: &ActorAddress<DdsDomainParticipant>,
     discovered_writer_data: &DiscoveredWriterData,
 ) -> DdsResult<()> {
@@ -733,8 +732,9 @@ fn announce_data_writer(
         .find(|x| x.get_type_name().unwrap() == "DiscoveredWriterData")
     {
         sedp_writer_announcer.write_w_timestamp(
-            serialized_data,
+            Data::new(serialized_data),
             dds_serialize_key(discovered_writer_data)?,
+            dds_key_hash(discovered_writer_data)?,
             None,
             timestamp,
         )??;
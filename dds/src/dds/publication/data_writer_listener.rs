@@ -3,13 +3,13 @@ use crate::{
         LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
         PublicationMatchedStatus,
     },
-    topic_definition::type_support::DdsHasKey,
+    topic_definition::type_support::{DdsHasKey, DdsSerialize},
 };
 
 use super::data_writer::DataWriter;
 
 pub trait DataWriterListener {
-    type Foo: DdsHasKey + serde::Serialize;
+    type Foo: DdsHasKey + DdsSerialize;
 
     fn on_liveliness_lost(
         &mut self,
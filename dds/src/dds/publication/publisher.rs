@@ -1,9 +1,15 @@
 use crate::{
-    domain::domain_participant::DomainParticipant,
+    domain::{
+        domain_participant::{
+            check_registered_type, check_type_of_type_name, delete_publisher, DomainParticipant,
+        },
+        domain_participant_factory::process_sedp_discovery,
+    },
     implementation::{
         dds::{
             dds_data_writer::DdsDataWriter,
             dds_data_writer_listener::DdsDataWriterListener,
+            dds_publisher_listener::DdsPublisherListener,
             nodes::{DataWriterNode, DataWriterNodeKind, PublisherNode},
         },
         rtps::{
@@ -20,15 +26,18 @@ use crate::{
         condition::StatusCondition,
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
+        owned::OwnedEntity,
         qos::{DataWriterQos, PublisherQos, QosKind, TopicQos},
         status::StatusKind,
         time::{Duration, DURATION_ZERO},
     },
-    publication::data_writer::DataWriter,
-    topic_definition::topic::Topic,
-    topic_definition::type_support::{DdsGetKey, DdsHasKey},
+    publication::data_writer::{announce_data_writer, DataWriter},
+    topic_definition::topic::{Topic, TopicType},
+    topic_definition::type_support::{DdsGetKey, DdsHasKey, DdsSerialize},
 };
 
+use std::time::Instant;
+
 use super::{data_writer_listener::DataWriterListener, publisher_listener::PublisherListener};
 
 /// The [`Publisher`] acts on the behalf of one or several [`DataWriter`] objects that belong to it. When it is informed of a change to the
@@ -62,6 +71,12 @@ impl Publisher {
 //     }
 // }
 
+impl OwnedEntity for Publisher {
+    fn delete_entity(&self) -> DdsResult<()> {
+        delete_publisher(self.0.parent_participant(), self)
+    }
+}
+
 impl Publisher {
     /// This operation creates a [`DataWriter`]. The returned [`DataWriter`] will be attached and belongs to the [`Publisher`].
     /// The [`DataWriter`] returned by this operation has an associated [`Topic`] and a type `Foo`.
@@ -69,6 +84,8 @@ impl Publisher {
     /// [`Publisher`]. If the [`Topic`] was created from a different [`DomainParticipant`], the operation will fail and
     /// return a [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError). In case of failure, the operation
     /// will return an error and no writer will be created.
+    /// The operation will also fail and return [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError) if
+    /// the type name of the [`Topic`] has been registered for a type different from `Foo`.
     ///
     /// The special value [`QosKind::Default`] can be used to indicate that the [`DataWriter`] should be created with the
     /// default qos set in the factory. The use of this value is equivalent to the application obtaining the default
@@ -82,14 +99,16 @@ impl Publisher {
 
     pub fn create_datawriter<Foo>(
         &self,
-        a_topic: &Topic,
+        a_topic: &Topic<impl TopicType<Foo>>,
         qos: QosKind<DataWriterQos>,
         a_listener: Option<Box<dyn DataWriterListener<Foo = Foo> + Send + Sync>>,
         mask: &[StatusKind],
     ) -> DdsResult<DataWriter<Foo>>
     where
-        Foo: DdsHasKey + DdsGetKey + serde::Serialize + Send + 'static,
+        Foo: DdsHasKey + DdsGetKey + DdsSerialize + Send + 'static,
     {
+        check_registered_type::<Foo>(self.0.parent_participant(), &a_topic.get_type_name()?)?;
+
         let default_unicast_locator_list = self
             .0
             .parent_participant()
@@ -100,16 +119,26 @@ impl Publisher {
             .get_default_multicast_locator_list()?;
         let data_max_size_serialized = self.0.parent_participant().data_max_size_serialized()?;
 
-        let qos = match qos {
+        let mut qos = match qos {
             QosKind::Default => self.0.address().get_default_datawriter_qos()?,
             QosKind::Specific(q) => {
                 q.is_consistent()?;
                 q
             }
         };
+        if qos.representation.value.is_empty() {
+            qos.representation.value = vec![Foo::REPRESENTATION.data_representation_id()];
+        }
+        Foo::REPRESENTATION.with_data_representation(&qos.representation)?;
 
         let guid_prefix = self.0.address().guid()?.prefix();
-        let entity_kind = match Foo::HAS_KEY {
+        let has_key = Foo::has_key(
+            self.0
+                .parent_participant()
+                .get_registered_dynamic_type(a_topic.get_type_name()?)?
+                .as_ref(),
+        );
+        let entity_kind = match has_key {
             true => USER_DEFINED_WRITER_WITH_KEY,
             false => USER_DEFINED_WRITER_NO_KEY,
         };
@@ -121,7 +150,7 @@ impl Publisher {
         let entity_id = EntityId::new(entity_key, entity_kind);
         let guid = Guid::new(guid_prefix, entity_id);
 
-        let topic_kind = match Foo::HAS_KEY {
+        let topic_kind = match has_key {
             true => TopicKind::WithKey,
             false => TopicKind::NoKey,
         };
@@ -142,7 +171,7 @@ impl Publisher {
         let topic_name = a_topic.get_name()?;
         let listener = a_listener.map(|l| spawn_actor(DdsDataWriterListener::new(Box::new(l))));
         let status_kind = mask.to_vec();
-        let data_writer = DdsDataWriter::new(
+        let mut data_writer = DdsDataWriter::new(
             rtps_writer_impl,
             a_topic.get_type_name()?,
             topic_name,
@@ -150,6 +179,8 @@ impl Publisher {
             status_kind,
             qos,
         );
+        data_writer.set_type_information(Foo::type_information(), Foo::minimal_type_objects());
+        data_writer.set_max_serialized_size(Foo::max_serialized_size());
         let data_writer_actor = spawn_actor(data_writer);
         let data_writer_address = data_writer_actor.address().clone();
         self.0.address().datawriter_add(data_writer_actor)?;
@@ -181,89 +212,48 @@ impl Publisher {
     /// [`DataWriter`].
     pub fn delete_datawriter<Foo>(&self, a_datawriter: &DataWriter<Foo>) -> DdsResult<()> {
         match a_datawriter.node() {
-            DataWriterNodeKind::Listener(_) => Err(DdsError::IllegalOperation),
-            DataWriterNodeKind::UserDefined(dw) => {
-                let writer_handle = dw.address().get_instance_handle()?;
+            DataWriterNodeKind::UserDefined(dw) | DataWriterNodeKind::Listener(dw) => {
                 if self.0.address().guid()? != dw.parent_publisher().guid()? {
                     return Err(DdsError::PreconditionNotMet(
                         "Data writer can only be deleted from its parent publisher".to_string(),
                     ));
                 }
 
-                let writer_is_enabled = dw.address().is_enabled()?;
-                self.0.address().datawriter_delete(writer_handle)?;
-
-                // The writer creation is announced only on enabled so its deletion must be announced only if it is enabled
-                if writer_is_enabled {
-                    let instance_serialized_key =
-                        cdr::serialize::<_, _, cdr::CdrLe>(&writer_handle, cdr::Infinite)
-                            .map_err(|e| DdsError::PreconditionNotMet(e.to_string()))
-                            .expect("Failed to serialize data");
-
-                    let timestamp = dw.parent_participant().get_current_time()?;
-
-                    if let Some(sedp_writer_announcer) = dw
-                        .parent_participant()
-                        .get_builtin_publisher()?
-                        .data_writer_list()?
-                        .iter()
-                        .find(|x| x.get_type_name().unwrap() == "DiscoveredWriterData")
-                    {
-                        sedp_writer_announcer.dispose_w_timestamp(
-                            instance_serialized_key,
-                            writer_handle,
-                            timestamp,
-                        )??;
-
-                        sedp_writer_announcer.send_message(
-                            RtpsMessageHeader::new(
-                                dw.parent_participant().get_protocol_version()?,
-                                dw.parent_participant().get_vendor_id()?,
-                                dw.parent_participant().get_guid()?.prefix(),
-                            ),
-                            dw.parent_participant().get_udp_transport_write()?,
-                            dw.parent_participant().get_current_time()?,
-                        )?;
-                    }
-                    // let timestamp = domain_participant.get_current_time();
-
-                    // domain_participant
-                    //     .get_builtin_publisher_mut()
-                    //     .stateful_data_writer_list()
-                    //     .iter()
-                    //     .find(|x| x.get_type_name().unwrap() == DiscoveredWriterData)
-                    //     .unwrap()
-                    //     .dispose_w_timestamp(instance_serialized_key, writer_handle, timestamp)
-                    //     .expect("Should not fail to write built-in message");
-                }
-
-                Ok(())
+                delete_data_writer_node(&self.0, dw)
             }
         }
-
-        // Ok(())
     }
 
     /// This operation retrieves a previously created [`DataWriter`] belonging to the [`Publisher`] that is attached to a [`Topic`] with a matching
     /// `topic_name`. If no such [`DataWriter`] exists, the operation will succeed but return [`None`].
     /// If multiple [`DataWriter`] attached to the [`Publisher`] satisfy this condition, then the operation will return one of them. It is not
     /// specified which one.
-    pub fn lookup_datawriter<Foo>(&self, _topic_name: &str) -> DdsResult<Option<DataWriter<Foo>>>
+    /// If the type `Foo` is not the type of the [`DataWriter`] the operation will return
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    pub fn lookup_datawriter<Foo>(&self, topic_name: &str) -> DdsResult<Option<DataWriter<Foo>>>
     where
-        Foo: DdsHasKey,
+        Foo: DdsHasKey + 'static,
     {
-        todo!()
-        // self.call_participant_mut_method(|dp| {
-        //     Ok(
-        //         crate::implementation::behavior::user_defined_publisher::lookup_datawriter(
-        //             dp,
-        //             self.0.guid(),
-        //             Foo,
-        //             topic_name,
-        //         )?
-        //         .map(|x| DataWriter::new(DataWriterNodeKind::UserDefined(x))),
-        //     )
-        // })
+        for data_writer in self.0.address().data_writer_list()? {
+            if data_writer.get_topic_name()? == topic_name {
+                check_type_of_type_name::<Foo>(
+                    self.0.parent_participant(),
+                    &data_writer.get_type_name()?,
+                )?;
+
+                // Publishers only contain user-defined writers so, unlike the readers returned by
+                // the Subscriber, the node kind does not depend on how the Publisher was obtained
+                return Ok(Some(DataWriter::new(DataWriterNodeKind::UserDefined(
+                    DataWriterNode::new(
+                        data_writer,
+                        self.0.address().clone(),
+                        self.0.parent_participant().clone(),
+                    ),
+                ))));
+            }
+        }
+
+        Ok(None)
     }
 
     /// This operation indicates to the Service that the application is about to make multiple modifications using [`DataWriter`] objects
@@ -273,7 +263,7 @@ impl Publisher {
     /// modifications has completed. If the [`Publisher`] is deleted before [`Publisher::resume_publications`] is called, any suspended updates yet to
     /// be published will be discarded.
     pub fn suspend_publications(&self) -> DdsResult<()> {
-        todo!()
+        Err(DdsError::Unsupported)
     }
 
     /// This operation indicates to the Service that the application has completed the multiple changes initiated by the previous
@@ -282,7 +272,7 @@ impl Publisher {
     /// The call to [`Publisher::resume_publications`] must match a previous call to [`Publisher::suspend_publications`] otherwise
     /// the operation will return [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
     pub fn resume_publications(&self) -> DdsResult<()> {
-        todo!()
+        Err(DdsError::Unsupported)
     }
 
     /// This operation requests that the application will begin a *coherent set* of modifications using [`DataWriter`] objects attached to
@@ -302,13 +292,13 @@ impl Publisher {
     /// same aircraft and both are changed, it may be useful to communicate those values in a way the reader can see both together;
     /// otherwise, it may e.g., erroneously interpret that the aircraft is on a collision course).
     pub fn begin_coherent_changes(&self) -> DdsResult<()> {
-        todo!()
+        Err(DdsError::Unsupported)
     }
 
     /// This operation terminates the *coherent set* initiated by the matching call to [`Publisher::begin_coherent_changes`]. If there is no matching
     /// call to [`Publisher::begin_coherent_changes`], the operation will return [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
     pub fn end_coherent_changes(&self) -> DdsResult<()> {
-        todo!()
+        Err(DdsError::Unsupported)
     }
 
     /// This operation blocks the calling thread until either all data written by the reliable [`DataWriter`] entities is acknowledged by all
@@ -316,8 +306,17 @@ impl Publisher {
     /// the `max_wait` parameter elapses, whichever happens first. A return value of [`Ok`] indicates that all the samples written
     /// have been acknowledged by all reliable matched data readers; a return value of [`DdsError::Timeout`](crate::infrastructure::error::DdsError)
     /// indicates that `max_wait` elapsed before all the data was acknowledged.
-    pub fn wait_for_acknowledgments(&self, _max_wait: Duration) -> DdsResult<()> {
-        todo!()
+    pub fn wait_for_acknowledgments(&self, max_wait: Duration) -> DdsResult<()> {
+        let start_time = Instant::now();
+        for data_writer in self.0.address().data_writer_list()? {
+            while !data_writer.are_all_changes_acknowledge()? {
+                if start_time.elapsed() >= std::time::Duration::from(max_wait) {
+                    return Err(DdsError::Timeout);
+                }
+            }
+        }
+
+        Ok(())
     }
 
     /// This operation returns the [`DomainParticipant`] to which the [`Publisher`] belongs.
@@ -332,8 +331,18 @@ impl Publisher {
     /// Once this operation returns successfully, the application may delete the [`Publisher`] knowing that it has no
     /// contained [`DataWriter`] objects
     pub fn delete_contained_entities(&self) -> DdsResult<()> {
-        todo!()
-        // crate::implementation::behavior::user_defined_publisher::delete_contained_entities()
+        for data_writer in self.0.address().data_writer_list()? {
+            delete_data_writer_node(
+                &self.0,
+                &DataWriterNode::new(
+                    data_writer,
+                    self.0.address().clone(),
+                    self.0.parent_participant().clone(),
+                ),
+            )?;
+        }
+
+        Ok(())
     }
 
     /// This operation sets the default value of the [`DataWriterQos`] which will be used for newly created [`DataWriter`] entities in
@@ -370,10 +379,23 @@ impl Publisher {
     /// may not be the final one, as the application can still modify some policies prior to applying the policies to the [`DataWriter`].
     pub fn copy_from_topic_qos(
         &self,
-        _a_datawriter_qos: &mut DataWriterQos,
-        _a_topic_qos: &TopicQos,
+        a_datawriter_qos: &mut DataWriterQos,
+        a_topic_qos: &TopicQos,
     ) -> DdsResult<()> {
-        todo!()
+        a_datawriter_qos.durability = a_topic_qos.durability.clone();
+        a_datawriter_qos.deadline = a_topic_qos.deadline.clone();
+        a_datawriter_qos.latency_budget = a_topic_qos.latency_budget.clone();
+        a_datawriter_qos.liveliness = a_topic_qos.liveliness.clone();
+        a_datawriter_qos.reliability = a_topic_qos.reliability.clone();
+        a_datawriter_qos.destination_order = a_topic_qos.destination_order.clone();
+        a_datawriter_qos.history = a_topic_qos.history.clone();
+        a_datawriter_qos.resource_limits = a_topic_qos.resource_limits.clone();
+        a_datawriter_qos.transport_priority = a_topic_qos.transport_priority.clone();
+        a_datawriter_qos.lifespan = a_topic_qos.lifespan.clone();
+        a_datawriter_qos.ownership = a_topic_qos.ownership.clone();
+        a_datawriter_qos.representation = a_topic_qos.representation.clone();
+
+        Ok(())
     }
 }
 
@@ -391,8 +413,37 @@ impl Publisher {
     /// The parameter `qos` can be set to [`QosKind::Default`] to indicate that the QoS of the Entity should be changed to match the current default QoS set in the Entity’s factory.
     /// The operation [`Self::set_qos()`] cannot modify the immutable QoS so a successful return of the operation indicates that the mutable QoS for the Entity has been
     /// modified to match the current default for the Entity’s factory.
-    pub fn set_qos(&self, _qos: QosKind<PublisherQos>) -> DdsResult<()> {
-        todo!()
+    pub fn set_qos(&self, qos: QosKind<PublisherQos>) -> DdsResult<()> {
+        let qos = match qos {
+            QosKind::Default => self.0.parent_participant().default_publisher_qos()?,
+            QosKind::Specific(q) => q,
+        };
+
+        self.0.address().set_qos(QosKind::Specific(qos))??;
+
+        if self.0.address().is_enabled()? {
+            for data_writer in self.0.address().data_writer_list()? {
+                if data_writer.is_enabled()? {
+                    announce_data_writer(
+                        self.0.parent_participant(),
+                        &data_writer.as_discovered_writer_data(
+                            TopicQos::default(),
+                            self.0.address().get_qos()?,
+                            self.0
+                                .parent_participant()
+                                .get_default_unicast_locator_list()?,
+                            self.0
+                                .parent_participant()
+                                .get_default_multicast_locator_list()?,
+                        )?,
+                    )?;
+                }
+            }
+
+            process_sedp_discovery(self.0.parent_participant())?;
+        }
+
+        Ok(())
     }
 
     /// This operation allows access to the existing set of [`PublisherQos`] policies.
@@ -408,17 +459,23 @@ impl Publisher {
     /// will be removed.
     pub fn set_listener(
         &self,
-        _a_listener: Option<Box<dyn PublisherListener + Send + Sync>>,
-        _mask: &[StatusKind],
+        a_listener: Option<Box<dyn PublisherListener + Send + Sync>>,
+        mask: &[StatusKind],
     ) -> DdsResult<()> {
-        todo!()
+        self.0.address().set_listener(
+            a_listener.map(|l| spawn_actor(DdsPublisherListener::new(l))),
+            mask.to_vec(),
+        )
     }
 
     /// This operation allows access to the [`StatusCondition`] associated with the Entity. The returned
     /// condition can then be added to a [`WaitSet`](crate::infrastructure::wait_set::WaitSet) so that the application can wait for specific status changes
     /// that affect the Entity.
     pub fn get_statuscondition(&self) -> DdsResult<StatusCondition> {
-        todo!()
+        self.0
+            .address()
+            .get_statuscondition()
+            .map(StatusCondition::new)
     }
 
     /// This operation retrieves the list of communication statuses in the Entity that are ‘triggered.’ That is, the list of statuses whose
@@ -428,7 +485,12 @@ impl Publisher {
     /// The list of statuses returned by the [`Self::get_status_changes`] operation refers to the status that are triggered on the Entity itself
     /// and does not include statuses that apply to contained entities.
     pub fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
+        Ok(self
+            .0
+            .address()
+            .get_statuscondition()?
+            .read_lock()
+            .get_status_changes())
     }
 
     /// This operation enables the Entity. Entity objects can be created either enabled or disabled. This is controlled by the value of
@@ -460,3 +522,44 @@ impl Publisher {
         self.0.address().get_instance_handle()
     }
 }
+
+fn delete_data_writer_node(p: &PublisherNode, dw: &DataWriterNode) -> DdsResult<()> {
+    let writer_handle = dw.address().get_instance_handle()?;
+    let writer_is_enabled = dw.address().is_enabled()?;
+    p.address().datawriter_delete(writer_handle)?;
+
+    // The writer creation is announced only on enabled so its deletion must be announced only if it is enabled
+    if writer_is_enabled {
+        let instance_serialized_key =
+            cdr::serialize::<_, _, cdr::CdrLe>(&writer_handle, cdr::Infinite)
+                .map_err(|e| DdsError::PreconditionNotMet(e.to_string()))?;
+
+        let timestamp = dw.parent_participant().get_current_time()?;
+
+        if let Some(sedp_writer_announcer) = dw
+            .parent_participant()
+            .get_builtin_publisher()?
+            .data_writer_list()?
+            .iter()
+            .find(|x| x.get_type_name().unwrap() == "DiscoveredWriterData")
+        {
+            sedp_writer_announcer.dispose_w_timestamp(
+                instance_serialized_key,
+                writer_handle,
+                timestamp,
+            )??;
+
+            sedp_writer_announcer.send_message(
+                RtpsMessageHeader::new(
+                    dw.parent_participant().get_protocol_version()?,
+                    dw.parent_participant().get_vendor_id()?,
+                    dw.parent_participant().get_guid()?.prefix(),
+                ),
+                dw.parent_participant().get_udp_transport_write()?,
+                dw.parent_participant().get_current_time()?,
+            )?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,24 +1,37 @@
 use crate::{
+    domain::domain_participant_factory::process_sedp_discovery,
     implementation::{
         data_representation_builtin_endpoints::discovered_reader_data::DiscoveredReaderData,
         dds::{
+            dds_data_reader_listener::DdsDataReaderListener,
             dds_domain_participant::DdsDomainParticipant,
-            nodes::{DataReaderNodeKind, TopicNode, TopicNodeKind},
+            nodes::{
+                DataReaderNode, DataReaderNodeKind, SubscriberNode, SubscriberNodeKind, TopicNode,
+                TopicNodeKind,
+            },
         },
-        rtps::messages::overall_structure::RtpsMessageHeader,
-        utils::actor::ActorAddress,
+        rtps::messages::{overall_structure::RtpsMessageHeader, submessage_elements::Data},
+        utils::actor::{spawn_actor, ActorAddress},
     },
     infrastructure::{
         error::DdsError,
         instance::InstanceHandle,
+        owned::OwnedEntity,
         qos::{QosKind, TopicQos},
+        qos_policy::{XCDR2_DATA_REPRESENTATION, XCDR_DATA_REPRESENTATION},
         status::StatusKind,
         time::Duration,
     },
     subscription::data_reader_listener::DataReaderListener,
     topic_definition::{
+        dynamic_type::DynamicType,
+        json::{key_to_json_value, to_json_value},
         topic::Topic,
-        type_support::{dds_serialize_key, dds_serialize_to_bytes, DdsHasKey, DdsRepresentation},
+        type_support::{
+            dds_key_hash, dds_serialize_key, dds_serialize_to_bytes,
+            dds_set_key_fields_from_serialized_key, DdsDeserialize, DdsGetKey, DdsHasKey,
+            DdsRepresentation, DdsSetKeyFields,
+        },
     },
     {
         builtin_topics::PublicationBuiltinTopicData,
@@ -35,14 +48,14 @@ use crate::{
     },
 };
 
-use std::marker::PhantomData;
+use std::{marker::PhantomData, ops::Deref};
 
 use super::{
     sample_info::{
         InstanceStateKind, SampleInfo, SampleStateKind, ViewStateKind, ANY_INSTANCE_STATE,
         ANY_VIEW_STATE,
     },
-    subscriber::Subscriber,
+    subscriber::{check_data_reader_type, Subscriber},
 };
 
 /// A [`Sample`] contains the data and [`SampleInfo`] read by the [`DataReader`].
@@ -54,6 +67,108 @@ pub struct Sample<Foo> {
     pub sample_info: SampleInfo,
 }
 
+impl<Foo> Sample<Foo> {
+    /// Converts the [`Sample`] into a [`serde_json::Value`] to render it independently of the representation of its
+    /// type. The result is a JSON object with the `data` converted with
+    /// [`to_json_value`](crate::topic_definition::json::to_json_value), the `key` fields of the data converted with
+    /// [`key_to_json_value`](crate::topic_definition::json::key_to_json_value) and the `sample_info`. The `data` and
+    /// `key` members are [`serde_json::Value::Null`] if the sample contains no valid data. The data of the
+    /// [`DynamicData`](crate::topic_definition::dynamic_data::DynamicData) samples is converted with
+    /// [`dynamic_data_to_json_value`](crate::topic_definition::json::dynamic_data_to_json_value).
+    pub fn to_json_value(&self) -> DdsResult<serde_json::Value>
+    where
+        Foo: serde::Serialize + DdsGetKey + DdsRepresentation,
+    {
+        let (data, key) = match &self.data {
+            Some(data) => (data.sample_to_json_value()?, key_to_json_value(data)?),
+            None => (serde_json::Value::Null, serde_json::Value::Null),
+        };
+        Ok(serde_json::json!({
+            "data": data,
+            "key": key,
+            "sample_info": to_json_value(&self.sample_info)?,
+        }))
+    }
+}
+
+/// A [`LoanedSample`] gives access to a sample stored in the [`DataReader`] without copying its serialized data.
+/// The data is only deserialized when the application requests it using [`LoanedSample::data`].
+pub struct LoanedSample<Foo> {
+    serialized_data: Data,
+    sample_info: SampleInfo,
+    // Type of the samples of a DataReader<DynamicData>
+    dynamic_type: Option<DynamicType>,
+    phantom: PhantomData<Foo>,
+}
+
+impl<Foo> LoanedSample<Foo> {
+    /// Information of the sample received by the [`DataReader`].
+    pub fn sample_info(&self) -> &SampleInfo {
+        &self.sample_info
+    }
+
+    /// Serialized data of the sample as it was received by the [`DataReader`]. For types with
+    /// [`Representation::Custom`](crate::topic_definition::type_support::Representation) these are the bytes
+    /// which are passed to [`DdsRepresentation::from_bytes`], for the other representations they start with the
+    /// encapsulation header. A sample might contain no valid data in which case this is [`None`].
+    pub fn serialized_data(&self) -> Option<&[u8]> {
+        if self.sample_info.valid_data {
+            Some(self.serialized_data.as_ref())
+        } else {
+            None
+        }
+    }
+
+    /// Deserializes the data of the sample from the loaned serialized data. A sample might contain no valid data
+    /// in which case the result is [`None`].
+    pub fn data<'a>(&'a self) -> DdsResult<Option<Foo>>
+    where
+        Foo: DdsDeserialize<'a>,
+    {
+        self.serialized_data()
+            .map(|data| Foo::deserialize_sample(data, self.dynamic_type.as_ref()))
+            .transpose()
+    }
+}
+
+/// Collection of [`LoanedSample`] returned by [`DataReader::read_loaned`] and [`DataReader::take_loaned`].
+/// The serialized data is shared with the [`DataReader`] cache and it stays available for as long as the
+/// collection exists, independently of what happens to the samples on the [`DataReader`].
+pub struct LoanedSamples<Foo>(Vec<LoanedSample<Foo>>);
+
+impl<Foo> LoanedSamples<Foo> {
+    fn new(loaned_samples: Vec<(Data, SampleInfo)>, dynamic_type: Option<DynamicType>) -> Self {
+        Self(
+            loaned_samples
+                .into_iter()
+                .map(|(serialized_data, sample_info)| LoanedSample {
+                    serialized_data,
+                    sample_info,
+                    dynamic_type: dynamic_type.clone(),
+                    phantom: PhantomData,
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<Foo> Deref for LoanedSamples<Foo> {
+    type Target = [LoanedSample<Foo>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, Foo> IntoIterator for &'a LoanedSamples<Foo> {
+    type Item = &'a LoanedSample<Foo>;
+    type IntoIter = std::slice::Iter<'a, LoanedSample<Foo>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 /// A [`DataReader`] allows the application (1) to declare the data it wishes to receive (i.e., make a subscription) and (2) to access the
 /// data received by the attached [`Subscriber`].
 ///
@@ -94,9 +209,15 @@ impl<Foo> DataReader<Foo> {
 //     }
 // }
 
+impl<Foo> OwnedEntity for DataReader<Foo> {
+    fn delete_entity(&self) -> DdsResult<()> {
+        self.get_subscriber()?.delete_datareader(self)
+    }
+}
+
 impl<Foo> DataReader<Foo>
 where
-    Foo: DdsRepresentation + DdsHasKey + for<'de> serde::Deserialize<'de> + Send + 'static,
+    Foo: DdsRepresentation + DdsHasKey + for<'de> DdsDeserialize<'de> + Send + 'static,
 {
     /// This operation accesses a collection of [`Sample`] from the [`DataReader`]. The size of the returned collection will
     /// be limited to the specified `max_samples`. The properties of the data values collection and the setting of the
@@ -146,8 +267,8 @@ where
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateful(dr)
-            | DataReaderNodeKind::_BuiltinStateless(dr)
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
             | DataReaderNodeKind::UserDefined(dr)
             | DataReaderNodeKind::Listener(dr) => dr.address().read(
                 max_samples,
@@ -170,17 +291,75 @@ where
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateless(_) | DataReaderNodeKind::_BuiltinStateful(_) => {
-                Err(DdsError::IllegalOperation)
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => dr.address().take(
+                max_samples,
+                sample_states,
+                view_states,
+                instance_states,
+                None,
+            ),
+        }
+    }
+
+    /// This operation accesses a collection of [`LoanedSample`] from the [`DataReader`]. The samples are selected
+    /// and their states updated following the same logic as [`DataReader::read`]. The difference is that the data of the
+    /// samples is not deserialized nor copied out of the [`DataReader`] cache. Instead, the returned [`LoanedSamples`]
+    /// share the serialized data received by the [`DataReader`] and each sample is only deserialized when the application
+    /// calls [`LoanedSample::data`].
+    pub fn read_loaned(
+        &self,
+        max_samples: i32,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<LoanedSamples<Foo>> {
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => {
+                let dynamic_type = dr.address().get_dynamic_type()?;
+                dr.address()
+                    .read_loaned(
+                        max_samples,
+                        sample_states,
+                        view_states,
+                        instance_states,
+                        None,
+                    )
+                    .map(|loaned_samples| LoanedSamples::new(loaned_samples, dynamic_type))
             }
-            DataReaderNodeKind::UserDefined(dr) | DataReaderNodeKind::Listener(dr) => {
-                dr.address().take(
-                    max_samples,
-                    sample_states,
-                    view_states,
-                    instance_states,
-                    None,
-                )
+        }
+    }
+
+    /// This operation accesses a collection of [`LoanedSample`] from the [`DataReader`]. This operation uses the same
+    /// logic as the [`DataReader::read_loaned`]. The only difference is that the samples returned by
+    /// [`DataReader::take_loaned`] will no longer be accessible to successive calls to read or take.
+    pub fn take_loaned(
+        &self,
+        max_samples: i32,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<LoanedSamples<Foo>> {
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => {
+                let dynamic_type = dr.address().get_dynamic_type()?;
+                dr.address()
+                    .take_loaned(
+                        max_samples,
+                        sample_states,
+                        view_states,
+                        instance_states,
+                        None,
+                    )
+                    .map(|loaned_samples| LoanedSamples::new(loaned_samples, dynamic_type))
             }
         }
     }
@@ -194,8 +373,8 @@ where
     /// sequences and specify states.
     pub fn read_next_sample(&self) -> DdsResult<Sample<Foo>> {
         let mut samples = match &self.0 {
-            DataReaderNodeKind::_BuiltinStateful(dr)
-            | DataReaderNodeKind::_BuiltinStateless(dr)
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
             | DataReaderNodeKind::UserDefined(dr)
             | DataReaderNodeKind::Listener(dr) => dr.address().read(
                 1,
@@ -217,10 +396,10 @@ where
     /// sequences and specify states.
     pub fn take_next_sample(&self) -> DdsResult<Sample<Foo>> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateless(_) | DataReaderNodeKind::_BuiltinStateful(_) => {
-                Err(DdsError::IllegalOperation)
-            }
-            DataReaderNodeKind::UserDefined(dr) | DataReaderNodeKind::Listener(dr) => {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => {
                 let mut samples = dr.address().take(
                     1,
                     &[SampleStateKind::NotRead],
@@ -250,8 +429,8 @@ where
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateful(dr)
-            | DataReaderNodeKind::_BuiltinStateless(dr)
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
             | DataReaderNodeKind::UserDefined(dr)
             | DataReaderNodeKind::Listener(dr) => dr.address().read(
                 max_samples,
@@ -280,18 +459,16 @@ where
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateless(_) | DataReaderNodeKind::_BuiltinStateful(_) => {
-                Err(DdsError::IllegalOperation)
-            }
-            DataReaderNodeKind::UserDefined(dr) | DataReaderNodeKind::Listener(dr) => {
-                dr.address().take(
-                    max_samples,
-                    sample_states,
-                    view_states,
-                    instance_states,
-                    Some(a_handle),
-                )
-            }
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => dr.address().take(
+                max_samples,
+                sample_states,
+                view_states,
+                instance_states,
+                Some(a_handle),
+            ),
         }
     }
 
@@ -327,8 +504,8 @@ where
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateful(dr)
-            | DataReaderNodeKind::_BuiltinStateless(dr)
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
             | DataReaderNodeKind::UserDefined(dr)
             | DataReaderNodeKind::Listener(dr) => dr.address().read_next_instance(
                 max_samples,
@@ -352,18 +529,16 @@ where
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateless(_) | DataReaderNodeKind::_BuiltinStateful(_) => {
-                Err(DdsError::IllegalOperation)
-            }
-            DataReaderNodeKind::UserDefined(dr) | DataReaderNodeKind::Listener(dr) => {
-                dr.address().take_next_instance(
-                    max_samples,
-                    previous_handle,
-                    sample_states.to_vec(),
-                    view_states.to_vec(),
-                    instance_states.to_vec(),
-                )
-            }
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => dr.address().take_next_instance(
+                max_samples,
+                previous_handle,
+                sample_states.to_vec(),
+                view_states.to_vec(),
+                instance_states.to_vec(),
+            ),
         }
     }
 
@@ -371,23 +546,19 @@ where
     /// The operation will only fill the fields that form the key inside the `key_holder` instance.
     /// This operation may return [`DdsError::BadParameter`](crate::infrastructure::error::DdsError)
     /// if the [`InstanceHandle`] `handle` does not correspond to an existing data object known to the [`DataReader`].
-    pub fn get_key_value(&self, _key_holder: &mut Foo, _handle: InstanceHandle) -> DdsResult<()> {
-        todo!()
-        // match &self.0 {
-        //     DataReaderNodeKind::BuiltinStateless(_) => todo!(),
-        //     DataReaderNodeKind::BuiltinStateful(_) => todo!(),
-        //     DataReaderNodeKind::UserDefined(r) => THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //         .get_participant(&r.guid().prefix(), |dp| {
-        //             crate::implementation::behavior::user_defined_data_reader::get_key_value(
-        //                 dp.ok_or(DdsError::AlreadyDeleted)?,
-        //                 r.guid(),
-        //                 r.parent_subscriber(),
-        //                 key_holder,
-        //                 handle,
-        //             )
-        //         }),
-        //     DataReaderNodeKind::Listener(_) => todo!(),
-        // }
+    pub fn get_key_value(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()>
+    where
+        Foo: DdsSetKeyFields,
+    {
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => {
+                let serialized_key = dr.address().get_key_value(handle)?;
+                dds_set_key_fields_from_serialized_key(key_holder, serialized_key.as_ref())
+            }
+        }
     }
 
     /// This operation takes as a parameter an instance and returns an [`InstanceHandle`] handle
@@ -396,162 +567,83 @@ where
     /// key. This operation does not register the instance in question. If the instance has not
     /// been previously registered, or if for any other reason the Service is unable to provide
     /// an instance handle, the operation will succeed and return [`None`].
-    pub fn lookup_instance(&self, _instance: &Foo) -> DdsResult<Option<InstanceHandle>> {
-        todo!()
-        // match &self.0 {
-        //     DataReaderNodeKind::BuiltinStateless(_) => todo!(),
-        //     DataReaderNodeKind::BuiltinStateful(_) => todo!(),
-        //     DataReaderNodeKind::UserDefined(r) => THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //         .get_participant(&r.guid().prefix(), |dp| {
-        //             crate::implementation::behavior::user_defined_data_reader::lookup_instance(
-        //                 dp.ok_or(DdsError::AlreadyDeleted)?,
-        //                 r.guid(),
-        //                 r.parent_subscriber(),
-        //                 instance,
-        //             )
-        //         }),
-        //     DataReaderNodeKind::Listener(_) => todo!(),
-        // }
+    pub fn lookup_instance(&self, instance: &Foo) -> DdsResult<Option<InstanceHandle>>
+    where
+        Foo: DdsGetKey,
+    {
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => {
+                dr.address().lookup_instance(dds_key_hash(instance)?)
+            }
+        }
     }
 }
 
 impl<Foo> DataReader<Foo> {
     /// This operation allows access to the [`LivelinessChangedStatus`].
     pub fn get_liveliness_changed_status(&self) -> DdsResult<LivelinessChangedStatus> {
-        todo!()
-        // match &self.0 {
-        //     DataReaderNodeKind::BuiltinStateless(_) => todo!(),
-        //     DataReaderNodeKind::BuiltinStateful(_) => todo!(),
-        //     DataReaderNodeKind::UserDefined(r) => {
-        //         let status = THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //             .get_participant_mut(&r.guid().prefix(), |dp| {
-        //                 crate::implementation::behavior::user_defined_data_reader::get_liveliness_changed_status(dp.ok_or(DdsError::AlreadyDeleted)?,r.guid(),
-        //                 r.parent_subscriber(),)
-        //             })?;
-
-        //         THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_data_reader_listener(&r.guid(), |l| {
-        //             l.ok_or(DdsError::AlreadyDeleted)?
-        //                 .remove_communication_state(StatusKind::LivelinessChanged);
-        //             Ok(())
-        //         })?;
-
-        //         Ok(status)
-        //     }
-        //     DataReaderNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => dr.address().get_liveliness_changed_status(),
+        }
     }
 
     /// This operation allows access to the [`RequestedDeadlineMissedStatus`].
     pub fn get_requested_deadline_missed_status(&self) -> DdsResult<RequestedDeadlineMissedStatus> {
-        todo!()
-        // match &self.0 {
-        //     DataReaderNodeKind::BuiltinStateless(_) => todo!(),
-        //     DataReaderNodeKind::BuiltinStateful(_) => todo!(),
-        //     DataReaderNodeKind::UserDefined(r) => {
-        //         let status = THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_participant_mut(
-        //             &r.guid().prefix(),
-        //             |dp| {
-        //                 crate::implementation::behavior::user_defined_data_reader::get_requested_deadline_missed_status(dp.ok_or(DdsError::AlreadyDeleted)?,r.guid(),
-        //                 r.parent_subscriber(),)
-        //             },
-        //         )?;
-
-        //         THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_data_reader_listener(&r.guid(), |l| {
-        //             l.ok_or(DdsError::AlreadyDeleted)?
-        //                 .remove_communication_state(StatusKind::RequestedDeadlineMissed);
-        //             Ok(())
-        //         })?;
-
-        //         Ok(status)
-        //     }
-        //     DataReaderNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => {
+                dr.address().get_requested_deadline_missed_status()
+            }
+        }
     }
 
     /// This operation allows access to the [`RequestedIncompatibleQosStatus`].
     pub fn get_requested_incompatible_qos_status(
         &self,
     ) -> DdsResult<RequestedIncompatibleQosStatus> {
-        todo!()
-        // match &self.0 {
-        //     DataReaderNodeKind::BuiltinStateless(_) => todo!(),
-        //     DataReaderNodeKind::BuiltinStateful(_) => todo!(),
-        //     DataReaderNodeKind::UserDefined(r) => {
-        //         let status = THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_participant_mut(
-        //             &r.guid().prefix(),
-        //             |dp| {
-        //                 crate::implementation::behavior::user_defined_data_reader::get_requested_incompatible_qos_status(dp.ok_or(DdsError::AlreadyDeleted)?,r.guid(),
-        //                 r.parent_subscriber(),)
-        //             },
-        //         )?;
-
-        //         THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_data_reader_listener(&r.guid(), |l| {
-        //             l.ok_or(DdsError::AlreadyDeleted)?
-        //                 .remove_communication_state(StatusKind::RequestedIncompatibleQos);
-        //             Ok(())
-        //         })?;
-
-        //         Ok(status)
-        //     }
-        //     DataReaderNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => {
+                dr.address().get_requested_incompatible_qos_status()
+            }
+        }
     }
 
     /// This operation allows access to the [`SampleLostStatus`].
     pub fn get_sample_lost_status(&self) -> DdsResult<SampleLostStatus> {
-        todo!()
-        // match &self.0 {
-        //     DataReaderNodeKind::BuiltinStateless(_) => todo!(),
-        //     DataReaderNodeKind::BuiltinStateful(_) => todo!(),
-        //     DataReaderNodeKind::UserDefined(r) => {
-        //         let status = THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //             .get_participant_mut(&r.guid().prefix(), |dp| {
-        //                 crate::implementation::behavior::user_defined_data_reader::get_sample_lost_status(dp.ok_or(DdsError::AlreadyDeleted)?,r.guid(),
-        //                 r.parent_subscriber(),)
-        //             })?;
-
-        //         THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_data_reader_listener(&r.guid(), |l| {
-        //             l.ok_or(DdsError::AlreadyDeleted)?
-        //                 .remove_communication_state(StatusKind::SampleLost);
-        //             Ok(())
-        //         })?;
-
-        //         Ok(status)
-        //     }
-        //     DataReaderNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => dr.address().get_sample_lost_status(),
+        }
     }
 
     /// This operation allows access to the [`SampleRejectedStatus`].
     pub fn get_sample_rejected_status(&self) -> DdsResult<SampleRejectedStatus> {
-        todo!()
-        // match &self.0 {
-        //     DataReaderNodeKind::BuiltinStateless(_) => todo!(),
-        //     DataReaderNodeKind::BuiltinStateful(_) => todo!(),
-        //     DataReaderNodeKind::UserDefined(r) => {
-        //         let status = THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //             .get_participant_mut(&r.guid().prefix(), |dp| {
-        //                 crate::implementation::behavior::user_defined_data_reader::get_sample_rejected_status(dp.ok_or(DdsError::AlreadyDeleted)?, r.guid(),
-        //                 r.parent_subscriber(),)
-        //             })?;
-
-        //         THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_data_reader_listener(&r.guid(), |l| {
-        //             l.ok_or(DdsError::AlreadyDeleted)?
-        //                 .remove_communication_state(StatusKind::SampleRejected);
-        //             Ok(())
-        //         })?;
-
-        //         Ok(status)
-        //     }
-        //     DataReaderNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => dr.address().get_sample_rejected_status(),
+        }
     }
 
     /// This operation allows access to the [`SubscriptionMatchedStatus`].
     pub fn get_subscription_matched_status(&self) -> DdsResult<SubscriptionMatchedStatus> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateful(dr)
-            | DataReaderNodeKind::_BuiltinStateless(dr)
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
             | DataReaderNodeKind::UserDefined(dr)
             | DataReaderNodeKind::Listener(dr) => dr.address().get_subscription_matched_status(),
         }
@@ -561,32 +653,40 @@ impl<Foo> DataReader<Foo> {
     /// that was used to create the [`DataReader`].
     pub fn get_topicdescription(&self) -> DdsResult<Topic> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateful(dr)
-            | DataReaderNodeKind::_BuiltinStateless(dr)
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
             | DataReaderNodeKind::UserDefined(dr)
-            | DataReaderNodeKind::Listener(dr) => Ok(Topic::new(TopicNodeKind::UserDefined(
-                TopicNode::new(dr.topic_address(), dr.parent_participant().clone()),
-            ))),
+            | DataReaderNodeKind::Listener(dr) => {
+                // The MultiTopic is not an entity of the participant so there is no Topic to return
+                if !dr.address().get_multitopic_source_readers()?.is_empty() {
+                    return Err(DdsError::IllegalOperation);
+                }
+                Ok(Topic::new(TopicNodeKind::UserDefined(TopicNode::new(
+                    dr.topic_address()?,
+                    dr.parent_participant().clone(),
+                ))))
+            }
         }
     }
 
     /// This operation returns the [`Subscriber`] to which the [`DataReader`] belongs.
     pub fn get_subscriber(&self) -> DdsResult<Subscriber> {
-        todo!()
-        // match &self.0 {
-        //     DataReaderNodeKind::BuiltinStateless(_) => Err(DdsError::IllegalOperation),
-        //     DataReaderNodeKind::BuiltinStateful(_) => Err(DdsError::IllegalOperation),
-        //     DataReaderNodeKind::UserDefined(r) => THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //         .get_participant(&r.guid().prefix(), |dp| {
-        //             Ok(Subscriber::new(SubscriberNodeKind::UserDefined(
-        //                 crate::implementation::behavior::user_defined_data_reader::get_subscriber(
-        //                     dp.ok_or(DdsError::AlreadyDeleted)?,
-        //                     r.parent_subscriber(),
-        //                 ),
-        //             )))
-        //         }),
-        //     DataReaderNodeKind::Listener(_) => Err(DdsError::IllegalOperation),
-        // }
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr) | DataReaderNodeKind::BuiltinStateless(dr) => {
+                Ok(Subscriber::new(SubscriberNodeKind::Builtin(
+                    SubscriberNode::new(
+                        dr.parent_subscriber().clone(),
+                        dr.parent_participant().clone(),
+                    ),
+                )))
+            }
+            DataReaderNodeKind::UserDefined(dr) | DataReaderNodeKind::Listener(dr) => Ok(
+                Subscriber::new(SubscriberNodeKind::UserDefined(SubscriberNode::new(
+                    dr.parent_subscriber().clone(),
+                    dr.parent_participant().clone(),
+                ))),
+            ),
+        }
     }
 
     /// This operation blocks the calling thread until either all “historical” data is received, or else the
@@ -603,10 +703,10 @@ impl<Foo> DataReader<Foo> {
     /// data is received.
     pub fn wait_for_historical_data(&self, max_wait: Duration) -> DdsResult<()> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateless(_) | DataReaderNodeKind::_BuiltinStateful(_) => {
-                todo!()
-            }
-            DataReaderNodeKind::UserDefined(dr) => {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => {
                 let start_time = std::time::Instant::now();
 
                 while start_time.elapsed() < std::time::Duration::from(max_wait) {
@@ -617,7 +717,6 @@ impl<Foo> DataReader<Foo> {
 
                 Err(DdsError::Timeout)
             }
-            DataReaderNodeKind::Listener(_) => todo!(),
         }
     }
 
@@ -633,8 +732,8 @@ impl<Foo> DataReader<Foo> {
         publication_handle: InstanceHandle,
     ) -> DdsResult<PublicationBuiltinTopicData> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateful(dr)
-            | DataReaderNodeKind::_BuiltinStateless(dr)
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
             | DataReaderNodeKind::UserDefined(dr)
             | DataReaderNodeKind::Listener(dr) => dr
                 .address()
@@ -650,8 +749,8 @@ impl<Foo> DataReader<Foo> {
     /// [`SampleInfo::instance_handle`](crate::subscription::sample_info::SampleInfo) when reading the “DCPSPublications” builtin topic.
     pub fn get_matched_publications(&self) -> DdsResult<Vec<InstanceHandle>> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateful(dr)
-            | DataReaderNodeKind::_BuiltinStateless(dr)
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
             | DataReaderNodeKind::UserDefined(dr)
             | DataReaderNodeKind::Listener(dr) => dr.address().get_matched_publications(),
         }
@@ -673,10 +772,7 @@ impl<Foo> DataReader<Foo> {
     /// modified to match the current default for the Entity’s factory.
     pub fn set_qos(&self, qos: QosKind<DataReaderQos>) -> DdsResult<()> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateful(dr)
-            | DataReaderNodeKind::_BuiltinStateless(dr)
-            | DataReaderNodeKind::UserDefined(dr)
-            | DataReaderNodeKind::Listener(dr) => {
+            DataReaderNodeKind::BuiltinStateful(dr) | DataReaderNodeKind::BuiltinStateless(dr) => {
                 let q = match qos {
                     QosKind::Default => dr.parent_subscriber().get_default_datareader_qos()?,
                     QosKind::Specific(q) => {
@@ -684,6 +780,21 @@ impl<Foo> DataReader<Foo> {
                         q
                     }
                 };
+                // The builtin readers are not announced nor matched through discovery
+                dr.address().set_qos(q)
+            }
+            DataReaderNodeKind::UserDefined(dr) | DataReaderNodeKind::Listener(dr) => {
+                let mut q = match qos {
+                    QosKind::Default => dr.parent_subscriber().get_default_datareader_qos()?,
+                    QosKind::Specific(q) => {
+                        q.is_consistent()?;
+                        q
+                    }
+                };
+                if q.representation.value.is_empty() {
+                    q.representation.value =
+                        vec![XCDR_DATA_REPRESENTATION, XCDR2_DATA_REPRESENTATION];
+                }
                 dr.address().set_qos(q)?;
 
                 if dr.address().is_enabled()? {
@@ -697,6 +808,8 @@ impl<Foo> DataReader<Foo> {
                                 .get_default_multicast_locator_list()?,
                         )?,
                     )?;
+
+                    process_sedp_discovery(dr.parent_participant())?;
                 }
 
                 Ok(())
@@ -707,8 +820,8 @@ impl<Foo> DataReader<Foo> {
     /// This operation allows access to the existing set of [`DataReaderQos`] policies.
     pub fn get_qos(&self) -> DdsResult<DataReaderQos> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateful(dr)
-            | DataReaderNodeKind::_BuiltinStateless(dr)
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
             | DataReaderNodeKind::UserDefined(dr)
             | DataReaderNodeKind::Listener(dr) => dr.address().get_qos(),
         }
@@ -719,12 +832,12 @@ impl<Foo> DataReader<Foo> {
     /// that affect the Entity.
     pub fn get_statuscondition(&self) -> DdsResult<StatusCondition> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateful(dr)
-            | DataReaderNodeKind::_BuiltinStateless(dr)
-            | DataReaderNodeKind::UserDefined(dr) => {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => {
                 dr.address().get_statuscondition().map(StatusCondition::new)
             }
-            DataReaderNodeKind::Listener(_) => todo!(),
         }
     }
 
@@ -735,16 +848,16 @@ impl<Foo> DataReader<Foo> {
     /// The list of statuses returned by the [`Self::get_status_changes`] operation refers to the status that are triggered on the Entity itself
     /// and does not include statuses that apply to contained entities.
     pub fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
-        // match &self.0 {
-        //     DataReaderNodeKind::BuiltinStateless(_) => todo!(),
-        //     DataReaderNodeKind::BuiltinStateful(_) => todo!(),
-        //     DataReaderNodeKind::UserDefined(r) => THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //         .get_data_reader_listener(&r.guid(), |l| {
-        //             Ok(l.ok_or(DdsError::AlreadyDeleted)?.get_status_changes())
-        //         }),
-        //     DataReaderNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => Ok(dr
+                .address()
+                .get_statuscondition()?
+                .read_lock()
+                .get_status_changes()),
+        }
     }
 
     /// This operation enables the Entity. Entity objects can be created either enabled or disabled. This is controlled by the value of
@@ -769,27 +882,29 @@ impl<Foo> DataReader<Foo> {
     /// enabled are “inactive,” that is, the operation [`StatusCondition::get_trigger_value()`] will always return `false`.
     pub fn enable(&self) -> DdsResult<()> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateless(_)
-            | DataReaderNodeKind::_BuiltinStateful(_)
-            | DataReaderNodeKind::Listener(_) => Err(DdsError::IllegalOperation),
+            DataReaderNodeKind::BuiltinStateless(_) | DataReaderNodeKind::BuiltinStateful(_) => {
+                Err(DdsError::IllegalOperation)
+            }
 
-            DataReaderNodeKind::UserDefined(r) => {
-                if !r.address().is_enabled()? {
-                    r.address().enable()?;
+            DataReaderNodeKind::UserDefined(r) | DataReaderNodeKind::Listener(r) => {
+                let multitopic_source_readers = r.address().get_multitopic_source_readers()?;
+                if multitopic_source_readers.is_empty() {
+                    enable_and_announce_data_reader(r)
+                } else {
+                    // A reader on a MultiTopic is not discovered by the remote participants,
+                    // instead the readers of the related topics are announced
+                    if !r.address().is_enabled()? {
+                        r.address().enable()?;
+                    }
+                    for source_reader in multitopic_source_readers {
+                        enable_and_announce_data_reader(&DataReaderNode::new(
+                            source_reader,
+                            r.parent_subscriber().clone(),
+                            r.parent_participant().clone(),
+                        ))?;
+                    }
+                    Ok(())
                 }
-
-                announce_data_reader(
-                    r.parent_participant(),
-                    r.address().as_discovered_reader_data(
-                        TopicQos::default(),
-                        r.parent_subscriber().get_qos()?,
-                        r.parent_participant().get_default_unicast_locator_list()?,
-                        r.parent_participant()
-                            .get_default_multicast_locator_list()?,
-                    )?,
-                )?;
-
-                Ok(())
             }
         }
     }
@@ -797,8 +912,8 @@ impl<Foo> DataReader<Foo> {
     /// This operation returns the [`InstanceHandle`] that represents the Entity.
     pub fn get_instance_handle(&self) -> DdsResult<InstanceHandle> {
         match &self.0 {
-            DataReaderNodeKind::_BuiltinStateless(r)
-            | DataReaderNodeKind::_BuiltinStateful(r)
+            DataReaderNodeKind::BuiltinStateless(r)
+            | DataReaderNodeKind::BuiltinStateful(r)
             | DataReaderNodeKind::UserDefined(r)
             | DataReaderNodeKind::Listener(r) => r.address().get_instance_handle(),
         }
@@ -807,7 +922,7 @@ impl<Foo> DataReader<Foo> {
 
 impl<Foo> DataReader<Foo>
 where
-    Foo: DdsHasKey + for<'de> serde::Deserialize<'de> + 'static + Send + Sync,
+    Foo: DdsHasKey + for<'de> DdsDeserialize<'de> + 'static + Send + Sync,
 {
     /// This operation installs a Listener on the Entity. The listener will only be invoked on the changes of communication status
     /// indicated by the specified mask. It is permitted to use [`None`] as the value of the listener. The [`None`] listener behaves
@@ -817,29 +932,92 @@ where
     /// will be removed.
     pub fn set_listener(
         &self,
-        _a_listener: Option<Box<dyn DataReaderListener<Foo = Foo> + Send + Sync>>,
-        _mask: &[StatusKind],
+        a_listener: Option<Box<dyn DataReaderListener<Foo = Foo> + Send + Sync>>,
+        mask: &[StatusKind],
     ) -> DdsResult<()> {
-        todo!()
-        // match &self.0 {
-        //     DataReaderNodeKind::BuiltinStateless(_) => todo!(),
-        //     DataReaderNodeKind::BuiltinStateful(_) => todo!(),
-        //     DataReaderNodeKind::UserDefined(_r) => {
-        //         todo!()
-        //         // #[allow(clippy::redundant_closure)]
-        //         // r.set_listener(
-        //         //     a_listener
-        //         //         .map::<Box<dyn AnyDataReaderListener + Send + Sync>, _>(|l| Box::new(l)),
-        //         //     mask,
-        //         // )
-        //     }
-        //     DataReaderNodeKind::Listener(_) => Err(DdsError::IllegalOperation),
-        // }
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => dr.address().set_listener(
+                a_listener.map(|l| spawn_actor(DdsDataReaderListener::new(Box::new(l)))),
+                mask.to_vec(),
+            ),
+        }
     }
 }
+
+/// A [`DataReader`] whose type is only known at runtime, as returned by [`Subscriber::get_datareaders`]. The readers of a
+/// [`Subscriber`] may be of different types so the application uses the name of the topic or of the type of the reader to
+/// convert it into the [`DataReader`] of the corresponding type with [`UntypedDataReader::into_typed`].
+pub struct UntypedDataReader(DataReaderNodeKind);
+
+impl UntypedDataReader {
+    pub(crate) fn new(data_reader: DataReaderNodeKind) -> Self {
+        Self(data_reader)
+    }
+
+    /// This operation returns the name of the topic of the [`DataReader`].
+    pub fn get_topic_name(&self) -> DdsResult<String> {
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => dr.address().get_topic_name(),
+        }
+    }
+
+    /// This operation returns the name of the type of the [`DataReader`].
+    pub fn get_type_name(&self) -> DdsResult<String> {
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => dr.address().get_type_name(),
+        }
+    }
+
+    /// This operation returns the [`InstanceHandle`] that represents the [`DataReader`].
+    pub fn get_instance_handle(&self) -> DdsResult<InstanceHandle> {
+        match &self.0 {
+            DataReaderNodeKind::BuiltinStateful(dr)
+            | DataReaderNodeKind::BuiltinStateless(dr)
+            | DataReaderNodeKind::UserDefined(dr)
+            | DataReaderNodeKind::Listener(dr) => dr.address().get_instance_handle(),
+        }
+    }
+
+    /// This operation converts the reader into the [`DataReader`] of type `Foo`.
+    /// If the type `Foo` is not the type of the [`DataReader`] the operation will return [`DdsError::PreconditionNotMet`].
+    pub fn into_typed<Foo>(self) -> DdsResult<DataReader<Foo>>
+    where
+        Foo: DdsHasKey + for<'de> DdsDeserialize<'de> + 'static,
+    {
+        check_data_reader_type::<Foo>(&self.0)?;
+        Ok(DataReader::new(self.0))
+    }
+}
+
 pub trait AnyDataReader {}
 
-fn announce_data_reader(
+fn enable_and_announce_data_reader(r: &DataReaderNode) -> DdsResult<()> {
+    if !r.address().is_enabled()? {
+        r.address().enable()?;
+    }
+
+    announce_data_reader(
+        r.parent_participant(),
+        r.address().as_discovered_reader_data(
+            TopicQos::default(),
+            r.parent_subscriber().get_qos()?,
+            r.parent_participant().get_default_unicast_locator_list()?,
+            r.parent_participant()
+                .get_default_multicast_locator_list()?,
+        )?,
+    )
+}
+
+pub(crate) fn announce_data_reader(
     domain_participant: &ActorAddress<DdsDomainParticipant>,
     discovered_reader_data: DiscoveredReaderData,
 ) -> DdsResult<()> {
@@ -853,8 +1031,9 @@ fn announce_data_reader(
         .find(|x| x.get_type_name().unwrap() == "DiscoveredReaderData")
     {
         sedp_reader_announcer.write_w_timestamp(
-            serialized_data,
+            Data::new(serialized_data),
             dds_serialize_key(&discovered_reader_data)?,
+            dds_key_hash(&discovered_reader_data)?,
             None,
             timestamp,
         )??;
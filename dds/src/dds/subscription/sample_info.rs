@@ -1,7 +1,7 @@
 use crate::infrastructure::{instance::InstanceHandle, time::Time};
 
 /// Enumeration of the possible sample states
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub enum SampleStateKind {
     /// This value indicates that the sample has already been access by means of a read operation.
     Read,
@@ -13,7 +13,7 @@ pub enum SampleStateKind {
 pub const ANY_SAMPLE_STATE: &[SampleStateKind] = &[SampleStateKind::Read, SampleStateKind::NotRead];
 
 /// Enumeration of the possible sample view states
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub enum ViewStateKind {
     /// This value indicates that either this is the first time that the reader has ever accessed samples of that instance, or else
     /// that the reader has accessed previous samples of the instance, but the instance has since been reborn (i.e., become
@@ -28,7 +28,7 @@ pub enum ViewStateKind {
 pub const ANY_VIEW_STATE: &[ViewStateKind] = &[ViewStateKind::New, ViewStateKind::NotNew];
 
 // Enumeration of the possible instance states
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
 pub enum InstanceStateKind {
     /// This value indicates that  (a) samples have been received for the instance, (b) there are live [`DataWriter`](crate::publication::data_writer::DataWriter)
     /// entities writing the instance, and (c) the instance has not been explicitly disposed (or else more samples have been received after it was disposed).
@@ -54,7 +54,7 @@ pub const NOT_ALIVE_INSTANCE_STATE: &[InstanceStateKind] = &[
 ];
 
 /// The [`SampleInfo`] contains the information associated with each received data value.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SampleInfo {
     /// This field indicates whether or not the corresponding data sample has already been read.
     pub sample_state: SampleStateKind,
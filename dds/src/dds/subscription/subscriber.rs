@@ -1,10 +1,28 @@
+use std::{any::TypeId, collections::HashMap};
+
 use crate::{
-    domain::domain_participant::DomainParticipant,
+    builtin_topics::{
+        ParticipantBuiltinTopicData, PublicationBuiltinTopicData, SubscriptionBuiltinTopicData,
+        TopicBuiltinTopicData,
+    },
+    domain::{
+        domain_participant::{
+            check_registered_type, check_type_of_type_name, delete_subscriber, DomainParticipant,
+        },
+        domain_participant_factory::process_sedp_discovery,
+    },
     implementation::{
+        data_representation_builtin_endpoints::{
+            discovered_reader_data::DCPS_SUBSCRIPTION, discovered_topic_data::DCPS_TOPIC,
+            discovered_writer_data::DCPS_PUBLICATION,
+            spdp_discovered_participant_data::DCPS_PARTICIPANT,
+        },
         dds::{
             dds_data_reader::DdsDataReader,
             dds_data_reader_listener::DdsDataReaderListener,
-            nodes::{DataReaderNode, DataReaderNodeKind, SubscriberNodeKind},
+            dds_subscriber_listener::DdsSubscriberListener,
+            multitopic_join::MultiTopicJoin,
+            nodes::{DataReaderNode, DataReaderNodeKind, SubscriberNode, SubscriberNodeKind},
         },
         rtps::{
             endpoint::RtpsEndpoint,
@@ -14,24 +32,29 @@ use crate::{
                 EntityId, Guid, TopicKind, USER_DEFINED_READER_NO_KEY, USER_DEFINED_READER_WITH_KEY,
             },
         },
-        utils::actor::spawn_actor,
+        utils::actor::{spawn_actor, ActorAddress},
     },
     infrastructure::{
         condition::StatusCondition,
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
+        owned::OwnedEntity,
         qos::{DataReaderQos, QosKind, SubscriberQos, TopicQos},
+        qos_policy::{XCDR2_DATA_REPRESENTATION, XCDR_DATA_REPRESENTATION},
         status::{SampleLostStatus, StatusKind},
         time::DURATION_ZERO,
     },
+    subscription::sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
     topic_definition::{
-        topic::Topic,
-        type_support::{DdsGetKey, DdsHasKey, DdsRepresentation},
+        multitopic::MultiTopic,
+        topic::{Topic, TopicType},
+        type_support::{DdsDeserialize, DdsGetKey, DdsHasKey, DdsRepresentation, DdsSetKeyFields},
     },
 };
 
 use super::{
-    data_reader::DataReader, data_reader_listener::DataReaderListener,
+    data_reader::{announce_data_reader, DataReader, UntypedDataReader},
+    data_reader_listener::DataReaderListener,
     subscriber_listener::SubscriberListener,
 };
 
@@ -71,6 +94,17 @@ impl Subscriber {
 //     }
 // }
 
+impl OwnedEntity for Subscriber {
+    fn delete_entity(&self) -> DdsResult<()> {
+        match &self.0 {
+            SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
+            SubscriberNodeKind::UserDefined(s) | SubscriberNodeKind::Listener(s) => {
+                delete_subscriber(s.parent_participant(), self)
+            }
+        }
+    }
+}
+
 impl Subscriber {
     /// This operation creates a [`DataReader`]. The returned [`DataReader`] will be attached and belong to the [`Subscriber`].
     /// The [`DataReader`] returned by this operation has an associated [`Topic`] and a type `Foo`.
@@ -78,6 +112,8 @@ impl Subscriber {
     /// [`Subscriber`]. If the [`Topic`] was created from a different [`DomainParticipant`], the operation will fail and
     /// return a [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError). In case of failure, the operation
     /// will return an error and no writer will be created.
+    /// The operation will also fail and return [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError) if
+    /// the type name of the [`Topic`] has been registered for a type different from `Foo`.
     ///
     /// The special value [`QosKind::Default`] can be used to indicate that the [`DataReader`] should be created with the
     /// default qos set in the factory. The use of this value is equivalent to the application obtaining the default
@@ -90,7 +126,7 @@ impl Subscriber {
     /// use the resulting [`DataReaderQos`] to construct the [`DataReader`].
     pub fn create_datareader<Foo>(
         &self,
-        a_topic: &Topic,
+        a_topic: &Topic<impl TopicType<Foo>>,
         qos: QosKind<DataReaderQos>,
         a_listener: Option<Box<dyn DataReaderListener<Foo = Foo> + Send + Sync>>,
         mask: &[StatusKind],
@@ -99,20 +135,14 @@ impl Subscriber {
         Foo: DdsRepresentation
             + DdsHasKey
             + DdsGetKey
-            + for<'de> serde::Deserialize<'de>
+            + DdsSetKeyFields
+            + for<'de> DdsDeserialize<'de>
             + Send
             + 'static,
     {
         match &self.0 {
-            SubscriberNodeKind::Builtin(_) | SubscriberNodeKind::Listener(_) => {
-                Err(DdsError::IllegalOperation)
-            }
-            SubscriberNodeKind::UserDefined(s) => {
-                let default_unicast_locator_list =
-                    s.parent_participant().get_default_unicast_locator_list()?;
-                let default_multicast_locator_list =
-                    s.parent_participant().get_default_unicast_locator_list()?;
-
+            SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
+            SubscriberNodeKind::UserDefined(s) | SubscriberNodeKind::Listener(s) => {
                 let qos = match qos {
                     QosKind::Default => s.address().get_default_datareader_qos()?,
                     QosKind::Specific(q) => {
@@ -121,60 +151,116 @@ impl Subscriber {
                     }
                 };
 
-                let entity_kind = match Foo::HAS_KEY {
-                    true => USER_DEFINED_READER_WITH_KEY,
-                    false => USER_DEFINED_READER_NO_KEY,
-                };
-                let subscriber_guid = s.address().guid()?;
+                let data_reader =
+                    DataReader::new(DataReaderNodeKind::UserDefined(create_data_reader_node::<
+                        Foo,
+                    >(
+                        s,
+                        a_topic.get_type_name()?,
+                        a_topic.get_name()?,
+                        qos,
+                        a_listener,
+                        mask,
+                        None,
+                    )?));
 
-                let entity_key: [u8; 3] = [
-                    subscriber_guid.entity_id().entity_key()[0],
-                    s.address().get_unique_reader_id()?,
-                    0,
-                ];
+                if s.address().is_enabled()?
+                    && s.address()
+                        .get_qos()?
+                        .entity_factory
+                        .autoenable_created_entities
+                {
+                    data_reader.enable()?;
+                }
 
-                let entity_id = EntityId::new(entity_key, entity_kind);
-                let guid = Guid::new(subscriber_guid.prefix(), entity_id);
+                Ok(data_reader)
+            }
+        }
+    }
 
-                let topic_kind = match Foo::HAS_KEY {
-                    true => TopicKind::WithKey,
-                    false => TopicKind::NoKey,
-                };
+    /// This operation creates a [`DataReader`] on a [`MultiTopic`]. The returned [`DataReader`] will be attached and belong to the [`Subscriber`].
+    /// The samples of the [`DataReader`] are of the type `Foo` resulting from joining the samples of the topics related by the [`MultiTopic`].
+    /// For this purpose the [`Subscriber`] additionally creates a [`DataReader`] for each of the related topics using the same qos.
+    /// Whenever any of these receives a sample, it is combined with the samples of the other related topics which have the same value on
+    /// all the fields they have in common into a new sample. The instances of `Foo` which can no longer be produced are disposed.
+    /// These internal [`DataReader`] entities are not returned by [`Subscriber::lookup_datareader`] or [`Subscriber::get_datareaders`]
+    /// and are deleted together with the [`DataReader`] of the [`MultiTopic`].
+    /// The [`MultiTopic`] passed to this operation must have been created from the same [`DomainParticipant`] that was used to create this
+    /// [`Subscriber`], otherwise the operation will fail and return a [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    pub fn create_multitopic_datareader<Foo>(
+        &self,
+        a_multitopic: &MultiTopic,
+        qos: QosKind<DataReaderQos>,
+        a_listener: Option<Box<dyn DataReaderListener<Foo = Foo> + Send + Sync>>,
+        mask: &[StatusKind],
+    ) -> DdsResult<DataReader<Foo>>
+    where
+        Foo: DdsRepresentation
+            + DdsHasKey
+            + DdsGetKey
+            + DdsSetKeyFields
+            + serde::Serialize
+            + for<'de> serde::Deserialize<'de>
+            + Send
+            + 'static,
+    {
+        match &self.0 {
+            SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
+            SubscriberNodeKind::UserDefined(s) | SubscriberNodeKind::Listener(s) => {
+                if a_multitopic.participant_address() != s.parent_participant() {
+                    return Err(DdsError::PreconditionNotMet(
+                        "MultiTopic must belong to the parent participant of the subscriber"
+                            .to_string(),
+                    ));
+                }
 
-                let rtps_reader = RtpsReader::new(
-                    RtpsEndpoint::new(
-                        guid,
-                        topic_kind,
-                        &default_unicast_locator_list,
-                        &default_multicast_locator_list,
-                    ),
-                    DURATION_ZERO,
-                    DURATION_ZERO,
-                    false,
-                );
+                if !s
+                    .parent_participant()
+                    .has_multitopic(a_multitopic.get_name()?)?
+                {
+                    return Err(DdsError::AlreadyDeleted);
+                }
 
-                let listener =
-                    a_listener.map(|l| spawn_actor(DdsDataReaderListener::new(Box::new(l))));
-                let status_kind = mask.to_vec();
-                let data_reader = DdsDataReader::new::<Foo>(
-                    rtps_reader,
-                    a_topic.get_type_name()?,
-                    a_topic.get_name()?,
-                    qos,
-                    listener,
-                    status_kind,
-                );
+                let qos = match qos {
+                    QosKind::Default => s.address().get_default_datareader_qos()?,
+                    QosKind::Specific(q) => {
+                        q.is_consistent()?;
+                        q
+                    }
+                };
 
-                let reader_actor = spawn_actor(data_reader);
-                let reader_address = reader_actor.address().clone();
-                s.address().data_reader_add(reader_actor)?;
+                let subscription_expression = a_multitopic.parsed_subscription_expression();
+                let multitopic_join = MultiTopicJoin::new::<Foo>(
+                    subscription_expression.field_selection().clone(),
+                    subscription_expression.related_topic_names().len(),
+                );
+                let reader_node = create_data_reader_node::<Foo>(
+                    s,
+                    a_multitopic.get_type_name()?,
+                    a_multitopic.get_name()?,
+                    qos.clone(),
+                    a_listener,
+                    mask,
+                    Some(multitopic_join),
+                )?;
+
+                match (a_multitopic.create_source_readers())(
+                    self,
+                    a_multitopic.related_topic_list(),
+                    &qos,
+                    &reader_node,
+                ) {
+                    Ok(source_readers) => reader_node
+                        .address()
+                        .set_multitopic_source_readers(source_readers)?,
+                    Err(e) => {
+                        s.address()
+                            .data_reader_delete(reader_node.address().get_instance_handle()?)?;
+                        return Err(e);
+                    }
+                }
 
-                let data_reader =
-                    DataReader::new(DataReaderNodeKind::UserDefined(DataReaderNode::new(
-                        reader_address,
-                        s.address().clone(),
-                        s.parent_participant().clone(),
-                    )));
+                let data_reader = DataReader::new(DataReaderNodeKind::UserDefined(reader_node));
 
                 if s.address().is_enabled()?
                     && s.address()
@@ -195,61 +281,30 @@ impl Subscriber {
     /// different [`Subscriber`], the operation will have no effect and it will return [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
     pub fn delete_datareader<Foo>(&self, a_datareader: &DataReader<Foo>) -> DdsResult<()> {
         match &self.0 {
-            SubscriberNodeKind::Builtin(_) | SubscriberNodeKind::Listener(_) => {
-                Err(DdsError::IllegalOperation)
-            }
-            SubscriberNodeKind::UserDefined(s) => match a_datareader.node() {
-                DataReaderNodeKind::_BuiltinStateful(_)
-                | DataReaderNodeKind::_BuiltinStateless(_)
-                | DataReaderNodeKind::Listener(_) => Err(DdsError::IllegalOperation),
-                DataReaderNodeKind::UserDefined(dr) => {
-                    let reader_handle = dr.address().get_instance_handle()?;
-                    if s.address().guid()? != dr.parent_subscriber().guid()? {
-                        return Err(DdsError::PreconditionNotMet(
-                            "Data reader can only be deleted from its parent subscriber"
-                                .to_string(),
-                        ));
-                    }
+            SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
+            SubscriberNodeKind::UserDefined(s) | SubscriberNodeKind::Listener(s) => {
+                match a_datareader.node() {
+                    DataReaderNodeKind::BuiltinStateful(_)
+                    | DataReaderNodeKind::BuiltinStateless(_) => Err(DdsError::IllegalOperation),
+                    DataReaderNodeKind::UserDefined(dr) | DataReaderNodeKind::Listener(dr) => {
+                        if s.address().guid()? != dr.parent_subscriber().guid()? {
+                            return Err(DdsError::PreconditionNotMet(
+                                "Data reader can only be deleted from its parent subscriber"
+                                    .to_string(),
+                            ));
+                        }
 
-                    let reader_is_enabled = dr.address().is_enabled()?;
-                    s.address().data_reader_delete(reader_handle)?;
-
-                    if reader_is_enabled {
-                        let instance_serialized_key =
-                            cdr::serialize::<_, _, cdr::CdrLe>(&reader_handle, cdr::Infinite)
-                                .map_err(|e| DdsError::PreconditionNotMet(e.to_string()))
-                                .expect("Failed to serialize data");
-
-                        let timestamp = dr.parent_participant().get_current_time()?;
-
-                        if let Some(sedp_reader_announcer) = dr
-                            .parent_participant()
-                            .get_builtin_publisher()?
-                            .data_writer_list()?
-                            .iter()
-                            .find(|x| x.get_type_name().unwrap() == "DiscoveredReaderData")
-                        {
-                            sedp_reader_announcer.dispose_w_timestamp(
-                                instance_serialized_key,
-                                reader_handle,
-                                timestamp,
-                            )??;
-
-                            sedp_reader_announcer.send_message(
-                                RtpsMessageHeader::new(
-                                    dr.parent_participant().get_protocol_version()?,
-                                    dr.parent_participant().get_vendor_id()?,
-                                    dr.parent_participant().get_guid()?.prefix(),
-                                ),
-                                dr.parent_participant().get_udp_transport_write()?,
-                                dr.parent_participant().get_current_time()?,
-                            )?;
+                        if !user_data_reader_list(s)?.contains(dr.address()) {
+                            return Err(DdsError::PreconditionNotMet(
+                                "Data reader of a topic related by a MultiTopic can only be deleted together with the MultiTopic data reader"
+                                    .to_string(),
+                            ));
                         }
-                    }
 
-                    Ok(())
+                        delete_data_reader_node(s, dr)
+                    }
                 }
-            },
+            }
         }
     }
 
@@ -257,46 +312,120 @@ impl Subscriber {
     /// If no such [`DataReader`] exists, the operation will succeed but return [`None`].
     /// If multiple [`DataReader`] attached to the [`Subscriber`] satisfy this condition, then the operation will return one of them. It is not
     /// specified which one.
+    /// If the type `Foo` is not the type of the [`DataReader`] the operation will return [`DdsError::PreconditionNotMet`].
     /// The use of this operation on the built-in [`Subscriber`] allows access to the built-in [`DataReader`] entities for the built-in topics.
-    pub fn lookup_datareader<Foo>(&self, _topic_name: &str) -> DdsResult<Option<DataReader<Foo>>>
+    pub fn lookup_datareader<Foo>(&self, topic_name: &str) -> DdsResult<Option<DataReader<Foo>>>
     where
-        Foo: DdsHasKey + for<'de> serde::Deserialize<'de>,
+        Foo: DdsHasKey + for<'de> DdsDeserialize<'de> + 'static,
     {
-        todo!()
-        // Ok(self
-        //     .stateful_data_reader_list
-        //     .iter()
-        //     .find(|data_reader| {
-        //         data_reader.get_topic_name() == topic_name
-        //             && data_reader.get_type_name() == type_name
-        //     })
-        //     .map(|x| DataReaderNode::new(x.guid(), subscriber_guid, domain_participant.guid())))
-
-        // match &self.0 {
-        //     SubscriberNodeKind::Builtin(s) => {
-        //         THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_participant(&s.guid().prefix(), |dp| {
-        //             Ok(
-        //                 crate::implementation::behavior::builtin_subscriber::lookup_datareader::<
-        //                     Foo,
-        //                 >(
-        //                     dp.ok_or(DdsError::AlreadyDeleted)?, s.guid(), topic_name
-        //                 )?
-        //                 .map(|x| DataReader::new(x)),
-        //             )
-        //         })
-        //     }
-        //     SubscriberNodeKind::UserDefined(s) => THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //         .get_participant(&s.guid().prefix(), |dp| {
-        //             Ok(crate::implementation::behavior::user_defined_subscriber::lookup_datareader(
-        //                 dp.ok_or(DdsError::AlreadyDeleted)?,
-        //                 s.guid(),
-        //                 Foo,
-        //                 topic_name,
-        //             )?
-        //             .map(|x| DataReader::new(DataReaderNodeKind::UserDefined(x))))
-        //         }),
-        //     SubscriberNodeKind::Listener(_) => todo!(),
-        // }
+        let s = match &self.0 {
+            SubscriberNodeKind::Builtin(s)
+            | SubscriberNodeKind::UserDefined(s)
+            | SubscriberNodeKind::Listener(s) => s,
+        };
+
+        for data_reader in user_data_reader_list(s)? {
+            if data_reader.get_topic_name()? == topic_name {
+                let data_reader_node_kind = data_reader_node_kind(&self.0, data_reader, topic_name);
+                check_data_reader_type::<Foo>(&data_reader_node_kind)?;
+                return Ok(Some(DataReader::new(data_reader_node_kind)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// This operation indicates that the application is about to access the data samples in any of the [`DataReader`] objects attached to
+    /// the [`Subscriber`].
+    /// The application is required to use this operation only if the [`PresentationQosPolicy`](crate::infrastructure::qos_policy::PresentationQosPolicy)
+    /// of the [`Subscriber`] to which the [`DataReader`] belongs has the `access_scope` set to
+    /// [`PresentationQosPolicyAccessScopeKind::Group`](crate::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind).
+    /// In the aforementioned case, the operation [`Subscriber::begin_access`] must be called prior to calling [`Subscriber::get_datareaders`]
+    /// and accessing the samples of the returned [`DataReader`] objects. Otherwise [`Subscriber::get_datareaders`] will return the error
+    /// [`DdsError::PreconditionNotMet`]. Once the application has finished accessing the data samples it must call
+    /// [`Subscriber::end_access`].
+    /// It is not required for the application to begin or end access if the `access_scope` is set to something other than
+    /// [`PresentationQosPolicyAccessScopeKind::Group`](crate::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind).
+    /// Calling [`Subscriber::begin_access`] in this case is not considered an error and has no effect.
+    /// The calls to [`Subscriber::begin_access`] and [`Subscriber::end_access`] may be nested. In that case, the application must call
+    /// [`Subscriber::end_access`] as many times as it called [`Subscriber::begin_access`].
+    /// The access block only delimits where [`Subscriber::get_datareaders`] may be called. No snapshot of the [`DataReader`] objects
+    /// is taken, so the samples received while the block is open are also made available to the application and the access is not
+    /// coherent across the readers.
+    pub fn begin_access(&self) -> DdsResult<()> {
+        match &self.0 {
+            SubscriberNodeKind::Builtin(s)
+            | SubscriberNodeKind::UserDefined(s)
+            | SubscriberNodeKind::Listener(s) => s.address().begin_access(),
+        }
+    }
+
+    /// Indicates that the application has finished accessing the data samples in [`DataReader`] objects managed by the [`Subscriber`].
+    /// This operation must be used to close a corresponding [`Subscriber::begin_access`].
+    /// After calling [`Subscriber::end_access`] the application should no longer access any of the data samples returned from
+    /// the sample-accessing operations. This call must close a previous call to [`Subscriber::begin_access`] otherwise the operation
+    /// will return the error [`DdsError::PreconditionNotMet`].
+    /// The samples are not held by the [`Subscriber`] so they remain accessible after this operation.
+    pub fn end_access(&self) -> DdsResult<()> {
+        match &self.0 {
+            SubscriberNodeKind::Builtin(s)
+            | SubscriberNodeKind::UserDefined(s)
+            | SubscriberNodeKind::Listener(s) => s.address().end_access()?,
+        }
+    }
+
+    /// This operation allows the application to access the [`DataReader`] objects that contain samples with the specified
+    /// `sample_states`, `view_states`, and `instance_states`. Since the readers may be of different types, they are returned
+    /// as [`UntypedDataReader`] objects which are converted into the [`DataReader`] of the type of their topic with
+    /// [`UntypedDataReader::into_typed`].
+    /// If the [`PresentationQosPolicy`](crate::infrastructure::qos_policy::PresentationQosPolicy) of the [`Subscriber`] has `access_scope`
+    /// set to [`PresentationQosPolicyAccessScopeKind::Group`](crate::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind),
+    /// this operation should only be invoked inside a [`Subscriber::begin_access`]/[`Subscriber::end_access`] block. Otherwise it will
+    /// return the error [`DdsError::PreconditionNotMet`].
+    /// Depending on the setting of the [`PresentationQosPolicy`](crate::infrastructure::qos_policy::PresentationQosPolicy), the returned
+    /// collection of [`DataReader`] objects may be a 'set' containing each [`DataReader`] at most once in no specified order, or a 'list'
+    /// containing each [`DataReader`] one or more times in a specific order.
+    /// If `access_scope` is [`PresentationQosPolicyAccessScopeKind::Group`](crate::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind)
+    /// and `ordered_access` is [`true`], then the returned collection is a 'list' in which a [`DataReader`] appears once per sample.
+    /// In this case the application should process each element in order and take exactly one sample from each [`DataReader`],
+    /// e.g. with [`DataReader::take_next_sample`], to access the samples in the order in which they were published.
+    /// Otherwise the returned collection is a 'set'.
+    pub fn get_datareaders(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<Vec<UntypedDataReader>> {
+        match &self.0 {
+            SubscriberNodeKind::Builtin(s)
+            | SubscriberNodeKind::UserDefined(s)
+            | SubscriberNodeKind::Listener(s) => {
+                let mut data_reader_list = HashMap::new();
+                let mut reader_sample_timestamp_list = Vec::new();
+                for data_reader in user_data_reader_list(s)? {
+                    let handle = data_reader.get_instance_handle()?;
+                    reader_sample_timestamp_list.push((
+                        handle,
+                        data_reader.get_sample_timestamp_list(
+                            sample_states,
+                            view_states,
+                            instance_states,
+                        )?,
+                    ));
+                    let topic_name = data_reader.get_topic_name()?;
+                    data_reader_list.insert(
+                        handle,
+                        data_reader_node_kind(&self.0, data_reader, &topic_name),
+                    );
+                }
+
+                Ok(s.address()
+                    .get_datareaders(reader_sample_timestamp_list)??
+                    .into_iter()
+                    .map(|handle| UntypedDataReader::new(data_reader_list[&handle].clone()))
+                    .collect())
+            }
+        }
     }
 
     /// This operation invokes the operation [`DataReaderListener::on_data_available`] on the listener objects attached to contained [`DataReader`]
@@ -305,9 +434,18 @@ impl Subscriber {
     /// [`SubscriberListener`] can delegate to the [`DataReaderListener`] objects the handling of the data.
     pub fn notify_datareaders(&self) -> DdsResult<()> {
         match &self.0 {
-            SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
-            SubscriberNodeKind::UserDefined(_) => todo!(),
-            SubscriberNodeKind::Listener(_) => todo!(),
+            SubscriberNodeKind::Builtin(s)
+            | SubscriberNodeKind::UserDefined(s)
+            | SubscriberNodeKind::Listener(s) => {
+                for data_reader in s.address().data_reader_list()? {
+                    data_reader.notify_data_available(
+                        data_reader.clone(),
+                        s.address().clone(),
+                        s.parent_participant().clone(),
+                    )?;
+                }
+                Ok(())
+            }
         }
     }
 
@@ -326,8 +464,9 @@ impl Subscriber {
     pub fn get_sample_lost_status(&self) -> DdsResult<SampleLostStatus> {
         match &self.0 {
             SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
-            SubscriberNodeKind::UserDefined(_) => todo!(),
-            SubscriberNodeKind::Listener(_) => todo!(),
+            SubscriberNodeKind::UserDefined(s) | SubscriberNodeKind::Listener(s) => {
+                s.address().get_sample_lost_status()
+            }
         }
     }
 
@@ -338,15 +477,23 @@ impl Subscriber {
     /// Once this operation returns successfully, the application may delete the [`Subscriber`] knowing that it has no
     /// contained [`DataReader`] objects.
     pub fn delete_contained_entities(&self) -> DdsResult<()> {
-        todo!()
-        // match &self.0 {
-        //     SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
-        //     SubscriberNodeKind::UserDefined(s) => THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //         .get_participant_mut(&s.guid().prefix(), |dp| {
-        //             crate::implementation::behavior::user_defined_subscriber::delete_contained_entities(dp.ok_or(DdsError::AlreadyDeleted)?, s.guid())
-        //         }),
-        //     SubscriberNodeKind::Listener(_) => Err(DdsError::IllegalOperation),
-        // }
+        match &self.0 {
+            SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
+            SubscriberNodeKind::UserDefined(s) | SubscriberNodeKind::Listener(s) => {
+                for data_reader in user_data_reader_list(s)? {
+                    delete_data_reader_node(
+                        s,
+                        &DataReaderNode::new(
+                            data_reader,
+                            s.address().clone(),
+                            s.parent_participant().clone(),
+                        ),
+                    )?;
+                }
+
+                Ok(())
+            }
+        }
     }
 
     /// This operation sets a default value of the [`DataReaderQos`] which will be used for newly created [`DataReader`] entities in
@@ -382,10 +529,21 @@ impl Subscriber {
     /// This operation does not check the resulting `a_datareader_qos` for consistency. This is because the merged `a_datareader_qos`
     /// may not be the final one, as the application can still modify some policies prior to applying the policies to the [`DataReader`].
     pub fn copy_from_topic_qos(
-        _a_datareader_qos: &mut DataReaderQos,
-        _a_topic_qos: &TopicQos,
+        a_datareader_qos: &mut DataReaderQos,
+        a_topic_qos: &TopicQos,
     ) -> DdsResult<()> {
-        todo!()
+        a_datareader_qos.durability = a_topic_qos.durability.clone();
+        a_datareader_qos.deadline = a_topic_qos.deadline.clone();
+        a_datareader_qos.latency_budget = a_topic_qos.latency_budget.clone();
+        a_datareader_qos.liveliness = a_topic_qos.liveliness.clone();
+        a_datareader_qos.reliability = a_topic_qos.reliability.clone();
+        a_datareader_qos.destination_order = a_topic_qos.destination_order.clone();
+        a_datareader_qos.history = a_topic_qos.history.clone();
+        a_datareader_qos.resource_limits = a_topic_qos.resource_limits.clone();
+        a_datareader_qos.ownership = a_topic_qos.ownership.clone();
+        a_datareader_qos.representation = a_topic_qos.representation.clone();
+
+        Ok(())
     }
 
     /// This operation is used to set the QoS policies of the Entity and replacing the values of any policies previously set.
@@ -400,20 +558,39 @@ impl Subscriber {
     /// The parameter `qos` can be set to [`QosKind::Default`] to indicate that the QoS of the Entity should be changed to match the current default QoS set in the Entity’s factory.
     /// The operation [`Self::set_qos()`] cannot modify the immutable QoS so a successful return of the operation indicates that the mutable QoS for the Entity has been
     /// modified to match the current default for the Entity’s factory.
-    pub fn set_qos(&self, _qos: QosKind<SubscriberQos>) -> DdsResult<()> {
-        todo!()
-        // match &self.0 {
-        //     SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
-        //     SubscriberNodeKind::UserDefined(s) => THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //         .get_participant_mut(&s.guid().prefix(), |dp| {
-        //             crate::implementation::behavior::user_defined_subscriber::set_qos(
-        //                 dp.ok_or(DdsError::AlreadyDeleted)?,
-        //                 s.guid(),
-        //                 qos,
-        //             )
-        //         }),
-        //     SubscriberNodeKind::Listener(_) => todo!(),
-        // }
+    pub fn set_qos(&self, qos: QosKind<SubscriberQos>) -> DdsResult<()> {
+        match &self.0 {
+            SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
+            SubscriberNodeKind::UserDefined(s) | SubscriberNodeKind::Listener(s) => {
+                let qos = match qos {
+                    QosKind::Default => s.parent_participant().default_subscriber_qos()?,
+                    QosKind::Specific(q) => q,
+                };
+
+                s.address().set_qos(QosKind::Specific(qos))??;
+
+                if s.address().is_enabled()? {
+                    for data_reader in s.address().data_reader_list()? {
+                        if data_reader.is_enabled()? {
+                            announce_data_reader(
+                                s.parent_participant(),
+                                data_reader.as_discovered_reader_data(
+                                    TopicQos::default(),
+                                    s.address().get_qos()?,
+                                    s.parent_participant().get_default_unicast_locator_list()?,
+                                    s.parent_participant()
+                                        .get_default_multicast_locator_list()?,
+                                )?,
+                            )?;
+                        }
+                    }
+
+                    process_sedp_discovery(s.parent_participant())?;
+                }
+
+                Ok(())
+            }
+        }
     }
 
     /// This operation allows access to the existing set of [`SubscriberQos`] policies.
@@ -433,13 +610,17 @@ impl Subscriber {
     /// will be removed.
     pub fn set_listener(
         &self,
-        _a_listener: Option<Box<dyn SubscriberListener + Send + Sync>>,
-        _mask: &[StatusKind],
+        a_listener: Option<Box<dyn SubscriberListener + Send + Sync>>,
+        mask: &[StatusKind],
     ) -> DdsResult<()> {
         match &self.0 {
             SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
-            SubscriberNodeKind::UserDefined(_) => todo!(),
-            SubscriberNodeKind::Listener(_) => Err(DdsError::IllegalOperation),
+            SubscriberNodeKind::UserDefined(s) | SubscriberNodeKind::Listener(s) => {
+                s.address().set_listener(
+                    a_listener.map(|l| spawn_actor(DdsSubscriberListener::new(l))),
+                    mask.to_vec(),
+                )
+            }
         }
     }
 
@@ -463,15 +644,15 @@ impl Subscriber {
     /// The list of statuses returned by the [`Self::get_status_changes`] operation refers to the status that are triggered on the Entity itself
     /// and does not include statuses that apply to contained entities.
     pub fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
-        // match &self.0 {
-        //     SubscriberNodeKind::Builtin(_) => todo!(),
-        //     SubscriberNodeKind::UserDefined(s) => THE_DDS_DOMAIN_PARTICIPANT_FACTORY
-        //         .get_subscriber_listener(&s.guid(), |s| {
-        //             Ok(s.ok_or(DdsError::AlreadyDeleted)?.get_status_changes())
-        //         }),
-        //     SubscriberNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.0 {
+            SubscriberNodeKind::Builtin(s)
+            | SubscriberNodeKind::UserDefined(s)
+            | SubscriberNodeKind::Listener(s) => Ok(s
+                .address()
+                .get_statuscondition()?
+                .read_lock()
+                .get_status_changes()),
+        }
     }
 
     /// This operation enables the Entity. Entity objects can be created either enabled or disabled. This is controlled by the value of
@@ -496,10 +677,8 @@ impl Subscriber {
     /// enabled are “inactive”, that is, the operation [`StatusCondition::get_trigger_value()`] will always return `false`.
     pub fn enable(&self) -> DdsResult<()> {
         match &self.0 {
-            SubscriberNodeKind::Builtin(_) | SubscriberNodeKind::Listener(_) => {
-                Err(DdsError::IllegalOperation)
-            }
-            SubscriberNodeKind::UserDefined(s) => {
+            SubscriberNodeKind::Builtin(_) => Err(DdsError::IllegalOperation),
+            SubscriberNodeKind::UserDefined(s) | SubscriberNodeKind::Listener(s) => {
                 if !s.address().is_enabled()? {
                     s.address().enable()?;
 
@@ -528,3 +707,230 @@ impl Subscriber {
         }
     }
 }
+
+#[allow(clippy::too_many_arguments)]
+fn create_data_reader_node<Foo>(
+    s: &SubscriberNode,
+    type_name: String,
+    topic_name: String,
+    mut qos: DataReaderQos,
+    a_listener: Option<Box<dyn DataReaderListener<Foo = Foo> + Send + Sync>>,
+    mask: &[StatusKind],
+    multitopic_join: Option<MultiTopicJoin>,
+) -> DdsResult<DataReaderNode>
+where
+    Foo: DdsRepresentation
+        + DdsHasKey
+        + DdsGetKey
+        + DdsSetKeyFields
+        + for<'de> DdsDeserialize<'de>
+        + Send
+        + 'static,
+{
+    check_registered_type::<Foo>(s.parent_participant(), &type_name)?;
+    let dynamic_type = s
+        .parent_participant()
+        .get_registered_dynamic_type(type_name.clone())?;
+
+    if qos.representation.value.is_empty() {
+        qos.representation.value = vec![XCDR_DATA_REPRESENTATION, XCDR2_DATA_REPRESENTATION];
+    }
+
+    let default_unicast_locator_list = s.parent_participant().get_default_unicast_locator_list()?;
+    let default_multicast_locator_list =
+        s.parent_participant().get_default_unicast_locator_list()?;
+
+    let has_key = Foo::has_key(dynamic_type.as_ref());
+    let entity_kind = match has_key {
+        true => USER_DEFINED_READER_WITH_KEY,
+        false => USER_DEFINED_READER_NO_KEY,
+    };
+    let subscriber_guid = s.address().guid()?;
+
+    let entity_key: [u8; 3] = [
+        subscriber_guid.entity_id().entity_key()[0],
+        s.address().get_unique_reader_id()?,
+        0,
+    ];
+
+    let entity_id = EntityId::new(entity_key, entity_kind);
+    let guid = Guid::new(subscriber_guid.prefix(), entity_id);
+
+    let topic_kind = match has_key {
+        true => TopicKind::WithKey,
+        false => TopicKind::NoKey,
+    };
+
+    let rtps_reader = RtpsReader::new(
+        RtpsEndpoint::new(
+            guid,
+            topic_kind,
+            &default_unicast_locator_list,
+            &default_multicast_locator_list,
+        ),
+        DURATION_ZERO,
+        DURATION_ZERO,
+        false,
+    );
+
+    let listener = a_listener.map(|l| spawn_actor(DdsDataReaderListener::new(Box::new(l))));
+    let status_kind = mask.to_vec();
+    let mut data_reader = DdsDataReader::new::<Foo>(
+        rtps_reader,
+        type_name,
+        topic_name,
+        qos,
+        listener,
+        status_kind,
+    );
+    if let Some(multitopic_join) = multitopic_join {
+        data_reader.set_multitopic_join(multitopic_join);
+    }
+    if let Some(dynamic_type) = dynamic_type {
+        data_reader.set_dynamic_type(dynamic_type);
+    }
+
+    let reader_actor = spawn_actor(data_reader);
+    let reader_address = reader_actor.address().clone();
+    s.address().data_reader_add(reader_actor)?;
+
+    Ok(DataReaderNode::new(
+        reader_address,
+        s.address().clone(),
+        s.parent_participant().clone(),
+    ))
+}
+
+// The readers of the topics related by a MultiTopic are internal to the reader created on it
+// and therefore not available to the application
+fn user_data_reader_list(s: &SubscriberNode) -> DdsResult<Vec<ActorAddress<DdsDataReader>>> {
+    let data_reader_list = s.address().data_reader_list()?;
+    let mut multitopic_source_readers = Vec::new();
+    for data_reader in &data_reader_list {
+        multitopic_source_readers.extend(data_reader.get_multitopic_source_readers()?);
+    }
+    Ok(data_reader_list
+        .into_iter()
+        .filter(|r| !multitopic_source_readers.contains(r))
+        .collect())
+}
+
+fn data_reader_node_kind(
+    subscriber: &SubscriberNodeKind,
+    data_reader: ActorAddress<DdsDataReader>,
+    topic_name: &str,
+) -> DataReaderNodeKind {
+    match subscriber {
+        // The participant discovery reader is the only builtin reader which is stateless
+        SubscriberNodeKind::Builtin(s) if topic_name == DCPS_PARTICIPANT => {
+            DataReaderNodeKind::BuiltinStateless(DataReaderNode::new(
+                data_reader,
+                s.address().clone(),
+                s.parent_participant().clone(),
+            ))
+        }
+        SubscriberNodeKind::Builtin(s) => DataReaderNodeKind::BuiltinStateful(DataReaderNode::new(
+            data_reader,
+            s.address().clone(),
+            s.parent_participant().clone(),
+        )),
+        SubscriberNodeKind::UserDefined(s) => DataReaderNodeKind::UserDefined(DataReaderNode::new(
+            data_reader,
+            s.address().clone(),
+            s.parent_participant().clone(),
+        )),
+        SubscriberNodeKind::Listener(s) => DataReaderNodeKind::Listener(DataReaderNode::new(
+            data_reader,
+            s.address().clone(),
+            s.parent_participant().clone(),
+        )),
+    }
+}
+
+pub(crate) fn check_data_reader_type<Foo>(data_reader: &DataReaderNodeKind) -> DdsResult<()>
+where
+    Foo: 'static,
+{
+    match data_reader {
+        DataReaderNodeKind::BuiltinStateful(dr) | DataReaderNodeKind::BuiltinStateless(dr) => {
+            let topic_name = dr.address().get_topic_name()?;
+            let builtin_type_id = match topic_name.as_str() {
+                DCPS_PARTICIPANT => TypeId::of::<ParticipantBuiltinTopicData>(),
+                DCPS_TOPIC => TypeId::of::<TopicBuiltinTopicData>(),
+                DCPS_PUBLICATION => TypeId::of::<PublicationBuiltinTopicData>(),
+                DCPS_SUBSCRIPTION => TypeId::of::<SubscriptionBuiltinTopicData>(),
+                _ => {
+                    return Err(DdsError::Error(format!(
+                        "Unknown built-in topic {}",
+                        topic_name
+                    )))
+                }
+            };
+            if builtin_type_id != TypeId::of::<Foo>() {
+                return Err(DdsError::PreconditionNotMet(format!(
+                    "Type {} does not match the type of built-in topic {}",
+                    std::any::type_name::<Foo>(),
+                    topic_name
+                )));
+            }
+            Ok(())
+        }
+        DataReaderNodeKind::UserDefined(dr) | DataReaderNodeKind::Listener(dr) => {
+            check_type_of_type_name::<Foo>(dr.parent_participant(), &dr.address().get_type_name()?)
+        }
+    }
+}
+
+fn delete_data_reader_node(s: &SubscriberNode, dr: &DataReaderNode) -> DdsResult<()> {
+    // The readers of the topics related by a MultiTopic are owned by the reader created on it
+    // so they are deleted together with it
+    for source_reader in dr.address().get_multitopic_source_readers()? {
+        delete_data_reader_node(
+            s,
+            &DataReaderNode::new(
+                source_reader,
+                s.address().clone(),
+                s.parent_participant().clone(),
+            ),
+        )?;
+    }
+
+    let reader_handle = dr.address().get_instance_handle()?;
+    let reader_is_enabled = dr.address().is_enabled()?;
+    s.address().data_reader_delete(reader_handle)?;
+
+    if reader_is_enabled {
+        let instance_serialized_key =
+            cdr::serialize::<_, _, cdr::CdrLe>(&reader_handle, cdr::Infinite)
+                .map_err(|e| DdsError::PreconditionNotMet(e.to_string()))
+                .expect("Failed to serialize data");
+
+        let timestamp = dr.parent_participant().get_current_time()?;
+
+        if let Some(sedp_reader_announcer) = dr
+            .parent_participant()
+            .get_builtin_publisher()?
+            .data_writer_list()?
+            .iter()
+            .find(|x| x.get_type_name().unwrap() == "DiscoveredReaderData")
+        {
+            sedp_reader_announcer.dispose_w_timestamp(
+                instance_serialized_key,
+                reader_handle,
+                timestamp,
+            )??;
+
+            sedp_reader_announcer.send_message(
+                RtpsMessageHeader::new(
+                    dr.parent_participant().get_protocol_version()?,
+                    dr.parent_participant().get_vendor_id()?,
+                    dr.parent_participant().get_guid()?.prefix(),
+                ),
+                dr.parent_participant().get_udp_transport_write()?,
+                dr.parent_participant().get_current_time()?,
+            )?;
+        }
+    }
+
+    Ok(())
+}
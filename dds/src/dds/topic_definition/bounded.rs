@@ -0,0 +1,380 @@
+use std::{convert::TryFrom, fmt, ops::Deref};
+
+use serde::de;
+
+use crate::infrastructure::error::{DdsError, DdsResult};
+
+use super::{
+    type_object::{TypeIdentifier, TypeObjectBuilder},
+    type_support::{
+        append_max_serialized_sizes, delimited_collections, DdsGetKey, DdsSetKeyFields,
+        DdsTypeDescriptor, StructDescriptor,
+    },
+};
+
+/// String of at most `N` bytes, corresponding to the IDL `string<N>` type.
+///
+/// Unlike the [`String`] fields, the [`BoundedString`] fields give a maximum size to the types deriving
+/// [`DdsType`](crate::topic_definition::type_support::DdsType). The bound is checked when the string is created
+/// and when it is deserialized.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct BoundedString<const N: usize>(String);
+
+impl<const N: usize> BoundedString<N> {
+    /// Creates a new [`BoundedString`] from the given string.
+    /// The operation fails with [`DdsError::BadParameter`] if the string is longer than `N` bytes.
+    pub fn new(value: impl Into<String>) -> DdsResult<Self> {
+        let value = value.into();
+        if value.len() <= N {
+            Ok(Self(value))
+        } else {
+            Err(DdsError::BadParameter)
+        }
+    }
+
+    /// Returns the string slice of the [`BoundedString`].
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Converts the [`BoundedString`] into the underlying [`String`].
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<const N: usize> Deref for BoundedString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize> fmt::Display for BoundedString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<const N: usize> From<BoundedString<N>> for String {
+    fn from(value: BoundedString<N>) -> Self {
+        value.0
+    }
+}
+
+impl<const N: usize> TryFrom<String> for BoundedString<N> {
+    type Error = DdsError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for BoundedString<N> {
+    type Error = DdsError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl<const N: usize> serde::Serialize for BoundedString<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize> serde::Deserialize<'de> for BoundedString<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        let length = value.len();
+        Self::new(value).map_err(|_| {
+            de::Error::custom(format!(
+                "String of length {} exceeds the bound {}",
+                length, N
+            ))
+        })
+    }
+}
+
+// The strings are serialized with their length, their bytes and a terminating null character
+impl<const N: usize> DdsTypeDescriptor for BoundedString<N> {
+    const MAX_SERIALIZED_SIZE: Option<usize> = Some(4 + N + 1);
+    const ALIGNMENT: usize = 4;
+
+    fn type_identifier(_builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+        Some(TypeIdentifier::String8 {
+            bound: u32::try_from(N).ok()?,
+        })
+    }
+}
+
+impl<const N: usize> DdsGetKey for BoundedString<N> {
+    type BorrowedKeyHolder<'a> = &'a str;
+
+    const MAX_KEY_SIZE: Option<usize> = Some(4 + N + 1);
+    const KEY_ALIGNMENT: usize = 4;
+
+    fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
+        &self.0
+    }
+}
+
+impl<const N: usize> DdsSetKeyFields for BoundedString<N> {
+    type OwningKeyHolder = Self;
+
+    fn set_key_from_holder(&mut self, key_holder: Self::OwningKeyHolder) {
+        *self = key_holder;
+    }
+}
+
+/// Sequence of at most `N` elements, corresponding to the IDL `sequence<T, N>` type.
+///
+/// Unlike the [`Vec`] fields, the [`BoundedVec`] fields give a maximum size to the types deriving
+/// [`DdsType`](crate::topic_definition::type_support::DdsType) when the size of the elements is bounded. The bound
+/// is checked when the sequence is created or grown and when it is deserialized.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BoundedVec<T, const N: usize>(Vec<T>);
+
+impl<T, const N: usize> BoundedVec<T, N> {
+    /// Creates a new [`BoundedVec`] from the given elements.
+    /// The operation fails with [`DdsError::BadParameter`] if there are more than `N` elements.
+    pub fn new(value: Vec<T>) -> DdsResult<Self> {
+        if value.len() <= N {
+            Ok(Self(value))
+        } else {
+            Err(DdsError::BadParameter)
+        }
+    }
+
+    /// Appends an element to the back of the [`BoundedVec`].
+    /// The operation fails with [`DdsError::OutOfResources`] if the [`BoundedVec`] already has `N` elements.
+    pub fn push(&mut self, value: T) -> DdsResult<()> {
+        if self.0.len() < N {
+            self.0.push(value);
+            Ok(())
+        } else {
+            Err(DdsError::OutOfResources)
+        }
+    }
+
+    /// Returns the slice of the elements of the [`BoundedVec`].
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Converts the [`BoundedVec`] into the underlying [`Vec`].
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const N: usize> Default for BoundedVec<T, N> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T, const N: usize> Deref for BoundedVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> From<BoundedVec<T, N>> for Vec<T> {
+    fn from(value: BoundedVec<T, N>) -> Self {
+        value.0
+    }
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for BoundedVec<T, N> {
+    type Error = DdsError;
+
+    fn try_from(value: Vec<T>) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl<T, const N: usize> serde::Serialize for BoundedVec<T, N>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T, const N: usize> serde::Deserialize<'de> for BoundedVec<T, N>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = Vec::<T>::deserialize(deserializer)?;
+        let length = value.len();
+        Self::new(value).map_err(|_| {
+            de::Error::custom(format!(
+                "Sequence of length {} exceeds the bound {}",
+                length, N
+            ))
+        })
+    }
+}
+
+// The sequences are serialized with their length preceded by a DHEADER in XCDR2 when their elements are not
+// primitive types
+impl<T: DdsTypeDescriptor, const N: usize> DdsTypeDescriptor for BoundedVec<T, N> {
+    const MAX_SERIALIZED_SIZE: Option<usize> =
+        append_max_serialized_sizes(Some(4 + 4), T::MAX_SERIALIZED_SIZE, T::ALIGNMENT, N);
+    const ALIGNMENT: usize = if T::ALIGNMENT > 4 { T::ALIGNMENT } else { 4 };
+    const DELIMITED_COLLECTIONS: u32 = delimited_collections::<T>();
+
+    fn collect_struct_descriptors(descriptors: &mut Vec<&'static StructDescriptor>) {
+        T::collect_struct_descriptors(descriptors)
+    }
+
+    fn type_identifier(builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+        Some(TypeIdentifier::Sequence {
+            bound: u32::try_from(N).ok()?,
+            element: Box::new(T::type_identifier(builder)?),
+        })
+    }
+}
+
+// The whole elements of a sequence form its key
+impl<T, const N: usize> DdsGetKey for BoundedVec<T, N>
+where
+    T: DdsTypeDescriptor + serde::Serialize,
+{
+    type BorrowedKeyHolder<'a>
+        = &'a [T]
+    where
+        T: 'a;
+
+    const MAX_KEY_SIZE: Option<usize> =
+        append_max_serialized_sizes(Some(4), T::MAX_SERIALIZED_SIZE, T::ALIGNMENT, N);
+    const KEY_ALIGNMENT: usize = if T::ALIGNMENT > 4 { T::ALIGNMENT } else { 4 };
+
+    fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> DdsSetKeyFields for BoundedVec<T, N>
+where
+    T: serde::Serialize + for<'de> serde::Deserialize<'de>,
+{
+    type OwningKeyHolder = Self;
+
+    fn set_key_from_holder(&mut self, key_holder: Self::OwningKeyHolder) {
+        *self = key_holder;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic_definition::type_support::{
+        dds_deserialize_from_bytes, dds_serialize_to_bytes, DdsRepresentation, Representation,
+    };
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Unbounded {
+        name: String,
+        values: Vec<u8>,
+    }
+
+    impl DdsRepresentation for Unbounded {
+        const REPRESENTATION: Representation = Representation::CdrLe;
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Bounded {
+        name: BoundedString<4>,
+        values: BoundedVec<u8, 2>,
+    }
+
+    impl DdsRepresentation for Bounded {
+        const REPRESENTATION: Representation = Representation::CdrLe;
+    }
+
+    #[test]
+    fn bounded_string_rejects_longer_values() {
+        assert_eq!(BoundedString::<4>::new("abcd").unwrap().as_str(), "abcd");
+        assert_eq!(
+            BoundedString::<4>::new("abcde"),
+            Err(DdsError::BadParameter)
+        );
+    }
+
+    #[test]
+    fn bounded_vec_rejects_more_elements() {
+        let mut value = BoundedVec::<u8, 2>::new(vec![1]).unwrap();
+        assert_eq!(value.push(2), Ok(()));
+        assert_eq!(value.push(3), Err(DdsError::OutOfResources));
+        assert_eq!(value.as_slice(), &[1, 2]);
+        assert_eq!(
+            BoundedVec::<u8, 2>::new(vec![1, 2, 3]),
+            Err(DdsError::BadParameter)
+        );
+    }
+
+    #[test]
+    fn bounded_values_are_serialized_as_unbounded_values() {
+        let bounded = Bounded {
+            name: BoundedString::new("abc").unwrap(),
+            values: BoundedVec::new(vec![1, 2]).unwrap(),
+        };
+        let unbounded = Unbounded {
+            name: "abc".to_string(),
+            values: vec![1, 2],
+        };
+        let bytes = dds_serialize_to_bytes(&bounded).unwrap();
+
+        assert_eq!(bytes, dds_serialize_to_bytes(&unbounded).unwrap());
+        assert_eq!(
+            dds_deserialize_from_bytes::<Bounded>(&bytes).unwrap(),
+            bounded
+        );
+    }
+
+    #[test]
+    fn deserializing_values_exceeding_the_bounds_fails() {
+        let long_name = dds_serialize_to_bytes(&Unbounded {
+            name: "abcde".to_string(),
+            values: vec![],
+        })
+        .unwrap();
+        let many_values = dds_serialize_to_bytes(&Unbounded {
+            name: String::new(),
+            values: vec![1, 2, 3],
+        })
+        .unwrap();
+
+        assert!(dds_deserialize_from_bytes::<Bounded>(&long_name).is_err());
+        assert!(dds_deserialize_from_bytes::<Bounded>(&many_values).is_err());
+    }
+
+    #[test]
+    fn max_serialized_size_of_bounded_types() {
+        assert_eq!(BoundedString::<4>::MAX_SERIALIZED_SIZE, Some(9));
+        assert_eq!(BoundedVec::<u8, 2>::MAX_SERIALIZED_SIZE, Some(10));
+        assert_eq!(BoundedVec::<u64, 2>::MAX_SERIALIZED_SIZE, Some(24));
+        assert_eq!(BoundedVec::<String, 2>::MAX_SERIALIZED_SIZE, None);
+        assert_eq!(BoundedString::<4>::MAX_KEY_SIZE, Some(9));
+    }
+}
@@ -0,0 +1,268 @@
+use super::type_support::{
+    DdsGetKey, DdsHasKey, DdsRepresentation, DdsSetKeyFields, DdsTypeDescriptor, Representation,
+    TypeSupport,
+};
+
+/// Built-in type of the topics exchanging text, equivalent to the `DDS::String` type defined by other vendors as
+/// `struct String { string value; };`.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct String {
+    pub value: std::string::String,
+}
+
+impl String {
+    pub fn new(value: impl Into<std::string::String>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+}
+
+/// Built-in type of the topics exchanging text identified by a key, equivalent to the `DDS::KeyedString` type defined
+/// by other vendors as `struct KeyedString { @key string key; string value; };`.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct KeyedString {
+    pub key: std::string::String,
+    pub value: std::string::String,
+}
+
+impl KeyedString {
+    pub fn new(key: impl Into<std::string::String>, value: impl Into<std::string::String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// Built-in type of the topics exchanging opaque data, equivalent to the `DDS::Octets` type defined by other vendors
+/// as `struct Octets { sequence<octet> value; };`.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct Bytes {
+    pub value: Vec<u8>,
+}
+
+impl Bytes {
+    pub fn new(value: impl Into<Vec<u8>>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+}
+
+/// Built-in type of the topics exchanging opaque data identified by a key, equivalent to the `DDS::KeyedOctets` type
+/// defined by other vendors as `struct KeyedOctets { @key string key; sequence<octet> value; };`.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct KeyedBytes {
+    pub key: std::string::String,
+    pub value: Vec<u8>,
+}
+
+impl KeyedBytes {
+    pub fn new(key: impl Into<std::string::String>, value: impl Into<Vec<u8>>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+// The built-in types are final structures which are serialized in the same way with the classic CDR
+// representation understood by all the vendors
+macro_rules! implement_built_in_type {
+    ($t:ident, $type_name:expr, $has_key:expr) => {
+        impl DdsHasKey for $t {
+            const HAS_KEY: bool = $has_key;
+        }
+
+        impl DdsRepresentation for $t {
+            const REPRESENTATION: Representation = Representation::CdrLe;
+        }
+
+        impl DdsTypeDescriptor for $t {}
+
+        impl TypeSupport for $t {
+            fn get_type_name() -> &'static str {
+                $type_name
+            }
+        }
+    };
+}
+
+implement_built_in_type!(String, "DDS::String", false);
+implement_built_in_type!(KeyedString, "DDS::KeyedString", true);
+implement_built_in_type!(Bytes, "DDS::Octets", false);
+implement_built_in_type!(KeyedBytes, "DDS::KeyedOctets", true);
+
+macro_rules! implement_dds_get_key_for_unkeyed_type {
+    ($t:ident) => {
+        impl DdsGetKey for $t {
+            type BorrowedKeyHolder<'a> = ();
+
+            const MAX_KEY_SIZE: Option<usize> = Some(0);
+
+            fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {}
+        }
+
+        impl DdsSetKeyFields for $t {
+            type OwningKeyHolder = ();
+
+            fn set_key_from_holder(&mut self, _key_holder: Self::OwningKeyHolder) {}
+        }
+    };
+}
+
+/// Key of the [`KeyedString`] and [`KeyedBytes`] samples, formed by their `key` field.
+#[doc(hidden)]
+#[derive(serde::Serialize)]
+pub struct BorrowedKey<'a> {
+    key: &'a str,
+}
+
+/// Key of the [`KeyedString`] and [`KeyedBytes`] samples, formed by their `key` field.
+#[doc(hidden)]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct OwningKey {
+    key: std::string::String,
+}
+
+macro_rules! implement_dds_get_key_for_keyed_type {
+    ($t:ident) => {
+        impl DdsGetKey for $t {
+            type BorrowedKeyHolder<'a> = BorrowedKey<'a>;
+
+            fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
+                BorrowedKey { key: &self.key }
+            }
+        }
+
+        impl DdsSetKeyFields for $t {
+            type OwningKeyHolder = OwningKey;
+
+            fn set_key_from_holder(&mut self, key_holder: Self::OwningKeyHolder) {
+                self.key = key_holder.key;
+            }
+        }
+    };
+}
+
+implement_dds_get_key_for_unkeyed_type!(String);
+implement_dds_get_key_for_keyed_type!(KeyedString);
+implement_dds_get_key_for_unkeyed_type!(Bytes);
+implement_dds_get_key_for_keyed_type!(KeyedBytes);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic_definition::{
+        json::key_to_json_value,
+        type_support::{dds_deserialize_from_bytes, dds_serialize_key, dds_serialize_to_bytes},
+    };
+
+    #[test]
+    fn serialize_keyed_string() {
+        let data = KeyedString::new("ab", "c");
+        let expected = vec![
+            0, 1, 0, 0, // CDR_LE
+            3, 0, 0, 0, // key length
+            b'a', b'b', 0, 0, // key
+            2, 0, 0, 0, // value length
+            b'c', 0, // value
+        ];
+
+        let bytes = dds_serialize_to_bytes(&data).unwrap();
+
+        assert_eq!(bytes, expected);
+        assert_eq!(
+            dds_deserialize_from_bytes::<KeyedString>(&bytes).unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn serialize_bytes() {
+        let data = Bytes::new([1, 2, 3]);
+        let expected = vec![
+            0, 1, 0, 0, // CDR_LE
+            3, 0, 0, 0, // length
+            1, 2, 3, // value
+        ];
+
+        let bytes = dds_serialize_to_bytes(&data).unwrap();
+
+        assert_eq!(bytes, expected);
+        assert_eq!(dds_deserialize_from_bytes::<Bytes>(&bytes).unwrap(), data);
+    }
+
+    #[test]
+    fn keyed_bytes_key_is_the_key_string() {
+        let data = KeyedBytes::new("ab", vec![1]);
+
+        assert_eq!(
+            dds_serialize_key(&data).unwrap(),
+            dds_serialize_key(&KeyedString::new("ab", "")).unwrap()
+        );
+    }
+
+    #[test]
+    fn keyed_string_key_is_converted_to_json_object() {
+        let data = KeyedString::new("ab", "c");
+
+        assert_eq!(
+            key_to_json_value(&data).unwrap(),
+            serde_json::json!({"key": "ab"})
+        );
+    }
+
+    #[test]
+    fn built_in_type_names() {
+        assert_eq!(String::get_type_name(), "DDS::String");
+        assert_eq!(KeyedString::get_type_name(), "DDS::KeyedString");
+        assert_eq!(Bytes::get_type_name(), "DDS::Octets");
+        assert_eq!(KeyedBytes::get_type_name(), "DDS::KeyedOctets");
+    }
+}
@@ -0,0 +1,1255 @@
+use std::{convert::TryFrom, fmt};
+
+use serde::{
+    de::{self, DeserializeSeed},
+    ser::{SerializeSeq, SerializeTuple, SerializeTupleStruct},
+};
+use serde_json::{Map, Value};
+
+use crate::infrastructure::{
+    error::{DdsError, DdsResult},
+    instance::InstanceHandle,
+};
+
+use super::{
+    dynamic_type::{is_discriminator_value, DynamicType, DynamicTypeMember, TypeKind},
+    type_support::{
+        dds_deserialize_seed_from_bytes, key_hash, DdsDeserialize, DdsGetKey, DdsHasKey,
+        DdsRepresentation, DdsSetKeyFields, Representation,
+    },
+};
+
+/// Value of a member of a [`DynamicData`] or of an element of a sequence or array.
+///
+/// The values of the byte and `UInt8` types are both represented with [`DynamicValue::UInt8`], the values of the
+/// enumerations with the value of their literal, the values of the structures and unions with a nested
+/// [`DynamicData`] and the values of the sequences and arrays with a [`DynamicValue::Collection`] of their elements.
+/// The elements of a multidimensional array are stored in row-major order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DynamicValue {
+    Boolean(bool),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    Float32(f32),
+    Float64(f64),
+    Char8(char),
+    String(String),
+    Enumeration(i32),
+    Data(DynamicData),
+    Collection(Vec<DynamicValue>),
+}
+
+macro_rules! implement_from_for_dynamic_value {
+    ($t:ty, $variant:ident) => {
+        impl From<$t> for DynamicValue {
+            fn from(value: $t) -> Self {
+                DynamicValue::$variant(value)
+            }
+        }
+    };
+}
+
+implement_from_for_dynamic_value!(bool, Boolean);
+implement_from_for_dynamic_value!(i8, Int8);
+implement_from_for_dynamic_value!(i16, Int16);
+implement_from_for_dynamic_value!(i32, Int32);
+implement_from_for_dynamic_value!(i64, Int64);
+implement_from_for_dynamic_value!(u8, UInt8);
+implement_from_for_dynamic_value!(u16, UInt16);
+implement_from_for_dynamic_value!(u32, UInt32);
+implement_from_for_dynamic_value!(u64, UInt64);
+implement_from_for_dynamic_value!(f32, Float32);
+implement_from_for_dynamic_value!(f64, Float64);
+implement_from_for_dynamic_value!(char, Char8);
+implement_from_for_dynamic_value!(String, String);
+implement_from_for_dynamic_value!(DynamicData, Data);
+implement_from_for_dynamic_value!(Vec<DynamicValue>, Collection);
+
+impl From<&str> for DynamicValue {
+    fn from(value: &str) -> Self {
+        DynamicValue::String(value.to_string())
+    }
+}
+
+impl DynamicValue {
+    fn default_of(dynamic_type: &DynamicType) -> Self {
+        match dynamic_type.kind() {
+            TypeKind::Boolean => DynamicValue::Boolean(false),
+            TypeKind::Byte | TypeKind::UInt8 => DynamicValue::UInt8(0),
+            TypeKind::Int8 => DynamicValue::Int8(0),
+            TypeKind::Int16 => DynamicValue::Int16(0),
+            TypeKind::Int32 => DynamicValue::Int32(0),
+            TypeKind::Int64 => DynamicValue::Int64(0),
+            TypeKind::UInt16 => DynamicValue::UInt16(0),
+            TypeKind::UInt32 => DynamicValue::UInt32(0),
+            TypeKind::UInt64 => DynamicValue::UInt64(0),
+            TypeKind::Float32 => DynamicValue::Float32(0.0),
+            TypeKind::Float64 => DynamicValue::Float64(0.0),
+            TypeKind::Char8 => DynamicValue::Char8('\0'),
+            TypeKind::String8 => DynamicValue::String(String::new()),
+            TypeKind::Enumeration => DynamicValue::Enumeration(dynamic_type.literals()[0].value()),
+            TypeKind::Structure | TypeKind::Union => {
+                DynamicValue::Data(DynamicData::new_default(dynamic_type))
+            }
+            TypeKind::Sequence => DynamicValue::Collection(Vec::new()),
+            TypeKind::Array => {
+                let element_type = dynamic_type.element_type().expect("Arrays have elements");
+                DynamicValue::Collection(
+                    (0..dynamic_type.element_count())
+                        .map(|_| DynamicValue::default_of(element_type))
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    fn is_value_of(&self, dynamic_type: &DynamicType) -> bool {
+        match (dynamic_type.kind(), self) {
+            (TypeKind::Boolean, DynamicValue::Boolean(_))
+            | (TypeKind::Byte | TypeKind::UInt8, DynamicValue::UInt8(_))
+            | (TypeKind::Int8, DynamicValue::Int8(_))
+            | (TypeKind::Int16, DynamicValue::Int16(_))
+            | (TypeKind::Int32, DynamicValue::Int32(_))
+            | (TypeKind::Int64, DynamicValue::Int64(_))
+            | (TypeKind::UInt16, DynamicValue::UInt16(_))
+            | (TypeKind::UInt32, DynamicValue::UInt32(_))
+            | (TypeKind::UInt64, DynamicValue::UInt64(_))
+            | (TypeKind::Float32, DynamicValue::Float32(_))
+            | (TypeKind::Float64, DynamicValue::Float64(_)) => true,
+            (TypeKind::Char8, DynamicValue::Char8(c)) => c.is_ascii(),
+            (TypeKind::String8, DynamicValue::String(s)) => {
+                dynamic_type.bound() == 0 || s.chars().count() <= dynamic_type.bound() as usize
+            }
+            (TypeKind::Enumeration, DynamicValue::Enumeration(v)) => {
+                dynamic_type.literals().iter().any(|l| l.value() == *v)
+            }
+            (TypeKind::Structure | TypeKind::Union, DynamicValue::Data(d)) => {
+                &d.dynamic_type == dynamic_type
+            }
+            (TypeKind::Sequence, DynamicValue::Collection(elements)) => {
+                (dynamic_type.bound() == 0 || elements.len() <= dynamic_type.bound() as usize)
+                    && elements_are_values_of(elements, dynamic_type)
+            }
+            (TypeKind::Array, DynamicValue::Collection(elements)) => {
+                elements.len() == dynamic_type.element_count()
+                    && elements_are_values_of(elements, dynamic_type)
+            }
+            _ => false,
+        }
+    }
+
+    fn to_discriminator(&self) -> i64 {
+        match self {
+            DynamicValue::Boolean(v) => i64::from(*v),
+            DynamicValue::Int8(v) => i64::from(*v),
+            DynamicValue::Int16(v) => i64::from(*v),
+            DynamicValue::Int32(v) | DynamicValue::Enumeration(v) => i64::from(*v),
+            DynamicValue::Int64(v) => *v,
+            DynamicValue::UInt8(v) => i64::from(*v),
+            DynamicValue::UInt16(v) => i64::from(*v),
+            DynamicValue::UInt32(v) => i64::from(*v),
+            DynamicValue::UInt64(v) => *v as i64,
+            DynamicValue::Char8(v) => i64::from(*v as u32),
+            _ => {
+                unreachable!("Discriminators are boolean, integer, character or enumeration values")
+            }
+        }
+    }
+
+    fn from_discriminator(discriminator_type: &DynamicType, discriminator: i64) -> Self {
+        match discriminator_type.kind() {
+            TypeKind::Boolean => DynamicValue::Boolean(discriminator != 0),
+            TypeKind::Byte | TypeKind::UInt8 => DynamicValue::UInt8(discriminator as u8),
+            TypeKind::Int8 => DynamicValue::Int8(discriminator as i8),
+            TypeKind::Int16 => DynamicValue::Int16(discriminator as i16),
+            TypeKind::Int32 => DynamicValue::Int32(discriminator as i32),
+            TypeKind::Int64 => DynamicValue::Int64(discriminator),
+            TypeKind::UInt16 => DynamicValue::UInt16(discriminator as u16),
+            TypeKind::UInt32 => DynamicValue::UInt32(discriminator as u32),
+            TypeKind::UInt64 => DynamicValue::UInt64(discriminator as u64),
+            TypeKind::Char8 => DynamicValue::Char8(discriminator as u8 as char),
+            TypeKind::Enumeration => DynamicValue::Enumeration(discriminator as i32),
+            _ => {
+                unreachable!("Discriminators are boolean, integer, character or enumeration values")
+            }
+        }
+    }
+}
+
+fn elements_are_values_of(elements: &[DynamicValue], dynamic_type: &DynamicType) -> bool {
+    let element_type = dynamic_type
+        .element_type()
+        .expect("Collections have elements");
+    elements.iter().all(|e| e.is_value_of(element_type))
+}
+
+/// A [`DynamicData`] is a sample of a structure or union [`DynamicType`] whose member values are accessed by the
+/// name or the id of the members.
+///
+/// [`DynamicData`] samples are written and read with a [`DataWriter<DynamicData>`](crate::publication::data_writer::DataWriter)
+/// and a [`DataReader<DynamicData>`](crate::subscription::data_reader::DataReader) created for a
+/// [`Topic`](crate::topic_definition::topic::Topic) whose type name was registered with
+/// [`DynamicType::register_type`]. The samples are serialized with the CDR representation, or the final XCDR2
+/// representation when the XCDR2 data representation is selected, following the layout of their type, so they can be exchanged with the applications using a compiled type with the same layout. The members
+/// of the structures added with [`DynamicTypeBuilder::add_key_member`](super::dynamic_type::DynamicTypeBuilder) form
+/// the key of the samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicData {
+    dynamic_type: DynamicType,
+    // The values of all the members of a structure or the value of the selected member of a union
+    values: Vec<DynamicValue>,
+    discriminator: i64,
+}
+
+impl DynamicData {
+    /// Creates a sample of `dynamic_type` with the default value of each member: zero for the numbers,
+    /// empty strings and sequences and the first literal for the enumerations. A union selects its first member.
+    /// The operation fails with [`DdsError::BadParameter`] if `dynamic_type` is neither a structure nor a union.
+    pub fn new(dynamic_type: &DynamicType) -> DdsResult<Self> {
+        match dynamic_type.kind() {
+            TypeKind::Structure | TypeKind::Union => Ok(Self::new_default(dynamic_type)),
+            _ => Err(DdsError::BadParameter),
+        }
+    }
+
+    fn new_default(dynamic_type: &DynamicType) -> Self {
+        match dynamic_type.kind() {
+            TypeKind::Union => {
+                let member = &dynamic_type.members()[0];
+                Self {
+                    dynamic_type: dynamic_type.clone(),
+                    values: vec![DynamicValue::default_of(member.member_type())],
+                    discriminator: member_discriminator(dynamic_type, member),
+                }
+            }
+            _ => Self {
+                dynamic_type: dynamic_type.clone(),
+                values: dynamic_type
+                    .members()
+                    .iter()
+                    .map(|m| DynamicValue::default_of(m.member_type()))
+                    .collect(),
+                discriminator: 0,
+            },
+        }
+    }
+
+    /// Deserializes a sample of `dynamic_type` from `data` serialized with the CDR or XCDR2 representation and
+    /// starting with the encapsulation header, like the data of a [`LoanedSample`](crate::subscription::data_reader::LoanedSample).
+    pub fn from_serialized_data(dynamic_type: &DynamicType, data: &[u8]) -> DdsResult<Self> {
+        if !matches!(dynamic_type.kind(), TypeKind::Structure | TypeKind::Union) {
+            return Err(DdsError::BadParameter);
+        }
+        dds_deserialize_seed_from_bytes(data, DynamicDataSeed(dynamic_type))
+    }
+
+    /// Type of the sample.
+    pub fn get_type(&self) -> &DynamicType {
+        &self.dynamic_type
+    }
+
+    /// Value of the discriminator of a union, using 0 and 1 for the booleans, the code of the characters and the value
+    /// of the literal for the enumerations. The operation fails with [`DdsError::BadParameter`] if the sample is not
+    /// a union.
+    pub fn get_discriminator(&self) -> DdsResult<i64> {
+        match self.dynamic_type.kind() {
+            TypeKind::Union => Ok(self.discriminator),
+            _ => Err(DdsError::BadParameter),
+        }
+    }
+
+    /// Value of the member with the given `name`. The operation fails with [`DdsError::BadParameter`] if the type
+    /// has no such member or if it is a member of a union which is not selected by the discriminator.
+    pub fn get_value(&self, name: &str) -> DdsResult<&DynamicValue> {
+        let index = self.member_index(|m| m.name() == name)?;
+        self.value(index)
+    }
+
+    /// Value of the member with the given `id`. The operation fails with [`DdsError::BadParameter`] if the type
+    /// has no such member or if it is a member of a union which is not selected by the discriminator.
+    pub fn get_value_by_id(&self, id: u32) -> DdsResult<&DynamicValue> {
+        let index = self.member_index(|m| m.id() == id)?;
+        self.value(index)
+    }
+
+    /// Sets the value of the member with the given `name`. Setting a member of a union selects it by setting the
+    /// discriminator to its first label. The operation fails with [`DdsError::BadParameter`] if the type has no such
+    /// member or if the value is not a value of the type of the member.
+    pub fn set_value(&mut self, name: &str, value: impl Into<DynamicValue>) -> DdsResult<()> {
+        let index = self.member_index(|m| m.name() == name)?;
+        self.set(index, value.into())
+    }
+
+    /// Sets the value of the member with the given `id`. Setting a member of a union selects it by setting the
+    /// discriminator to its first label. The operation fails with [`DdsError::BadParameter`] if the type has no such
+    /// member or if the value is not a value of the type of the member.
+    pub fn set_value_by_id(&mut self, id: u32, value: impl Into<DynamicValue>) -> DdsResult<()> {
+        let index = self.member_index(|m| m.id() == id)?;
+        self.set(index, value.into())
+    }
+
+    fn member_index(&self, predicate: impl Fn(&DynamicTypeMember) -> bool) -> DdsResult<usize> {
+        self.dynamic_type
+            .members()
+            .iter()
+            .position(predicate)
+            .ok_or(DdsError::BadParameter)
+    }
+
+    fn selected_member(&self) -> Option<usize> {
+        selected_member(&self.dynamic_type, self.discriminator)
+    }
+
+    fn value(&self, index: usize) -> DdsResult<&DynamicValue> {
+        match self.dynamic_type.kind() {
+            TypeKind::Union if self.selected_member() == Some(index) => Ok(&self.values[0]),
+            TypeKind::Union => Err(DdsError::BadParameter),
+            _ => Ok(&self.values[index]),
+        }
+    }
+
+    fn set(&mut self, index: usize, value: DynamicValue) -> DdsResult<()> {
+        let member = &self.dynamic_type.members()[index];
+        if !value.is_value_of(member.member_type()) {
+            return Err(DdsError::BadParameter);
+        }
+
+        match self.dynamic_type.kind() {
+            TypeKind::Union => {
+                if self.selected_member() != Some(index) {
+                    self.discriminator = member_discriminator(&self.dynamic_type, member);
+                }
+                self.values = vec![value];
+            }
+            _ => self.values[index] = value,
+        }
+        Ok(())
+    }
+}
+
+// Discriminator selecting the member of the union. The default member is selected by the first
+// value of the discriminator type which is not the label of another member.
+fn member_discriminator(union_type: &DynamicType, member: &DynamicTypeMember) -> i64 {
+    match member.labels().first() {
+        Some(&label) => label,
+        None => {
+            let discriminator_type = union_type
+                .discriminator_type()
+                .expect("Unions have a discriminator");
+            let is_label = |value: i64| {
+                union_type
+                    .members()
+                    .iter()
+                    .any(|m| m.labels().contains(&value))
+            };
+            match discriminator_type.kind() {
+                TypeKind::Enumeration => discriminator_type
+                    .literals()
+                    .iter()
+                    .map(|l| i64::from(l.value()))
+                    .find(|&v| !is_label(v)),
+                _ => (0..)
+                    .take_while(|&v| is_discriminator_value(discriminator_type, v))
+                    .find(|&v| !is_label(v)),
+            }
+            .unwrap_or(0)
+        }
+    }
+}
+
+fn selected_member(union_type: &DynamicType, discriminator: i64) -> Option<usize> {
+    let members = union_type.members();
+    members
+        .iter()
+        .position(|m| m.labels().contains(&discriminator))
+        .or_else(|| members.iter().position(|m| m.is_default_label()))
+}
+
+// Value with the type needed to serialize it. The sequences and the arrays are both
+// represented as a collection of values but only the sequences are serialized with their length.
+struct TypedValue<'a>(&'a DynamicValue, &'a DynamicType);
+
+impl serde::Serialize for TypedValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.0 {
+            DynamicValue::Boolean(v) => serializer.serialize_bool(*v),
+            DynamicValue::Int8(v) => serializer.serialize_i8(*v),
+            DynamicValue::Int16(v) => serializer.serialize_i16(*v),
+            DynamicValue::Int32(v) => serializer.serialize_i32(*v),
+            DynamicValue::Int64(v) => serializer.serialize_i64(*v),
+            DynamicValue::UInt8(v) => serializer.serialize_u8(*v),
+            DynamicValue::UInt16(v) => serializer.serialize_u16(*v),
+            DynamicValue::UInt32(v) => serializer.serialize_u32(*v),
+            DynamicValue::UInt64(v) => serializer.serialize_u64(*v),
+            DynamicValue::Float32(v) => serializer.serialize_f32(*v),
+            DynamicValue::Float64(v) => serializer.serialize_f64(*v),
+            DynamicValue::Char8(v) => serializer.serialize_char(*v),
+            DynamicValue::String(v) => serializer.serialize_str(v),
+            DynamicValue::Enumeration(v) => serializer.serialize_u32(*v as u32),
+            DynamicValue::Data(v) => v.serialize(serializer),
+            DynamicValue::Collection(elements) => {
+                let element_type = self.1.element_type().expect("Collections have elements");
+                match self.1.kind() {
+                    TypeKind::Sequence => {
+                        let mut seq = serializer.serialize_seq(Some(elements.len()))?;
+                        for element in elements {
+                            seq.serialize_element(&TypedValue(element, element_type))?;
+                        }
+                        seq.end()
+                    }
+                    _ => {
+                        let mut tuple = serializer.serialize_tuple(elements.len())?;
+                        for element in elements {
+                            tuple.serialize_element(&TypedValue(element, element_type))?;
+                        }
+                        tuple.end()
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl serde::Serialize for DynamicData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.dynamic_type.kind() {
+            TypeKind::Union => {
+                let discriminator_type = self
+                    .dynamic_type
+                    .discriminator_type()
+                    .expect("Unions have a discriminator");
+                let discriminator =
+                    DynamicValue::from_discriminator(discriminator_type, self.discriminator);
+                let mut tuple = serializer.serialize_tuple(1 + self.values.len())?;
+                tuple.serialize_element(&TypedValue(&discriminator, discriminator_type))?;
+                if let Some(index) = self.selected_member() {
+                    let member_type = self.dynamic_type.members()[index].member_type();
+                    tuple.serialize_element(&TypedValue(&self.values[0], member_type))?;
+                }
+                tuple.end()
+            }
+            _ => {
+                let mut tuple_struct =
+                    serializer.serialize_tuple_struct(DYNAMIC_DATA, self.values.len())?;
+                for (value, member) in self.values.iter().zip(self.dynamic_type.members()) {
+                    tuple_struct.serialize_field(&TypedValue(value, member.member_type()))?;
+                }
+                tuple_struct.end()
+            }
+        }
+    }
+}
+
+// Name given to the serializers for the structures of the DynamicData samples. The structures are serialized as
+// tuple structures so that, like the structures of the DdsType derive, they take the extensibility of the XCDR2
+// representation and are not preceded by a DHEADER in the final representation.
+const DYNAMIC_DATA: &str = "DynamicData";
+
+// A DynamicData can only be deserialized with the DynamicData::from_serialized_data function which knows
+// its type. The DataReader and its loaned samples deserialize it with the DynamicType of the reader through
+// DdsRepresentation::deserialize_sample, so this implementation only exists to fulfill their bounds.
+impl<'de> serde::Deserialize<'de> for DynamicData {
+    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Err(de::Error::custom(
+            "DynamicData can only be deserialized with its DynamicType",
+        ))
+    }
+}
+
+#[derive(Clone, Copy)]
+struct DynamicValueSeed<'a>(&'a DynamicType);
+
+impl<'de> DeserializeSeed<'de> for DynamicValueSeed<'_> {
+    type Value = DynamicValue;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match self.0.kind() {
+            TypeKind::Boolean => deserializer.deserialize_bool(PrimitiveVisitor),
+            TypeKind::Byte | TypeKind::UInt8 => deserializer.deserialize_u8(PrimitiveVisitor),
+            TypeKind::Int8 => deserializer.deserialize_i8(PrimitiveVisitor),
+            TypeKind::Int16 => deserializer.deserialize_i16(PrimitiveVisitor),
+            TypeKind::Int32 => deserializer.deserialize_i32(PrimitiveVisitor),
+            TypeKind::Int64 => deserializer.deserialize_i64(PrimitiveVisitor),
+            TypeKind::UInt16 => deserializer.deserialize_u16(PrimitiveVisitor),
+            TypeKind::UInt32 => deserializer.deserialize_u32(PrimitiveVisitor),
+            TypeKind::UInt64 => deserializer.deserialize_u64(PrimitiveVisitor),
+            TypeKind::Float32 => deserializer.deserialize_f32(PrimitiveVisitor),
+            TypeKind::Float64 => deserializer.deserialize_f64(PrimitiveVisitor),
+            TypeKind::Char8 => deserializer.deserialize_char(PrimitiveVisitor),
+            TypeKind::String8 => deserializer.deserialize_string(PrimitiveVisitor),
+            TypeKind::Enumeration => match deserializer.deserialize_u32(PrimitiveVisitor)? {
+                DynamicValue::UInt32(v) => {
+                    let value = DynamicValue::Enumeration(v as i32);
+                    if value.is_value_of(self.0) {
+                        Ok(value)
+                    } else {
+                        Err(de::Error::custom(format!(
+                            "{} is not a literal of the enumeration",
+                            v
+                        )))
+                    }
+                }
+                _ => Err(de::Error::custom("Invalid enumeration value")),
+            },
+            TypeKind::Structure | TypeKind::Union => DynamicDataSeed(self.0)
+                .deserialize(deserializer)
+                .map(DynamicValue::Data),
+            TypeKind::Sequence => deserializer.deserialize_seq(CollectionVisitor(self.0)),
+            TypeKind::Array => {
+                deserializer.deserialize_tuple(self.0.element_count(), CollectionVisitor(self.0))
+            }
+        }
+    }
+}
+
+struct PrimitiveVisitor;
+
+macro_rules! implement_visit_for_primitive_visitor {
+    ($visit:ident, $t:ty, $variant:ident) => {
+        fn $visit<E>(self, v: $t) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(DynamicValue::$variant(v))
+        }
+    };
+}
+
+impl<'de> de::Visitor<'de> for PrimitiveVisitor {
+    type Value = DynamicValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a primitive value")
+    }
+
+    implement_visit_for_primitive_visitor!(visit_bool, bool, Boolean);
+    implement_visit_for_primitive_visitor!(visit_i8, i8, Int8);
+    implement_visit_for_primitive_visitor!(visit_i16, i16, Int16);
+    implement_visit_for_primitive_visitor!(visit_i32, i32, Int32);
+    implement_visit_for_primitive_visitor!(visit_i64, i64, Int64);
+    implement_visit_for_primitive_visitor!(visit_u8, u8, UInt8);
+    implement_visit_for_primitive_visitor!(visit_u16, u16, UInt16);
+    implement_visit_for_primitive_visitor!(visit_u32, u32, UInt32);
+    implement_visit_for_primitive_visitor!(visit_u64, u64, UInt64);
+    implement_visit_for_primitive_visitor!(visit_f32, f32, Float32);
+    implement_visit_for_primitive_visitor!(visit_f64, f64, Float64);
+    implement_visit_for_primitive_visitor!(visit_char, char, Char8);
+    implement_visit_for_primitive_visitor!(visit_string, String, String);
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(DynamicValue::String(v.to_string()))
+    }
+}
+
+struct CollectionVisitor<'a>(&'a DynamicType);
+
+impl<'de> de::Visitor<'de> for CollectionVisitor<'_> {
+    type Value = DynamicValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence or array")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let element_type = self.0.element_type().expect("Collections have elements");
+        let mut elements = Vec::new();
+        while let Some(element) = seq.next_element_seed(DynamicValueSeed(element_type))? {
+            elements.push(element);
+        }
+        let value = DynamicValue::Collection(elements);
+        if value.is_value_of(self.0) {
+            Ok(value)
+        } else {
+            Err(de::Error::custom(
+                "Collection exceeds the bounds of its type",
+            ))
+        }
+    }
+}
+
+struct DynamicDataSeed<'a>(&'a DynamicType);
+
+impl<'de> DeserializeSeed<'de> for DynamicDataSeed<'_> {
+    type Value = DynamicData;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match self.0.kind() {
+            TypeKind::Union => deserializer.deserialize_tuple(2, self),
+            _ => deserializer.deserialize_tuple_struct(DYNAMIC_DATA, self.0.members().len(), self),
+        }
+    }
+}
+
+impl<'de> de::Visitor<'de> for DynamicDataSeed<'_> {
+    type Value = DynamicData;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a structure or union")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        let mut discriminator = 0;
+        match self.0.kind() {
+            TypeKind::Union => {
+                let discriminator_type = self
+                    .0
+                    .discriminator_type()
+                    .expect("Unions have a discriminator");
+                discriminator = seq
+                    .next_element_seed(DynamicValueSeed(discriminator_type))?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?
+                    .to_discriminator();
+                if let Some(index) = selected_member(self.0, discriminator) {
+                    let member_type = self.0.members()[index].member_type();
+                    values.push(
+                        seq.next_element_seed(DynamicValueSeed(member_type))?
+                            .ok_or_else(|| de::Error::invalid_length(1, &self))?,
+                    );
+                }
+            }
+            _ => {
+                for (index, member) in self.0.members().iter().enumerate() {
+                    values.push(
+                        seq.next_element_seed(DynamicValueSeed(member.member_type()))?
+                            .ok_or_else(|| de::Error::invalid_length(index, &self))?,
+                    );
+                }
+            }
+        }
+
+        Ok(DynamicData {
+            dynamic_type: self.0.clone(),
+            values,
+            discriminator,
+        })
+    }
+}
+
+// Name of the member holding the discriminator in the JSON objects of the unions
+const JSON_DISCRIMINATOR: &str = "discriminator";
+
+impl DynamicData {
+    // JSON object with a member for each member of a structure, or with the discriminator and the selected member
+    // of a union
+    pub(crate) fn to_json_value(&self) -> Value {
+        let mut object = Map::new();
+        match self.dynamic_type.kind() {
+            TypeKind::Union => {
+                object.insert(
+                    JSON_DISCRIMINATOR.to_string(),
+                    Value::from(self.discriminator),
+                );
+                if let Some(index) = self.selected_member() {
+                    let member = &self.dynamic_type.members()[index];
+                    object.insert(
+                        member.name().to_string(),
+                        json_value(&self.values[0], member.member_type()),
+                    );
+                }
+            }
+            _ => {
+                for (value, member) in self.values.iter().zip(self.dynamic_type.members()) {
+                    object.insert(
+                        member.name().to_string(),
+                        json_value(value, member.member_type()),
+                    );
+                }
+            }
+        }
+        Value::Object(object)
+    }
+
+    pub(crate) fn from_json_value(dynamic_type: &DynamicType, value: &Value) -> DdsResult<Self> {
+        if !matches!(dynamic_type.kind(), TypeKind::Structure | TypeKind::Union) {
+            return Err(DdsError::BadParameter);
+        }
+        let object = value
+            .as_object()
+            .ok_or_else(|| json_type_mismatch(value, dynamic_type))?;
+        let member = |name: &str| dynamic_type.members().iter().position(|m| m.name() == name);
+
+        match dynamic_type.kind() {
+            TypeKind::Structure => {
+                if let Some(name) = object.keys().find(|&name| member(name).is_none()) {
+                    return Err(DdsError::Error(format!("Unknown member {}", name)));
+                }
+                let values = dynamic_type
+                    .members()
+                    .iter()
+                    .map(|m| match object.get(m.name()) {
+                        Some(value) => dynamic_value_from_json(m.member_type(), value),
+                        None => Err(DdsError::Error(format!("Missing member {}", m.name()))),
+                    })
+                    .collect::<DdsResult<_>>()?;
+                Ok(Self {
+                    dynamic_type: dynamic_type.clone(),
+                    values,
+                    discriminator: 0,
+                })
+            }
+            TypeKind::Union => {
+                let discriminator_type = dynamic_type
+                    .discriminator_type()
+                    .expect("Unions have a discriminator");
+                let mut members = object.keys().filter(|&name| name != JSON_DISCRIMINATOR);
+                let member_value = match (members.next(), members.next()) {
+                    (None, _) => None,
+                    (Some(name), None) => {
+                        let index = member(name)
+                            .ok_or_else(|| DdsError::Error(format!("Unknown member {}", name)))?;
+                        Some((index, &object[name]))
+                    }
+                    (Some(_), Some(_)) => {
+                        return Err(DdsError::Error(
+                            "A union has a single selected member".to_string(),
+                        ))
+                    }
+                };
+                let discriminator = match object.get(JSON_DISCRIMINATOR) {
+                    Some(value) => match value.as_i64() {
+                        Some(d) if is_discriminator_value(discriminator_type, d) => Some(d),
+                        _ => return Err(json_type_mismatch(value, discriminator_type)),
+                    },
+                    None => None,
+                };
+
+                let discriminator = match (discriminator, member_value) {
+                    (Some(d), Some((index, _)))
+                        if selected_member(dynamic_type, d) != Some(index) =>
+                    {
+                        return Err(DdsError::Error(format!(
+                            "Discriminator {} does not select member {}",
+                            d,
+                            dynamic_type.members()[index].name()
+                        )))
+                    }
+                    (Some(d), _) => d,
+                    (None, Some((index, _))) => {
+                        member_discriminator(dynamic_type, &dynamic_type.members()[index])
+                    }
+                    (None, None) => {
+                        return Err(DdsError::Error(format!(
+                            "Missing member {}",
+                            JSON_DISCRIMINATOR
+                        )))
+                    }
+                };
+                let values = match (selected_member(dynamic_type, discriminator), member_value) {
+                    (Some(index), Some((_, value))) => vec![dynamic_value_from_json(
+                        dynamic_type.members()[index].member_type(),
+                        value,
+                    )?],
+                    (Some(index), None) => vec![DynamicValue::default_of(
+                        dynamic_type.members()[index].member_type(),
+                    )],
+                    (None, _) => Vec::new(),
+                };
+                Ok(Self {
+                    dynamic_type: dynamic_type.clone(),
+                    values,
+                    discriminator,
+                })
+            }
+            _ => unreachable!("Only structures and unions are converted"),
+        }
+    }
+}
+
+// The enumerations are represented with the name of their literal and the characters with a string
+// of one character
+fn json_value(value: &DynamicValue, dynamic_type: &DynamicType) -> Value {
+    match value {
+        DynamicValue::Boolean(v) => Value::from(*v),
+        DynamicValue::Int8(v) => Value::from(*v),
+        DynamicValue::Int16(v) => Value::from(*v),
+        DynamicValue::Int32(v) => Value::from(*v),
+        DynamicValue::Int64(v) => Value::from(*v),
+        DynamicValue::UInt8(v) => Value::from(*v),
+        DynamicValue::UInt16(v) => Value::from(*v),
+        DynamicValue::UInt32(v) => Value::from(*v),
+        DynamicValue::UInt64(v) => Value::from(*v),
+        DynamicValue::Float32(v) => Value::from(*v),
+        DynamicValue::Float64(v) => Value::from(*v),
+        DynamicValue::Char8(v) => Value::from(v.to_string()),
+        DynamicValue::String(v) => Value::from(v.as_str()),
+        DynamicValue::Enumeration(v) => {
+            match dynamic_type.literals().iter().find(|l| l.value() == *v) {
+                Some(literal) => Value::from(literal.name()),
+                None => Value::from(*v),
+            }
+        }
+        DynamicValue::Data(v) => v.to_json_value(),
+        DynamicValue::Collection(elements) => {
+            let element_type = dynamic_type
+                .element_type()
+                .expect("Collections have elements");
+            elements
+                .iter()
+                .map(|e| json_value(e, element_type))
+                .collect()
+        }
+    }
+}
+
+fn dynamic_value_from_json(dynamic_type: &DynamicType, value: &Value) -> DdsResult<DynamicValue> {
+    fn integer<T: TryFrom<i64>>(value: &Value) -> Option<T> {
+        value.as_i64().and_then(|v| T::try_from(v).ok())
+    }
+
+    let dynamic_value = match dynamic_type.kind() {
+        TypeKind::Boolean => value.as_bool().map(DynamicValue::Boolean),
+        TypeKind::Byte | TypeKind::UInt8 => integer(value).map(DynamicValue::UInt8),
+        TypeKind::Int8 => integer(value).map(DynamicValue::Int8),
+        TypeKind::Int16 => integer(value).map(DynamicValue::Int16),
+        TypeKind::Int32 => integer(value).map(DynamicValue::Int32),
+        TypeKind::Int64 => value.as_i64().map(DynamicValue::Int64),
+        TypeKind::UInt16 => integer(value).map(DynamicValue::UInt16),
+        TypeKind::UInt32 => integer(value).map(DynamicValue::UInt32),
+        TypeKind::UInt64 => value.as_u64().map(DynamicValue::UInt64),
+        TypeKind::Float32 => value.as_f64().map(|v| DynamicValue::Float32(v as f32)),
+        TypeKind::Float64 => value.as_f64().map(DynamicValue::Float64),
+        TypeKind::Char8 => value.as_str().and_then(|s| {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(DynamicValue::Char8(c)),
+                _ => None,
+            }
+        }),
+        TypeKind::String8 => value.as_str().map(DynamicValue::from),
+        TypeKind::Enumeration => value.as_str().and_then(|name| {
+            dynamic_type
+                .literals()
+                .iter()
+                .find(|l| l.name() == name)
+                .map(|l| DynamicValue::Enumeration(l.value()))
+        }),
+        TypeKind::Structure | TypeKind::Union => {
+            return DynamicData::from_json_value(dynamic_type, value).map(DynamicValue::Data)
+        }
+        TypeKind::Sequence | TypeKind::Array => match value {
+            Value::Array(elements) => {
+                let element_type = dynamic_type
+                    .element_type()
+                    .expect("Collections have elements");
+                let elements = elements
+                    .iter()
+                    .map(|e| dynamic_value_from_json(element_type, e))
+                    .collect::<DdsResult<_>>()?;
+                Some(DynamicValue::Collection(elements))
+            }
+            _ => None,
+        },
+    };
+
+    match dynamic_value {
+        Some(dynamic_value) if dynamic_value.is_value_of(dynamic_type) => Ok(dynamic_value),
+        _ => Err(json_type_mismatch(value, dynamic_type)),
+    }
+}
+
+fn json_type_mismatch(value: &Value, dynamic_type: &DynamicType) -> DdsError {
+    DdsError::Error(format!(
+        "{} is not a value of the {:?} type",
+        value,
+        dynamic_type.kind()
+    ))
+}
+
+/// Key of a [`DynamicData`] sample, formed by the values of the key members of its structure.
+#[doc(hidden)]
+pub struct DynamicDataKey<'a>(&'a DynamicData);
+
+impl serde::Serialize for DynamicDataKey<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // Like the key of the nested structures of the DdsType derive, the key of a nested
+        // structure is formed by its own key members
+        let key_members: Vec<_> = match self.0.dynamic_type.kind() {
+            TypeKind::Structure => self
+                .0
+                .values
+                .iter()
+                .zip(self.0.dynamic_type.members())
+                .filter(|(_, m)| m.is_key())
+                .collect(),
+            _ => Vec::new(),
+        };
+        let mut tuple = serializer.serialize_tuple(key_members.len())?;
+        for (value, member) in key_members {
+            match value {
+                DynamicValue::Data(data) => tuple.serialize_element(&DynamicDataKey(data))?,
+                _ => tuple.serialize_element(&TypedValue(value, member.member_type()))?,
+            }
+        }
+        tuple.end()
+    }
+}
+
+// The key members are only known from the DynamicType so the keyedness used by the entities is
+// given by the registered type. Without it, the samples of types without key members all belong
+// to the same instance.
+impl DdsHasKey for DynamicData {
+    const HAS_KEY: bool = true;
+
+    fn has_key(dynamic_type: Option<&DynamicType>) -> bool {
+        dynamic_type.map_or(Self::HAS_KEY, DynamicType::has_key)
+    }
+}
+
+impl DdsRepresentation for DynamicData {
+    const REPRESENTATION: Representation = Representation::CdrLe;
+
+    fn deserialize_sample<'de>(
+        data: &'de [u8],
+        dynamic_type: Option<&DynamicType>,
+    ) -> DdsResult<Self>
+    where
+        Self: DdsDeserialize<'de>,
+    {
+        match dynamic_type {
+            Some(dynamic_type) => DynamicData::from_serialized_data(dynamic_type, data),
+            None => Err(DdsError::PreconditionNotMet(
+                "DynamicData can only be deserialized with its DynamicType".to_string(),
+            )),
+        }
+    }
+
+    fn sample_to_json_value(&self) -> DdsResult<Value> {
+        Ok(self.to_json_value())
+    }
+}
+
+impl DdsGetKey for DynamicData {
+    type BorrowedKeyHolder<'a> = DynamicDataKey<'a>;
+
+    fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
+        DynamicDataKey(self)
+    }
+
+    fn max_key_size(&self) -> Option<usize> {
+        self.dynamic_type.max_key_size()
+    }
+}
+
+// The key holder of a DynamicData is a sample of the key type of its DynamicType, which like the samples can only
+// be deserialized with this type
+impl DdsSetKeyFields for DynamicData {
+    type OwningKeyHolder = DynamicData;
+
+    fn set_key_from_holder(&mut self, key_holder: Self::OwningKeyHolder) {
+        for (value, member) in key_holder
+            .values
+            .into_iter()
+            .zip(key_holder.dynamic_type.members())
+        {
+            if let Ok(index) = self.member_index(|m| m.name() == member.name()) {
+                match (&mut self.values[index], value) {
+                    (DynamicValue::Data(data), DynamicValue::Data(key)) => {
+                        data.set_key_from_holder(key)
+                    }
+                    (self_value, value) => *self_value = value,
+                }
+            }
+        }
+    }
+
+    fn serialized_key_hash(
+        data: &[u8],
+        dynamic_type: Option<&DynamicType>,
+    ) -> DdsResult<InstanceHandle> {
+        match dynamic_type {
+            Some(dynamic_type) => {
+                let key = DynamicData::from_serialized_data(&dynamic_type.key_type(), data)?;
+                key_hash(&key.get_key(), dynamic_type.max_key_size())
+            }
+            None => Err(DdsError::PreconditionNotMet(
+                "DynamicData can only be deserialized with its DynamicType".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic_definition::{
+        dynamic_type::DynamicTypeBuilder,
+        type_support::{
+            dds_key_hash, dds_serialize_key, dds_serialize_key_to_bytes, dds_serialize_to_bytes,
+            DdsSerialize,
+        },
+    };
+
+    fn primitive(kind: TypeKind) -> DynamicType {
+        DynamicType::primitive(kind).unwrap()
+    }
+
+    fn shape_type() -> DynamicType {
+        DynamicTypeBuilder::new_struct("ShapeType")
+            .add_key_member("color", DynamicType::string(128))
+            .add_member("x", primitive(TypeKind::Int32))
+            .add_member("y", primitive(TypeKind::Int32))
+            .add_member("shapesize", primitive(TypeKind::Int32))
+            .build()
+            .unwrap()
+    }
+
+    fn value_type() -> DynamicType {
+        let kind = DynamicTypeBuilder::new_enum("Kind")
+            .add_literal("INTEGER", 0)
+            .add_literal("TEXT", 1)
+            .add_literal("NONE", 2)
+            .build()
+            .unwrap();
+        DynamicTypeBuilder::new_union("Value", kind)
+            .add_union_member("integer", primitive(TypeKind::Int64), &[0])
+            .add_union_member("text", DynamicType::string(0), &[1])
+            .build()
+            .unwrap()
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct ShapeType {
+        color: String,
+        x: i32,
+        y: i32,
+        shapesize: i32,
+    }
+
+    #[derive(serde::Serialize)]
+    struct ShapeTypeKey<'a> {
+        color: &'a str,
+    }
+
+    #[test]
+    fn new_data_has_default_values() {
+        let data = DynamicData::new(&shape_type()).unwrap();
+        assert_eq!(
+            data.get_value("color"),
+            Ok(&DynamicValue::String(String::new()))
+        );
+        assert_eq!(data.get_value_by_id(3), Ok(&DynamicValue::Int32(0)));
+        assert_eq!(
+            DynamicData::new(&primitive(TypeKind::Int32)),
+            Err(DdsError::BadParameter)
+        );
+    }
+
+    #[test]
+    fn set_value_checks_member_type() {
+        let mut data = DynamicData::new(&shape_type()).unwrap();
+        data.set_value("color", "BLUE").unwrap();
+        data.set_value_by_id(1, 10).unwrap();
+
+        assert_eq!(data.get_value("color"), Ok(&DynamicValue::from("BLUE")));
+        assert_eq!(data.get_value("x"), Ok(&DynamicValue::Int32(10)));
+        assert_eq!(data.set_value("y", 1.0), Err(DdsError::BadParameter));
+        assert_eq!(
+            data.set_value("color", "C".repeat(129)),
+            Err(DdsError::BadParameter)
+        );
+        assert_eq!(data.set_value("z", 1), Err(DdsError::BadParameter));
+    }
+
+    #[test]
+    fn set_union_member_selects_it() {
+        let mut data = DynamicData::new(&value_type()).unwrap();
+        assert_eq!(data.get_discriminator(), Ok(0));
+        assert_eq!(data.get_value("integer"), Ok(&DynamicValue::Int64(0)));
+
+        data.set_value("text", "hello").unwrap();
+        assert_eq!(data.get_discriminator(), Ok(1));
+        assert_eq!(data.get_value("text"), Ok(&DynamicValue::from("hello")));
+        assert_eq!(data.get_value("integer"), Err(DdsError::BadParameter));
+    }
+
+    #[test]
+    fn serialize_struct_with_cdr_layout() {
+        let mut data = DynamicData::new(&shape_type()).unwrap();
+        data.set_value("color", "BLUE").unwrap();
+        data.set_value("x", 10).unwrap();
+        data.set_value("y", 20).unwrap();
+        data.set_value("shapesize", 30).unwrap();
+
+        let shape = ShapeType {
+            color: "BLUE".to_string(),
+            x: 10,
+            y: 20,
+            shapesize: 30,
+        };
+        assert_eq!(
+            dds_serialize_to_bytes(&data).unwrap(),
+            cdr::serialize::<_, _, cdr::CdrLe>(&shape, cdr::Infinite).unwrap()
+        );
+        assert_eq!(
+            dds_serialize_key(&data).unwrap(),
+            cdr::ser::serialize_data::<_, _, cdr::LittleEndian>(
+                &ShapeTypeKey { color: "BLUE" },
+                cdr::Infinite
+            )
+            .unwrap()
+            .into()
+        );
+    }
+
+    #[test]
+    fn deserialize_struct_from_cdr_layout() {
+        let shape = ShapeType {
+            color: "RED".to_string(),
+            x: -1,
+            y: 2,
+            shapesize: 3,
+        };
+        let serialized_data = cdr::serialize::<_, _, cdr::CdrBe>(&shape, cdr::Infinite).unwrap();
+
+        let data = DynamicData::from_serialized_data(&shape_type(), &serialized_data).unwrap();
+        assert_eq!(data.get_value("color"), Ok(&DynamicValue::from("RED")));
+        assert_eq!(data.get_value("x"), Ok(&DynamicValue::Int32(-1)));
+        assert_eq!(data.get_value("shapesize"), Ok(&DynamicValue::Int32(3)));
+    }
+
+    #[test]
+    fn serialize_and_deserialize_nested_types() {
+        let point = DynamicTypeBuilder::new_struct("Point")
+            .add_member("x", primitive(TypeKind::Float64))
+            .add_member("y", primitive(TypeKind::Float64))
+            .build()
+            .unwrap();
+        let path = DynamicTypeBuilder::new_struct("Path")
+            .add_key_member("id", primitive(TypeKind::UInt16))
+            .add_member("points", DynamicType::sequence(point.clone(), 10))
+            .add_member(
+                "matrix",
+                DynamicType::array(primitive(TypeKind::Byte), &[2, 2]).unwrap(),
+            )
+            .add_member("value", value_type())
+            .add_member("tag", primitive(TypeKind::Char8))
+            .build()
+            .unwrap();
+
+        let mut first_point = DynamicData::new(&point).unwrap();
+        first_point.set_value("x", 1.5).unwrap();
+        let mut value = DynamicData::new(&value_type()).unwrap();
+        value.set_value("text", "end").unwrap();
+        let mut data = DynamicData::new(&path).unwrap();
+        data.set_value("id", 7_u16).unwrap();
+        data.set_value(
+            "points",
+            vec![first_point.into(), DynamicData::new(&point).unwrap().into()],
+        )
+        .unwrap();
+        data.set_value(
+            "matrix",
+            vec![1_u8.into(), 2_u8.into(), 3_u8.into(), 4_u8.into()],
+        )
+        .unwrap();
+        data.set_value("value", value).unwrap();
+        data.set_value("tag", 'A').unwrap();
+
+        let serialized_data = dds_serialize_to_bytes(&data).unwrap();
+        assert_eq!(
+            DynamicData::from_serialized_data(&path, &serialized_data),
+            Ok(data)
+        );
+    }
+
+    #[test]
+    fn serialize_and_deserialize_with_xcdr2_layout() {
+        let point = DynamicTypeBuilder::new_struct("Point")
+            .add_member("x", primitive(TypeKind::Int32))
+            .add_member("y", primitive(TypeKind::Int32))
+            .build()
+            .unwrap();
+        let line = DynamicTypeBuilder::new_struct("Line")
+            .add_member("start", point.clone())
+            .add_member("end", point.clone())
+            .build()
+            .unwrap();
+        let mut end = DynamicData::new(&point).unwrap();
+        end.set_value("x", 3).unwrap();
+        let mut data = DynamicData::new(&line).unwrap();
+        data.set_value("end", end).unwrap();
+
+        let mut serialized_data = Vec::new();
+        data.serialize_data(&Representation::Cdr2Le, &mut serialized_data)
+            .unwrap();
+        // The final structures are not preceded by a DHEADER
+        assert_eq!(
+            serialized_data,
+            vec![0, 7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            DynamicData::from_serialized_data(&line, &serialized_data),
+            Ok(data)
+        );
+    }
+
+    #[test]
+    fn key_hash_is_computed_from_serialized_key() {
+        let inner = DynamicTypeBuilder::new_struct("Inner")
+            .add_member("name", DynamicType::string(0))
+            .add_key_member("index", primitive(TypeKind::Int32))
+            .build()
+            .unwrap();
+        let outer = DynamicTypeBuilder::new_struct("Outer")
+            .add_key_member("id", primitive(TypeKind::UInt8))
+            .add_key_member("inner", inner.clone())
+            .add_key_member("value", value_type())
+            .add_member("x", primitive(TypeKind::Float64))
+            .build()
+            .unwrap();
+        let mut inner_data = DynamicData::new(&inner).unwrap();
+        inner_data.set_value("name", "a").unwrap();
+        inner_data.set_value("index", 3).unwrap();
+        let mut data = DynamicData::new(&outer).unwrap();
+        data.set_value("id", 1_u8).unwrap();
+        data.set_value("inner", inner_data).unwrap();
+        data.set_value("x", 2.5).unwrap();
+
+        let serialized_key = dds_serialize_key_to_bytes(&data).unwrap();
+        assert_eq!(
+            DynamicData::serialized_key_hash(serialized_key.as_ref(), Some(&outer)),
+            dds_key_hash(&data)
+        );
+    }
+
+    #[test]
+    fn deserialize_without_type_fails() {
+        let serialized_data =
+            dds_serialize_to_bytes(&DynamicData::new(&shape_type()).unwrap()).unwrap();
+        assert!(DynamicData::deserialize_sample(&serialized_data, None).is_err());
+    }
+}
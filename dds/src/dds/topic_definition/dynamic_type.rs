@@ -0,0 +1,792 @@
+use std::{collections::HashSet, convert::TryFrom, sync::Arc};
+
+use crate::{
+    domain::domain_participant::DomainParticipant,
+    infrastructure::error::{DdsError, DdsResult},
+};
+
+// The member ids are stored in the EMHEADER of the mutable types with 28 bits
+const MAX_MEMBER_ID: u32 = 0x0fff_ffff;
+
+/// Kind of a [`DynamicType`] as defined in the DDS-XTypes standard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeKind {
+    Boolean,
+    Byte,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Float32,
+    Float64,
+    Char8,
+    String8,
+    Enumeration,
+    Structure,
+    Union,
+    Sequence,
+    Array,
+}
+
+impl TypeKind {
+    fn is_primitive(&self) -> bool {
+        !matches!(
+            self,
+            TypeKind::String8
+                | TypeKind::Enumeration
+                | TypeKind::Structure
+                | TypeKind::Union
+                | TypeKind::Sequence
+                | TypeKind::Array
+        )
+    }
+
+    fn is_discriminator(&self) -> bool {
+        !matches!(
+            self,
+            TypeKind::Float32
+                | TypeKind::Float64
+                | TypeKind::String8
+                | TypeKind::Structure
+                | TypeKind::Union
+                | TypeKind::Sequence
+                | TypeKind::Array
+        )
+    }
+}
+
+/// Member of a structure or union [`DynamicType`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicTypeMember {
+    name: String,
+    id: u32,
+    member_type: DynamicType,
+    key: bool,
+    labels: Vec<i64>,
+    default_label: bool,
+}
+
+impl DynamicTypeMember {
+    /// Name of the member.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Member id, which identifies the member in the mutable types and can be used instead of its name to access
+    /// the member value of a [`DynamicData`](super::dynamic_data::DynamicData).
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Type of the member.
+    pub fn member_type(&self) -> &DynamicType {
+        &self.member_type
+    }
+
+    /// Whether the member is part of the key of a structure.
+    pub fn is_key(&self) -> bool {
+        self.key
+    }
+
+    /// Values of the discriminator selecting this member of a union.
+    pub fn labels(&self) -> &[i64] {
+        &self.labels
+    }
+
+    /// Whether this member of a union is selected by the discriminator values which are not the label of any other member.
+    pub fn is_default_label(&self) -> bool {
+        self.default_label
+    }
+}
+
+/// Literal of an enumeration [`DynamicType`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumeratedLiteral {
+    name: String,
+    value: i32,
+}
+
+impl EnumeratedLiteral {
+    /// Name of the literal.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Value of the literal.
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum TypeDescriptor {
+    Primitive(TypeKind),
+    String8 {
+        bound: u32,
+    },
+    Sequence {
+        element_type: DynamicType,
+        bound: u32,
+    },
+    Array {
+        element_type: DynamicType,
+        dimensions: Vec<u32>,
+    },
+    Enumeration {
+        name: String,
+        literals: Vec<EnumeratedLiteral>,
+    },
+    Structure {
+        name: String,
+        members: Vec<DynamicTypeMember>,
+    },
+    Union {
+        name: String,
+        discriminator_type: DynamicType,
+        members: Vec<DynamicTypeMember>,
+    },
+}
+
+/// A [`DynamicType`] describes a type defined at runtime, whose samples are represented with
+/// [`DynamicData`](super::dynamic_data::DynamicData). The primitive, string, sequence and array types are created with
+/// the constructors of [`DynamicType`] and the structures, enumerations and unions with a [`DynamicTypeBuilder`].
+///
+/// A [`DynamicType`] is cheap to clone since the clones share the same description of the type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicType(Arc<TypeDescriptor>);
+
+impl DynamicType {
+    /// Creates the primitive type of the given `kind`. The operation fails with
+    /// [`DdsError::BadParameter`] if `kind` is not the kind of a primitive type.
+    pub fn primitive(kind: TypeKind) -> DdsResult<Self> {
+        if kind.is_primitive() {
+            Ok(Self::new(TypeDescriptor::Primitive(kind)))
+        } else {
+            Err(DdsError::BadParameter)
+        }
+    }
+
+    /// Creates a string type with at most `bound` characters. A `bound` of 0 creates an unbounded string type.
+    pub fn string(bound: u32) -> Self {
+        Self::new(TypeDescriptor::String8 { bound })
+    }
+
+    /// Creates a sequence type with at most `bound` elements of `element_type`. A `bound` of 0 creates an
+    /// unbounded sequence type.
+    pub fn sequence(element_type: DynamicType, bound: u32) -> Self {
+        Self::new(TypeDescriptor::Sequence {
+            element_type,
+            bound,
+        })
+    }
+
+    /// Creates an array type of `element_type` with the given `dimensions`. The operation fails with
+    /// [`DdsError::BadParameter`] if no dimension is given or if any of them is 0.
+    pub fn array(element_type: DynamicType, dimensions: &[u32]) -> DdsResult<Self> {
+        if dimensions.is_empty() || dimensions.contains(&0) {
+            Err(DdsError::BadParameter)
+        } else {
+            Ok(Self::new(TypeDescriptor::Array {
+                element_type,
+                dimensions: dimensions.to_vec(),
+            }))
+        }
+    }
+
+    fn new(type_descriptor: TypeDescriptor) -> Self {
+        Self(Arc::new(type_descriptor))
+    }
+
+    /// Kind of the type.
+    pub fn kind(&self) -> TypeKind {
+        match self.0.as_ref() {
+            TypeDescriptor::Primitive(kind) => *kind,
+            TypeDescriptor::String8 { .. } => TypeKind::String8,
+            TypeDescriptor::Sequence { .. } => TypeKind::Sequence,
+            TypeDescriptor::Array { .. } => TypeKind::Array,
+            TypeDescriptor::Enumeration { .. } => TypeKind::Enumeration,
+            TypeDescriptor::Structure { .. } => TypeKind::Structure,
+            TypeDescriptor::Union { .. } => TypeKind::Union,
+        }
+    }
+
+    /// Name of the structure, enumeration and union types. The other types have no name.
+    pub fn name(&self) -> Option<&str> {
+        match self.0.as_ref() {
+            TypeDescriptor::Enumeration { name, .. }
+            | TypeDescriptor::Structure { name, .. }
+            | TypeDescriptor::Union { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Maximum number of characters of a string type or of elements of a sequence type. The bound is 0 for the
+    /// unbounded types and for the types which are neither strings nor sequences.
+    pub fn bound(&self) -> u32 {
+        match self.0.as_ref() {
+            TypeDescriptor::String8 { bound } | TypeDescriptor::Sequence { bound, .. } => *bound,
+            _ => 0,
+        }
+    }
+
+    /// Dimensions of an array type. The other types have no dimensions.
+    pub fn dimensions(&self) -> &[u32] {
+        match self.0.as_ref() {
+            TypeDescriptor::Array { dimensions, .. } => dimensions,
+            _ => &[],
+        }
+    }
+
+    /// Type of the elements of a sequence or array type.
+    pub fn element_type(&self) -> Option<&DynamicType> {
+        match self.0.as_ref() {
+            TypeDescriptor::Sequence { element_type, .. }
+            | TypeDescriptor::Array { element_type, .. } => Some(element_type),
+            _ => None,
+        }
+    }
+
+    /// Type of the discriminator of a union type.
+    pub fn discriminator_type(&self) -> Option<&DynamicType> {
+        match self.0.as_ref() {
+            TypeDescriptor::Union {
+                discriminator_type, ..
+            } => Some(discriminator_type),
+            _ => None,
+        }
+    }
+
+    /// Literals of an enumeration type.
+    pub fn literals(&self) -> &[EnumeratedLiteral] {
+        match self.0.as_ref() {
+            TypeDescriptor::Enumeration { literals, .. } => literals,
+            _ => &[],
+        }
+    }
+
+    /// Members of a structure or union type, in the order in which they are serialized.
+    pub fn members(&self) -> &[DynamicTypeMember] {
+        match self.0.as_ref() {
+            TypeDescriptor::Structure { members, .. } | TypeDescriptor::Union { members, .. } => {
+                members
+            }
+            _ => &[],
+        }
+    }
+
+    /// Returns the member of a structure or union type with the given `name`. The operation fails with
+    /// [`DdsError::BadParameter`] if the type has no such member.
+    pub fn member_by_name(&self, name: &str) -> DdsResult<&DynamicTypeMember> {
+        self.members()
+            .iter()
+            .find(|m| m.name == name)
+            .ok_or(DdsError::BadParameter)
+    }
+
+    /// Returns the member of a structure or union type with the given `id`. The operation fails with
+    /// [`DdsError::BadParameter`] if the type has no such member.
+    pub fn member_by_id(&self, id: u32) -> DdsResult<&DynamicTypeMember> {
+        self.members()
+            .iter()
+            .find(|m| m.id == id)
+            .ok_or(DdsError::BadParameter)
+    }
+
+    /// Number of elements of an array type, which is the product of its dimensions.
+    pub(crate) fn element_count(&self) -> usize {
+        self.dimensions().iter().map(|&d| d as usize).product()
+    }
+
+    /// Whether the samples of this type have a key, which is the case of the structures with key members.
+    pub(crate) fn has_key(&self) -> bool {
+        self.members().iter().any(|m| m.is_key())
+    }
+
+    /// Maximum size of the key of the samples of this type serialized with the CDR representation or
+    /// [`None`] if the size of the key is unbounded.
+    pub(crate) fn max_key_size(&self) -> Option<usize> {
+        match self.0.as_ref() {
+            TypeDescriptor::Structure { .. } => self.max_key_end(0),
+            _ => Some(0),
+        }
+    }
+
+    // Type of the serialized key of the samples of this structure: a structure with only its key members, whose
+    // nested structures are also reduced to their key members and whose unions, which have no key members, are
+    // reduced to empty structures
+    pub(crate) fn key_type(&self) -> DynamicType {
+        match self.0.as_ref() {
+            TypeDescriptor::Structure { name, members } => Self::new(TypeDescriptor::Structure {
+                name: name.clone(),
+                members: members
+                    .iter()
+                    .filter(|m| m.is_key())
+                    .map(|m| DynamicTypeMember {
+                        member_type: m.member_type.key_type(),
+                        ..m.clone()
+                    })
+                    .collect(),
+            }),
+            TypeDescriptor::Union { name, .. } => Self::new(TypeDescriptor::Structure {
+                name: name.clone(),
+                members: Vec::new(),
+            }),
+            _ => self.clone(),
+        }
+    }
+
+    // Offset at which the key of a structure starting at `offset` ends in the worst case. The key
+    // of a nested structure is formed by its own key members and unions have no key members.
+    fn max_key_end(&self, offset: usize) -> Option<usize> {
+        match self.0.as_ref() {
+            TypeDescriptor::Structure { members, .. } => members
+                .iter()
+                .filter(|m| m.is_key())
+                .try_fold(offset, |offset, m| m.member_type().max_key_end(offset)),
+            TypeDescriptor::Union { .. } => Some(offset),
+            _ => self.max_serialized_end(offset),
+        }
+    }
+
+    // Offset at which a value starting at `offset` ends in the worst case
+    fn max_serialized_end(&self, offset: usize) -> Option<usize> {
+        fn primitive_end(offset: usize, size: usize) -> usize {
+            offset + (size - offset % size) % size + size
+        }
+
+        match self.0.as_ref() {
+            TypeDescriptor::Primitive(kind) => Some(match kind {
+                TypeKind::Int16 | TypeKind::UInt16 => primitive_end(offset, 2),
+                TypeKind::Int32 | TypeKind::UInt32 | TypeKind::Float32 => primitive_end(offset, 4),
+                TypeKind::Int64 | TypeKind::UInt64 | TypeKind::Float64 => primitive_end(offset, 8),
+                _ => offset + 1,
+            }),
+            TypeDescriptor::Enumeration { .. } => Some(primitive_end(offset, 4)),
+            TypeDescriptor::String8 { bound } => match bound {
+                0 => None,
+                bound => Some(primitive_end(offset, 4) + *bound as usize + 1),
+            },
+            TypeDescriptor::Sequence {
+                element_type,
+                bound,
+            } => match bound {
+                0 => None,
+                bound => (0..*bound).try_fold(primitive_end(offset, 4), |offset, _| {
+                    element_type.max_serialized_end(offset)
+                }),
+            },
+            TypeDescriptor::Array { element_type, .. } => (0..self.element_count())
+                .try_fold(offset, |offset, _| element_type.max_serialized_end(offset)),
+            TypeDescriptor::Structure { members, .. } => {
+                members.iter().try_fold(offset, |offset, m| {
+                    m.member_type().max_serialized_end(offset)
+                })
+            }
+            TypeDescriptor::Union {
+                discriminator_type,
+                members,
+                ..
+            } => {
+                let offset = discriminator_type.max_serialized_end(offset)?;
+                members.iter().try_fold(offset, |end, m| {
+                    Some(end.max(m.member_type().max_serialized_end(offset)?))
+                })
+            }
+        }
+    }
+
+    /// This operation registers the type with the given `type_name` on the `participant` so that
+    /// [`DataReader<DynamicData>`](crate::subscription::data_reader::DataReader) and
+    /// [`DataWriter<DynamicData>`](crate::publication::data_writer::DataWriter) can be created for the
+    /// [`Topic`](crate::topic_definition::topic::Topic) with the `type_name`. Like
+    /// [`TypeSupport::register_type`](super::type_support::TypeSupport::register_type), registering a `type_name`
+    /// which is already in use by a different type results in [`DdsError::PreconditionNotMet`].
+    pub fn register_type(&self, participant: &DomainParticipant, type_name: &str) -> DdsResult<()> {
+        participant.register_dynamic_type(type_name, self.clone())
+    }
+}
+
+/// Builder of the structure, enumeration and union [`DynamicType`]s.
+///
+/// The members are serialized in the order in which they are added. Members added without an explicit id take the
+/// id following the one of the previous member, the first one taking the id 0.
+/// The description of the type is only validated when the type is built.
+pub struct DynamicTypeBuilder {
+    kind: TypeKind,
+    name: String,
+    discriminator_type: Option<DynamicType>,
+    members: Vec<DynamicTypeMember>,
+    literals: Vec<EnumeratedLiteral>,
+}
+
+impl DynamicTypeBuilder {
+    /// Starts the description of a structure type.
+    pub fn new_struct(name: &str) -> Self {
+        Self::new(TypeKind::Structure, name, None)
+    }
+
+    /// Starts the description of an enumeration type.
+    pub fn new_enum(name: &str) -> Self {
+        Self::new(TypeKind::Enumeration, name, None)
+    }
+
+    /// Starts the description of a union type whose members are selected by a discriminator of
+    /// `discriminator_type`, which must be a boolean, byte, integer, character or enumeration type.
+    pub fn new_union(name: &str, discriminator_type: DynamicType) -> Self {
+        Self::new(TypeKind::Union, name, Some(discriminator_type))
+    }
+
+    fn new(kind: TypeKind, name: &str, discriminator_type: Option<DynamicType>) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            discriminator_type,
+            members: Vec::new(),
+            literals: Vec::new(),
+        }
+    }
+
+    /// Adds a member to a structure type.
+    pub fn add_member(self, name: &str, member_type: DynamicType) -> Self {
+        let id = self.next_member_id();
+        self.add(name, id, member_type, false, Vec::new(), false)
+    }
+
+    /// Adds a member with the given `id` to a structure type.
+    pub fn add_member_with_id(self, id: u32, name: &str, member_type: DynamicType) -> Self {
+        self.add(name, id, member_type, false, Vec::new(), false)
+    }
+
+    /// Adds a member which is part of the key to a structure type.
+    pub fn add_key_member(self, name: &str, member_type: DynamicType) -> Self {
+        let id = self.next_member_id();
+        self.add(name, id, member_type, true, Vec::new(), false)
+    }
+
+    /// Adds a member to a union type which is selected by the discriminator values in `labels`.
+    pub fn add_union_member(self, name: &str, member_type: DynamicType, labels: &[i64]) -> Self {
+        let id = self.next_member_id();
+        self.add(name, id, member_type, false, labels.to_vec(), false)
+    }
+
+    /// Adds the member to a union type which is selected by the discriminator values which are not the label
+    /// of any other member.
+    pub fn add_default_union_member(self, name: &str, member_type: DynamicType) -> Self {
+        let id = self.next_member_id();
+        self.add(name, id, member_type, false, Vec::new(), true)
+    }
+
+    /// Adds a literal with the given `value` to an enumeration type.
+    pub fn add_literal(mut self, name: &str, value: i32) -> Self {
+        self.literals.push(EnumeratedLiteral {
+            name: name.to_string(),
+            value,
+        });
+        self
+    }
+
+    fn next_member_id(&self) -> u32 {
+        self.members.last().map_or(0, |m| m.id + 1)
+    }
+
+    fn add(
+        mut self,
+        name: &str,
+        id: u32,
+        member_type: DynamicType,
+        key: bool,
+        labels: Vec<i64>,
+        default_label: bool,
+    ) -> Self {
+        self.members.push(DynamicTypeMember {
+            name: name.to_string(),
+            id,
+            member_type,
+            key,
+            labels,
+            default_label,
+        });
+        self
+    }
+
+    /// Builds the described type. The operation fails with [`DdsError::PreconditionNotMet`] if the description
+    /// is not valid, for example if two members have the same name or id, if members are added to an enumeration
+    /// or if two members of a union share a label.
+    pub fn build(self) -> DdsResult<DynamicType> {
+        let name = self.name.clone();
+        let invalid = |reason: &str| {
+            Err(DdsError::PreconditionNotMet(format!(
+                "Invalid type {}: {}",
+                name, reason
+            )))
+        };
+
+        match self.kind {
+            TypeKind::Enumeration => {
+                if !self.members.is_empty() {
+                    return invalid("an enumeration has no members");
+                }
+                if self.literals.is_empty() {
+                    return invalid("an enumeration must have at least one literal");
+                }
+                if !all_unique(self.literals.iter().map(|l| l.name.as_str())) {
+                    return invalid("literal names must be unique");
+                }
+                if !all_unique(self.literals.iter().map(|l| l.value)) {
+                    return invalid("literal values must be unique");
+                }
+            }
+            TypeKind::Structure | TypeKind::Union => {
+                if !self.literals.is_empty() {
+                    return invalid("only enumerations have literals");
+                }
+                if !all_unique(self.members.iter().map(|m| m.name.as_str())) {
+                    return invalid("member names must be unique");
+                }
+                if !all_unique(self.members.iter().map(|m| m.id)) {
+                    return invalid("member ids must be unique");
+                }
+                if self.members.iter().any(|m| m.id > MAX_MEMBER_ID) {
+                    return invalid("member ids must fit in 28 bits");
+                }
+            }
+            _ => unreachable!("Only aggregated types are built"),
+        }
+
+        let type_descriptor = match self.kind {
+            TypeKind::Enumeration => TypeDescriptor::Enumeration {
+                name: self.name,
+                literals: self.literals,
+            },
+            TypeKind::Structure => {
+                if self
+                    .members
+                    .iter()
+                    .any(|m| m.default_label || !m.labels.is_empty())
+                {
+                    return invalid("only union members have labels");
+                }
+                TypeDescriptor::Structure {
+                    name: self.name,
+                    members: self.members,
+                }
+            }
+            _ => {
+                let discriminator_type = self
+                    .discriminator_type
+                    .expect("Unions are created with a discriminator type");
+                if !discriminator_type.kind().is_discriminator() {
+                    return invalid("the discriminator type is not a boolean, byte, integer, character or enumeration");
+                }
+                if self.members.is_empty() {
+                    return invalid("a union must have at least one member");
+                }
+                if self.members.iter().any(|m| m.key) {
+                    return invalid("union members can not be part of a key");
+                }
+                if self
+                    .members
+                    .iter()
+                    .any(|m| m.labels.is_empty() && !m.default_label)
+                {
+                    return invalid("union members must have a label");
+                }
+                if self.members.iter().filter(|m| m.default_label).count() > 1 {
+                    return invalid("only one union member can be the default one");
+                }
+                if !all_unique(self.members.iter().flat_map(|m| m.labels.iter())) {
+                    return invalid("union labels must be unique");
+                }
+                if self
+                    .members
+                    .iter()
+                    .flat_map(|m| m.labels.iter())
+                    .any(|&l| !is_discriminator_value(&discriminator_type, l))
+                {
+                    return invalid("union labels must be values of the discriminator type");
+                }
+                TypeDescriptor::Union {
+                    name: self.name,
+                    discriminator_type,
+                    members: self.members,
+                }
+            }
+        };
+
+        Ok(DynamicType::new(type_descriptor))
+    }
+}
+
+fn all_unique<T: Eq + std::hash::Hash>(mut values: impl Iterator<Item = T>) -> bool {
+    let mut set = HashSet::new();
+    values.all(|v| set.insert(v))
+}
+
+/// Whether `value` is a valid value of the discriminator of a union with `discriminator_type`. The booleans are
+/// represented with 0 and 1, the characters with their code and the enumerations with the value of their literals.
+pub(crate) fn is_discriminator_value(discriminator_type: &DynamicType, value: i64) -> bool {
+    match discriminator_type.kind() {
+        TypeKind::Boolean => value == 0 || value == 1,
+        TypeKind::Byte | TypeKind::UInt8 | TypeKind::Char8 => u8::try_from(value).is_ok(),
+        TypeKind::Int8 => i8::try_from(value).is_ok(),
+        TypeKind::Int16 => i16::try_from(value).is_ok(),
+        TypeKind::UInt16 => u16::try_from(value).is_ok(),
+        TypeKind::Int32 => i32::try_from(value).is_ok(),
+        TypeKind::UInt32 => u32::try_from(value).is_ok(),
+        TypeKind::Int64 | TypeKind::UInt64 => true,
+        TypeKind::Enumeration => discriminator_type
+            .literals()
+            .iter()
+            .any(|l| i64::from(l.value) == value),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int32() -> DynamicType {
+        DynamicType::primitive(TypeKind::Int32).unwrap()
+    }
+
+    #[test]
+    fn primitive_type_requires_primitive_kind() {
+        assert_eq!(int32().kind(), TypeKind::Int32);
+        assert_eq!(
+            DynamicType::primitive(TypeKind::Structure),
+            Err(DdsError::BadParameter)
+        );
+    }
+
+    #[test]
+    fn array_dimensions_can_not_be_zero() {
+        let array = DynamicType::array(int32(), &[2, 3]).unwrap();
+        assert_eq!(array.dimensions(), &[2, 3]);
+        assert_eq!(array.element_count(), 6);
+        assert_eq!(
+            DynamicType::array(int32(), &[2, 0]),
+            Err(DdsError::BadParameter)
+        );
+        assert_eq!(
+            DynamicType::array(int32(), &[]),
+            Err(DdsError::BadParameter)
+        );
+    }
+
+    #[test]
+    fn struct_member_ids_follow_previous_member() {
+        let shape = DynamicTypeBuilder::new_struct("Shape")
+            .add_key_member("color", DynamicType::string(128))
+            .add_member_with_id(10, "x", int32())
+            .add_member("y", int32())
+            .build()
+            .unwrap();
+
+        assert_eq!(shape.kind(), TypeKind::Structure);
+        assert_eq!(shape.name(), Some("Shape"));
+        let members = shape.members();
+        assert_eq!(members.len(), 3);
+        assert_eq!((members[0].id(), members[0].is_key()), (0, true));
+        assert_eq!((members[1].id(), members[1].is_key()), (10, false));
+        assert_eq!(members[2].id(), 11);
+        assert_eq!(shape.member_by_id(11).unwrap().name(), "y");
+        assert_eq!(shape.member_by_name("z"), Err(DdsError::BadParameter));
+    }
+
+    #[test]
+    fn struct_members_must_be_unique() {
+        assert!(DynamicTypeBuilder::new_struct("S")
+            .add_member("a", int32())
+            .add_member("a", int32())
+            .build()
+            .is_err());
+        assert!(DynamicTypeBuilder::new_struct("S")
+            .add_member_with_id(1, "a", int32())
+            .add_member_with_id(1, "b", int32())
+            .build()
+            .is_err());
+        assert!(DynamicTypeBuilder::new_struct("S")
+            .add_literal("A", 0)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn enum_literals_must_be_unique() {
+        let color = DynamicTypeBuilder::new_enum("Color")
+            .add_literal("RED", 0)
+            .add_literal("GREEN", 1)
+            .build()
+            .unwrap();
+        assert_eq!(color.literals()[1].name(), "GREEN");
+
+        assert!(DynamicTypeBuilder::new_enum("Color")
+            .add_literal("RED", 0)
+            .add_literal("GREEN", 0)
+            .build()
+            .is_err());
+        assert!(DynamicTypeBuilder::new_enum("Color").build().is_err());
+    }
+
+    #[test]
+    fn union_labels_must_be_valid() {
+        let discriminator = DynamicTypeBuilder::new_enum("Kind")
+            .add_literal("A", 0)
+            .add_literal("B", 1)
+            .build()
+            .unwrap();
+        assert!(DynamicTypeBuilder::new_union("U", discriminator.clone())
+            .add_union_member("a", int32(), &[0])
+            .add_default_union_member("b", DynamicType::string(0))
+            .build()
+            .is_ok());
+        assert!(DynamicTypeBuilder::new_union("U", discriminator.clone())
+            .add_union_member("a", int32(), &[0])
+            .add_union_member("b", int32(), &[0])
+            .build()
+            .is_err());
+        assert!(DynamicTypeBuilder::new_union("U", discriminator)
+            .add_union_member("a", int32(), &[2])
+            .build()
+            .is_err());
+        assert!(DynamicTypeBuilder::new_union("U", DynamicType::string(0))
+            .add_union_member("a", int32(), &[0])
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn max_key_size_is_computed_from_key_members() {
+        let byte = DynamicType::primitive(TypeKind::UInt8).unwrap();
+        let inner = DynamicTypeBuilder::new_struct("Inner")
+            .add_member("a", DynamicType::string(0))
+            .add_key_member("b", int32())
+            .build()
+            .unwrap();
+        let outer = DynamicTypeBuilder::new_struct("Outer")
+            .add_key_member("id", byte.clone())
+            .add_key_member("inner", inner)
+            .add_member("value", DynamicType::sequence(byte.clone(), 0))
+            .build()
+            .unwrap();
+        assert_eq!(outer.max_key_size(), Some(8));
+
+        let bounded_key = DynamicTypeBuilder::new_struct("BoundedKey")
+            .add_key_member("id", byte.clone())
+            .add_key_member("name", DynamicType::string(10))
+            .build()
+            .unwrap();
+        assert_eq!(bounded_key.max_key_size(), Some(19));
+
+        let unbounded_key = DynamicTypeBuilder::new_struct("UnboundedKey")
+            .add_key_member("values", DynamicType::sequence(byte, 0))
+            .build()
+            .unwrap();
+        assert_eq!(unbounded_key.max_key_size(), None);
+    }
+}
@@ -0,0 +1,266 @@
+use serde_json::Value;
+
+use crate::infrastructure::error::{DdsError, DdsResult};
+
+use super::{dynamic_data::DynamicData, dynamic_type::DynamicType, type_support::DdsGetKey};
+
+/// Converts a value of a type deriving [`DdsType`](super::type_support::DdsType) into a [`serde_json::Value`]. The
+/// structures are converted into JSON objects with a member for each of their fields independently of the
+/// representation used to exchange the type, so that the value can be logged or pretty-printed with
+/// [`serde_json::to_string_pretty`].
+///
+/// The conversion follows the [`serde::Serialize`] implementation of the type, so the types with a custom
+/// representation can only be converted if they implement it. The [`DynamicData`] samples, whose serde
+/// implementation follows the layout of their serialized data, are converted with [`dynamic_data_to_json_value`].
+pub fn to_json_value<Foo>(data: &Foo) -> DdsResult<Value>
+where
+    Foo: serde::Serialize + ?Sized,
+{
+    serde_json::to_value(data).map_err(|e| DdsError::Error(e.to_string()))
+}
+
+/// Converts a [`serde_json::Value`] into a value of a type deriving [`DdsType`](super::type_support::DdsType), for
+/// example to write a sample received from a REST endpoint. The operation fails with [`DdsError::Error`] if the value
+/// does not match the type. The [`DynamicData`] samples can only be converted with their type using
+/// [`dynamic_data_from_json_value`].
+pub fn from_json_value<Foo>(value: Value) -> DdsResult<Foo>
+where
+    Foo: for<'de> serde::Deserialize<'de>,
+{
+    serde_json::from_value(value).map_err(|e| DdsError::Error(e.to_string()))
+}
+
+/// Converts the key fields of a value of a type deriving [`DdsType`](super::type_support::DdsType) into a
+/// [`serde_json::Value`]. The key of a structure is a JSON object with a member for each of its key fields and the
+/// key of a type without key fields is [`Value::Null`]. The key of a [`DynamicData`] sample is a JSON array with the
+/// values of its key members.
+pub fn key_to_json_value<Foo>(data: &Foo) -> DdsResult<Value>
+where
+    Foo: DdsGetKey,
+{
+    to_json_value(&data.get_key())
+}
+
+/// Converts a [`DynamicData`] sample into a [`serde_json::Value`] following its [`DynamicType`]. A structure is
+/// converted into a JSON object with a member for each of its members and a union into a JSON object with a
+/// `discriminator` member, holding the value returned by [`DynamicData::get_discriminator`], and a member for its
+/// selected member, if any. The enumerations are converted into the name of their literal, the characters into a
+/// string of one character and the sequences and arrays into JSON arrays with the elements of the arrays in
+/// row-major order.
+pub fn dynamic_data_to_json_value(data: &DynamicData) -> Value {
+    data.to_json_value()
+}
+
+/// Converts a [`serde_json::Value`] with the format produced by [`dynamic_data_to_json_value`] into a
+/// [`DynamicData`] sample of `dynamic_type`. The `discriminator` of a union can be omitted when its selected member
+/// is given and the selected member can be omitted to take its default value. The operation fails with
+/// [`DdsError::BadParameter`] if `dynamic_type` is neither a structure nor a union and with [`DdsError::Error`] if
+/// the value does not match the type.
+pub fn dynamic_data_from_json_value(
+    dynamic_type: &DynamicType,
+    value: &Value,
+) -> DdsResult<DynamicData> {
+    DynamicData::from_json_value(dynamic_type, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic_definition::{
+        dynamic_data::DynamicValue,
+        dynamic_type::{DynamicTypeBuilder, TypeKind},
+        type_support::{DdsRepresentation, DdsSetKeyFields},
+    };
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Shape {
+        color: String,
+        x: i32,
+        points: Vec<u8>,
+    }
+
+    impl DdsGetKey for Shape {
+        type BorrowedKeyHolder<'a> = ShapeKey<'a>;
+
+        fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
+            ShapeKey { color: &self.color }
+        }
+    }
+
+    impl DdsSetKeyFields for Shape {
+        type OwningKeyHolder = String;
+
+        fn set_key_from_holder(&mut self, key_holder: Self::OwningKeyHolder) {
+            self.color = key_holder;
+        }
+    }
+
+    #[derive(serde::Serialize)]
+    struct ShapeKey<'a> {
+        color: &'a str,
+    }
+
+    #[test]
+    fn value_is_converted_to_and_from_json() {
+        let shape = Shape {
+            color: "BLUE".to_string(),
+            x: 3,
+            points: vec![1, 2],
+        };
+
+        let value = to_json_value(&shape).unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({"color": "BLUE", "x": 3, "points": [1, 2]})
+        );
+        assert_eq!(from_json_value::<Shape>(value).unwrap(), shape);
+    }
+
+    #[test]
+    fn key_is_converted_to_json() {
+        let shape = Shape {
+            color: "RED".to_string(),
+            x: 3,
+            points: vec![],
+        };
+
+        assert_eq!(
+            key_to_json_value(&shape).unwrap(),
+            serde_json::json!({"color": "RED"})
+        );
+    }
+
+    #[test]
+    fn invalid_json_value_is_rejected() {
+        let value = serde_json::json!({"color": "RED", "x": "three", "points": []});
+
+        assert!(matches!(
+            from_json_value::<Shape>(value),
+            Err(DdsError::Error(_))
+        ));
+    }
+    fn primitive(kind: TypeKind) -> DynamicType {
+        DynamicType::primitive(kind).unwrap()
+    }
+
+    fn reading_type() -> DynamicType {
+        let unit = DynamicTypeBuilder::new_enum("Unit")
+            .add_literal("CELSIUS", 0)
+            .add_literal("KELVIN", 1)
+            .build()
+            .unwrap();
+        let value = DynamicTypeBuilder::new_union("Value", primitive(TypeKind::Int32))
+            .add_union_member("integer", primitive(TypeKind::Int64), &[0])
+            .add_union_member("text", DynamicType::string(0), &[1, 2])
+            .build()
+            .unwrap();
+        DynamicTypeBuilder::new_struct("Reading")
+            .add_key_member("sensor", DynamicType::string(8))
+            .add_member("level", primitive(TypeKind::UInt8))
+            .add_member("unit", unit)
+            .add_member("grade", primitive(TypeKind::Char8))
+            .add_member(
+                "samples",
+                DynamicType::sequence(primitive(TypeKind::Int16), 3),
+            )
+            .add_member(
+                "matrix",
+                DynamicType::array(primitive(TypeKind::Float64), &[2, 2]).unwrap(),
+            )
+            .add_member("value", value)
+            .build()
+            .unwrap()
+    }
+
+    fn reading_json() -> Value {
+        serde_json::json!({
+            "sensor": "s1",
+            "level": 200,
+            "unit": "KELVIN",
+            "grade": "A",
+            "samples": [-1, 2],
+            "matrix": [1.0, 2.0, 3.0, 4.5],
+            "value": {"discriminator": 2, "text": "high"},
+        })
+    }
+
+    #[test]
+    fn dynamic_data_is_converted_to_and_from_json() {
+        let dynamic_type = reading_type();
+
+        let data = dynamic_data_from_json_value(&dynamic_type, &reading_json()).unwrap();
+
+        assert_eq!(data.get_value("level"), Ok(&DynamicValue::UInt8(200)));
+        assert_eq!(data.get_value("unit"), Ok(&DynamicValue::Enumeration(1)));
+        assert_eq!(data.get_value("grade"), Ok(&DynamicValue::Char8('A')));
+        match data.get_value("value") {
+            Ok(DynamicValue::Data(value)) => {
+                assert_eq!(value.get_discriminator(), Ok(2));
+                assert_eq!(value.get_value("text"), Ok(&DynamicValue::from("high")));
+            }
+            v => panic!("Unexpected union value {:?}", v),
+        }
+        assert_eq!(dynamic_data_to_json_value(&data), reading_json());
+        assert_eq!(data.sample_to_json_value(), Ok(reading_json()));
+    }
+
+    #[test]
+    fn union_discriminator_or_member_can_be_omitted() {
+        let dynamic_type = reading_type();
+        let value_type = dynamic_type.members()[6].member_type();
+
+        let selected_by_member =
+            dynamic_data_from_json_value(value_type, &serde_json::json!({"text": "low"})).unwrap();
+        let selected_by_discriminator =
+            dynamic_data_from_json_value(value_type, &serde_json::json!({"discriminator": 0}))
+                .unwrap();
+        let selecting_nothing =
+            dynamic_data_from_json_value(value_type, &serde_json::json!({"discriminator": 5}))
+                .unwrap();
+
+        assert_eq!(selected_by_member.get_discriminator(), Ok(1));
+        assert_eq!(
+            selected_by_discriminator.get_value("integer"),
+            Ok(&DynamicValue::Int64(0))
+        );
+        assert_eq!(
+            dynamic_data_to_json_value(&selecting_nothing),
+            serde_json::json!({"discriminator": 5})
+        );
+    }
+
+    #[test]
+    fn invalid_dynamic_data_json_value_is_rejected() {
+        let dynamic_type = reading_type();
+        let with = |member: &str, value: Value| {
+            let mut json = reading_json();
+            json[member] = value;
+            dynamic_data_from_json_value(&dynamic_type, &json)
+        };
+        let mut missing_member = reading_json();
+        missing_member.as_object_mut().unwrap().remove("unit");
+
+        for result in [
+            with("level", serde_json::json!(256)),
+            with("unit", serde_json::json!("FAHRENHEIT")),
+            with("grade", serde_json::json!("AB")),
+            with("sensor", serde_json::json!("too long sensor")),
+            with("samples", serde_json::json!([1, 2, 3, 4])),
+            with("matrix", serde_json::json!([1.0])),
+            with(
+                "value",
+                serde_json::json!({"discriminator": 0, "text": "a"}),
+            ),
+            with("value", serde_json::json!({"integer": 1, "text": "a"})),
+            with("other", serde_json::json!(1)),
+            dynamic_data_from_json_value(&dynamic_type, &missing_member),
+        ] {
+            assert!(matches!(result, Err(DdsError::Error(_))), "{:?}", result);
+        }
+        assert_eq!(
+            dynamic_data_from_json_value(&primitive(TypeKind::Int32), &serde_json::json!(1)),
+            Err(DdsError::BadParameter)
+        );
+    }
+}
@@ -1,8 +1,38 @@
+/// Contains the [`BoundedString`](crate::topic_definition::bounded::BoundedString) and
+/// [`BoundedVec`](crate::topic_definition::bounded::BoundedVec) types giving a maximum size to the types.
+pub mod bounded;
+
+/// Contains the built-in [`String`](crate::topic_definition::builtin_types::String),
+/// [`KeyedString`](crate::topic_definition::builtin_types::KeyedString),
+/// [`Bytes`](crate::topic_definition::builtin_types::Bytes) and
+/// [`KeyedBytes`](crate::topic_definition::builtin_types::KeyedBytes) types exchanged with the standard type names
+/// used by other vendors.
+pub mod builtin_types;
+
+/// Contains the [`DynamicData`](crate::topic_definition::dynamic_data::DynamicData) used to read and write the samples
+/// of the types defined at runtime.
+pub mod dynamic_data;
+
+/// Contains the [`DynamicType`](crate::topic_definition::dynamic_type::DynamicType) and the
+/// [`DynamicTypeBuilder`](crate::topic_definition::dynamic_type::DynamicTypeBuilder) used to define types at runtime.
+pub mod dynamic_type;
+
+/// Contains the conversions of the samples from and to [`serde_json::Value`] used to render and inject them
+/// independently of the representation of their type.
+pub mod json;
+
+/// Contains the [`MultiTopic`](crate::topic_definition::multitopic::MultiTopic) and the traits required to create it.
+pub mod multitopic;
+
 /// Contains the [`Topic`](crate::topic_definition::topic::Topic) and any related objects.
 pub mod topic;
 
 /// Contains the [`TopicListener`](crate::topic_definition::topic_listener::TopicListener) trait.
 pub mod topic_listener;
 
+/// Contains the [`TypeObject`](crate::topic_definition::type_object::TypeObject) and the other XTypes
+/// representations of the types exchanged during discovery.
+pub mod type_object;
+
 /// Contains the traits necessary to make a type capable of being used by the DDS middleware.
 pub mod type_support;
@@ -0,0 +1,297 @@
+use std::marker::PhantomData;
+
+use crate::{
+    domain::domain_participant::DomainParticipant,
+    implementation::{
+        dds::{
+            dds_data_reader::DdsDataReader,
+            dds_domain_participant::DdsDomainParticipant,
+            multitopic_join::SubscriptionExpression,
+            nodes::{DataReaderNode, DataReaderNodeKind},
+        },
+        utils::actor::ActorAddress,
+    },
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        qos::{DataReaderQos, QosKind},
+        status::StatusKind,
+    },
+    subscription::{
+        data_reader::DataReader,
+        data_reader_listener::DataReaderListener,
+        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+        subscriber::Subscriber,
+    },
+};
+
+use super::{
+    topic::Topic,
+    type_support::{DdsGetKey, DdsHasKey, DdsRepresentation, DdsSetKeyFields},
+};
+
+pub(crate) type CreateSourceReaders = fn(
+    &Subscriber,
+    &[Topic],
+    &DataReaderQos,
+    &DataReaderNode,
+) -> DdsResult<Vec<ActorAddress<DdsDataReader>>>;
+
+/// [`MultiTopic`] is a topic description that allows subscriptions to combine data coming from several [`Topic`] objects.
+/// The `subscription_expression` has the form `SELECT <fields> FROM <topic> NATURAL JOIN <topic> ...` as defined in the
+/// DDS standard. The most recent sample of each instance of a related topic is joined with the samples of the other related
+/// topics which have the same value on all the fields they have in common, whether these fields are keys or not.
+/// Each of the related topics needs a type which can be converted to and from JSON since the joined sample is built by
+/// selecting the fields by name.
+pub struct MultiTopic {
+    name: String,
+    type_name: String,
+    subscription_expression: String,
+    expression_parameters: Vec<String>,
+    parsed_subscription_expression: SubscriptionExpression,
+    related_topic_list: Vec<Topic>,
+    create_source_readers: CreateSourceReaders,
+    participant: ActorAddress<DdsDomainParticipant>,
+}
+
+impl MultiTopic {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        name: String,
+        type_name: String,
+        subscription_expression: String,
+        expression_parameters: Vec<String>,
+        parsed_subscription_expression: SubscriptionExpression,
+        related_topic_list: Vec<Topic>,
+        create_source_readers: CreateSourceReaders,
+        participant: ActorAddress<DdsDomainParticipant>,
+    ) -> Self {
+        Self {
+            name,
+            type_name,
+            subscription_expression,
+            expression_parameters,
+            parsed_subscription_expression,
+            related_topic_list,
+            create_source_readers,
+            participant,
+        }
+    }
+
+    pub(crate) fn parsed_subscription_expression(&self) -> &SubscriptionExpression {
+        &self.parsed_subscription_expression
+    }
+
+    pub(crate) fn related_topic_list(&self) -> &[Topic] {
+        &self.related_topic_list
+    }
+
+    pub(crate) fn create_source_readers(&self) -> CreateSourceReaders {
+        self.create_source_readers
+    }
+
+    pub(crate) fn participant_address(&self) -> &ActorAddress<DdsDomainParticipant> {
+        &self.participant
+    }
+}
+
+/// This implementation block represents the TopicDescription operations for the [`MultiTopic`].
+impl MultiTopic {
+    /// This operation returns the [`DomainParticipant`] to which the [`MultiTopic`] belongs.
+    pub fn get_participant(&self) -> DdsResult<DomainParticipant> {
+        Ok(DomainParticipant::new(self.participant.clone()))
+    }
+
+    /// The name of the type of the samples resulting from the join of the related topics.
+    pub fn get_type_name(&self) -> DdsResult<String> {
+        Ok(self.type_name.clone())
+    }
+
+    /// The name used to create the [`MultiTopic`].
+    pub fn get_name(&self) -> DdsResult<String> {
+        Ok(self.name.clone())
+    }
+}
+
+impl MultiTopic {
+    /// This operation returns the `subscription_expression` associated with the [`MultiTopic`]. That is, the expression specified
+    /// when the [`MultiTopic`] was created.
+    pub fn get_subscription_expression(&self) -> String {
+        self.subscription_expression.clone()
+    }
+
+    /// This operation returns the `expression_parameters` associated with the [`MultiTopic`]. That is, the parameters specified
+    /// on the last successful call to [`MultiTopic::set_expression_parameters()`], or if it was never called, the parameters
+    /// specified when the [`MultiTopic`] was created.
+    pub fn get_expression_parameters(&self) -> DdsResult<Vec<String>> {
+        Ok(self.expression_parameters.clone())
+    }
+
+    /// This operation changes the `expression_parameters` associated with the [`MultiTopic`].
+    /// Since the parameters can only be referenced by a `WHERE` clause, which is not supported, the operation
+    /// returns [`DdsError::BadParameter`] if any parameter is given.
+    pub fn set_expression_parameters(&mut self, expression_parameters: &[String]) -> DdsResult<()> {
+        if !expression_parameters.is_empty() {
+            return Err(DdsError::BadParameter);
+        }
+        self.expression_parameters = expression_parameters.to_vec();
+        Ok(())
+    }
+}
+
+/// This trait is implemented for the tuples of types which can be joined by a [`MultiTopic`]. The types
+/// must be listed in the same order as the related topics appear in the subscription expression.
+pub trait MultiTopicSources {
+    #[doc(hidden)]
+    const NUMBER_OF_RELATED_TOPICS: usize;
+
+    #[doc(hidden)]
+    fn create_source_readers(
+        subscriber: &Subscriber,
+        related_topic_list: &[Topic],
+        qos: &DataReaderQos,
+        multitopic_reader: &DataReaderNode,
+    ) -> DdsResult<Vec<ActorAddress<DdsDataReader>>>;
+}
+
+macro_rules! impl_multitopic_sources {
+    ($number_of_related_topics:literal; $($index:tt: $source:ident),+) => {
+        impl<$($source),+> MultiTopicSources for ($($source,)+)
+        where
+            $($source: DdsRepresentation
+                + DdsHasKey
+                + DdsGetKey
+                + DdsSetKeyFields
+                + serde::Serialize
+                + for<'de> serde::Deserialize<'de>
+                + Send
+                + Sync
+                + 'static,)+
+        {
+            const NUMBER_OF_RELATED_TOPICS: usize = $number_of_related_topics;
+
+            fn create_source_readers(
+                subscriber: &Subscriber,
+                related_topic_list: &[Topic],
+                qos: &DataReaderQos,
+                multitopic_reader: &DataReaderNode,
+            ) -> DdsResult<Vec<ActorAddress<DdsDataReader>>> {
+                Ok(vec![$(create_source_reader::<$source>(
+                    subscriber,
+                    &related_topic_list[$index],
+                    $index,
+                    qos,
+                    multitopic_reader,
+                )?),+])
+            }
+        }
+    };
+}
+
+impl_multitopic_sources!(1; 0: A);
+impl_multitopic_sources!(2; 0: A, 1: B);
+impl_multitopic_sources!(3; 0: A, 1: B, 2: C);
+impl_multitopic_sources!(4; 0: A, 1: B, 2: C, 3: D);
+impl_multitopic_sources!(5; 0: A, 1: B, 2: C, 3: D, 4: E);
+impl_multitopic_sources!(6; 0: A, 1: B, 2: C, 3: D, 4: E, 5: F);
+
+fn create_source_reader<Foo>(
+    subscriber: &Subscriber,
+    related_topic: &Topic,
+    related_topic_index: usize,
+    qos: &DataReaderQos,
+    multitopic_reader: &DataReaderNode,
+) -> DdsResult<ActorAddress<DdsDataReader>>
+where
+    Foo: DdsRepresentation
+        + DdsHasKey
+        + DdsGetKey
+        + DdsSetKeyFields
+        + serde::Serialize
+        + for<'de> serde::Deserialize<'de>
+        + Send
+        + Sync
+        + 'static,
+{
+    let listener = MultiTopicSourceListener::<Foo> {
+        related_topic_index,
+        multitopic_reader: multitopic_reader.clone(),
+        phantom: PhantomData,
+    };
+    let source_reader = subscriber.create_datareader::<Foo>(
+        related_topic,
+        QosKind::Specific(qos.clone()),
+        Some(Box::new(listener)),
+        &[StatusKind::DataAvailable],
+    )?;
+
+    match source_reader.node() {
+        DataReaderNodeKind::UserDefined(r) | DataReaderNodeKind::Listener(r) => {
+            Ok(r.address().clone())
+        }
+        DataReaderNodeKind::BuiltinStateful(_) | DataReaderNodeKind::BuiltinStateless(_) => {
+            Err(DdsError::IllegalOperation)
+        }
+    }
+}
+
+// Listener installed on the readers of the related topics which passes every received sample
+// on to the reader created on the MultiTopic
+struct MultiTopicSourceListener<Foo> {
+    related_topic_index: usize,
+    multitopic_reader: DataReaderNode,
+    phantom: PhantomData<Foo>,
+}
+
+impl<Foo> DataReaderListener for MultiTopicSourceListener<Foo>
+where
+    Foo: DdsRepresentation
+        + DdsHasKey
+        + serde::Serialize
+        + for<'de> serde::Deserialize<'de>
+        + Send
+        + 'static,
+{
+    type Foo = Foo;
+
+    fn on_data_available(&mut self, the_reader: &DataReader<Foo>) {
+        let samples = match the_reader.take(
+            i32::MAX,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        ) {
+            Ok(samples) => samples,
+            Err(_) => return,
+        };
+
+        for sample in samples {
+            let value = sample.data.and_then(|data| serde_json::to_value(data).ok());
+            let reception_timestamp = match self
+                .multitopic_reader
+                .parent_participant()
+                .get_current_time()
+            {
+                Ok(t) => t,
+                Err(_) => return,
+            };
+            self.multitopic_reader
+                .address()
+                .add_multitopic_source_sample(
+                    self.related_topic_index,
+                    sample.sample_info.instance_handle,
+                    value,
+                    Some(
+                        sample
+                            .sample_info
+                            .source_timestamp
+                            .unwrap_or(reception_timestamp),
+                    ),
+                    reception_timestamp,
+                    self.multitopic_reader.address().clone(),
+                    self.multitopic_reader.parent_subscriber().clone(),
+                    self.multitopic_reader.parent_participant().clone(),
+                )
+                .ok();
+        }
+    }
+}
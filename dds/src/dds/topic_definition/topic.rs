@@ -1,15 +1,18 @@
+use std::marker::PhantomData;
+
 use crate::{
-    domain::domain_participant::DomainParticipant,
+    domain::domain_participant::{delete_topic, DomainParticipant},
     implementation::{
         data_representation_builtin_endpoints::discovered_topic_data::DiscoveredTopicData,
         dds::{dds_domain_participant::DdsDomainParticipant, nodes::TopicNodeKind},
-        rtps::messages::overall_structure::RtpsMessageHeader,
+        rtps::messages::{overall_structure::RtpsMessageHeader, submessage_elements::Data},
         utils::actor::ActorAddress,
     },
     infrastructure::{
         condition::StatusCondition,
-        error::DdsResult,
+        error::{DdsError, DdsResult},
         instance::InstanceHandle,
+        owned::OwnedEntity,
         qos::{QosKind, TopicQos},
         status::{InconsistentTopicStatus, StatusKind},
     },
@@ -17,20 +20,75 @@ use crate::{
 
 use super::{
     topic_listener::TopicListener,
-    type_support::{dds_serialize_key_to_bytes, dds_serialize_to_bytes},
+    type_support::{dds_key_hash, dds_serialize_key_to_bytes, dds_serialize_to_bytes, TypeSupport},
 };
 
 /// The [`Topic`] represents the fact that both publications and subscriptions are tied to a single data-type. Its attributes
 /// `type_name` defines a unique resulting type for the publication or the subscription. It has also a `name` that allows it to
 /// be retrieved locally.
-#[derive(PartialEq, Eq)]
-pub struct Topic {
+/// A [`Topic`] created with [`DomainParticipant::create_typed_topic()`] is bound to the type `Foo` registered with its
+/// `type_name`. A [`Topic`] without type argument is bound to the type of the first
+/// [`DataWriter`](crate::publication::data_writer::DataWriter) or
+/// [`DataReader`](crate::subscription::data_reader::DataReader) created on it if its `type_name` was never registered.
+pub struct Topic<Foo = ()> {
     node: TopicNodeKind,
+    phantom: PhantomData<Foo>,
+}
+
+/// The [`TopicType`] trait relates the type argument of a [`Topic`] to the type `Foo` of the
+/// [`DataWriter`](crate::publication::data_writer::DataWriter) and
+/// [`DataReader`](crate::subscription::data_reader::DataReader) created on it.
+/// A typed [`Topic<Foo>`] only accepts readers and writers of type `Foo`:
+///
+/// ```compile_fail
+/// # use dust_dds::{
+/// #     domain::domain_participant_factory::DomainParticipantFactory,
+/// #     infrastructure::{qos::QosKind, status::NO_STATUS},
+/// #     topic_definition::type_support::DdsType,
+/// # };
+/// #[derive(serde::Serialize, serde::Deserialize, DdsType)]
+/// struct MyData {
+///     x: u8,
+/// }
+///
+/// #[derive(serde::Serialize, serde::Deserialize, DdsType)]
+/// struct OtherData {
+///     y: u8,
+/// }
+///
+/// let participant = DomainParticipantFactory::get_instance()
+///     .create_participant(0, QosKind::Default, None, NO_STATUS)
+///     .unwrap();
+/// let topic = participant
+///     .create_typed_topic::<MyData>("MyTopic", QosKind::Default, None, NO_STATUS)
+///     .unwrap();
+/// let publisher = participant
+///     .create_publisher(QosKind::Default, None, NO_STATUS)
+///     .unwrap();
+/// let _writer = publisher
+///     .create_datawriter::<OtherData>(&topic, QosKind::Default, None, NO_STATUS)
+///     .unwrap();
+/// ```
+pub trait TopicType<Foo> {}
+
+impl<Foo> TopicType<Foo> for Foo where Foo: TypeSupport {}
+
+impl<Foo> TopicType<Foo> for () {}
+
+impl<Foo> PartialEq for Topic<Foo> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
 }
 
-impl Topic {
+impl<Foo> Eq for Topic<Foo> {}
+
+impl<Foo> Topic<Foo> {
     pub(crate) fn new(node: TopicNodeKind) -> Self {
-        Self { node }
+        Self {
+            node,
+            phantom: PhantomData,
+        }
     }
 
     pub(crate) fn node(&self) -> &TopicNodeKind {
@@ -57,7 +115,15 @@ impl Topic {
 //     }
 // }
 
-impl Topic {
+impl<Foo> OwnedEntity for Topic<Foo> {
+    fn delete_entity(&self) -> DdsResult<()> {
+        match &self.node {
+            TopicNodeKind::UserDefined(t) => delete_topic(t.parent_participant(), self),
+        }
+    }
+}
+
+impl<Foo> Topic<Foo> {
     /// This method allows the application to retrieve the [`InconsistentTopicStatus`] of the [`Topic`].
     pub fn get_inconsistent_topic_status(&self) -> DdsResult<InconsistentTopicStatus> {
         match &self.node {
@@ -67,7 +133,7 @@ impl Topic {
 }
 
 /// This implementation block represents the TopicDescription operations for the [`Topic`].
-impl Topic {
+impl<Foo> Topic<Foo> {
     /// This operation returns the [`DomainParticipant`] to which the [`Topic`] belongs.
     pub fn get_participant(&self) -> DdsResult<DomainParticipant> {
         match &self.node {
@@ -93,7 +159,7 @@ impl Topic {
 }
 
 /// This implementation block contains the Entity operations for the [`Topic`].
-impl Topic {
+impl<Foo> Topic<Foo> {
     /// This operation is used to set the QoS policies of the Entity and replacing the values of any policies previously set.
     /// Certain policies are “immutable;” they can only be set at Entity creation time, or before the entity is made enabled.
     /// If [`Self::set_qos()`] is invoked after the Entity is enabled and it attempts to change the value of an “immutable” policy, the operation will
@@ -151,17 +217,13 @@ impl Topic {
     /// The list of statuses returned by the [`Self::get_status_changes`] operation refers to the status that are triggered on the Entity itself
     /// and does not include statuses that apply to contained entities.
     pub fn get_status_changes(&self) -> DdsResult<Vec<StatusKind>> {
-        todo!()
-        // match &self.node {
-        //     TopicNodeKind::UserDefined(t) => {
-        //         THE_DDS_DOMAIN_PARTICIPANT_FACTORY.get_topic_listener(&t.guid(), |topic_listener| {
-        //             Ok(topic_listener
-        //                 .ok_or(DdsError::AlreadyDeleted)?
-        //                 .get_status_changes())
-        //         })
-        //     }
-        //     TopicNodeKind::Listener(_) => todo!(),
-        // }
+        match &self.node {
+            TopicNodeKind::UserDefined(t) => Ok(t
+                .address()
+                .get_statuscondition()?
+                .read_lock()
+                .get_status_changes()),
+        }
     }
 
     /// This operation enables the Entity. Entity objects can be created either enabled or disabled. This is controlled by the value of
@@ -219,11 +281,7 @@ impl Topic {
         _a_listener: Option<Box<dyn TopicListener + Send + Sync>>,
         _mask: &[StatusKind],
     ) -> DdsResult<()> {
-        todo!()
-        // match &self.node {
-        //     TopicNodeKind::UserDefined(_) => todo!(),
-        //     TopicNodeKind::Listener(_) => Err(DdsError::IllegalOperation),
-        // }
+        Err(DdsError::Unsupported)
     }
 }
 
@@ -241,8 +299,9 @@ fn announce_topic(
         .find(|x| x.get_type_name().unwrap() == "DiscoveredTopicData")
     {
         sedp_topic_announcer.write_w_timestamp(
-            serialized_data,
+            Data::new(serialized_data),
             dds_serialize_key_to_bytes(&discovered_topic_data)?,
+            dds_key_hash(&discovered_topic_data)?,
             None,
             timestamp,
         )??;
@@ -0,0 +1,1593 @@
+use std::{collections::HashMap, convert::TryFrom, marker::PhantomData};
+
+use byteorder::{ByteOrder, LittleEndian};
+use serde::{
+    de::{self, DeserializeOwned},
+    ser::{self, SerializeStruct, SerializeTuple, SerializeTupleStruct},
+};
+
+use crate::{
+    implementation::xcdr2_serde::{
+        serde_xcdr2_deserializer, serde_xcdr2_serializer::Xcdr2Serializer,
+    },
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        qos_policy::{TypeConsistencyEnforcementQosPolicy, TypeConsistencyKind},
+    },
+};
+
+use super::type_support::{
+    DdsTypeDescriptor, ExtensibilityKind, MemberDescriptor, StructDescriptor,
+};
+
+// Type kinds
+const TK_NONE: u8 = 0x00;
+const TK_BOOLEAN: u8 = 0x01;
+const TK_BYTE: u8 = 0x02;
+const TK_INT16: u8 = 0x03;
+const TK_INT32: u8 = 0x04;
+const TK_INT64: u8 = 0x05;
+const TK_UINT16: u8 = 0x06;
+const TK_UINT32: u8 = 0x07;
+const TK_UINT64: u8 = 0x08;
+const TK_FLOAT32: u8 = 0x09;
+const TK_FLOAT64: u8 = 0x0A;
+const TK_FLOAT128: u8 = 0x0B;
+const TK_INT8: u8 = 0x0C;
+const TK_UINT8: u8 = 0x0D;
+const TK_CHAR8: u8 = 0x10;
+const TK_CHAR16: u8 = 0x11;
+const TK_STRUCTURE: u8 = 0x51;
+
+// Equivalence kinds
+const EK_MINIMAL: u8 = 0xF1;
+const EK_COMPLETE: u8 = 0xF2;
+const EK_BOTH: u8 = 0xF3;
+
+// Type identifier kinds
+const TI_STRING8_SMALL: u8 = 0x70;
+const TI_STRING8_LARGE: u8 = 0x71;
+const TI_PLAIN_SEQUENCE_SMALL: u8 = 0x80;
+const TI_PLAIN_SEQUENCE_LARGE: u8 = 0x81;
+const TI_PLAIN_ARRAY_SMALL: u8 = 0x90;
+const TI_PLAIN_ARRAY_LARGE: u8 = 0x91;
+
+// Member flags
+const TRY_CONSTRUCT1: u16 = 1 << 0;
+const IS_OPTIONAL: u16 = 1 << 3;
+const IS_MUST_UNDERSTAND: u16 = 1 << 4;
+const IS_KEY: u16 = 1 << 5;
+
+// Type flags
+const IS_FINAL: u16 = 1 << 0;
+const IS_APPENDABLE: u16 = 1 << 1;
+const IS_MUTABLE: u16 = 1 << 2;
+const EXTENSIBILITY_FLAGS: u16 = IS_FINAL | IS_APPENDABLE | IS_MUTABLE;
+
+// Largest number of fields of the union types serialized as tuples
+const TYPE_IDENTIFIER_FIELDS: usize = 5;
+const TYPE_OBJECT_FIELDS: usize = 3;
+
+/// Hash identifying a type, made of the first 14 bytes of the MD5 of its serialized [`TypeObject`].
+pub type EquivalenceHash = [u8; 14];
+
+/// First 4 bytes of the MD5 of a member name which identify the member in the minimal type objects.
+pub type NameHash = [u8; 4];
+
+/// Kind of the [`TypeObject`] describing a type. The minimal type objects describe only what is needed
+/// to decide the assignability of the types while the complete ones also contain the type and member names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquivalenceKind {
+    Minimal,
+    Complete,
+}
+
+/// Primitive types of the DDS-XTypes standard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrimitiveKind {
+    Boolean,
+    Byte,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Float32,
+    Float64,
+    Float128,
+    Char8,
+    Char16,
+}
+
+impl PrimitiveKind {
+    fn type_kind(self) -> u8 {
+        match self {
+            PrimitiveKind::Boolean => TK_BOOLEAN,
+            PrimitiveKind::Byte => TK_BYTE,
+            PrimitiveKind::Int8 => TK_INT8,
+            PrimitiveKind::Int16 => TK_INT16,
+            PrimitiveKind::Int32 => TK_INT32,
+            PrimitiveKind::Int64 => TK_INT64,
+            PrimitiveKind::UInt8 => TK_UINT8,
+            PrimitiveKind::UInt16 => TK_UINT16,
+            PrimitiveKind::UInt32 => TK_UINT32,
+            PrimitiveKind::UInt64 => TK_UINT64,
+            PrimitiveKind::Float32 => TK_FLOAT32,
+            PrimitiveKind::Float64 => TK_FLOAT64,
+            PrimitiveKind::Float128 => TK_FLOAT128,
+            PrimitiveKind::Char8 => TK_CHAR8,
+            PrimitiveKind::Char16 => TK_CHAR16,
+        }
+    }
+
+    fn from_type_kind(type_kind: u8) -> Option<Self> {
+        Some(match type_kind {
+            TK_BOOLEAN => PrimitiveKind::Boolean,
+            TK_BYTE => PrimitiveKind::Byte,
+            TK_INT8 => PrimitiveKind::Int8,
+            TK_INT16 => PrimitiveKind::Int16,
+            TK_INT32 => PrimitiveKind::Int32,
+            TK_INT64 => PrimitiveKind::Int64,
+            TK_UINT8 => PrimitiveKind::UInt8,
+            TK_UINT16 => PrimitiveKind::UInt16,
+            TK_UINT32 => PrimitiveKind::UInt32,
+            TK_UINT64 => PrimitiveKind::UInt64,
+            TK_FLOAT32 => PrimitiveKind::Float32,
+            TK_FLOAT64 => PrimitiveKind::Float64,
+            TK_FLOAT128 => PrimitiveKind::Float128,
+            TK_CHAR8 => PrimitiveKind::Char8,
+            TK_CHAR16 => PrimitiveKind::Char16,
+            _ => return None,
+        })
+    }
+}
+
+/// Identifier of a type as defined in the DDS-XTypes standard.
+///
+/// The primitive types, strings and plain collections are fully described by their identifier. The other types
+/// are identified by the [`EquivalenceHash`] of their minimal or complete [`TypeObject`]. The bound of the
+/// strings and sequences is 0 when they are unbounded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TypeIdentifier {
+    NoType,
+    Primitive(PrimitiveKind),
+    String8 {
+        bound: u32,
+    },
+    Sequence {
+        bound: u32,
+        element: Box<TypeIdentifier>,
+    },
+    Array {
+        dimensions: Vec<u32>,
+        element: Box<TypeIdentifier>,
+    },
+    Minimal(EquivalenceHash),
+    Complete(EquivalenceHash),
+}
+
+impl TypeIdentifier {
+    // Equivalence kind given in the header of the plain collections with elements of this type
+    fn equivalence_kind(&self) -> u8 {
+        match self {
+            TypeIdentifier::Minimal(_) => EK_MINIMAL,
+            TypeIdentifier::Complete(_) => EK_COMPLETE,
+            TypeIdentifier::Sequence { element, .. } | TypeIdentifier::Array { element, .. } => {
+                element.equivalence_kind()
+            }
+            _ => EK_BOTH,
+        }
+    }
+}
+
+impl ser::Serialize for TypeIdentifier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        // Final union with an octet discriminator. The headers of the plain collections are
+        // final structures which are serialized in place.
+        let mut s = serializer.serialize_tuple_struct("TypeIdentifier", TYPE_IDENTIFIER_FIELDS)?;
+        match self {
+            TypeIdentifier::NoType => s.serialize_field(&TK_NONE)?,
+            TypeIdentifier::Primitive(kind) => s.serialize_field(&kind.type_kind())?,
+            TypeIdentifier::String8 { bound } => match u8::try_from(*bound) {
+                Ok(bound) => {
+                    s.serialize_field(&TI_STRING8_SMALL)?;
+                    s.serialize_field(&bound)?;
+                }
+                Err(_) => {
+                    s.serialize_field(&TI_STRING8_LARGE)?;
+                    s.serialize_field(bound)?;
+                }
+            },
+            TypeIdentifier::Sequence { bound, element } => {
+                let small_bound = u8::try_from(*bound).ok();
+                s.serialize_field(&match small_bound {
+                    Some(_) => TI_PLAIN_SEQUENCE_SMALL,
+                    None => TI_PLAIN_SEQUENCE_LARGE,
+                })?;
+                s.serialize_field(&element.equivalence_kind())?;
+                s.serialize_field(&0u16)?;
+                match small_bound {
+                    Some(bound) => s.serialize_field(&bound)?,
+                    None => s.serialize_field(bound)?,
+                }
+                s.serialize_field(element)?;
+            }
+            TypeIdentifier::Array {
+                dimensions,
+                element,
+            } => {
+                let small_dimensions: Option<Vec<u8>> = dimensions
+                    .iter()
+                    .map(|dimension| u8::try_from(*dimension).ok())
+                    .collect();
+                s.serialize_field(&match small_dimensions {
+                    Some(_) => TI_PLAIN_ARRAY_SMALL,
+                    None => TI_PLAIN_ARRAY_LARGE,
+                })?;
+                s.serialize_field(&element.equivalence_kind())?;
+                s.serialize_field(&0u16)?;
+                match small_dimensions {
+                    Some(dimensions) => s.serialize_field(&dimensions)?,
+                    None => s.serialize_field(dimensions)?,
+                }
+                s.serialize_field(element)?;
+            }
+            TypeIdentifier::Minimal(hash) => {
+                s.serialize_field(&EK_MINIMAL)?;
+                s.serialize_field(hash)?;
+            }
+            TypeIdentifier::Complete(hash) => {
+                s.serialize_field(&EK_COMPLETE)?;
+                s.serialize_field(hash)?;
+            }
+        }
+        s.end()
+    }
+}
+
+fn next_field<'de, A, T>(seq: &mut A) -> Result<T, A::Error>
+where
+    A: de::SeqAccess<'de>,
+    T: de::Deserialize<'de>,
+{
+    seq.next_element()?
+        .ok_or_else(|| de::Error::custom("missing union field"))
+}
+
+impl<'de> de::Deserialize<'de> for TypeIdentifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct TypeIdentifierVisitor;
+
+        impl<'de> de::Visitor<'de> for TypeIdentifierVisitor {
+            type Value = TypeIdentifier;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("union TypeIdentifier")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let discriminator: u8 = next_field(&mut seq)?;
+                Ok(match discriminator {
+                    TK_NONE => TypeIdentifier::NoType,
+                    TI_STRING8_SMALL => TypeIdentifier::String8 {
+                        bound: next_field::<_, u8>(&mut seq)? as u32,
+                    },
+                    TI_STRING8_LARGE => TypeIdentifier::String8 {
+                        bound: next_field(&mut seq)?,
+                    },
+                    TI_PLAIN_SEQUENCE_SMALL | TI_PLAIN_SEQUENCE_LARGE => {
+                        let _equivalence_kind: u8 = next_field(&mut seq)?;
+                        let _element_flags: u16 = next_field(&mut seq)?;
+                        let bound = if discriminator == TI_PLAIN_SEQUENCE_SMALL {
+                            next_field::<_, u8>(&mut seq)? as u32
+                        } else {
+                            next_field(&mut seq)?
+                        };
+                        TypeIdentifier::Sequence {
+                            bound,
+                            element: next_field(&mut seq)?,
+                        }
+                    }
+                    TI_PLAIN_ARRAY_SMALL | TI_PLAIN_ARRAY_LARGE => {
+                        let _equivalence_kind: u8 = next_field(&mut seq)?;
+                        let _element_flags: u16 = next_field(&mut seq)?;
+                        let dimensions = if discriminator == TI_PLAIN_ARRAY_SMALL {
+                            next_field::<_, Vec<u8>>(&mut seq)?
+                                .into_iter()
+                                .map(u32::from)
+                                .collect()
+                        } else {
+                            next_field(&mut seq)?
+                        };
+                        TypeIdentifier::Array {
+                            dimensions,
+                            element: next_field(&mut seq)?,
+                        }
+                    }
+                    EK_MINIMAL => TypeIdentifier::Minimal(next_field(&mut seq)?),
+                    EK_COMPLETE => TypeIdentifier::Complete(next_field(&mut seq)?),
+                    type_kind => TypeIdentifier::Primitive(
+                        PrimitiveKind::from_type_kind(type_kind).ok_or_else(|| {
+                            de::Error::custom(format!(
+                                "unsupported type identifier kind {:#04x}",
+                                type_kind
+                            ))
+                        })?,
+                    ),
+                })
+            }
+        }
+
+        deserializer.deserialize_tuple_struct(
+            "TypeIdentifier",
+            TYPE_IDENTIFIER_FIELDS,
+            TypeIdentifierVisitor,
+        )
+    }
+}
+
+/// Members of a structure common to its minimal and complete type objects. The `member_flags` contain the
+/// optional, must understand and key flags of the member.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CommonStructMember {
+    pub member_id: u32,
+    pub member_flags: u16,
+    pub member_type_id: TypeIdentifier,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompleteMemberDetail {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CompleteStructMember {
+    pub common: CommonStructMember,
+    pub detail: CompleteMemberDetail,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompleteTypeDetail {
+    pub type_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CompleteStructHeader {
+    pub base_type: TypeIdentifier,
+    pub detail: CompleteTypeDetail,
+}
+
+/// Complete description of a structure. The `struct_flags` contain its extensibility kind.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CompleteStructType {
+    pub struct_flags: u16,
+    pub header: CompleteStructHeader,
+    pub member_seq: Vec<CompleteStructMember>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MinimalMemberDetail {
+    pub name_hash: NameHash,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MinimalStructMember {
+    pub common: CommonStructMember,
+    pub detail: MinimalMemberDetail,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MinimalStructHeader {
+    pub base_type: TypeIdentifier,
+}
+
+/// Minimal description of a structure. The `struct_flags` contain its extensibility kind.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MinimalStructType {
+    pub struct_flags: u16,
+    pub header: MinimalStructHeader,
+    pub member_seq: Vec<MinimalStructMember>,
+}
+
+// The details of the types and members are final structures in which the builtin and custom
+// annotations are optional members. The annotations are not supported so they are always absent.
+fn serialize_detail<S>(
+    serializer: S,
+    name: &'static str,
+    field: &'static str,
+    value: &str,
+    annotations_first: bool,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    let mut s = serializer.serialize_struct(name, 3)?;
+    if !annotations_first {
+        s.serialize_field(field, value)?;
+    }
+    s.serialize_field("ann_builtin", &None::<()>)?;
+    s.serialize_field("ann_custom", &None::<()>)?;
+    if annotations_first {
+        s.serialize_field(field, value)?;
+    }
+    s.end()
+}
+
+fn deserialize_detail<'de, D>(
+    deserializer: D,
+    name: &'static str,
+    fields: &'static [&'static str],
+    annotations_first: bool,
+) -> Result<String, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    struct DetailVisitor {
+        annotations_first: bool,
+    }
+
+    impl DetailVisitor {
+        fn no_annotations<'de, A>(seq: &mut A) -> Result<(), A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            for _ in 0..2 {
+                if next_field::<_, Option<()>>(seq)?.is_some() {
+                    return Err(de::Error::custom("type annotations are not supported"));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl<'de> de::Visitor<'de> for DetailVisitor {
+        type Value = String;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("type or member detail")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            if self.annotations_first {
+                Self::no_annotations(&mut seq)?;
+                next_field(&mut seq)
+            } else {
+                let name = next_field(&mut seq)?;
+                Self::no_annotations(&mut seq)?;
+                Ok(name)
+            }
+        }
+    }
+
+    deserializer.deserialize_struct(name, fields, DetailVisitor { annotations_first })
+}
+
+impl ser::Serialize for CompleteMemberDetail {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serialize_detail(
+            serializer,
+            "CompleteMemberDetail",
+            "name",
+            &self.name,
+            false,
+        )
+    }
+}
+
+impl<'de> de::Deserialize<'de> for CompleteMemberDetail {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserialize_detail(
+            deserializer,
+            "CompleteMemberDetail",
+            &["name", "ann_builtin", "ann_custom"],
+            false,
+        )
+        .map(|name| CompleteMemberDetail { name })
+    }
+}
+
+impl ser::Serialize for CompleteTypeDetail {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serialize_detail(
+            serializer,
+            "CompleteTypeDetail",
+            "type_name",
+            &self.type_name,
+            true,
+        )
+    }
+}
+
+impl<'de> de::Deserialize<'de> for CompleteTypeDetail {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserialize_detail(
+            deserializer,
+            "CompleteTypeDetail",
+            &["ann_builtin", "ann_custom", "type_name"],
+            true,
+        )
+        .map(|type_name| CompleteTypeDetail { type_name })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompleteTypeObject {
+    Struct(CompleteStructType),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MinimalTypeObject {
+    Struct(MinimalStructType),
+}
+
+/// Description of a type as defined in the DDS-XTypes standard. The type objects are generated for the
+/// structures deriving [`DdsType`](super::type_support::DdsType) with an extensibility attribute and
+/// are identified by the hash of their serialization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeObject {
+    Complete(CompleteTypeObject),
+    Minimal(MinimalTypeObject),
+}
+
+impl TypeObject {
+    pub fn equivalence_kind(&self) -> EquivalenceKind {
+        match self {
+            TypeObject::Complete(_) => EquivalenceKind::Complete,
+            TypeObject::Minimal(_) => EquivalenceKind::Minimal,
+        }
+    }
+
+    /// Returns the [`TypeIdentifier`] of the type described by this type object.
+    pub fn type_identifier(&self) -> DdsResult<TypeIdentifier> {
+        let digest = md5::compute(serialize(self)?);
+        let mut hash = EquivalenceHash::default();
+        let hash_length = hash.len();
+        hash.copy_from_slice(&digest.0[..hash_length]);
+        Ok(match self.equivalence_kind() {
+            EquivalenceKind::Minimal => TypeIdentifier::Minimal(hash),
+            EquivalenceKind::Complete => TypeIdentifier::Complete(hash),
+        })
+    }
+}
+
+impl ser::Serialize for TypeObject {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        // Appendable union of the final unions CompleteTypeObject and MinimalTypeObject
+        // which are serialized in place
+        let mut s = serializer.serialize_tuple_struct("TypeObject", TYPE_OBJECT_FIELDS)?;
+        match self {
+            TypeObject::Complete(CompleteTypeObject::Struct(struct_type)) => {
+                s.serialize_field(&EK_COMPLETE)?;
+                s.serialize_field(&TK_STRUCTURE)?;
+                s.serialize_field(struct_type)?;
+            }
+            TypeObject::Minimal(MinimalTypeObject::Struct(struct_type)) => {
+                s.serialize_field(&EK_MINIMAL)?;
+                s.serialize_field(&TK_STRUCTURE)?;
+                s.serialize_field(struct_type)?;
+            }
+        }
+        s.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for TypeObject {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct TypeObjectVisitor;
+
+        impl<'de> de::Visitor<'de> for TypeObjectVisitor {
+            type Value = TypeObject;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("union TypeObject")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let equivalence_kind: u8 = next_field(&mut seq)?;
+                let type_kind: u8 = next_field(&mut seq)?;
+                match (equivalence_kind, type_kind) {
+                    (EK_COMPLETE, TK_STRUCTURE) => Ok(TypeObject::Complete(
+                        CompleteTypeObject::Struct(next_field(&mut seq)?),
+                    )),
+                    (EK_MINIMAL, TK_STRUCTURE) => Ok(TypeObject::Minimal(
+                        MinimalTypeObject::Struct(next_field(&mut seq)?),
+                    )),
+                    _ => Err(de::Error::custom(format!(
+                        "unsupported type object kind {:#04x} {:#04x}",
+                        equivalence_kind, type_kind
+                    ))),
+                }
+            }
+        }
+
+        deserializer.deserialize_tuple_struct("TypeObject", TYPE_OBJECT_FIELDS, TypeObjectVisitor)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TypeIdentifierWithSize {
+    pub type_id: TypeIdentifier,
+    pub typeobject_serialized_size: u32,
+}
+
+/// Identifier of a type together with the identifiers of the types it depends on.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TypeIdentifierWithDependencies {
+    pub typeid_with_size: TypeIdentifierWithSize,
+    pub dependent_typeid_count: i32,
+    pub dependent_typeids: Vec<TypeIdentifierWithSize>,
+}
+
+/// Type information announced in the discovery data of the [`DataWriter`](crate::publication::data_writer::DataWriter)
+/// and [`DataReader`](crate::subscription::data_reader::DataReader) to decide whether their types are consistent.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TypeInformation {
+    pub minimal: TypeIdentifierWithDependencies,
+    pub complete: TypeIdentifierWithDependencies,
+}
+
+const fn appendable_struct(name: &'static str) -> StructDescriptor {
+    StructDescriptor {
+        name,
+        extensibility: ExtensibilityKind::Appendable,
+        members: &[],
+    }
+}
+
+// Descriptors of the types of this module which are not final
+static STRUCT_DESCRIPTORS: [StructDescriptor; 8] = [
+    appendable_struct("TypeObject"),
+    appendable_struct("CompleteStructHeader"),
+    appendable_struct("CompleteStructMember"),
+    appendable_struct("MinimalStructHeader"),
+    appendable_struct("MinimalStructMember"),
+    appendable_struct("TypeIdentifierWithSize"),
+    appendable_struct("TypeIdentifierWithDependencies"),
+    StructDescriptor {
+        name: "TypeInformation",
+        extensibility: ExtensibilityKind::Mutable,
+        members: &[
+            MemberDescriptor {
+                name: "minimal",
+                id: 0x1001,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "complete",
+                id: 0x1002,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+        ],
+    },
+];
+
+fn serialize<T>(value: &T) -> DdsResult<Vec<u8>>
+where
+    T: ser::Serialize + ?Sized,
+{
+    let mut buffer = Vec::new();
+    let mut serializer = Xcdr2Serializer::<LittleEndian>::new(
+        &mut buffer,
+        ExtensibilityKind::Final,
+        STRUCT_DESCRIPTORS.iter().collect(),
+    );
+    ser::Serialize::serialize(value, &mut serializer)
+        .map_err(|err| DdsError::PreconditionNotMet(err.to_string()))?;
+    Ok(buffer)
+}
+
+/// Name of the newtype structures containing XTypes data, which the deserializer of the discovery parameter lists
+/// deserializes with the [`xtypes_deserializer`] of the endianness of the parameter list.
+pub(crate) const XTYPES_DATA: &str = "XTypesData";
+
+pub(crate) fn xtypes_deserializer<E>(
+    data: &[u8],
+) -> serde_xcdr2_deserializer::Xcdr2Deserializer<'_, E>
+where
+    E: ByteOrder,
+{
+    serde_xcdr2_deserializer::Xcdr2Deserializer::new(
+        data,
+        ExtensibilityKind::Final,
+        STRUCT_DESCRIPTORS.iter().collect(),
+    )
+}
+
+fn name_hash(name: &str) -> NameHash {
+    let digest = md5::compute(name);
+    let mut hash = NameHash::default();
+    let hash_length = hash.len();
+    hash.copy_from_slice(&digest.0[..hash_length]);
+    hash
+}
+
+/// Builds the type objects of a structure and of the structures it depends on. It is used by the code
+/// generated by the [`DdsType`](super::type_support::DdsType) derive.
+#[doc(hidden)]
+pub struct TypeObjectBuilder {
+    equivalence_kind: EquivalenceKind,
+    // Type objects built so far, the dependencies of a type being built before the type
+    type_objects: Vec<(&'static str, TypeIdentifier, TypeObject)>,
+    // Names of the structures whose type object is being built
+    in_progress: Vec<&'static str>,
+}
+
+impl TypeObjectBuilder {
+    fn new(equivalence_kind: EquivalenceKind) -> Self {
+        Self {
+            equivalence_kind,
+            type_objects: Vec::new(),
+            in_progress: Vec::new(),
+        }
+    }
+
+    /// Returns the identifier of the structure with the given descriptor, whose members have the types returned
+    /// by `member_type_identifiers`. Recursive structures are not described and give `None`.
+    pub fn struct_type_identifier(
+        &mut self,
+        descriptor: &'static StructDescriptor,
+        member_type_identifiers: &[fn(&mut TypeObjectBuilder) -> Option<TypeIdentifier>],
+    ) -> Option<TypeIdentifier> {
+        if let Some((_, type_identifier, _)) = self
+            .type_objects
+            .iter()
+            .find(|(name, _, _)| *name == descriptor.name)
+        {
+            return Some(type_identifier.clone());
+        }
+        if self.in_progress.contains(&descriptor.name)
+            || member_type_identifiers.len() != descriptor.members.len()
+        {
+            return None;
+        }
+
+        self.in_progress.push(descriptor.name);
+        let member_type_ids: Option<Vec<_>> = member_type_identifiers
+            .iter()
+            .map(|type_identifier| type_identifier(self))
+            .collect();
+        self.in_progress.pop();
+
+        let struct_flags = match descriptor.extensibility {
+            ExtensibilityKind::Final => IS_FINAL,
+            ExtensibilityKind::Appendable => IS_APPENDABLE,
+            ExtensibilityKind::Mutable => IS_MUTABLE,
+        };
+        let common_members =
+            descriptor
+                .members
+                .iter()
+                .zip(member_type_ids?)
+                .map(|(member, member_type_id)| {
+                    let mut member_flags = TRY_CONSTRUCT1;
+                    if member.optional {
+                        member_flags |= IS_OPTIONAL;
+                    }
+                    if member.must_understand {
+                        member_flags |= IS_MUST_UNDERSTAND;
+                    }
+                    if member.key {
+                        member_flags |= IS_KEY;
+                    }
+                    (
+                        member,
+                        CommonStructMember {
+                            member_id: member.id,
+                            member_flags,
+                            member_type_id,
+                        },
+                    )
+                });
+        let type_object = match self.equivalence_kind {
+            EquivalenceKind::Minimal => {
+                TypeObject::Minimal(MinimalTypeObject::Struct(MinimalStructType {
+                    struct_flags,
+                    header: MinimalStructHeader {
+                        base_type: TypeIdentifier::NoType,
+                    },
+                    member_seq: common_members
+                        .map(|(member, common)| MinimalStructMember {
+                            common,
+                            detail: MinimalMemberDetail {
+                                name_hash: name_hash(member.name),
+                            },
+                        })
+                        .collect(),
+                }))
+            }
+            EquivalenceKind::Complete => {
+                TypeObject::Complete(CompleteTypeObject::Struct(CompleteStructType {
+                    struct_flags,
+                    header: CompleteStructHeader {
+                        base_type: TypeIdentifier::NoType,
+                        detail: CompleteTypeDetail {
+                            type_name: descriptor.name.to_string(),
+                        },
+                    },
+                    member_seq: common_members
+                        .map(|(member, common)| CompleteStructMember {
+                            common,
+                            detail: CompleteMemberDetail {
+                                name: member.name.to_string(),
+                            },
+                        })
+                        .collect(),
+                }))
+            }
+        };
+
+        let type_identifier = type_object.type_identifier().ok()?;
+        self.type_objects
+            .push((descriptor.name, type_identifier.clone(), type_object));
+        Some(type_identifier)
+    }
+}
+
+/// Returns the type objects of the given kind describing the type `T` followed by those of the types it
+/// depends on. The list is empty if the type can not be described, which is the case of the types not deriving
+/// [`DdsType`](super::type_support::DdsType) with an extensibility attribute and of the recursive types.
+pub fn type_objects<T>(equivalence_kind: EquivalenceKind) -> Vec<TypeObject>
+where
+    T: DdsTypeDescriptor + ?Sized,
+{
+    let mut builder = TypeObjectBuilder::new(equivalence_kind);
+    match T::type_identifier(&mut builder) {
+        Some(TypeIdentifier::Minimal(_)) | Some(TypeIdentifier::Complete(_)) => builder
+            .type_objects
+            .into_iter()
+            .rev()
+            .map(|(_, _, type_object)| type_object)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the [`TypeInformation`] of the type `T` if it can be described with type objects.
+pub fn type_information<T>() -> Option<TypeInformation>
+where
+    T: DdsTypeDescriptor + ?Sized,
+{
+    Some(TypeInformation {
+        minimal: type_identifier_with_dependencies(&type_objects::<T>(EquivalenceKind::Minimal))?,
+        complete: type_identifier_with_dependencies(&type_objects::<T>(EquivalenceKind::Complete))?,
+    })
+}
+
+fn type_identifier_with_dependencies(
+    type_objects: &[TypeObject],
+) -> Option<TypeIdentifierWithDependencies> {
+    let mut type_ids = type_objects
+        .iter()
+        .map(|type_object| {
+            Ok(TypeIdentifierWithSize {
+                type_id: type_object.type_identifier()?,
+                typeobject_serialized_size: serialize(type_object)?.len() as u32,
+            })
+        })
+        .collect::<DdsResult<Vec<_>>>()
+        .ok()?;
+    if type_ids.is_empty() {
+        return None;
+    }
+    let typeid_with_size = type_ids.remove(0);
+    Some(TypeIdentifierWithDependencies {
+        typeid_with_size,
+        dependent_typeid_count: type_ids.len() as i32,
+        dependent_typeids: type_ids,
+    })
+}
+
+// Largest parameter value which can be given in a parameter list, whose length is an i16 multiple of 4
+const MAX_PARAMETER_VALUE_SIZE: usize = 0x7FFC;
+
+/// Value of the discovery parameters containing XTypes data, which is serialized in XCDR2 with the endianness
+/// of the parameter list. It is always serialized in little endian since the discovery data is sent with the
+/// `PL_CDR_LE` representation. Data which can not be decoded is ignored so that it does not prevent the
+/// discovery of the remote entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct XTypesParameterValue<T>(Option<T>);
+
+impl<T> XTypesParameterValue<T>
+where
+    T: ser::Serialize,
+{
+    pub fn new(value: Option<T>) -> Self {
+        Self(value.filter(
+            |value| matches!(serialize(value), Ok(data) if data.len() <= MAX_PARAMETER_VALUE_SIZE),
+        ))
+    }
+}
+
+impl<T> XTypesParameterValue<T> {
+    pub fn value(&self) -> Option<&T> {
+        self.0.as_ref()
+    }
+}
+
+impl<T> Default for XTypesParameterValue<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T> ser::Serialize for XTypesParameterValue<T>
+where
+    T: ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let data = match &self.0 {
+            Some(value) => {
+                serialize(value).map_err(|err| ser::Error::custom(format!("{:?}", err)))?
+            }
+            None => Vec::new(),
+        };
+        let mut s = serializer.serialize_tuple(data.len())?;
+        for byte in &data {
+            s.serialize_element(byte)?;
+        }
+        s.end()
+    }
+}
+
+impl<'de, T> de::Deserialize<'de> for XTypesParameterValue<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct XTypesParameterValueVisitor<T>(PhantomData<T>);
+
+        impl<'de, T> de::Visitor<'de> for XTypesParameterValueVisitor<T>
+        where
+            T: DeserializeOwned,
+        {
+            type Value = XTypesParameterValue<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("XCDR2 serialized XTypes data")
+            }
+
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                Ok(XTypesParameterValue(T::deserialize(deserializer).ok()))
+            }
+        }
+
+        deserializer
+            .deserialize_newtype_struct(XTYPES_DATA, XTypesParameterValueVisitor(PhantomData))
+    }
+}
+
+/// Decides whether the type of a [`DataWriter`](crate::publication::data_writer::DataWriter) is consistent
+/// with the type of a [`DataReader`](crate::subscription::data_reader::DataReader) with the same topic and type
+/// names according to the [`TypeConsistencyEnforcementQosPolicy`] of the reader. The types are compared using
+/// their minimal type objects. Without the type information of both types only their names are compared.
+pub(crate) fn is_type_consistent(
+    reader_type_information: Option<&TypeInformation>,
+    reader_type_objects: &[TypeObject],
+    writer_type_information: Option<&TypeInformation>,
+    writer_type_objects: &[TypeObject],
+    type_consistency: &TypeConsistencyEnforcementQosPolicy,
+) -> bool {
+    let (reader_type_information, writer_type_information) =
+        match (reader_type_information, writer_type_information) {
+            (Some(reader), Some(writer)) => (reader, writer),
+            _ => return !type_consistency.force_type_validation,
+        };
+    let reader_type = &reader_type_information.minimal.typeid_with_size.type_id;
+    let writer_type = &writer_type_information.minimal.typeid_with_size.type_id;
+    if reader_type == writer_type {
+        return true;
+    }
+
+    let reader_types = minimal_struct_types(reader_type_objects);
+    let writer_types = minimal_struct_types(writer_type_objects);
+    match type_consistency.kind {
+        TypeConsistencyKind::AllowTypeCoercion => Assignability {
+            type_consistency,
+            prevent_type_widening: type_consistency.prevent_type_widening,
+            reader_types: &reader_types,
+            writer_types: &writer_types,
+            in_progress: Vec::new(),
+        }
+        .is_assignable(reader_type, writer_type),
+        // The types are the same, apart from the differences ignored by the policy, if they
+        // are assignable in both directions without widening
+        TypeConsistencyKind::DisallowTypeCoercion => {
+            Assignability {
+                type_consistency,
+                prevent_type_widening: true,
+                reader_types: &reader_types,
+                writer_types: &writer_types,
+                in_progress: Vec::new(),
+            }
+            .is_assignable(reader_type, writer_type)
+                && Assignability {
+                    type_consistency,
+                    prevent_type_widening: true,
+                    reader_types: &writer_types,
+                    writer_types: &reader_types,
+                    in_progress: Vec::new(),
+                }
+                .is_assignable(writer_type, reader_type)
+        }
+    }
+}
+
+fn minimal_struct_types(
+    type_objects: &[TypeObject],
+) -> HashMap<EquivalenceHash, &MinimalStructType> {
+    type_objects
+        .iter()
+        .filter_map(
+            |type_object| match (type_object, type_object.type_identifier()) {
+                (
+                    TypeObject::Minimal(MinimalTypeObject::Struct(struct_type)),
+                    Ok(TypeIdentifier::Minimal(hash)),
+                ) => Some((hash, struct_type)),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+// Assignability of the types of the DDS-XTypes standard restricted to the samples which can be
+// deserialized, i.e. the members missing in the samples of the writer must be optional in the reader type
+struct Assignability<'a> {
+    type_consistency: &'a TypeConsistencyEnforcementQosPolicy,
+    prevent_type_widening: bool,
+    reader_types: &'a HashMap<EquivalenceHash, &'a MinimalStructType>,
+    writer_types: &'a HashMap<EquivalenceHash, &'a MinimalStructType>,
+    // Pairs of structures being compared which are considered assignable when they are found again
+    in_progress: Vec<(EquivalenceHash, EquivalenceHash)>,
+}
+
+impl Assignability<'_> {
+    fn is_assignable(
+        &mut self,
+        reader_type: &TypeIdentifier,
+        writer_type: &TypeIdentifier,
+    ) -> bool {
+        match (reader_type, writer_type) {
+            (TypeIdentifier::Minimal(reader_hash), TypeIdentifier::Minimal(writer_hash)) => {
+                if reader_hash == writer_hash
+                    || self.in_progress.contains(&(*reader_hash, *writer_hash))
+                {
+                    return true;
+                }
+                let reader_types = self.reader_types;
+                let writer_types = self.writer_types;
+                match (reader_types.get(reader_hash), writer_types.get(writer_hash)) {
+                    (Some(reader_struct), Some(writer_struct)) => {
+                        self.in_progress.push((*reader_hash, *writer_hash));
+                        let is_assignable = self.is_struct_assignable(reader_struct, writer_struct);
+                        self.in_progress.pop();
+                        is_assignable
+                    }
+                    _ => false,
+                }
+            }
+            (
+                TypeIdentifier::String8 {
+                    bound: reader_bound,
+                },
+                TypeIdentifier::String8 {
+                    bound: writer_bound,
+                },
+            ) => {
+                self.type_consistency.ignore_string_bounds
+                    || is_bound_assignable(*reader_bound, *writer_bound)
+            }
+            (
+                TypeIdentifier::Sequence {
+                    bound: reader_bound,
+                    element: reader_element,
+                },
+                TypeIdentifier::Sequence {
+                    bound: writer_bound,
+                    element: writer_element,
+                },
+            ) => {
+                (self.type_consistency.ignore_sequence_bounds
+                    || is_bound_assignable(*reader_bound, *writer_bound))
+                    && self.is_assignable(reader_element, writer_element)
+            }
+            (
+                TypeIdentifier::Array {
+                    dimensions: reader_dimensions,
+                    element: reader_element,
+                },
+                TypeIdentifier::Array {
+                    dimensions: writer_dimensions,
+                    element: writer_element,
+                },
+            ) => {
+                reader_dimensions == writer_dimensions
+                    && self.is_assignable(reader_element, writer_element)
+            }
+            _ => reader_type == writer_type,
+        }
+    }
+
+    fn is_member_assignable(
+        &mut self,
+        reader_member: &MinimalStructMember,
+        writer_member: &MinimalStructMember,
+    ) -> bool {
+        reader_member.common.member_id == writer_member.common.member_id
+            && (self.type_consistency.ignore_member_names
+                || reader_member.detail.name_hash == writer_member.detail.name_hash)
+            && reader_member.common.member_flags & IS_KEY
+                == writer_member.common.member_flags & IS_KEY
+            && self.is_assignable(
+                &reader_member.common.member_type_id,
+                &writer_member.common.member_type_id,
+            )
+    }
+
+    // Members of the reader type which can be missing in the samples of the writer
+    fn is_missing_member_allowed(reader_member: &MinimalStructMember) -> bool {
+        reader_member.common.member_flags & IS_OPTIONAL == IS_OPTIONAL
+    }
+
+    // Members of the writer type which can be ignored by the reader
+    fn is_unknown_member_allowed(&self, writer_member: &MinimalStructMember) -> bool {
+        !self.prevent_type_widening
+            && writer_member.common.member_flags & (IS_KEY | IS_MUST_UNDERSTAND) == 0
+    }
+
+    fn is_struct_assignable(
+        &mut self,
+        reader_struct: &MinimalStructType,
+        writer_struct: &MinimalStructType,
+    ) -> bool {
+        let extensibility = reader_struct.struct_flags & EXTENSIBILITY_FLAGS;
+        if extensibility != writer_struct.struct_flags & EXTENSIBILITY_FLAGS
+            || !self.is_assignable(
+                &reader_struct.header.base_type,
+                &writer_struct.header.base_type,
+            )
+        {
+            return false;
+        }
+
+        let reader_members = &reader_struct.member_seq;
+        let writer_members = &writer_struct.member_seq;
+        match extensibility {
+            // The members of the mutable types are matched by their id
+            IS_MUTABLE => {
+                let mut has_common_member = false;
+                for reader_member in reader_members {
+                    match writer_members
+                        .iter()
+                        .find(|m| m.common.member_id == reader_member.common.member_id)
+                    {
+                        Some(writer_member) => {
+                            if !self.is_member_assignable(reader_member, writer_member) {
+                                return false;
+                            }
+                            has_common_member = true;
+                        }
+                        None => {
+                            if !Self::is_missing_member_allowed(reader_member) {
+                                return false;
+                            }
+                        }
+                    }
+                }
+                has_common_member
+                    && writer_members
+                        .iter()
+                        .filter(|writer_member| {
+                            !reader_members
+                                .iter()
+                                .any(|m| m.common.member_id == writer_member.common.member_id)
+                        })
+                        .all(|writer_member| self.is_unknown_member_allowed(writer_member))
+            }
+            // The members of the final and appendable types are matched by their position and only
+            // the appendable types can have additional members at the end
+            _ => {
+                if extensibility == IS_FINAL && reader_members.len() != writer_members.len() {
+                    return false;
+                }
+                reader_members.iter().zip(writer_members.iter()).all(
+                    |(reader_member, writer_member)| {
+                        self.is_member_assignable(reader_member, writer_member)
+                    },
+                ) && reader_members
+                    .iter()
+                    .skip(writer_members.len())
+                    .all(Self::is_missing_member_allowed)
+                    && writer_members
+                        .iter()
+                        .skip(reader_members.len())
+                        .all(|writer_member| self.is_unknown_member_allowed(writer_member))
+            }
+        }
+    }
+}
+
+fn is_bound_assignable(reader_bound: u32, writer_bound: u32) -> bool {
+    reader_bound == 0 || (writer_bound != 0 && writer_bound <= reader_bound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementation::{
+        data_representation_builtin_endpoints::parameter_id_values::{
+            PID_SENTINEL, PID_TYPE_INFORMATION,
+        },
+        parameter_list_serde::{
+            parameter::ParameterWithDefault,
+            serde_parameter_list_deserializer::ParameterListDeserializer,
+            serde_parameter_list_serializer::ParameterListSerializer,
+        },
+    };
+    use byteorder::BigEndian;
+    use serde::{Deserialize, Serialize};
+
+    struct ShapeV1;
+
+    impl DdsTypeDescriptor for ShapeV1 {
+        fn struct_descriptor() -> Option<&'static StructDescriptor> {
+            static DESCRIPTOR: StructDescriptor = StructDescriptor {
+                name: "Shape",
+                extensibility: ExtensibilityKind::Mutable,
+                members: &[
+                    MemberDescriptor {
+                        name: "id",
+                        id: 0,
+                        optional: false,
+                        must_understand: true,
+                        key: true,
+                        delimited_collections: Some(0),
+                    },
+                    MemberDescriptor {
+                        name: "x",
+                        id: 10,
+                        optional: false,
+                        must_understand: false,
+                        key: false,
+                        delimited_collections: Some(0),
+                    },
+                ],
+            };
+            Some(&DESCRIPTOR)
+        }
+
+        fn type_identifier(builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+            builder.struct_type_identifier(
+                Self::struct_descriptor()?,
+                &[u8::type_identifier, i32::type_identifier],
+            )
+        }
+    }
+
+    struct ShapeV2;
+
+    impl DdsTypeDescriptor for ShapeV2 {
+        fn struct_descriptor() -> Option<&'static StructDescriptor> {
+            static DESCRIPTOR: StructDescriptor = StructDescriptor {
+                name: "Shape",
+                extensibility: ExtensibilityKind::Mutable,
+                members: &[
+                    MemberDescriptor {
+                        name: "id",
+                        id: 0,
+                        optional: false,
+                        must_understand: true,
+                        key: true,
+                        delimited_collections: Some(0),
+                    },
+                    MemberDescriptor {
+                        name: "color",
+                        id: 20,
+                        optional: true,
+                        must_understand: false,
+                        key: false,
+                        delimited_collections: Some(0),
+                    },
+                    MemberDescriptor {
+                        name: "x",
+                        id: 10,
+                        optional: false,
+                        must_understand: false,
+                        key: false,
+                        delimited_collections: Some(0),
+                    },
+                ],
+            };
+            Some(&DESCRIPTOR)
+        }
+
+        fn type_identifier(builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+            builder.struct_type_identifier(
+                Self::struct_descriptor()?,
+                &[
+                    u8::type_identifier,
+                    Option::<String>::type_identifier,
+                    i32::type_identifier,
+                ],
+            )
+        }
+    }
+
+    struct ShapeWithTextCoordinate;
+
+    impl DdsTypeDescriptor for ShapeWithTextCoordinate {
+        fn struct_descriptor() -> Option<&'static StructDescriptor> {
+            ShapeV1::struct_descriptor()
+        }
+
+        fn type_identifier(builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+            builder.struct_type_identifier(
+                Self::struct_descriptor()?,
+                &[u8::type_identifier, String::type_identifier],
+            )
+        }
+    }
+
+    fn is_consistent<R, W>(type_consistency: &TypeConsistencyEnforcementQosPolicy) -> bool
+    where
+        R: DdsTypeDescriptor,
+        W: DdsTypeDescriptor,
+    {
+        is_type_consistent(
+            type_information::<R>().as_ref(),
+            &type_objects::<R>(EquivalenceKind::Minimal),
+            type_information::<W>().as_ref(),
+            &type_objects::<W>(EquivalenceKind::Minimal),
+            type_consistency,
+        )
+    }
+
+    #[test]
+    fn serialize_type_identifiers() {
+        let sequence = TypeIdentifier::Sequence {
+            bound: 0,
+            element: Box::new(TypeIdentifier::Primitive(PrimitiveKind::Int32)),
+        };
+        assert_eq!(
+            serialize(&sequence).unwrap(),
+            vec![
+                0x80, 0xf3, 0, 0, // kind, equivalence kind, element flags
+                0, 0x04, // bound, element
+            ]
+        );
+
+        let string = TypeIdentifier::String8 { bound: 300 };
+        assert_eq!(
+            serialize(&string).unwrap(),
+            vec![
+                0x71, 0, 0, 0, // kind, padding
+                44, 1, 0, 0, // bound
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_type_identifiers() {
+        let array = TypeIdentifier::Array {
+            dimensions: vec![2, 3],
+            element: Box::new(TypeIdentifier::Minimal([7; 14])),
+        };
+        let data = serialize(&array).unwrap();
+        assert_eq!(
+            TypeIdentifier::deserialize(&mut xtypes_deserializer::<LittleEndian>(&data)).unwrap(),
+            array
+        );
+    }
+
+    #[test]
+    fn type_objects_are_built_for_described_types() {
+        let shape_type_objects = type_objects::<ShapeV2>(EquivalenceKind::Minimal);
+        assert_eq!(shape_type_objects.len(), 1);
+        let struct_type = match &shape_type_objects[0] {
+            TypeObject::Minimal(MinimalTypeObject::Struct(struct_type)) => struct_type,
+            _ => panic!("Expected minimal struct type object"),
+        };
+        assert_eq!(struct_type.struct_flags, IS_MUTABLE);
+        assert_eq!(
+            struct_type.member_seq[0].common.member_flags,
+            TRY_CONSTRUCT1 | IS_MUST_UNDERSTAND | IS_KEY
+        );
+        assert_eq!(
+            struct_type.member_seq[1].common.member_type_id,
+            TypeIdentifier::String8 { bound: 0 }
+        );
+        assert_eq!(struct_type.member_seq[2].detail.name_hash, name_hash("x"));
+
+        assert!(type_objects::<i32>(EquivalenceKind::Minimal).is_empty());
+        assert_eq!(type_information::<i32>(), None);
+    }
+
+    #[test]
+    fn type_information_is_deterministic() {
+        let shape_type_information = type_information::<ShapeV1>().unwrap();
+        assert_eq!(
+            type_information::<ShapeV1>().unwrap(),
+            shape_type_information
+        );
+        assert_ne!(
+            type_information::<ShapeV2>().unwrap().minimal,
+            shape_type_information.minimal
+        );
+        assert!(matches!(
+            shape_type_information.complete.typeid_with_size.type_id,
+            TypeIdentifier::Complete(_)
+        ));
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct TypeInformationParameterList {
+        type_information:
+            ParameterWithDefault<PID_TYPE_INFORMATION, XTypesParameterValue<TypeInformation>>,
+    }
+
+    fn type_information_parameter_list<E: ByteOrder>(value: &[u8]) -> Vec<u8> {
+        let padding = (4 - value.len() % 4) % 4;
+        let mut data = vec![0; 4];
+        E::write_i16(&mut data[0..2], PID_TYPE_INFORMATION);
+        E::write_i16(&mut data[2..4], (value.len() + padding) as i16);
+        data.extend_from_slice(value);
+        data.resize(data.len() + padding, 0);
+        let mut sentinel = vec![0; 4];
+        E::write_i16(&mut sentinel[0..2], PID_SENTINEL);
+        data.extend(sentinel);
+        data
+    }
+
+    #[test]
+    fn type_information_parameter_value_round_trip() {
+        let value = TypeInformationParameterList {
+            type_information: XTypesParameterValue::new(type_information::<ShapeV2>()).into(),
+        };
+
+        let mut data = Vec::new();
+        value
+            .serialize(&mut ParameterListSerializer::<_, LittleEndian>::new(
+                &mut data,
+            ))
+            .unwrap();
+        let result = TypeInformationParameterList::deserialize(&mut ParameterListDeserializer::<
+            LittleEndian,
+        >::new(&data))
+        .unwrap();
+        assert_eq!(result, value);
+        assert!(result.type_information.as_ref().value().is_some());
+    }
+
+    #[test]
+    fn type_information_parameter_value_has_the_endianness_of_the_parameter_list() {
+        let type_information = type_information::<ShapeV2>().unwrap();
+        let mut value = Vec::new();
+        type_information
+            .serialize(&mut Xcdr2Serializer::<BigEndian>::new(
+                &mut value,
+                ExtensibilityKind::Final,
+                STRUCT_DESCRIPTORS.iter().collect(),
+            ))
+            .unwrap();
+
+        let data = type_information_parameter_list::<BigEndian>(&value);
+        let result = TypeInformationParameterList::deserialize(&mut ParameterListDeserializer::<
+            BigEndian,
+        >::new(&data))
+        .unwrap();
+        assert_eq!(
+            result.type_information.as_ref().value(),
+            Some(&type_information)
+        );
+    }
+
+    #[test]
+    fn invalid_parameter_value_is_ignored() {
+        let data =
+            type_information_parameter_list::<LittleEndian>(&[4, 0, 0, 0, 0xff, 0xff, 0xff, 0xff]);
+        let result = TypeInformationParameterList::deserialize(&mut ParameterListDeserializer::<
+            LittleEndian,
+        >::new(&data))
+        .unwrap();
+        assert_eq!(result.type_information.as_ref().value(), None);
+    }
+
+    #[test]
+    fn mutable_types_with_optional_members_are_assignable() {
+        let type_consistency = TypeConsistencyEnforcementQosPolicy::default();
+        assert!(is_consistent::<ShapeV1, ShapeV2>(&type_consistency));
+        assert!(is_consistent::<ShapeV2, ShapeV1>(&type_consistency));
+    }
+
+    #[test]
+    fn type_widening_can_be_prevented() {
+        let type_consistency = TypeConsistencyEnforcementQosPolicy {
+            prevent_type_widening: true,
+            ..Default::default()
+        };
+        assert!(!is_consistent::<ShapeV1, ShapeV2>(&type_consistency));
+        assert!(is_consistent::<ShapeV2, ShapeV1>(&type_consistency));
+    }
+
+    #[test]
+    fn different_types_are_inconsistent_without_type_coercion() {
+        let type_consistency = TypeConsistencyEnforcementQosPolicy {
+            kind: TypeConsistencyKind::DisallowTypeCoercion,
+            ..Default::default()
+        };
+        assert!(is_consistent::<ShapeV1, ShapeV1>(&type_consistency));
+        assert!(!is_consistent::<ShapeV1, ShapeV2>(&type_consistency));
+        assert!(!is_consistent::<ShapeV2, ShapeV1>(&type_consistency));
+    }
+
+    #[test]
+    fn members_with_different_types_are_not_assignable() {
+        let type_consistency = TypeConsistencyEnforcementQosPolicy::default();
+        assert!(!is_consistent::<ShapeV1, ShapeWithTextCoordinate>(
+            &type_consistency
+        ));
+    }
+
+    #[test]
+    fn types_without_type_information_are_consistent_unless_validation_is_forced() {
+        assert!(is_consistent::<i32, ShapeV1>(
+            &TypeConsistencyEnforcementQosPolicy::default()
+        ));
+        assert!(!is_consistent::<i32, ShapeV1>(
+            &TypeConsistencyEnforcementQosPolicy {
+                force_type_validation: true,
+                ..Default::default()
+            }
+        ));
+    }
+}
@@ -1,23 +1,110 @@
-use std::io::{Read, Write};
+use std::{
+    convert::TryFrom,
+    io::{Read, Write},
+};
 
 use crate::{
-    implementation::parameter_list_serde::{
-        serde_parameter_list_deserializer::ParameterListDeserializer,
-        serde_parameter_list_serializer::ParameterListSerializer,
+    domain::domain_participant::DomainParticipant,
+    implementation::{
+        parameter_list_serde::{
+            serde_parameter_list_deserializer::ParameterListDeserializer,
+            serde_parameter_list_serializer::ParameterListSerializer,
+        },
+        xcdr2_serde::{serde_xcdr2_deserializer, serde_xcdr2_serializer::Xcdr2Serializer},
+    },
+    infrastructure::{
+        error::{
+            DdsError::{self, PreconditionNotMet},
+            DdsResult,
+        },
+        instance::InstanceHandle,
+        qos_policy::{
+            DataRepresentationId, DataRepresentationQosPolicy, XCDR2_DATA_REPRESENTATION,
+            XCDR_DATA_REPRESENTATION,
+        },
     },
-    infrastructure::error::{DdsError::PreconditionNotMet, DdsResult},
 };
 
-pub use dust_dds_derive::{DdsGetKey, DdsHasKey, DdsRepresentation, DdsSetKeyFields, DdsType};
+use super::{
+    dynamic_type::DynamicType,
+    type_object::{PrimitiveKind, TypeIdentifier, TypeInformation, TypeObject, TypeObjectBuilder},
+};
+
+pub use dust_dds_derive::{
+    DdsGetKey, DdsHasKey, DdsRepresentation, DdsSetKeyFields, DdsType, TypeSupport,
+};
 
+/// Representation used to serialize the samples of a type.
+///
+/// The XCDR2 representations follow the encoding of the DDS-XTypes standard: `Cdr2` is the encoding of final types,
+/// `DCdr2` of appendable types, whose members are preceded by a DHEADER with their size, and `PlCdr2` of mutable types,
+/// whose members are additionally preceded by an EMHEADER with the index of the member in the type as member id.
+/// The collections of non-primitive elements are preceded by a DHEADER, which is determined from the member types of
+/// the structures deriving [`DdsType`] with an extensibility attribute. The collections whose element type is not
+/// known this way, such as the members of structures without such an attribute, only get the DHEADER when their
+/// first element is not primitive, so that an empty collection is then serialized without it.
+///
+/// The [`DataRepresentationQosPolicy`](crate::infrastructure::qos_policy::DataRepresentationQosPolicy) of a
+/// [`DataWriter`](crate::publication::data_writer::DataWriter) can select the XCDR representation with the same endianness
+/// and extensibility instead, i.e. `Cdr` for `Cdr2` and `DCdr2` and `PlCdr` for `PlCdr2`, and vice-versa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Representation {
     CdrLe,
     CdrBe,
     PlCdrBe,
     PlCdrLe,
+    Cdr2Be,
+    Cdr2Le,
+    DCdr2Be,
+    DCdr2Le,
+    PlCdr2Be,
+    PlCdr2Le,
     Custom,
 }
 
+impl Representation {
+    pub(crate) fn data_representation_id(&self) -> DataRepresentationId {
+        match self {
+            Representation::Cdr2Be
+            | Representation::Cdr2Le
+            | Representation::DCdr2Be
+            | Representation::DCdr2Le
+            | Representation::PlCdr2Be
+            | Representation::PlCdr2Le => XCDR2_DATA_REPRESENTATION,
+            _ => XCDR_DATA_REPRESENTATION,
+        }
+    }
+
+    // Representation equivalent to this one in the first data representation of the policy. A custom
+    // representation is used for any data representation.
+    pub(crate) fn with_data_representation(
+        &self,
+        data_representation: &DataRepresentationQosPolicy,
+    ) -> DdsResult<Representation> {
+        let data_representation_id = match data_representation.value.first() {
+            Some(&data_representation_id) => data_representation_id,
+            None => return Ok(*self),
+        };
+        match (data_representation_id, self) {
+            (_, Representation::Custom) => Some(Representation::Custom),
+            (XCDR_DATA_REPRESENTATION, Representation::Cdr2Be | Representation::DCdr2Be) => {
+                Some(Representation::CdrBe)
+            }
+            (XCDR_DATA_REPRESENTATION, Representation::Cdr2Le | Representation::DCdr2Le) => {
+                Some(Representation::CdrLe)
+            }
+            (XCDR_DATA_REPRESENTATION, Representation::PlCdr2Be | Representation::PlCdr2Le) => None,
+            (XCDR2_DATA_REPRESENTATION, Representation::CdrBe) => Some(Representation::Cdr2Be),
+            (XCDR2_DATA_REPRESENTATION, Representation::CdrLe) => Some(Representation::Cdr2Le),
+            (XCDR2_DATA_REPRESENTATION, Representation::PlCdrBe) => Some(Representation::PlCdr2Be),
+            (XCDR2_DATA_REPRESENTATION, Representation::PlCdrLe) => Some(Representation::PlCdr2Le),
+            (id, r) if id == r.data_representation_id() => Some(*r),
+            _ => None,
+        }
+        .ok_or(DdsError::InconsistentPolicy)
+    }
+}
+
 type RepresentationIdentifier = [u8; 2];
 type RepresentationOptions = [u8; 2];
 
@@ -25,6 +112,12 @@ const CDR_BE: RepresentationIdentifier = [0x00, 0x00];
 const CDR_LE: RepresentationIdentifier = [0x00, 0x01];
 const PL_CDR_BE: RepresentationIdentifier = [0x00, 0x02];
 const PL_CDR_LE: RepresentationIdentifier = [0x00, 0x03];
+const CDR2_BE: RepresentationIdentifier = [0x00, 0x06];
+const CDR2_LE: RepresentationIdentifier = [0x00, 0x07];
+const D_CDR2_BE: RepresentationIdentifier = [0x00, 0x08];
+const D_CDR2_LE: RepresentationIdentifier = [0x00, 0x09];
+const PL_CDR2_BE: RepresentationIdentifier = [0x00, 0x0a];
+const PL_CDR2_LE: RepresentationIdentifier = [0x00, 0x0b];
 const REPRESENTATION_OPTIONS: RepresentationOptions = [0x00, 0x00];
 
 #[derive(Debug, PartialEq, Clone, Eq, serde::Serialize, serde::Deserialize)]
@@ -50,22 +143,327 @@ impl AsRef<[u8]> for DdsSerializedKey {
 
 pub trait DdsType: DdsRepresentation + DdsHasKey + DdsGetKey + DdsSetKeyFields {}
 
+/// The [`TypeSupport`] trait associates a name to a type so that it can be used by the [`DomainParticipant`].
+/// It is derived together with [`DdsType`] taking the name of the type as the type name.
+pub trait TypeSupport {
+    /// This operation returns the default name for the data-type represented by the [`TypeSupport`].
+    fn get_type_name() -> &'static str;
+
+    /// This operation allows an application to communicate to the Service the existence of a data type under the
+    /// given `type_name`. The same type can be registered multiple times with different names on the same
+    /// [`DomainParticipant`] but registering a `type_name` which is already in use by a different type results
+    /// in [`PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    /// Once registered, creating a [`DataWriter`](crate::publication::data_writer::DataWriter) or
+    /// [`DataReader`](crate::subscription::data_reader::DataReader) of a different type on a
+    /// [`Topic`](crate::topic_definition::topic::Topic) with the `type_name` fails.
+    fn register_type(participant: &DomainParticipant, type_name: &str) -> DdsResult<()>
+    where
+        Self: Sized + 'static,
+    {
+        participant.register_type::<Self>(type_name)
+    }
+}
+
 pub trait DdsHasKey {
     const HAS_KEY: bool;
+
+    // Whether the samples of the type have a key, for the types whose key members are only
+    // known at runtime from the DynamicType registered with their type name
+    #[doc(hidden)]
+    fn has_key(_dynamic_type: Option<&DynamicType>) -> bool {
+        Self::HAS_KEY
+    }
 }
 
 pub trait DdsRepresentation {
     const REPRESENTATION: Representation;
 
+    #[doc(hidden)]
+    fn struct_descriptors() -> Vec<&'static StructDescriptor> {
+        Vec::new()
+    }
+
+    #[doc(hidden)]
+    fn type_information() -> Option<TypeInformation> {
+        None
+    }
+
+    #[doc(hidden)]
+    fn minimal_type_objects() -> Vec<TypeObject> {
+        Vec::new()
+    }
+
+    // Upper bound of the serialized size of the samples announced by the writers of the type
+    #[doc(hidden)]
+    fn max_serialized_size() -> Option<usize> {
+        None
+    }
+
+    #[doc(hidden)]
+    fn deserialize_sample<'de>(
+        data: &'de [u8],
+        _dynamic_type: Option<&DynamicType>,
+    ) -> DdsResult<Self>
+    where
+        Self: DdsDeserialize<'de>,
+    {
+        Self::deserialize_data(data)
+    }
+
+    // JSON value of the data of the samples rendered by Sample::to_json_value
+    #[doc(hidden)]
+    fn sample_to_json_value(&self) -> DdsResult<serde_json::Value>
+    where
+        Self: serde::Serialize,
+    {
+        super::json::to_json_value(self)
+    }
+
+    /// Serializes the sample into `writer` when the type has [`Representation::Custom`]. The operation fails with
+    /// [`DdsError::Unsupported`] unless the type implements it.
     fn to_bytes(&self, _writer: impl std::io::Write) -> DdsResult<()> {
-        unimplemented!("Custom type conversion to bytes is not implemented")
+        Err(DdsError::Unsupported)
     }
 
+    /// Deserializes a sample from `bytes` when the type has [`Representation::Custom`]. The operation fails with
+    /// [`DdsError::Unsupported`] unless the type implements it.
     fn from_bytes(_bytes: &[u8]) -> DdsResult<Self>
     where
         Self: Sized,
     {
-        unimplemented!("Custom type conversion from bytes is not implemented")
+        Err(DdsError::Unsupported)
+    }
+}
+
+/// The [`DdsSerialize`] trait serializes the samples written by a [`DataWriter`](crate::publication::data_writer::DataWriter).
+/// It is implemented for all the types implementing [`serde::Serialize`], which are serialized with their
+/// [`DdsRepresentation::REPRESENTATION`]. A type with [`Representation::Custom`] which does not implement serde
+/// can implement it without any method, in which case its samples are serialized with [`DdsRepresentation::to_bytes`]:
+///
+/// ```
+/// # use dust_dds::{
+/// #     infrastructure::error::{DdsError, DdsResult},
+/// #     topic_definition::type_support::{DdsRepresentation, DdsSerialize, Representation},
+/// # };
+/// struct Frame(Vec<u8>);
+///
+/// impl DdsRepresentation for Frame {
+///     const REPRESENTATION: Representation = Representation::Custom;
+///
+///     fn to_bytes(&self, mut writer: impl std::io::Write) -> DdsResult<()> {
+///         writer
+///             .write_all(&self.0)
+///             .map_err(|e| DdsError::Error(e.to_string()))
+///     }
+/// }
+///
+/// impl DdsSerialize for Frame {}
+/// ```
+pub trait DdsSerialize: DdsRepresentation {
+    #[doc(hidden)]
+    fn serialize_data(
+        &self,
+        _representation: &Representation,
+        writer: impl Write,
+    ) -> DdsResult<()> {
+        self.to_bytes(writer)
+    }
+}
+
+impl<T> DdsSerialize for T
+where
+    T: serde::Serialize + DdsRepresentation,
+{
+    fn serialize_data(&self, representation: &Representation, writer: impl Write) -> DdsResult<()> {
+        dds_serialize_to_writer(self, representation, writer)
+    }
+}
+
+/// The [`DdsDeserialize`] trait deserializes the samples read by a [`DataReader`](crate::subscription::data_reader::DataReader).
+/// It is implemented for all the types implementing [`serde::Deserialize`]. A type with [`Representation::Custom`] which does
+/// not implement serde can implement it without any method, in which case its samples are deserialized with
+/// [`DdsRepresentation::from_bytes`]. Since the serialized data is padded with zeros to a multiple of 4 bytes when it
+/// is sent, the custom representation must tolerate these trailing bytes.
+pub trait DdsDeserialize<'de>: DdsRepresentation + Sized {
+    #[doc(hidden)]
+    fn deserialize_data(serialized_data: &'de [u8]) -> DdsResult<Self> {
+        Self::from_bytes(serialized_data)
+    }
+}
+
+impl<'de, T> DdsDeserialize<'de> for T
+where
+    T: serde::Deserialize<'de> + DdsRepresentation,
+{
+    fn deserialize_data(serialized_data: &'de [u8]) -> DdsResult<Self> {
+        dds_deserialize_from_bytes(serialized_data)
+    }
+}
+
+/// Extensibility kind of the aggregated types as defined in the DDS-XTypes standard. It decides whether
+/// the members of a structure are preceded by a DHEADER (appendable) or by a DHEADER and a member header
+/// for each member (mutable).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensibilityKind {
+    Final,
+    Appendable,
+    Mutable,
+}
+
+#[doc(hidden)]
+#[derive(Debug, PartialEq, Eq)]
+pub struct MemberDescriptor {
+    pub name: &'static str,
+    pub id: u32,
+    pub optional: bool,
+    pub must_understand: bool,
+    pub key: bool,
+    // Value of DdsTypeDescriptor::DELIMITED_COLLECTIONS for the type of the member or None if it is not known
+    pub delimited_collections: Option<u32>,
+}
+
+#[doc(hidden)]
+#[derive(Debug, PartialEq, Eq)]
+pub struct StructDescriptor {
+    pub name: &'static str,
+    pub extensibility: ExtensibilityKind,
+    pub members: &'static [MemberDescriptor],
+}
+
+/// The [`DdsTypeDescriptor`] trait collects the XTypes annotations given to the structures with the
+/// `#[dust_dds(...)]` attributes of the [`DdsType`] derive so that they can be used when serializing
+/// the structures and the structures nested in them, and describes the types with the
+/// [`TypeObject`](super::type_object::TypeObject) announced during discovery.
+///
+/// It also gives the maximum size of the serialized values of the type, which the [`DdsType`] derive computes from
+/// the types of the fields. The size of the [`String`] and [`Vec`] fields is unbounded, the
+/// [`BoundedString`](super::bounded::BoundedString) and [`BoundedVec`](super::bounded::BoundedVec) types can be used
+/// instead to bound the size of a type.
+pub trait DdsTypeDescriptor {
+    /// Upper bound of the size in bytes of a value of the type serialized with any of the representations, not
+    /// including the representation header, or [`None`] if the size of the values is unbounded. The derive computes
+    /// it for the structures and unions whose fields are all bounded, and for the C-like enums. The types without the
+    /// extensibility attribute are sized as final types. The writers announce it as the maximum size of their serialized
+    /// data and send all the samples fragmented when it exceeds the fragment size.
+    const MAX_SERIALIZED_SIZE: Option<usize> = None;
+
+    /// Whether the size of the serialized values of the type is bounded.
+    const IS_BOUNDED: bool = Self::MAX_SERIALIZED_SIZE.is_some();
+
+    // Alignment of the largest primitive type of the type used to compute the maximum size of the types
+    // containing this type
+    #[doc(hidden)]
+    const ALIGNMENT: usize = 1;
+
+    // Whether the type is primitive so that the collections of this type are not preceded by a DHEADER in XCDR2
+    #[doc(hidden)]
+    const IS_PRIMITIVE: bool = false;
+
+    // Nesting levels of the collections of the type whose elements are not primitive, starting with the outermost
+    // collection in the least significant bit. These collections are preceded by a DHEADER in XCDR2.
+    #[doc(hidden)]
+    const DELIMITED_COLLECTIONS: u32 = 0;
+
+    #[doc(hidden)]
+    fn collect_struct_descriptors(_descriptors: &mut Vec<&'static StructDescriptor>) {}
+
+    #[doc(hidden)]
+    fn struct_descriptor() -> Option<&'static StructDescriptor> {
+        None
+    }
+
+    #[doc(hidden)]
+    fn type_identifier(_builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+        None
+    }
+}
+
+macro_rules! implement_dds_type_descriptor_for_built_in_type {
+    ($($t:ty => $kind:ident($size:expr)),*) => {
+        $(impl DdsTypeDescriptor for $t {
+            const MAX_SERIALIZED_SIZE: Option<usize> = Some($size);
+            const ALIGNMENT: usize = $size;
+            const IS_PRIMITIVE: bool = true;
+
+            fn type_identifier(_builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+                Some(TypeIdentifier::Primitive(PrimitiveKind::$kind))
+            }
+        })*
+    };
+}
+
+implement_dds_type_descriptor_for_built_in_type!(
+    bool => Boolean(1), char => Char8(1), u8 => UInt8(1), i8 => Int8(1), u16 => UInt16(2),
+    i16 => Int16(2), u32 => UInt32(4), i32 => Int32(4), u64 => UInt64(8), i64 => Int64(8),
+    usize => UInt64(8), isize => Int64(8), f32 => Float32(4), f64 => Float64(8)
+);
+
+impl DdsTypeDescriptor for String {
+    fn type_identifier(_builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+        Some(TypeIdentifier::String8 { bound: 0 })
+    }
+}
+
+impl<T: DdsTypeDescriptor> DdsTypeDescriptor for Vec<T> {
+    const DELIMITED_COLLECTIONS: u32 = delimited_collections::<T>();
+
+    fn collect_struct_descriptors(descriptors: &mut Vec<&'static StructDescriptor>) {
+        T::collect_struct_descriptors(descriptors)
+    }
+
+    fn type_identifier(builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+        Some(TypeIdentifier::Sequence {
+            bound: 0,
+            element: Box::new(T::type_identifier(builder)?),
+        })
+    }
+}
+
+impl<const N: usize, T: DdsTypeDescriptor> DdsTypeDescriptor for [T; N] {
+    const MAX_SERIALIZED_SIZE: Option<usize> =
+        append_max_serialized_sizes(Some(0), T::MAX_SERIALIZED_SIZE, T::ALIGNMENT, N);
+    const ALIGNMENT: usize = T::ALIGNMENT;
+    const DELIMITED_COLLECTIONS: u32 = delimited_collections::<T>();
+
+    fn collect_struct_descriptors(descriptors: &mut Vec<&'static StructDescriptor>) {
+        T::collect_struct_descriptors(descriptors)
+    }
+
+    fn type_identifier(builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+        Some(TypeIdentifier::Array {
+            dimensions: vec![u32::try_from(N).ok()?],
+            element: Box::new(T::type_identifier(builder)?),
+        })
+    }
+}
+
+// The optional values are preceded by a presence flag in the final and appendable types
+impl<T: DdsTypeDescriptor> DdsTypeDescriptor for Option<T> {
+    const MAX_SERIALIZED_SIZE: Option<usize> =
+        append_max_serialized_size(Some(1), T::MAX_SERIALIZED_SIZE, T::ALIGNMENT);
+    const DELIMITED_COLLECTIONS: u32 = T::DELIMITED_COLLECTIONS;
+
+    fn collect_struct_descriptors(descriptors: &mut Vec<&'static StructDescriptor>) {
+        T::collect_struct_descriptors(descriptors)
+    }
+
+    fn type_identifier(builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+        T::type_identifier(builder)
+    }
+}
+
+impl<T: DdsTypeDescriptor> DdsTypeDescriptor for Box<T> {
+    const MAX_SERIALIZED_SIZE: Option<usize> = T::MAX_SERIALIZED_SIZE;
+    const ALIGNMENT: usize = T::ALIGNMENT;
+    const IS_PRIMITIVE: bool = T::IS_PRIMITIVE;
+    const DELIMITED_COLLECTIONS: u32 = T::DELIMITED_COLLECTIONS;
+
+    fn collect_struct_descriptors(descriptors: &mut Vec<&'static StructDescriptor>) {
+        T::collect_struct_descriptors(descriptors)
+    }
+
+    fn type_identifier(builder: &mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+        T::type_identifier(builder)
     }
 }
 
@@ -76,20 +474,192 @@ pub trait DdsGetKey {
     where
         Self: 'a;
 
+    /// Maximum size in bytes of the key serialized with the big-endian CDR representation or [`None`] if the size
+    /// of the key is unbounded. The key hash identifying the instances on the network is the serialized key padded
+    /// with zeros when this size is not larger than 16 bytes and the MD5 hash of the serialized key otherwise.
+    const MAX_KEY_SIZE: Option<usize> = None;
+
+    // Alignment of the largest primitive type of the key used to compute the maximum size of the keys
+    // containing this key
+    #[doc(hidden)]
+    const KEY_ALIGNMENT: usize = 1;
+
+    // Maximum offsets at which the key ends when it starts at each of the offsets 0 to 7, used to compute the
+    // maximum size of the keys containing this key since its padding depends on its offset modulo 8
+    #[doc(hidden)]
+    const MAX_KEY_ENDS: [Option<usize>; 8] = max_key_ends(Self::MAX_KEY_SIZE, Self::KEY_ALIGNMENT);
+
     fn get_key(&self) -> Self::BorrowedKeyHolder<'_>;
+
+    /// Serializes the key of the sample into `writer` with the classic CDR `representation`:
+    /// [`Representation::CdrBe`] to compute the key hash and [`Representation::CdrLe`] for the serialized keys sent
+    /// when disposing or unregistering an instance. By default the result of [`DdsGetKey::get_key`] is serialized with
+    /// serde. A type whose key fields do not implement serde, such as a type with [`Representation::Custom`], can
+    /// serialize them here instead and use `()` as [`DdsGetKey::BorrowedKeyHolder`].
+    fn serialize_key(&self, representation: &Representation, writer: impl Write) -> DdsResult<()> {
+        serialize_cdr(&self.get_key(), representation, writer)
+    }
+
+    // Maximum size of the key of the samples whose type is only known at run time
+    #[doc(hidden)]
+    fn max_key_size(&self) -> Option<usize> {
+        Self::MAX_KEY_SIZE
+    }
+}
+
+/// Returns the maximum size of a serialized value ending at `size` to which a member of maximum size `member_size`
+/// and alignment `member_alignment` is appended.
+#[doc(hidden)]
+pub const fn append_max_serialized_size(
+    size: Option<usize>,
+    member_size: Option<usize>,
+    member_alignment: usize,
+) -> Option<usize> {
+    match (size, member_size) {
+        (Some(size), Some(member_size)) => {
+            let padding = (member_alignment - size % member_alignment) % member_alignment;
+            Some(size + padding + member_size)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the maximum offsets at which a key of maximum size `size` and alignment `alignment` ends when it starts
+/// at each of the offsets 0 to 7.
+#[doc(hidden)]
+pub const fn max_key_ends(size: Option<usize>, alignment: usize) -> [Option<usize>; 8] {
+    let mut ends = [None; 8];
+    let mut offset = 0;
+    while offset < 8 {
+        ends[offset] = append_max_serialized_size(Some(offset), size, alignment);
+        offset += 1;
+    }
+    ends
+}
+
+/// Returns the maximum offset at which a key with maximum end offsets `key_ends` ends when it is appended to a key
+/// ending at `end`.
+#[doc(hidden)]
+pub const fn append_max_key_end(end: Option<usize>, key_ends: [Option<usize>; 8]) -> Option<usize> {
+    match end {
+        Some(end) => match key_ends[end % 8] {
+            Some(key_end) => Some(end - end % 8 + key_end),
+            None => None,
+        },
+        None => None,
+    }
+}
+
+/// Returns the maximum size of a serialized value ending at `size` to which `count` members of maximum size
+/// `member_size` and alignment `member_alignment` are appended.
+#[doc(hidden)]
+pub const fn append_max_serialized_sizes(
+    size: Option<usize>,
+    member_size: Option<usize>,
+    member_alignment: usize,
+    count: usize,
+) -> Option<usize> {
+    match (count, member_size) {
+        (0, _) => size,
+        (count, Some(member_size)) => {
+            // Each member following the first one starts at the size of a member rounded up to its alignment
+            // after the start of the previous one
+            let stride = member_size
+                + (member_alignment - member_size % member_alignment) % member_alignment;
+            match append_max_serialized_size(size, Some(member_size), member_alignment) {
+                Some(size) => Some(size + (count - 1) * stride),
+                None => None,
+            }
+        }
+        (_, None) => None,
+    }
+}
+
+/// Returns the value of [`DdsTypeDescriptor::DELIMITED_COLLECTIONS`] of a collection with elements of type `T`.
+#[doc(hidden)]
+pub const fn delimited_collections<T: DdsTypeDescriptor + ?Sized>() -> u32 {
+    (T::DELIMITED_COLLECTIONS << 1) | !T::IS_PRIMITIVE as u32
+}
+
+/// Returns the larger of the maximum sizes `a` and `b`.
+#[doc(hidden)]
+pub const fn max_serialized_size(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) if a > b => Some(a),
+        (Some(_), Some(b)) => Some(b),
+        _ => None,
+    }
+}
+
+// The fields of the structures without the extensibility attribute are not required to implement
+// DdsTypeDescriptor. The derive takes the type identifier of these fields from this wrapper, which resolves to the
+// DescribedFieldType implementation when the field type implements DdsTypeDescriptor since method lookup tries
+// the receiver by reference first, and to the UndescribedFieldType implementation otherwise. Likewise the
+// inherent constants of the wrapper take precedence over those of UndescribedFieldType, which leave the size of
+// the fields not implementing DdsTypeDescriptor unbounded.
+#[doc(hidden)]
+pub struct FieldTypeDescriptor<T: ?Sized>(pub std::marker::PhantomData<T>);
+
+impl<T: DdsTypeDescriptor + ?Sized> FieldTypeDescriptor<T> {
+    pub const MAX_SERIALIZED_SIZE: Option<usize> = T::MAX_SERIALIZED_SIZE;
+    pub const ALIGNMENT: usize = T::ALIGNMENT;
+}
+
+#[doc(hidden)]
+pub trait DescribedFieldType {
+    fn type_identifier_fn(&self) -> fn(&mut TypeObjectBuilder) -> Option<TypeIdentifier>;
+}
+
+impl<T: DdsTypeDescriptor + ?Sized> DescribedFieldType for &FieldTypeDescriptor<T> {
+    fn type_identifier_fn(&self) -> fn(&mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+        T::type_identifier
+    }
+}
+
+#[doc(hidden)]
+pub trait UndescribedFieldType {
+    const MAX_SERIALIZED_SIZE: Option<usize> = None;
+    const ALIGNMENT: usize = 1;
+
+    fn type_identifier_fn(&self) -> fn(&mut TypeObjectBuilder) -> Option<TypeIdentifier>;
+}
+
+impl<T: ?Sized> UndescribedFieldType for FieldTypeDescriptor<T> {
+    fn type_identifier_fn(&self) -> fn(&mut TypeObjectBuilder) -> Option<TypeIdentifier> {
+        |_| None
+    }
 }
 
 pub trait DdsSetKeyFields {
-    type OwningKeyHolder: for<'de> serde::Deserialize<'de>;
+    type OwningKeyHolder: for<'de> serde::Deserialize<'de> + serde::Serialize;
 
     fn set_key_from_holder(&mut self, key_holder: Self::OwningKeyHolder);
+
+    // Key hash of the instance whose key is serialized in `data`, which the disposed and unregistered samples
+    // received without the key hash parameter carry instead of the sample
+    #[doc(hidden)]
+    fn serialized_key_hash(
+        data: &[u8],
+        _dynamic_type: Option<&DynamicType>,
+    ) -> DdsResult<InstanceHandle>
+    where
+        Self: DdsGetKey + Sized,
+    {
+        key_hash(
+            &dds_deserialize_key_from_bytes::<Self>(data)?,
+            Self::MAX_KEY_SIZE,
+        )
+    }
 }
 
 macro_rules! implement_dds_get_key_for_built_in_type {
-    ($t:ty) => {
+    ($t:ty, $size:expr) => {
         impl DdsGetKey for $t {
             type BorrowedKeyHolder<'a> = $t;
 
+            const MAX_KEY_SIZE: Option<usize> = Some($size);
+            const KEY_ALIGNMENT: usize = $size;
+
             fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
                 *self
             }
@@ -105,26 +675,30 @@ macro_rules! implement_dds_get_key_for_built_in_type {
     };
 }
 
-implement_dds_get_key_for_built_in_type!(bool);
-implement_dds_get_key_for_built_in_type!(char);
-implement_dds_get_key_for_built_in_type!(u8);
-implement_dds_get_key_for_built_in_type!(i8);
-implement_dds_get_key_for_built_in_type!(u16);
-implement_dds_get_key_for_built_in_type!(i16);
-implement_dds_get_key_for_built_in_type!(u32);
-implement_dds_get_key_for_built_in_type!(i32);
-implement_dds_get_key_for_built_in_type!(u64);
-implement_dds_get_key_for_built_in_type!(i64);
-implement_dds_get_key_for_built_in_type!(usize);
-implement_dds_get_key_for_built_in_type!(isize);
-implement_dds_get_key_for_built_in_type!(f32);
-implement_dds_get_key_for_built_in_type!(f64);
+// The char type is serialized as a single byte and the usize and isize types as 64-bit integers
+implement_dds_get_key_for_built_in_type!(bool, 1);
+implement_dds_get_key_for_built_in_type!(char, 1);
+implement_dds_get_key_for_built_in_type!(u8, 1);
+implement_dds_get_key_for_built_in_type!(i8, 1);
+implement_dds_get_key_for_built_in_type!(u16, 2);
+implement_dds_get_key_for_built_in_type!(i16, 2);
+implement_dds_get_key_for_built_in_type!(u32, 4);
+implement_dds_get_key_for_built_in_type!(i32, 4);
+implement_dds_get_key_for_built_in_type!(u64, 8);
+implement_dds_get_key_for_built_in_type!(i64, 8);
+implement_dds_get_key_for_built_in_type!(usize, 8);
+implement_dds_get_key_for_built_in_type!(isize, 8);
+implement_dds_get_key_for_built_in_type!(f32, 4);
+implement_dds_get_key_for_built_in_type!(f64, 8);
 
 impl<T> DdsGetKey for Vec<T>
 where
     T: serde::Serialize + for<'de> serde::Deserialize<'de>,
 {
-    type BorrowedKeyHolder<'a> = &'a Vec<T> where T:'a;
+    type BorrowedKeyHolder<'a>
+        = &'a Vec<T>
+    where
+        T: 'a;
 
     fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
         self
@@ -158,11 +732,19 @@ impl DdsSetKeyFields for String {
     }
 }
 
+// The whole elements of an array form its key
 impl<const N: usize, T> DdsGetKey for [T; N]
 where
     [T; N]: serde::Serialize + for<'de> serde::Deserialize<'de>,
+    T: DdsTypeDescriptor,
 {
-    type BorrowedKeyHolder<'a> = &'a [T; N] where T:'a;
+    type BorrowedKeyHolder<'a>
+        = &'a [T; N]
+    where
+        T: 'a;
+
+    const MAX_KEY_SIZE: Option<usize> = <[T; N] as DdsTypeDescriptor>::MAX_SERIALIZED_SIZE;
+    const KEY_ALIGNMENT: usize = T::ALIGNMENT;
 
     fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
         self
@@ -185,7 +767,19 @@ where
     T: serde::Serialize + DdsRepresentation,
 {
     let mut writer = vec![];
-    match T::REPRESENTATION {
+    dds_serialize_to_writer(value, &T::REPRESENTATION, &mut writer)?;
+    Ok(writer)
+}
+
+pub(crate) fn dds_serialize_to_writer<T>(
+    value: &T,
+    representation: &Representation,
+    mut writer: impl Write,
+) -> DdsResult<()>
+where
+    T: serde::Serialize + DdsRepresentation,
+{
+    match representation {
         Representation::CdrLe => {
             writer
                 .write_all(&CDR_LE)
@@ -233,11 +827,79 @@ where
             serde::Serialize::serialize(value, &mut serializer)
                 .map_err(|err| PreconditionNotMet(err.to_string()))?;
         }
+        Representation::Cdr2Be => serialize_xcdr2::<_, byteorder::BigEndian>(
+            value,
+            ExtensibilityKind::Final,
+            CDR2_BE,
+            writer,
+        )?,
+        Representation::Cdr2Le => serialize_xcdr2::<_, byteorder::LittleEndian>(
+            value,
+            ExtensibilityKind::Final,
+            CDR2_LE,
+            writer,
+        )?,
+        Representation::DCdr2Be => serialize_xcdr2::<_, byteorder::BigEndian>(
+            value,
+            ExtensibilityKind::Appendable,
+            D_CDR2_BE,
+            writer,
+        )?,
+        Representation::DCdr2Le => serialize_xcdr2::<_, byteorder::LittleEndian>(
+            value,
+            ExtensibilityKind::Appendable,
+            D_CDR2_LE,
+            writer,
+        )?,
+        Representation::PlCdr2Be => serialize_xcdr2::<_, byteorder::BigEndian>(
+            value,
+            ExtensibilityKind::Mutable,
+            PL_CDR2_BE,
+            writer,
+        )?,
+        Representation::PlCdr2Le => serialize_xcdr2::<_, byteorder::LittleEndian>(
+            value,
+            ExtensibilityKind::Mutable,
+            PL_CDR2_LE,
+            writer,
+        )?,
         Representation::Custom => {
             T::to_bytes(value, &mut writer)?;
         }
     };
-    Ok(writer)
+    Ok(())
+}
+
+fn serialize_xcdr2<T, E>(
+    value: &T,
+    extensibility: ExtensibilityKind,
+    representation_identifier: RepresentationIdentifier,
+    mut writer: impl Write,
+) -> DdsResult<()>
+where
+    T: serde::Serialize + DdsRepresentation,
+    E: byteorder::ByteOrder,
+{
+    let mut data = Vec::new();
+    let mut serializer =
+        Xcdr2Serializer::<E>::new(&mut data, extensibility, T::struct_descriptors());
+    serde::Serialize::serialize(value, &mut serializer)
+        .map_err(|err| PreconditionNotMet(err.to_string()))?;
+
+    // The serialized data is padded to a multiple of 4 bytes and the amount of padding
+    // is indicated in the two least significant bits of the representation options
+    let padding = (4 - data.len() % 4) % 4;
+    data.resize(data.len() + padding, 0);
+
+    writer
+        .write_all(&representation_identifier)
+        .map_err(|err| PreconditionNotMet(err.to_string()))?;
+    writer
+        .write_all(&[0x00, padding as u8])
+        .map_err(|err| PreconditionNotMet(err.to_string()))?;
+    writer
+        .write_all(&data)
+        .map_err(|err| PreconditionNotMet(err.to_string()))
 }
 
 pub fn dds_deserialize_from_bytes<'de, T>(mut data: &'de [u8]) -> DdsResult<T>
@@ -282,6 +944,27 @@ where
                     serde::Deserialize::deserialize(&mut deserializer)
                         .map_err(|err| PreconditionNotMet(err.to_string()))
                 }
+                CDR2_BE => {
+                    deserialize_xcdr2::<_, byteorder::BigEndian>(data, ExtensibilityKind::Final)
+                }
+                CDR2_LE => {
+                    deserialize_xcdr2::<_, byteorder::LittleEndian>(data, ExtensibilityKind::Final)
+                }
+                D_CDR2_BE => deserialize_xcdr2::<_, byteorder::BigEndian>(
+                    data,
+                    ExtensibilityKind::Appendable,
+                ),
+                D_CDR2_LE => deserialize_xcdr2::<_, byteorder::LittleEndian>(
+                    data,
+                    ExtensibilityKind::Appendable,
+                ),
+                PL_CDR2_BE => {
+                    deserialize_xcdr2::<_, byteorder::BigEndian>(data, ExtensibilityKind::Mutable)
+                }
+                PL_CDR2_LE => deserialize_xcdr2::<_, byteorder::LittleEndian>(
+                    data,
+                    ExtensibilityKind::Mutable,
+                ),
                 _ => Err(PreconditionNotMet(
                     "Illegal representation identifier".to_string(),
                 )),
@@ -290,18 +973,167 @@ where
     }
 }
 
+// Deserializes with the `seed` the data serialized with the CDR or XCDR2 representations. The
+// types deserialized with a seed have no struct descriptors so all their structures take the
+// extensibility of the representation.
+pub(crate) fn dds_deserialize_seed_from_bytes<'de, S>(
+    mut data: &'de [u8],
+    seed: S,
+) -> DdsResult<S::Value>
+where
+    S: serde::de::DeserializeSeed<'de>,
+{
+    let mut representation_identifier = [0u8, 0];
+    data.read_exact(&mut representation_identifier)
+        .map_err(|err| PreconditionNotMet(err.to_string()))?;
+
+    let mut representation_option = [0u8, 0];
+    data.read_exact(&mut representation_option)
+        .map_err(|err| PreconditionNotMet(err.to_string()))?;
+
+    match representation_identifier {
+        CDR_BE => {
+            let mut deserializer =
+                cdr::Deserializer::<_, _, byteorder::BigEndian>::new(data, cdr::Infinite);
+            seed.deserialize(&mut deserializer)
+                .map_err(|err| PreconditionNotMet(err.to_string()))
+        }
+        CDR_LE => {
+            let mut deserializer =
+                cdr::Deserializer::<_, _, byteorder::LittleEndian>::new(data, cdr::Infinite);
+            seed.deserialize(&mut deserializer)
+                .map_err(|err| PreconditionNotMet(err.to_string()))
+        }
+        CDR2_BE => {
+            deserialize_seed_xcdr2::<_, byteorder::BigEndian>(data, seed, ExtensibilityKind::Final)
+        }
+        CDR2_LE => deserialize_seed_xcdr2::<_, byteorder::LittleEndian>(
+            data,
+            seed,
+            ExtensibilityKind::Final,
+        ),
+        D_CDR2_BE => deserialize_seed_xcdr2::<_, byteorder::BigEndian>(
+            data,
+            seed,
+            ExtensibilityKind::Appendable,
+        ),
+        D_CDR2_LE => deserialize_seed_xcdr2::<_, byteorder::LittleEndian>(
+            data,
+            seed,
+            ExtensibilityKind::Appendable,
+        ),
+        PL_CDR2_BE => deserialize_seed_xcdr2::<_, byteorder::BigEndian>(
+            data,
+            seed,
+            ExtensibilityKind::Mutable,
+        ),
+        PL_CDR2_LE => deserialize_seed_xcdr2::<_, byteorder::LittleEndian>(
+            data,
+            seed,
+            ExtensibilityKind::Mutable,
+        ),
+        _ => Err(PreconditionNotMet(
+            "Only the CDR and XCDR2 representations can be deserialized with a seed".to_string(),
+        )),
+    }
+}
+
+fn deserialize_seed_xcdr2<'de, S, E>(
+    data: &'de [u8],
+    seed: S,
+    extensibility: ExtensibilityKind,
+) -> DdsResult<S::Value>
+where
+    S: serde::de::DeserializeSeed<'de>,
+    E: byteorder::ByteOrder,
+{
+    let mut deserializer =
+        serde_xcdr2_deserializer::Xcdr2Deserializer::<E>::new(data, extensibility, Vec::new());
+    seed.deserialize(&mut deserializer)
+        .map_err(|err| PreconditionNotMet(err.to_string()))
+}
+
+fn deserialize_xcdr2<'de, T, E>(data: &'de [u8], extensibility: ExtensibilityKind) -> DdsResult<T>
+where
+    T: serde::Deserialize<'de> + DdsRepresentation,
+    E: byteorder::ByteOrder,
+{
+    serde_xcdr2_deserializer::deserialize_from_bytes::<_, E>(
+        data,
+        extensibility,
+        T::struct_descriptors(),
+    )
+    .map_err(|err| PreconditionNotMet(err.to_string()))
+}
+
 pub fn dds_serialize_key<T>(value: &T) -> DdsResult<DdsSerializedKey>
 where
     T: DdsGetKey,
 {
     let mut writer = vec![];
-    let mut serializer = cdr::ser::Serializer::<_, byteorder::LittleEndian>::new(&mut writer);
-    let key = value.get_key();
-    serde::Serialize::serialize(&key, &mut serializer)
-        .map_err(|err| PreconditionNotMet(err.to_string()))?;
+    value.serialize_key(&Representation::CdrLe, &mut writer)?;
     Ok(writer.into())
 }
 
+/// Computes the key hash of the instance of `value` as specified by the RTPS protocol: the key serialized with the
+/// big-endian CDR representation, padded with zeros to 16 bytes if [`DdsGetKey::MAX_KEY_SIZE`] is not larger than
+/// 16 bytes or hashed with MD5 otherwise.
+pub fn dds_key_hash<T>(value: &T) -> DdsResult<InstanceHandle>
+where
+    T: DdsGetKey,
+{
+    let mut writer = vec![];
+    value.serialize_key(&Representation::CdrBe, &mut writer)?;
+    Ok(key_hash_from_serialized_key(&writer, value.max_key_size()))
+}
+
+pub(crate) fn key_hash<K>(key: &K, max_key_size: Option<usize>) -> DdsResult<InstanceHandle>
+where
+    K: serde::Serialize,
+{
+    let mut writer = vec![];
+    serialize_cdr(key, &Representation::CdrBe, &mut writer)?;
+    Ok(key_hash_from_serialized_key(&writer, max_key_size))
+}
+
+fn key_hash_from_serialized_key(
+    serialized_key: &[u8],
+    max_key_size: Option<usize>,
+) -> InstanceHandle {
+    let key_hash = match max_key_size {
+        Some(max_key_size) if max_key_size <= 16 && serialized_key.len() <= 16 => {
+            let mut key_hash = [0; 16];
+            key_hash[..serialized_key.len()].copy_from_slice(serialized_key);
+            key_hash
+        }
+        _ => md5::compute(serialized_key).into(),
+    };
+    InstanceHandle::new(key_hash)
+}
+
+// Serializes a value with the big-endian classic CDR representation if `representation` is
+// Representation::CdrBe and with the little-endian one otherwise, without the representation header
+fn serialize_cdr<T>(
+    value: &T,
+    representation: &Representation,
+    mut writer: impl Write,
+) -> DdsResult<()>
+where
+    T: serde::Serialize + ?Sized,
+{
+    match representation {
+        Representation::CdrBe => serde::Serialize::serialize(
+            value,
+            &mut cdr::ser::Serializer::<_, byteorder::BigEndian>::new(&mut writer),
+        ),
+        _ => serde::Serialize::serialize(
+            value,
+            &mut cdr::ser::Serializer::<_, byteorder::LittleEndian>::new(&mut writer),
+        ),
+    }
+    .map_err(|err| PreconditionNotMet(err.to_string()))
+}
+
 pub fn dds_serialize_key_to_bytes<T>(value: &T) -> DdsResult<DdsSerializedKey>
 where
     T: DdsGetKey,
@@ -313,13 +1145,20 @@ where
     writer
         .write_all(&REPRESENTATION_OPTIONS)
         .map_err(|err| PreconditionNotMet(err.to_string()))?;
-    let mut serializer = cdr::ser::Serializer::<_, byteorder::LittleEndian>::new(&mut writer);
-    let key = value.get_key();
-    serde::Serialize::serialize(&key, &mut serializer)
-        .map_err(|err| PreconditionNotMet(err.to_string()))?;
+    value.serialize_key(&Representation::CdrLe, &mut writer)?;
     Ok(writer.into())
 }
 
+pub fn dds_deserialize_key<T>(data: &[u8]) -> DdsResult<T::OwningKeyHolder>
+where
+    T: DdsSetKeyFields,
+{
+    let mut deserializer =
+        cdr::Deserializer::<_, _, byteorder::LittleEndian>::new(data, cdr::Infinite);
+    serde::Deserialize::deserialize(&mut deserializer)
+        .map_err(|err| PreconditionNotMet(err.to_string()))
+}
+
 pub fn dds_deserialize_key_from_bytes<T>(mut data: &[u8]) -> DdsResult<T::OwningKeyHolder>
 where
     T: DdsSetKeyFields,
@@ -357,7 +1196,7 @@ pub fn dds_set_key_fields_from_serialized_key<T>(
 where
     T: DdsSetKeyFields,
 {
-    let key_holder = dds_deserialize_key_from_bytes::<T>(serialized_key)?;
+    let key_holder = dds_deserialize_key::<T>(serialized_key)?;
     value.set_key_from_holder(key_holder);
     Ok(())
 }
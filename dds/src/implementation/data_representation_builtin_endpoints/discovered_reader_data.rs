@@ -2,16 +2,17 @@ use crate::{
     builtin_topics::SubscriptionBuiltinTopicData,
     implementation::{
         parameter_list_serde::parameter::{Parameter, ParameterVector, ParameterWithDefault},
-        rtps::types::{EntityId, Guid, Locator},
+        rtps::types::{EntityId, Guid, Locator, VendorId, VENDOR_ID_S2E},
     },
-    topic_definition::type_support::{
-        DdsGetKey, DdsHasKey, DdsRepresentation, DdsSetKeyFields, Representation,
+    topic_definition::{
+        type_object::{TypeObject, XTypesParameterValue},
+        type_support::{DdsGetKey, DdsHasKey, DdsRepresentation, DdsSetKeyFields, Representation},
     },
 };
 
 use super::parameter_id_values::{
     DEFAULT_EXPECTS_INLINE_QOS, PID_ENDPOINT_GUID, PID_EXPECTS_INLINE_QOS, PID_GROUP_ENTITYID,
-    PID_MULTICAST_LOCATOR, PID_UNICAST_LOCATOR,
+    PID_MULTICAST_LOCATOR, PID_TYPE_OBJECTS, PID_UNICAST_LOCATOR,
 };
 
 pub const DCPS_SUBSCRIPTION: &str = "DCPSSubscription";
@@ -84,16 +85,20 @@ impl ReaderProxy {
 pub struct DiscoveredReaderData {
     reader_proxy: ReaderProxy,
     subscription_builtin_topic_data: SubscriptionBuiltinTopicData,
+    type_objects: ParameterWithDefault<PID_TYPE_OBJECTS, XTypesParameterValue<Vec<TypeObject>>>,
 }
 
 impl DiscoveredReaderData {
     pub fn new(
         reader_proxy: ReaderProxy,
         subscription_builtin_topic_data: SubscriptionBuiltinTopicData,
+        type_objects: Vec<TypeObject>,
     ) -> Self {
+        let type_objects = Some(type_objects).filter(|type_objects| !type_objects.is_empty());
         Self {
             reader_proxy,
             subscription_builtin_topic_data,
+            type_objects: XTypesParameterValue::new(type_objects).into(),
         }
     }
 
@@ -104,6 +109,26 @@ impl DiscoveredReaderData {
     pub fn subscription_builtin_topic_data(&self) -> &SubscriptionBuiltinTopicData {
         &self.subscription_builtin_topic_data
     }
+
+    pub fn type_objects(&self) -> &[TypeObject] {
+        self.type_objects
+            .as_ref()
+            .value()
+            .map_or(&[], Vec::as_slice)
+    }
+
+    // The type objects are given in a parameter specific to Dust DDS whose id can be used by other
+    // vendors for other data, so it is discarded when the data is sent by another vendor
+    pub fn filter_vendor_specific_parameters(self, vendor_id: VendorId) -> Self {
+        if vendor_id == VENDOR_ID_S2E {
+            self
+        } else {
+            Self {
+                type_objects: XTypesParameterValue::default().into(),
+                ..self
+            }
+        }
+    }
 }
 
 impl DdsHasKey for DiscoveredReaderData {
@@ -117,6 +142,8 @@ impl DdsRepresentation for DiscoveredReaderData {
 impl DdsGetKey for DiscoveredReaderData {
     type BorrowedKeyHolder<'a> = [u8; 16];
 
+    const MAX_KEY_SIZE: Option<usize> = Some(16);
+
     fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
         self.subscription_builtin_topic_data.key().value
     }
@@ -138,9 +165,10 @@ mod tests {
         BUILT_IN_WRITER_WITH_KEY, USER_DEFINED_READER_WITH_KEY, USER_DEFINED_UNKNOWN,
     };
     use crate::infrastructure::qos_policy::{
-        DeadlineQosPolicy, DestinationOrderQosPolicy, DurabilityQosPolicy, GroupDataQosPolicy,
-        LatencyBudgetQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy, PartitionQosPolicy,
-        PresentationQosPolicy, TimeBasedFilterQosPolicy, TopicDataQosPolicy, UserDataQosPolicy,
+        DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
+        DurabilityQosPolicy, GroupDataQosPolicy, LatencyBudgetQosPolicy, LivelinessQosPolicy,
+        OwnershipQosPolicy, PartitionQosPolicy, PresentationQosPolicy, TimeBasedFilterQosPolicy,
+        TopicDataQosPolicy, TypeConsistencyEnforcementQosPolicy, UserDataQosPolicy,
         DEFAULT_RELIABILITY_QOS_POLICY_DATA_READER_AND_TOPICS,
     };
     use crate::topic_definition::type_support::{
@@ -182,7 +210,11 @@ mod tests {
                 PartitionQosPolicy::default(),
                 TopicDataQosPolicy::default(),
                 GroupDataQosPolicy::default(),
+                DataRepresentationQosPolicy::default(),
+                TypeConsistencyEnforcementQosPolicy::default(),
+                None,
             ),
+            type_objects: XTypesParameterValue::default().into(),
         };
 
         let expected = vec![
@@ -247,7 +279,11 @@ mod tests {
                 PartitionQosPolicy::default(),
                 TopicDataQosPolicy::default(),
                 GroupDataQosPolicy::default(),
+                DataRepresentationQosPolicy::default(),
+                TypeConsistencyEnforcementQosPolicy::default(),
+                None,
             ),
+            type_objects: XTypesParameterValue::default().into(),
         };
 
         let data = &[
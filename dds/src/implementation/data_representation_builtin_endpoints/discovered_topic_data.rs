@@ -35,6 +35,8 @@ impl DdsRepresentation for DiscoveredTopicData {
 impl DdsGetKey for DiscoveredTopicData {
     type BorrowedKeyHolder<'a> = [u8; 16];
 
+    const MAX_KEY_SIZE: Option<usize> = Some(16);
+
     fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
         self.topic_builtin_topic_data.key().value
     }
@@ -52,10 +54,10 @@ impl DdsSetKeyFields for DiscoveredTopicData {
 mod tests {
     use crate::builtin_topics::BuiltInTopicKey;
     use crate::infrastructure::qos_policy::{
-        DeadlineQosPolicy, DestinationOrderQosPolicy, DurabilityQosPolicy, HistoryQosPolicy,
-        LatencyBudgetQosPolicy, LifespanQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy,
-        ResourceLimitsQosPolicy, TopicDataQosPolicy, TransportPriorityQosPolicy,
-        DEFAULT_RELIABILITY_QOS_POLICY_DATA_READER_AND_TOPICS,
+        DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
+        DurabilityQosPolicy, HistoryQosPolicy, LatencyBudgetQosPolicy, LifespanQosPolicy,
+        LivelinessQosPolicy, OwnershipQosPolicy, ResourceLimitsQosPolicy, TopicDataQosPolicy,
+        TransportPriorityQosPolicy, DEFAULT_RELIABILITY_QOS_POLICY_DATA_READER_AND_TOPICS,
     };
     use crate::topic_definition::type_support::{
         dds_deserialize_from_bytes, dds_serialize_to_bytes,
@@ -84,6 +86,7 @@ mod tests {
                 ResourceLimitsQosPolicy::default(),
                 OwnershipQosPolicy::default(),
                 TopicDataQosPolicy::default(),
+                DataRepresentationQosPolicy::default(),
             ),
         };
 
@@ -127,6 +130,7 @@ mod tests {
                 ResourceLimitsQosPolicy::default(),
                 OwnershipQosPolicy::default(),
                 TopicDataQosPolicy::default(),
+                DataRepresentationQosPolicy::default(),
             ),
         };
 
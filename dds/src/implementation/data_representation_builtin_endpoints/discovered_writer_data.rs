@@ -2,16 +2,17 @@ use crate::{
     builtin_topics::PublicationBuiltinTopicData,
     implementation::{
         parameter_list_serde::parameter::{Parameter, ParameterVector, ParameterWithDefault},
-        rtps::types::{EntityId, Guid, Locator},
+        rtps::types::{EntityId, Guid, Locator, VendorId, VENDOR_ID_S2E},
     },
-    topic_definition::type_support::{
-        DdsGetKey, DdsHasKey, DdsRepresentation, DdsSetKeyFields, Representation,
+    topic_definition::{
+        type_object::{TypeObject, XTypesParameterValue},
+        type_support::{DdsGetKey, DdsHasKey, DdsRepresentation, DdsSetKeyFields, Representation},
     },
 };
 
 use super::parameter_id_values::{
     PID_DATA_MAX_SIZE_SERIALIZED, PID_ENDPOINT_GUID, PID_GROUP_ENTITYID, PID_MULTICAST_LOCATOR,
-    PID_UNICAST_LOCATOR,
+    PID_TYPE_OBJECTS, PID_UNICAST_LOCATOR,
 };
 #[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WriterProxy {
@@ -21,7 +22,9 @@ pub struct WriterProxy {
     remote_group_entity_id: ParameterWithDefault<PID_GROUP_ENTITYID, EntityId>,
     unicast_locator_list: ParameterVector<PID_UNICAST_LOCATOR, Locator>,
     multicast_locator_list: ParameterVector<PID_MULTICAST_LOCATOR, Locator>,
-    data_max_size_serialized: ParameterWithDefault<PID_DATA_MAX_SIZE_SERIALIZED, Option<i32>>,
+    // A serialized size of 0 stands for a writer which does not announce its maximum size, so that the
+    // parameter is omitted by default
+    data_max_size_serialized: ParameterWithDefault<PID_DATA_MAX_SIZE_SERIALIZED, i32>,
 }
 
 impl WriterProxy {
@@ -37,7 +40,7 @@ impl WriterProxy {
             remote_group_entity_id: remote_group_entity_id.into(),
             unicast_locator_list: unicast_locator_list.into(),
             multicast_locator_list: multicast_locator_list.into(),
-            data_max_size_serialized: data_max_size_serialized.into(),
+            data_max_size_serialized: data_max_size_serialized.unwrap_or_default().into(),
         }
     }
 
@@ -58,7 +61,7 @@ impl WriterProxy {
     }
 
     pub fn data_max_size_serialized(&self) -> Option<i32> {
-        *self.data_max_size_serialized.as_ref()
+        Some(*self.data_max_size_serialized.as_ref()).filter(|&size| size != 0)
     }
 }
 
@@ -66,16 +69,20 @@ impl WriterProxy {
 pub struct DiscoveredWriterData {
     dds_publication_data: PublicationBuiltinTopicData,
     writer_proxy: WriterProxy,
+    type_objects: ParameterWithDefault<PID_TYPE_OBJECTS, XTypesParameterValue<Vec<TypeObject>>>,
 }
 
 impl DiscoveredWriterData {
     pub fn new(
         dds_publication_data: PublicationBuiltinTopicData,
         writer_proxy: WriterProxy,
+        type_objects: Vec<TypeObject>,
     ) -> Self {
+        let type_objects = Some(type_objects).filter(|type_objects| !type_objects.is_empty());
         Self {
             dds_publication_data,
             writer_proxy,
+            type_objects: XTypesParameterValue::new(type_objects).into(),
         }
     }
 
@@ -86,6 +93,26 @@ impl DiscoveredWriterData {
     pub fn writer_proxy(&self) -> &WriterProxy {
         &self.writer_proxy
     }
+
+    pub fn type_objects(&self) -> &[TypeObject] {
+        self.type_objects
+            .as_ref()
+            .value()
+            .map_or(&[], Vec::as_slice)
+    }
+
+    // The type objects are given in a parameter specific to Dust DDS whose id can be used by other
+    // vendors for other data, so it is discarded when the data is sent by another vendor
+    pub fn filter_vendor_specific_parameters(self, vendor_id: VendorId) -> Self {
+        if vendor_id == VENDOR_ID_S2E {
+            self
+        } else {
+            Self {
+                type_objects: XTypesParameterValue::default().into(),
+                ..self
+            }
+        }
+    }
 }
 
 pub const DCPS_PUBLICATION: &str = "DCPSPublication";
@@ -101,6 +128,8 @@ impl DdsRepresentation for DiscoveredWriterData {
 impl DdsGetKey for DiscoveredWriterData {
     type BorrowedKeyHolder<'a> = [u8; 16];
 
+    const MAX_KEY_SIZE: Option<usize> = Some(16);
+
     fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
         self.dds_publication_data.key().value
     }
@@ -121,10 +150,10 @@ mod tests {
         BUILT_IN_PARTICIPANT, BUILT_IN_READER_GROUP, BUILT_IN_WRITER_WITH_KEY, USER_DEFINED_UNKNOWN,
     };
     use crate::infrastructure::qos_policy::{
-        DeadlineQosPolicy, DestinationOrderQosPolicy, DurabilityQosPolicy, GroupDataQosPolicy,
-        LatencyBudgetQosPolicy, LifespanQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy,
-        PartitionQosPolicy, PresentationQosPolicy, TopicDataQosPolicy, UserDataQosPolicy,
-        DEFAULT_RELIABILITY_QOS_POLICY_DATA_WRITER,
+        DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
+        DurabilityQosPolicy, GroupDataQosPolicy, LatencyBudgetQosPolicy, LifespanQosPolicy,
+        LivelinessQosPolicy, OwnershipQosPolicy, PartitionQosPolicy, PresentationQosPolicy,
+        TopicDataQosPolicy, UserDataQosPolicy, DEFAULT_RELIABILITY_QOS_POLICY_DATA_WRITER,
     };
     use crate::topic_definition::type_support::{
         dds_deserialize_from_bytes, dds_serialize_to_bytes,
@@ -157,6 +186,8 @@ mod tests {
                 PartitionQosPolicy::default(),
                 TopicDataQosPolicy::default(),
                 GroupDataQosPolicy::default(),
+                DataRepresentationQosPolicy::default(),
+                None,
             ),
             WriterProxy::new(
                 Guid::new(
@@ -168,6 +199,7 @@ mod tests {
                 vec![],
                 None,
             ),
+            vec![],
         );
 
         let expected = vec![
@@ -221,6 +253,8 @@ mod tests {
                 PartitionQosPolicy::default(),
                 TopicDataQosPolicy::default(),
                 GroupDataQosPolicy::default(),
+                DataRepresentationQosPolicy::default(),
+                None,
             ),
             WriterProxy::new(
                 // must correspond to publication_builtin_topic_data.key
@@ -233,6 +267,7 @@ mod tests {
                 vec![],
                 None,
             ),
+            vec![],
         );
 
         let data = &[
@@ -260,4 +295,55 @@ mod tests {
         let result = dds_deserialize_from_bytes::<DiscoveredWriterData>(data).unwrap();
         assert_eq!(result, expected);
     }
+    #[test]
+    fn data_max_size_serialized_is_serialized_and_deserialized() {
+        let data = DiscoveredWriterData::new(
+            PublicationBuiltinTopicData::new(
+                BuiltInTopicKey {
+                    value: [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0],
+                },
+                BuiltInTopicKey {
+                    value: [6, 0, 0, 0, 7, 0, 0, 0, 8, 0, 0, 0, 9, 0, 0, 0],
+                },
+                "ab".to_string(),
+                "cd".to_string(),
+                DurabilityQosPolicy::default(),
+                DeadlineQosPolicy::default(),
+                LatencyBudgetQosPolicy::default(),
+                LivelinessQosPolicy::default(),
+                DEFAULT_RELIABILITY_QOS_POLICY_DATA_WRITER,
+                LifespanQosPolicy::default(),
+                UserDataQosPolicy::default(),
+                OwnershipQosPolicy::default(),
+                DestinationOrderQosPolicy::default(),
+                PresentationQosPolicy::default(),
+                PartitionQosPolicy::default(),
+                TopicDataQosPolicy::default(),
+                GroupDataQosPolicy::default(),
+                DataRepresentationQosPolicy::default(),
+                None,
+            ),
+            WriterProxy::new(
+                Guid::new(
+                    [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0],
+                    EntityId::new([4, 0, 0], USER_DEFINED_UNKNOWN),
+                ),
+                EntityId::new([21, 22, 23], BUILT_IN_PARTICIPANT),
+                vec![],
+                vec![],
+                Some(52),
+            ),
+            vec![],
+        );
+
+        let bytes = dds_serialize_to_bytes(&data).unwrap();
+
+        assert!(bytes
+            .windows(8)
+            .any(|w| w == [0x60, 0x00, 4, 0, 52, 0, 0, 0])); // PID_TYPE_MAX_SIZE_SERIALIZED
+        assert_eq!(
+            dds_deserialize_from_bytes::<DiscoveredWriterData>(&bytes).unwrap(),
+            data
+        );
+    }
 }
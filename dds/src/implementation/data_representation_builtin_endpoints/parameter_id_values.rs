@@ -49,6 +49,12 @@ pub const _PID_PROPERTY_LIST: ParameterId = 0x0059;
 pub const PID_TYPE_MAX_SIZE_SERIALIZED: ParameterId = 0x0060;
 pub const _PID_ENTITY_NAME: ParameterId = 0x0062;
 pub const PID_ENDPOINT_GUID: ParameterId = 0x005a;
+pub const PID_DATA_REPRESENTATION: ParameterId = 0x0073;
+pub const PID_TYPE_CONSISTENCY_ENFORCEMENT: ParameterId = 0x0074;
+pub const PID_TYPE_INFORMATION: ParameterId = 0x0075;
+// Following PID is vendor specific. The type objects are sent together with
+// the endpoint data since the TypeLookup service is not available
+pub const PID_TYPE_OBJECTS: ParameterId = 0x8001_u16 as ParameterId;
 // Following PID is not defined in standard
 // (but its listed in "Table 9.14 - ParameterId mapping and default values")
 pub const PID_DATA_MAX_SIZE_SERIALIZED: ParameterId = PID_TYPE_MAX_SIZE_SERIALIZED;
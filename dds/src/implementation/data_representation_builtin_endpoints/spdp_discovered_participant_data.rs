@@ -146,7 +146,7 @@ impl ParticipantProxy {
         *self.guid_prefix.as_ref()
     }
 
-    pub fn _vendor_id(&self) -> VendorId {
+    pub fn vendor_id(&self) -> VendorId {
         *self.vendor_id.as_ref()
     }
 
@@ -227,6 +227,8 @@ impl DdsRepresentation for SpdpDiscoveredParticipantData {
 impl DdsGetKey for SpdpDiscoveredParticipantData {
     type BorrowedKeyHolder<'a> = [u8; 16];
 
+    const MAX_KEY_SIZE: Option<usize> = Some(16);
+
     fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
         self.dds_participant_data.key().value
     }
@@ -4,7 +4,7 @@ use crate::{
         PublicationMatchedStatus,
     },
     publication::{data_writer::DataWriter, data_writer_listener::DataWriterListener},
-    topic_definition::type_support::DdsHasKey,
+    topic_definition::type_support::{DdsHasKey, DdsSerialize},
 };
 
 use super::nodes::{DataWriterNode, DataWriterNodeKind};
@@ -34,7 +34,7 @@ pub trait AnyDataWriterListener {
 
 impl<Foo> AnyDataWriterListener for Box<dyn DataWriterListener<Foo = Foo> + Send + Sync>
 where
-    Foo: DdsHasKey + serde::Serialize + 'static,
+    Foo: DdsHasKey + DdsSerialize + 'static,
 {
     fn trigger_on_liveliness_lost(
         &mut self,
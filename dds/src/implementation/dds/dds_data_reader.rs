@@ -17,7 +17,10 @@ use crate::{
                 STATUS_INFO_UNREGISTERED,
             },
         },
-        dds::nodes::DataReaderNode,
+        dds::{
+            multitopic_join::{JoinedChange, MultiTopicJoin},
+            nodes::DataReaderNode,
+        },
         rtps::{
             messages::{
                 overall_structure::{RtpsMessageHeader, RtpsMessageRead, RtpsSubmessageReadKind},
@@ -47,8 +50,9 @@ use crate::{
         instance::InstanceHandle,
         qos::{DataReaderQos, SubscriberQos, TopicQos},
         qos_policy::{
-            DestinationOrderQosPolicyKind, DurabilityQosPolicyKind, HistoryQosPolicyKind,
-            QosPolicyId, ReliabilityQosPolicyKind, DEADLINE_QOS_POLICY_ID,
+            is_data_representation_compatible, DestinationOrderQosPolicyKind,
+            DurabilityQosPolicyKind, HistoryQosPolicyKind, QosPolicyId, ReliabilityQosPolicyKind,
+            DATAREPRESENTATION_QOS_POLICY_ID, DEADLINE_QOS_POLICY_ID,
             DESTINATIONORDER_QOS_POLICY_ID, DURABILITY_QOS_POLICY_ID, LATENCYBUDGET_QOS_POLICY_ID,
             LIVELINESS_QOS_POLICY_ID, PRESENTATION_QOS_POLICY_ID, RELIABILITY_QOS_POLICY_ID,
         },
@@ -57,15 +61,22 @@ use crate::{
             RequestedIncompatibleQosStatus, SampleLostStatus, SampleRejectedStatus,
             SampleRejectedStatusKind, StatusKind, SubscriptionMatchedStatus,
         },
-        time::{DurationKind, Time},
+        time::{Duration, DurationKind, Time},
     },
     subscription::{
         data_reader::Sample,
-        sample_info::{InstanceStateKind, SampleInfo, SampleStateKind, ViewStateKind},
+        sample_info::{
+            InstanceStateKind, SampleInfo, SampleStateKind, ViewStateKind, ANY_INSTANCE_STATE,
+            ANY_SAMPLE_STATE, ANY_VIEW_STATE,
+        },
     },
-    topic_definition::type_support::{
-        dds_deserialize_from_bytes, dds_serialize_key, DdsGetKey, DdsHasKey, DdsRepresentation,
-        DdsSerializedKey,
+    topic_definition::{
+        dynamic_type::DynamicType,
+        type_object::{is_type_consistent, TypeInformation, TypeObject},
+        type_support::{
+            dds_key_hash, dds_serialize_key, DdsDeserialize, DdsGetKey, DdsHasKey,
+            DdsRepresentation, DdsSerializedKey, DdsSetKeyFields,
+        },
     },
 };
 
@@ -75,52 +86,95 @@ use super::{
     status_condition_impl::StatusConditionImpl,
 };
 
-struct InstanceHandleBuilder(fn(&mut &[u8]) -> DdsResult<DdsSerializedKey>);
+// Incomplete fragmented samples of best-effort writers are considered lost
+// if they are not completed within this time after their first fragment is received
+const DATA_FRAG_TIMEOUT: Duration = Duration::new(1, 0);
+
+struct InstanceHandleBuilder {
+    deserialize_data_to_key: fn(&[u8], Option<&DynamicType>) -> DdsResult<DdsSerializedKey>,
+    deserialize_data_to_key_hash: fn(&[u8], Option<&DynamicType>) -> DdsResult<InstanceHandle>,
+    deserialize_key_to_key_hash: fn(&[u8], Option<&DynamicType>) -> DdsResult<InstanceHandle>,
+    dynamic_type: Option<DynamicType>,
+}
 
 impl InstanceHandleBuilder {
     fn new<Foo>() -> Self
     where
-        Foo: for<'de> serde::Deserialize<'de> + DdsHasKey + DdsGetKey + DdsRepresentation,
+        Foo: for<'de> DdsDeserialize<'de>
+            + DdsHasKey
+            + DdsGetKey
+            + DdsSetKeyFields
+            + DdsRepresentation,
     {
-        fn deserialize_data_to_key<Foo>(data: &mut &[u8]) -> DdsResult<DdsSerializedKey>
+        fn deserialize_data_to_key<Foo>(
+            data: &[u8],
+            dynamic_type: Option<&DynamicType>,
+        ) -> DdsResult<DdsSerializedKey>
         where
-            Foo: for<'de> serde::Deserialize<'de> + DdsHasKey + DdsGetKey + DdsRepresentation,
+            Foo: for<'de> DdsDeserialize<'de> + DdsHasKey + DdsGetKey + DdsRepresentation,
         {
             dds_serialize_key(
-                &dds_deserialize_from_bytes::<Foo>(data)
+                &Foo::deserialize_sample(data, dynamic_type)
                     .map_err(|_| DdsError::Error("Failed to deserialize data".to_string()))?,
             )
             .map_err(|_| DdsError::Error("Failed to serialize key".to_string()))
         }
 
-        Self(deserialize_data_to_key::<Foo>)
+        fn deserialize_data_to_key_hash<Foo>(
+            data: &[u8],
+            dynamic_type: Option<&DynamicType>,
+        ) -> DdsResult<InstanceHandle>
+        where
+            Foo: for<'de> DdsDeserialize<'de> + DdsHasKey + DdsGetKey + DdsRepresentation,
+        {
+            dds_key_hash(
+                &Foo::deserialize_sample(data, dynamic_type)
+                    .map_err(|_| DdsError::Error("Failed to deserialize data".to_string()))?,
+            )
+            .map_err(|_| DdsError::Error("Failed to compute key hash".to_string()))
+        }
+
+        Self {
+            deserialize_data_to_key: deserialize_data_to_key::<Foo>,
+            deserialize_data_to_key_hash: deserialize_data_to_key_hash::<Foo>,
+            deserialize_key_to_key_hash: Foo::serialized_key_hash,
+            dynamic_type: None,
+        }
     }
 
     fn build_instance_handle(
         &self,
         change_kind: ChangeKind,
-        mut data: &[u8],
+        data: &[u8],
         inline_qos: &[Parameter],
     ) -> DdsResult<InstanceHandle> {
         Ok(match change_kind {
-            ChangeKind::Alive | ChangeKind::AliveFiltered => (self.0)(&mut data)?.into(),
+            ChangeKind::Alive | ChangeKind::AliveFiltered => {
+                (self.deserialize_data_to_key_hash)(data, self.dynamic_type.as_ref())?
+            }
             ChangeKind::NotAliveDisposed
             | ChangeKind::NotAliveUnregistered
             | ChangeKind::NotAliveDisposedUnregistered => match inline_qos
                 .iter()
                 .find(|&x| x.parameter_id() == PID_KEY_HASH)
             {
-                Some(p) => InstanceHandle::new(p.value().try_into().unwrap()),
-                None => DdsSerializedKey::from(data[4..].to_vec()).into(),
+                Some(p) => InstanceHandle::new(p.value().try_into().map_err(|_| {
+                    DdsError::Error("Key hash parameter must have 16 bytes".to_string())
+                })?),
+                None => (self.deserialize_key_to_key_hash)(data, self.dynamic_type.as_ref())?,
             },
         })
     }
+
+    fn build_serialized_key(&self, data: &[u8]) -> DdsResult<DdsSerializedKey> {
+        (self.deserialize_data_to_key)(data, self.dynamic_type.as_ref())
+    }
 }
 
 impl SampleLostStatus {
-    fn _increment(&mut self) {
-        self.total_count += 1;
-        self.total_count_change += 1;
+    fn increment(&mut self, number_of_lost_samples: i32) {
+        self.total_count += number_of_lost_samples;
+        self.total_count_change += number_of_lost_samples;
     }
 
     fn read_and_reset(&mut self) -> Self {
@@ -234,6 +288,8 @@ pub struct DdsDataReader {
     qos: DataReaderQos,
     instance_handle_builder: InstanceHandleBuilder,
     type_name: String,
+    type_information: Option<TypeInformation>,
+    type_objects: Vec<TypeObject>,
     topic_name: String,
     liveliness_changed_status: LivelinessChangedStatus,
     requested_deadline_missed_status: RequestedDeadlineMissedStatus,
@@ -244,12 +300,20 @@ pub struct DdsDataReader {
     matched_publication_list: HashMap<InstanceHandle, PublicationBuiltinTopicData>,
     enabled: bool,
     instance_reception_time: HashMap<InstanceHandle, Time>,
+    instance_not_alive_time: HashMap<InstanceHandle, Time>,
     data_available_status_changed_flag: bool,
     incompatible_writer_list: HashSet<InstanceHandle>,
+    inconsistent_writer_list: HashSet<InstanceHandle>,
     status_condition: DdsShared<DdsRwLock<StatusConditionImpl>>,
     listener: Option<Actor<DdsDataReaderListener>>,
     status_kind: Vec<StatusKind>,
     instances: HashMap<InstanceHandle, Instance>,
+    instance_serialized_key: HashMap<InstanceHandle, DdsSerializedKey>,
+    multitopic_join: Option<MultiTopicJoin>,
+    multitopic_source_readers: Vec<ActorAddress<DdsDataReader>>,
+    // Collects the changes added to the reader cache while a message is processed for
+    // the discovery. It is only set for the duration of the processing of that message.
+    added_changes: Option<Vec<RtpsReaderCacheChange>>,
 }
 
 impl DdsDataReader {
@@ -262,7 +326,11 @@ impl DdsDataReader {
         status_kind: Vec<StatusKind>,
     ) -> Self
     where
-        Foo: for<'de> serde::Deserialize<'de> + DdsHasKey + DdsGetKey + DdsRepresentation,
+        Foo: for<'de> DdsDeserialize<'de>
+            + DdsHasKey
+            + DdsGetKey
+            + DdsSetKeyFields
+            + DdsRepresentation,
     {
         let instance_handle_builder = InstanceHandleBuilder::new::<Foo>();
 
@@ -271,6 +339,8 @@ impl DdsDataReader {
             matched_writers: Vec::new(),
             changes: Vec::new(),
             type_name,
+            type_information: Foo::type_information(),
+            type_objects: Foo::minimal_type_objects(),
             topic_name,
             liveliness_changed_status: LivelinessChangedStatus::default(),
             requested_deadline_missed_status: RequestedDeadlineMissedStatus::default(),
@@ -281,17 +351,35 @@ impl DdsDataReader {
             matched_publication_list: HashMap::new(),
             enabled: false,
             instance_reception_time: HashMap::new(),
+            instance_not_alive_time: HashMap::new(),
             data_available_status_changed_flag: false,
             incompatible_writer_list: HashSet::new(),
+            inconsistent_writer_list: HashSet::new(),
             status_condition: DdsShared::new(DdsRwLock::new(StatusConditionImpl::default())),
             status_kind,
             listener,
             qos,
             instance_handle_builder,
             instances: HashMap::new(),
+            instance_serialized_key: HashMap::new(),
+            multitopic_join: None,
+            multitopic_source_readers: Vec::new(),
+            added_changes: None,
         }
     }
 
+    pub fn set_multitopic_join(&mut self, multitopic_join: MultiTopicJoin) {
+        self.multitopic_join = Some(multitopic_join);
+    }
+
+    pub fn set_dynamic_type(&mut self, dynamic_type: DynamicType) {
+        self.instance_handle_builder.dynamic_type = Some(dynamic_type);
+    }
+
+    pub fn get_dynamic_type(&self) -> Option<DynamicType> {
+        self.instance_handle_builder.dynamic_type.clone()
+    }
+
     pub fn get_type_name(&self) -> String {
         self.type_name.clone()
     }
@@ -301,22 +389,37 @@ impl DdsDataReader {
     }
 
     pub fn get_liveliness_changed_status(&mut self) -> LivelinessChangedStatus {
+        self.status_condition
+            .write_lock()
+            .remove_communication_state(StatusKind::LivelinessChanged);
         self.liveliness_changed_status.read_and_reset()
     }
 
     pub fn get_requested_deadline_missed_status(&mut self) -> RequestedDeadlineMissedStatus {
+        self.status_condition
+            .write_lock()
+            .remove_communication_state(StatusKind::RequestedDeadlineMissed);
         self.requested_deadline_missed_status.read_and_reset()
     }
 
     pub fn get_requested_incompatible_qos_status(&mut self) -> RequestedIncompatibleQosStatus {
+        self.status_condition
+            .write_lock()
+            .remove_communication_state(StatusKind::RequestedIncompatibleQos);
         self.requested_incompatible_qos_status.read_and_reset()
     }
 
     pub fn get_sample_lost_status(&mut self) -> SampleLostStatus {
+        self.status_condition
+            .write_lock()
+            .remove_communication_state(StatusKind::SampleLost);
         self.sample_lost_status.read_and_reset()
     }
 
     pub fn get_sample_rejected_status(&mut self) -> SampleRejectedStatus {
+        self.status_condition
+            .write_lock()
+            .remove_communication_state(StatusKind::SampleRejected);
         self.sample_rejected_status.read_and_reset()
     }
 
@@ -398,6 +501,9 @@ impl DdsDataReader {
                     self.on_gap_submessage_received(
                         &gap_submessage,
                         message_receiver.source_guid_prefix(),
+                        &data_reader_address,
+                        &subscriber_address,
+                        &participant_address,
                     );
                 }
                 RtpsSubmessageReadKind::Heartbeat(heartbeat_submessage) => self
@@ -508,7 +614,7 @@ impl DdsDataReader {
             .iter_mut()
             .find(|wp| wp.remote_writer_guid() == writer_guid)
         {
-            writer_proxy.push_data_frag(data_frag_submessage);
+            writer_proxy.push_data_frag(data_frag_submessage, reception_timestamp);
             let cache_change = writer_proxy.extract_frag(sequence_number).map(|data| {
                 self.convert_received_data_to_cache_change(
                     writer_guid,
@@ -604,6 +710,28 @@ impl DdsDataReader {
             && publication_builtin_topic_data.get_type_name() == self.type_name
         {
             let instance_handle = dds_serialize_key(&discovered_writer_data).unwrap().into();
+            if !is_type_consistent(
+                self.type_information.as_ref(),
+                &self.type_objects,
+                publication_builtin_topic_data.type_information(),
+                discovered_writer_data.type_objects(),
+                &self.qos.type_consistency,
+            ) {
+                if self.matched_publication_list.contains_key(&instance_handle) {
+                    self.remove_matched_writer(
+                        instance_handle,
+                        data_reader_address,
+                        subscriber_address,
+                        participant_address.clone(),
+                    );
+                }
+                if self.inconsistent_writer_list.insert(instance_handle) {
+                    self.on_inconsistent_topic(&participant_address);
+                }
+                return;
+            }
+            self.inconsistent_writer_list.remove(&instance_handle);
+
             let incompatible_qos_policy_list = self
                 .get_discovered_writer_incompatible_qos_policy_list(
                     &discovered_writer_data,
@@ -649,6 +777,7 @@ impl DdsDataReader {
                 );
 
                 self.matched_writer_add(writer_proxy);
+                self.incompatible_writer_list.remove(&instance_handle);
                 let insert_matched_publication_result = self
                     .matched_publication_list
                     .insert(instance_handle, publication_builtin_topic_data.clone());
@@ -668,13 +797,32 @@ impl DdsDataReader {
                     ),
                     _ => (),
                 }
-            } else if self.incompatible_writer_list.insert(instance_handle) {
-                self.on_requested_incompatible_qos(
-                    incompatible_qos_policy_list,
-                    &data_reader_address,
-                    &subscriber_address,
-                    &participant_address,
-                );
+            } else {
+                if self.matched_publication_list.contains_key(&instance_handle) {
+                    self.remove_matched_writer(
+                        instance_handle,
+                        data_reader_address.clone(),
+                        subscriber_address.clone(),
+                        participant_address.clone(),
+                    );
+                }
+
+                if self.incompatible_writer_list.insert(instance_handle) {
+                    self.on_requested_incompatible_qos(
+                        incompatible_qos_policy_list,
+                        &data_reader_address,
+                        &subscriber_address,
+                        &participant_address,
+                    );
+                }
+            }
+        }
+    }
+
+    fn on_inconsistent_topic(&self, participant_address: &ActorAddress<DdsDomainParticipant>) {
+        for topic in participant_address.get_user_defined_topic_list().unwrap() {
+            if topic.get_name() == Ok(self.topic_name.clone()) {
+                topic.on_inconsistent_topic().ok();
             }
         }
     }
@@ -714,6 +862,12 @@ impl DdsDataReader {
         if &self.qos.destination_order > writer_info.destination_order() {
             incompatible_qos_policy_list.push(DESTINATIONORDER_QOS_POLICY_ID);
         }
+        if !is_data_representation_compatible(
+            writer_info.representation(),
+            &self.qos.representation,
+        ) {
+            incompatible_qos_policy_list.push(DATAREPRESENTATION_QOS_POLICY_ID);
+        }
 
         incompatible_qos_policy_list
     }
@@ -749,7 +903,7 @@ impl DdsDataReader {
         specific_instance_handle: Option<InstanceHandle>,
     ) -> DdsResult<Vec<Sample<Foo>>>
     where
-        Foo: DdsRepresentation + for<'de> serde::Deserialize<'de>,
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
     {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
@@ -759,7 +913,7 @@ impl DdsDataReader {
             .write_lock()
             .remove_communication_state(StatusKind::DataAvailable);
 
-        let indexed_sample_list = self.create_indexed_sample_collection::<Foo>(
+        let indexed_sample_info_list = self.create_indexed_sample_collection(
             max_samples,
             sample_states,
             view_states,
@@ -767,16 +921,261 @@ impl DdsDataReader {
             specific_instance_handle,
         )?;
 
-        let change_index_list: Vec<usize>;
-        let samples;
+        let samples = indexed_sample_info_list
+            .iter()
+            .map(|(index, sample_info)| self.create_sample(*index, sample_info.clone()))
+            .collect::<DdsResult<Vec<Sample<Foo>>>>()?;
+
+        self.mark_samples_read(&indexed_sample_info_list);
 
-        (change_index_list, samples) = indexed_sample_list.into_iter().map(|(i, s)| (i, s)).unzip();
+        Ok(samples)
+    }
 
-        for index in change_index_list {
-            self.changes[index].sample_state = SampleStateKind::Read;
+    /// Same as [`Self::read`] but instead of deserializing the samples it returns the serialized
+    /// data stored in the reader cache. The data is shared with the cache changes so no copy
+    /// of the payload is made.
+    pub fn read_loaned(
+        &mut self,
+        max_samples: i32,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        specific_instance_handle: Option<InstanceHandle>,
+    ) -> DdsResult<Vec<(Data, SampleInfo)>> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
         }
 
-        Ok(samples)
+        self.status_condition
+            .write_lock()
+            .remove_communication_state(StatusKind::DataAvailable);
+
+        let indexed_sample_info_list = self.create_indexed_sample_collection(
+            max_samples,
+            sample_states,
+            view_states,
+            instance_states,
+            specific_instance_handle,
+        )?;
+
+        let loaned_samples = indexed_sample_info_list
+            .iter()
+            .map(|(index, sample_info)| (self.changes[*index].data.clone(), sample_info.clone()))
+            .collect();
+
+        self.mark_samples_read(&indexed_sample_info_list);
+
+        Ok(loaned_samples)
+    }
+
+    /// Returns all the samples in the reader cache without changing their sample state,
+    /// the view state of their instances or the DataAvailable status. This allows the
+    /// discovery to process the data received by the builtin readers while these readers
+    /// remain usable by the application like any other reader.
+    pub fn peek<Foo>(&self) -> DdsResult<Vec<Sample<Foo>>>
+    where
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
+    {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        self.create_indexed_sample_collection(
+            i32::MAX,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+            None,
+        )?
+        .into_iter()
+        .map(|(index, sample_info)| self.create_sample(index, sample_info))
+        .collect()
+    }
+
+    /// Processes the message like [`Self::process_rtps_message`] and returns only the samples
+    /// it added to the reader cache. As with [`Self::peek`], the samples are left untouched in
+    /// the cache so that the discovery does not have to go over the whole cache of the builtin
+    /// readers for every message it receives.
+    pub fn process_rtps_message_and_get_added_samples<Foo>(
+        &mut self,
+        message: RtpsMessageRead,
+        reception_timestamp: Time,
+        data_reader_address: ActorAddress<DdsDataReader>,
+        subscriber_address: ActorAddress<DdsSubscriber>,
+        participant_address: ActorAddress<DdsDomainParticipant>,
+    ) -> DdsResult<Vec<Sample<Foo>>>
+    where
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
+    {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        self.added_changes = Some(Vec::new());
+        self.process_rtps_message(
+            message,
+            reception_timestamp,
+            data_reader_address,
+            subscriber_address,
+            participant_address,
+        );
+        let added_changes = self.added_changes.take().unwrap_or_default();
+
+        added_changes
+            .iter()
+            .filter_map(|cache_change| {
+                // The instance might have been removed by a later change of the same message
+                let instance = self.instances.get(&cache_change.instance_handle)?;
+                let valid_data = match cache_change.kind {
+                    ChangeKind::Alive | ChangeKind::AliveFiltered => true,
+                    ChangeKind::NotAliveDisposed
+                    | ChangeKind::NotAliveUnregistered
+                    | ChangeKind::NotAliveDisposedUnregistered => false,
+                };
+                let sample_info = SampleInfo {
+                    sample_state: cache_change.sample_state,
+                    view_state: instance.view_state,
+                    instance_state: instance.instance_state,
+                    disposed_generation_count: cache_change.disposed_generation_count,
+                    no_writers_generation_count: cache_change.no_writers_generation_count,
+                    sample_rank: 0,
+                    generation_rank: 0,
+                    absolute_generation_rank: (instance.most_recent_disposed_generation_count
+                        + instance.most_recent_no_writers_generation_count)
+                        - (cache_change.disposed_generation_count
+                            + cache_change.no_writers_generation_count),
+                    source_timestamp: cache_change.source_timestamp,
+                    instance_handle: cache_change.instance_handle,
+                    publication_handle: cache_change.writer_guid.into(),
+                    valid_data,
+                };
+                let data = if valid_data {
+                    match Foo::deserialize_sample(
+                        cache_change.data.as_ref(),
+                        self.instance_handle_builder.dynamic_type.as_ref(),
+                    ) {
+                        Ok(data) => Some(data),
+                        Err(e) => return Some(Err(e)),
+                    }
+                } else {
+                    None
+                };
+                Some(Ok(Sample { data, sample_info }))
+            })
+            .collect()
+    }
+
+    /// Returns the timestamps of the samples in the reader cache which match the given states.
+    /// These are used by the subscriber to order the access to the samples across its readers.
+    pub fn get_sample_timestamp_list(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> Vec<Time> {
+        self.changes
+            .iter()
+            .filter(|cc| {
+                sample_states.contains(&cc.sample_state)
+                    && self.instances.get(&cc.instance_handle).is_some_and(|i| {
+                        view_states.contains(&i.view_state)
+                            && instance_states.contains(&i.instance_state)
+                    })
+            })
+            .map(|cc| cc.source_timestamp.unwrap_or(cc.reception_timestamp))
+            .collect()
+    }
+
+    pub fn notify_data_available(
+        &self,
+        data_reader_address: ActorAddress<DdsDataReader>,
+        subscriber_address: ActorAddress<DdsSubscriber>,
+        participant_address: ActorAddress<DdsDomainParticipant>,
+    ) {
+        if self
+            .status_condition
+            .read_lock()
+            .get_status_changes()
+            .contains(&StatusKind::DataAvailable)
+        {
+            if let Some(listener) = &self.listener {
+                listener
+                    .address()
+                    .trigger_on_data_available(DataReaderNode::new(
+                        data_reader_address,
+                        subscriber_address,
+                        participant_address,
+                    ))
+                    .expect("Should not fail to send message");
+            }
+        }
+    }
+
+    pub fn set_multitopic_source_readers(
+        &mut self,
+        multitopic_source_readers: Vec<ActorAddress<DdsDataReader>>,
+    ) {
+        self.multitopic_source_readers = multitopic_source_readers;
+    }
+
+    pub fn get_multitopic_source_readers(&self) -> Vec<ActorAddress<DdsDataReader>> {
+        self.multitopic_source_readers.clone()
+    }
+
+    /// Add a sample received by the reader of one of the topics related by the MultiTopic this reader
+    /// was created on. The joined samples it produces are added to the reader and the joined instances
+    /// which can no longer be produced are disposed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_multitopic_source_sample(
+        &mut self,
+        related_topic_index: usize,
+        instance_handle: InstanceHandle,
+        value: Option<serde_json::Value>,
+        source_timestamp: Option<Time>,
+        reception_timestamp: Time,
+        data_reader_address: ActorAddress<DdsDataReader>,
+        subscriber_address: ActorAddress<DdsSubscriber>,
+        participant_address: ActorAddress<DdsDomainParticipant>,
+    ) -> DdsResult<()> {
+        let joined_changes = self
+            .multitopic_join
+            .as_mut()
+            .ok_or(DdsError::IllegalOperation)?
+            .add_sample(related_topic_index, instance_handle, value)?;
+
+        for joined_change in joined_changes {
+            let (key_flag, inline_qos, data) = match joined_change {
+                JoinedChange::Alive { data, .. } => (false, ParameterList::empty(), data),
+                JoinedChange::Disposed { instance_handle } => {
+                    let mut serialized_status_info = Vec::new();
+                    let mut serializer =
+                        cdr::Serializer::<_, cdr::LittleEndian>::new(&mut serialized_status_info);
+                    serde::Serialize::serialize(&STATUS_INFO_DISPOSED, &mut serializer)
+                        .map_err(|e| DdsError::Error(e.to_string()))?;
+                    let inline_qos = ParameterList::new(vec![
+                        Parameter::new(PID_STATUS_INFO, serialized_status_info),
+                        Parameter::new(PID_KEY_HASH, instance_handle.as_ref().to_vec()),
+                    ]);
+                    (true, inline_qos, Vec::new())
+                }
+            };
+            let cache_change = self.convert_received_data_to_cache_change(
+                GUID_UNKNOWN,
+                key_flag,
+                inline_qos,
+                Data::new(data),
+                source_timestamp,
+                reception_timestamp,
+            )?;
+            self.add_change(
+                cache_change,
+                &data_reader_address,
+                &subscriber_address,
+                &participant_address,
+            );
+        }
+
+        Ok(())
     }
 
     pub fn take<Foo>(
@@ -788,13 +1187,13 @@ impl DdsDataReader {
         specific_instance_handle: Option<InstanceHandle>,
     ) -> DdsResult<Vec<Sample<Foo>>>
     where
-        Foo: DdsRepresentation + for<'de> serde::Deserialize<'de>,
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
     {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
         }
 
-        let indexed_sample_list = self.create_indexed_sample_collection::<Foo>(
+        let indexed_sample_info_list = self.create_indexed_sample_collection(
             max_samples,
             sample_states,
             view_states,
@@ -806,16 +1205,50 @@ impl DdsDataReader {
             .write_lock()
             .remove_communication_state(StatusKind::DataAvailable);
 
-        let mut change_index_list: Vec<usize>;
-        let samples;
+        let samples = indexed_sample_info_list
+            .iter()
+            .map(|(index, sample_info)| self.create_sample(*index, sample_info.clone()))
+            .collect::<DdsResult<Vec<Sample<Foo>>>>()?;
 
-        (change_index_list, samples) = indexed_sample_list.into_iter().map(|(i, s)| (i, s)).unzip();
+        self.remove_taken_samples(&indexed_sample_info_list);
 
-        while let Some(index) = change_index_list.pop() {
-            self.changes.remove(index);
+        Ok(samples)
+    }
+
+    /// Same as [`Self::take`] but instead of deserializing the samples it returns the serialized
+    /// data which was stored in the reader cache.
+    pub fn take_loaned(
+        &mut self,
+        max_samples: i32,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        specific_instance_handle: Option<InstanceHandle>,
+    ) -> DdsResult<Vec<(Data, SampleInfo)>> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
         }
 
-        Ok(samples)
+        let indexed_sample_info_list = self.create_indexed_sample_collection(
+            max_samples,
+            sample_states,
+            view_states,
+            instance_states,
+            specific_instance_handle,
+        )?;
+
+        self.status_condition
+            .write_lock()
+            .remove_communication_state(StatusKind::DataAvailable);
+
+        let loaned_samples = indexed_sample_info_list
+            .iter()
+            .map(|(index, sample_info)| (self.changes[*index].data.clone(), sample_info.clone()))
+            .collect();
+
+        self.remove_taken_samples(&indexed_sample_info_list);
+
+        Ok(loaned_samples)
     }
 
     pub fn read_next_instance<Foo>(
@@ -827,7 +1260,7 @@ impl DdsDataReader {
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>>
     where
-        Foo: DdsRepresentation + for<'de> serde::Deserialize<'de>,
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
     {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
@@ -854,7 +1287,7 @@ impl DdsDataReader {
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>>
     where
-        Foo: DdsRepresentation + for<'de> serde::Deserialize<'de>,
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
     {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
@@ -872,20 +1305,30 @@ impl DdsDataReader {
         }
     }
 
-    pub fn get_key_value<Foo>(
-        &self,
-        _key_holder: &mut Foo,
-        _handle: InstanceHandle,
-    ) -> DdsResult<()> {
+    pub fn get_key_value(&self, handle: InstanceHandle) -> DdsResult<DdsSerializedKey> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
         }
 
-        todo!()
+        self.instance_serialized_key
+            .get(&handle)
+            .cloned()
+            .ok_or(DdsError::BadParameter)
     }
 
-    pub fn lookup_instance<Foo>(&self, _instance: &Foo) -> DdsResult<Option<InstanceHandle>> {
-        todo!()
+    pub fn lookup_instance(
+        &self,
+        instance_handle: InstanceHandle,
+    ) -> DdsResult<Option<InstanceHandle>> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        if self.instances.contains_key(&instance_handle) {
+            Ok(Some(instance_handle))
+        } else {
+            Ok(None)
+        }
     }
 
     pub fn is_historical_data_received(&self) -> DdsResult<bool> {
@@ -921,6 +1364,15 @@ impl DdsDataReader {
         self.qos.clone()
     }
 
+    pub fn set_listener(
+        &mut self,
+        listener: Option<Actor<DdsDataReaderListener>>,
+        status_kind: Vec<StatusKind>,
+    ) {
+        self.listener = listener;
+        self.status_kind = status_kind;
+    }
+
     pub fn get_instance_handle(&self) -> InstanceHandle {
         self.rtps_reader.guid().into()
     }
@@ -974,7 +1426,11 @@ impl DdsDataReader {
                 subscriber_qos.partition.clone(),
                 topic_qos.topic_data,
                 subscriber_qos.group_data,
+                self.qos.representation.clone(),
+                self.qos.type_consistency.clone(),
+                self.type_information.clone(),
             ),
+            self.type_objects.clone(),
         )
     }
 
@@ -995,6 +1451,28 @@ impl DdsDataReader {
         subscriber_address: ActorAddress<DdsSubscriber>,
         participant_address: ActorAddress<DdsDomainParticipant>,
     ) {
+        self.purge_not_alive_instances(now);
+
+        let matched_publication_list = &self.matched_publication_list;
+        self.changes
+            .retain(|cc| !is_change_lifespan_expired(cc, matched_publication_list, now));
+
+        if self.qos.reliability.kind == ReliabilityQosPolicyKind::BestEffort {
+            let number_of_expired_frags: usize = self
+                .matched_writers
+                .iter_mut()
+                .map(|wp| wp.remove_expired_frags(now, DATA_FRAG_TIMEOUT).len())
+                .sum();
+            if number_of_expired_frags > 0 {
+                self.on_sample_lost(
+                    number_of_expired_frags as i32,
+                    &data_reader_address,
+                    &subscriber_address,
+                    &participant_address,
+                );
+            }
+        }
+
         let (missed_deadline_instances, instance_reception_time) = self
             .instance_reception_time
             .iter()
@@ -1066,6 +1544,9 @@ impl DdsDataReader {
         &mut self,
         gap_submessage: &GapSubmessageRead,
         source_guid_prefix: GuidPrefix,
+        data_reader_address: &ActorAddress<DdsDataReader>,
+        subscriber_address: &ActorAddress<DdsSubscriber>,
+        participant_address: &ActorAddress<DdsDomainParticipant>,
     ) {
         let writer_guid = Guid::new(source_guid_prefix, gap_submessage.writer_id());
         if let Some(writer_proxy) = self
@@ -1073,28 +1554,96 @@ impl DdsDataReader {
             .iter_mut()
             .find(|x| x.remote_writer_guid() == writer_guid)
         {
-            for seq_num in
-                i64::from(gap_submessage.gap_start())..i64::from(gap_submessage.gap_list().base())
-            {
-                writer_proxy.irrelevant_change_set(SequenceNumber::from(seq_num))
+            let gap_seq_num_list: Vec<SequenceNumber> = (i64::from(gap_submessage.gap_start())
+                ..i64::from(gap_submessage.gap_list().base()))
+                .map(SequenceNumber::from)
+                .chain(gap_submessage.gap_list().set().iter().cloned())
+                .collect();
+
+            // Changes that are skipped before anything is received from the writer are
+            // the ones which are not relevant for this reader (e.g. volatile durability).
+            // Afterwards any change which was not yet received is not going to be received anymore.
+            let highest_received_change_sn = writer_proxy.highest_received_change_sn();
+            let mut number_of_lost_changes = 0;
+            for seq_num in gap_seq_num_list {
+                if highest_received_change_sn > SequenceNumber::from(0)
+                    && seq_num > highest_received_change_sn
+                    && !writer_proxy.is_irrelevant_change(seq_num)
+                {
+                    number_of_lost_changes += 1;
+                }
+                writer_proxy.irrelevant_change_set(seq_num)
             }
 
-            for seq_num in gap_submessage.gap_list().set() {
-                writer_proxy.irrelevant_change_set(*seq_num)
+            if number_of_lost_changes > 0 {
+                self.on_sample_lost(
+                    number_of_lost_changes,
+                    data_reader_address,
+                    subscriber_address,
+                    participant_address,
+                );
             }
         }
     }
 
-    fn on_sample_lost(&mut self) {
-        todo!()
-        // self.sample_lost_status.increment();
-        // listener_sender
-        //     .try_send(ListenerTriggerKind::OnSampleLost(DataReaderNode::new(
-        //         self.guid(),
-        //         parent_subscriber_guid,
-        //         parent_participant_guid,
-        //     )))
-        //     .ok();
+    fn on_sample_lost(
+        &mut self,
+        number_of_lost_samples: i32,
+        data_reader_address: &ActorAddress<DdsDataReader>,
+        subscriber_address: &ActorAddress<DdsSubscriber>,
+        participant_address: &ActorAddress<DdsDomainParticipant>,
+    ) {
+        self.sample_lost_status.increment(number_of_lost_samples);
+        subscriber_address
+            .increment_sample_lost_status(number_of_lost_samples)
+            .expect("Should not fail to send message");
+        self.status_condition
+            .write_lock()
+            .add_communication_state(StatusKind::SampleLost);
+        if self.listener.is_some() && self.status_kind.contains(&StatusKind::SampleLost) {
+            let status = self.get_sample_lost_status();
+            let listener_address = self.listener.as_ref().unwrap().address();
+            let reader = DataReaderNode::new(
+                data_reader_address.clone(),
+                subscriber_address.clone(),
+                participant_address.clone(),
+            );
+            listener_address
+                .trigger_on_sample_lost(reader, status)
+                .expect("Should not fail to send message");
+        } else if subscriber_address.get_listener().unwrap().is_some()
+            && subscriber_address
+                .status_kind()
+                .unwrap()
+                .contains(&StatusKind::SampleLost)
+        {
+            let status = self.get_sample_lost_status();
+            let listener_address = subscriber_address.get_listener().unwrap().unwrap();
+            let reader = DataReaderNode::new(
+                data_reader_address.clone(),
+                subscriber_address.clone(),
+                participant_address.clone(),
+            );
+            listener_address
+                .trigger_on_sample_lost(reader, status)
+                .expect("Should not fail to send message");
+        } else if participant_address.get_listener().unwrap().is_some()
+            && participant_address
+                .status_kind()
+                .unwrap()
+                .contains(&StatusKind::SampleLost)
+        {
+            let status = self.get_sample_lost_status();
+            let listener_address = participant_address.get_listener().unwrap().unwrap();
+            let reader = DataReaderNode::new(
+                data_reader_address.clone(),
+                subscriber_address.clone(),
+                participant_address.clone(),
+            );
+            listener_address
+                .trigger_on_sample_lost(reader, status)
+                .expect("Should not fail to send message");
+        }
     }
 
     fn on_subscription_matched(
@@ -1330,6 +1879,13 @@ impl DdsDataReader {
 
         match change_kind {
             ChangeKind::Alive | ChangeKind::AliveFiltered => {
+                if !self.instance_serialized_key.contains_key(&instance_handle) {
+                    let serialized_key = self
+                        .instance_handle_builder
+                        .build_serialized_key(data.as_ref())?;
+                    self.instance_serialized_key
+                        .insert(instance_handle, serialized_key);
+                }
                 self.instances
                     .entry(instance_handle)
                     .or_insert_with(Instance::new)
@@ -1351,6 +1907,17 @@ impl DdsDataReader {
             }
         }?;
 
+        match self.instances[&instance_handle].instance_state {
+            InstanceStateKind::Alive => {
+                self.instance_not_alive_time.remove(&instance_handle);
+            }
+            InstanceStateKind::NotAliveDisposed | InstanceStateKind::NotAliveNoWriters => {
+                self.instance_not_alive_time
+                    .entry(instance_handle)
+                    .or_insert(reception_timestamp);
+            }
+        }
+
         Ok(RtpsReaderCacheChange {
             kind: change_kind,
             writer_guid,
@@ -1383,11 +1950,25 @@ impl DdsDataReader {
         match (self.qos.reliability.kind, writer_proxy) {
             (ReliabilityQosPolicyKind::BestEffort, Some(writer_proxy)) => {
                 let expected_seq_num = writer_proxy.available_changes_max() + 1;
-                if sequence_number >= expected_seq_num {
+                if sequence_number >= expected_seq_num
+                    && !writer_proxy.is_irrelevant_change(sequence_number)
+                {
+                    // Any incomplete fragmented change before this one can not be received anymore
+                    // and is counted as lost together with the skipped sequence numbers
+                    let number_of_lost_changes =
+                        writer_proxy.number_of_lost_changes(sequence_number);
                     writer_proxy.received_change_set(sequence_number);
+                    writer_proxy.remove_frags_before(sequence_number);
                     if sequence_number > expected_seq_num {
                         writer_proxy.lost_changes_update(sequence_number);
-                        self.on_sample_lost();
+                        if number_of_lost_changes > 0 {
+                            self.on_sample_lost(
+                                number_of_lost_changes,
+                                data_reader_address,
+                                subscriber_address,
+                                participant_address,
+                            );
+                        }
                     }
                     self.add_change(
                         cache_change,
@@ -1431,6 +2012,14 @@ impl DdsDataReader {
         subscriber_address: &ActorAddress<DdsSubscriber>,
         participant_address: &ActorAddress<DdsDomainParticipant>,
     ) {
+        if is_change_lifespan_expired(
+            &change,
+            &self.matched_publication_list,
+            change.reception_timestamp,
+        ) {
+            return;
+        }
+
         if self.is_sample_of_interest_based_on_time(&change) {
             if self.is_max_samples_limit_reached(&change) {
                 self.on_sample_rejected(
@@ -1481,6 +2070,9 @@ impl DdsDataReader {
 
                 self.instance_reception_time
                     .insert(change.instance_handle, change.reception_timestamp);
+                if let Some(added_changes) = &mut self.added_changes {
+                    added_changes.push(change.clone());
+                }
                 self.changes.push(change);
                 self.data_available_status_changed_flag = true;
 
@@ -1560,17 +2152,14 @@ impl DdsDataReader {
         total_samples_of_instance == self.qos.resource_limits.max_samples_per_instance
     }
 
-    fn create_indexed_sample_collection<Foo>(
-        &mut self,
+    fn create_indexed_sample_collection(
+        &self,
         max_samples: i32,
         sample_states: &[SampleStateKind],
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
         specific_instance_handle: Option<InstanceHandle>,
-    ) -> DdsResult<Vec<(usize, Sample<Foo>)>>
-    where
-        Foo: for<'de> serde::Deserialize<'de> + DdsRepresentation,
-    {
+    ) -> DdsResult<Vec<(usize, SampleInfo)>> {
         if let Some(h) = specific_instance_handle {
             if !self.instances.contains_key(&h) {
                 return Err(DdsError::BadParameter);
@@ -1584,7 +2173,7 @@ impl DdsDataReader {
         let mut instances_in_collection = HashMap::new();
         for (index, cache_change) in self
             .changes
-            .iter_mut()
+            .iter()
             .enumerate()
             .filter(|(_, cc)| {
                 let sample_instance_handle = instance_handle_build
@@ -1630,16 +2219,11 @@ impl DdsDataReader {
                     + instances_in_collection[&sample_instance_handle]
                         .most_recent_no_writers_generation_count);
 
-            let (data, valid_data) = match cache_change.kind {
-                ChangeKind::Alive | ChangeKind::AliveFiltered => (
-                    Some(dds_deserialize_from_bytes::<Foo>(
-                        cache_change.data.as_ref(),
-                    )?),
-                    true,
-                ),
+            let valid_data = match cache_change.kind {
+                ChangeKind::Alive | ChangeKind::AliveFiltered => true,
                 ChangeKind::NotAliveDisposed
                 | ChangeKind::NotAliveUnregistered
-                | ChangeKind::NotAliveDisposedUnregistered => (None, false),
+                | ChangeKind::NotAliveDisposedUnregistered => false,
             };
 
             let sample_info = SampleInfo {
@@ -1657,40 +2241,33 @@ impl DdsDataReader {
                 valid_data,
             };
 
-            let sample = Sample { data, sample_info };
-
-            indexed_samples.push((index, sample))
+            indexed_samples.push((index, sample_info))
         }
 
-        // After the collection is created, update the relative generation rank values and mark the read instances as viewed
+        // After the collection is created, update the relative generation rank values
         for handle in instances_in_collection.into_keys() {
             let most_recent_sample_absolute_generation_rank = indexed_samples
                 .iter()
-                .filter(|(_, s)| s.sample_info.instance_handle == handle)
-                .map(|(_, s)| s.sample_info.absolute_generation_rank)
-                .last()
+                .filter(|(_, s)| s.instance_handle == handle)
+                .map(|(_, s)| s.absolute_generation_rank)
+                .next_back()
                 .expect("Instance handle must exist on collection");
 
             let mut total_instance_samples_in_collection = indexed_samples
                 .iter()
-                .filter(|(_, s)| s.sample_info.instance_handle == handle)
+                .filter(|(_, s)| s.instance_handle == handle)
                 .count();
 
-            for (_, sample) in indexed_samples
+            for (_, sample_info) in indexed_samples
                 .iter_mut()
-                .filter(|(_, s)| s.sample_info.instance_handle == handle)
+                .filter(|(_, s)| s.instance_handle == handle)
             {
-                sample.sample_info.generation_rank = sample.sample_info.absolute_generation_rank
+                sample_info.generation_rank = sample_info.absolute_generation_rank
                     - most_recent_sample_absolute_generation_rank;
 
                 total_instance_samples_in_collection -= 1;
-                sample.sample_info.sample_rank = total_instance_samples_in_collection as i32;
+                sample_info.sample_rank = total_instance_samples_in_collection as i32;
             }
-
-            self.instances
-                .get_mut(&handle)
-                .expect("Sample must exist on hash map")
-                .mark_viewed()
         }
 
         if indexed_samples.is_empty() {
@@ -1700,6 +2277,77 @@ impl DdsDataReader {
         }
     }
 
+    fn create_sample<Foo>(&self, index: usize, sample_info: SampleInfo) -> DdsResult<Sample<Foo>>
+    where
+        Foo: for<'de> DdsDeserialize<'de> + DdsRepresentation,
+    {
+        let data = if sample_info.valid_data {
+            Some(Foo::deserialize_sample(
+                self.changes[index].data.as_ref(),
+                self.instance_handle_builder.dynamic_type.as_ref(),
+            )?)
+        } else {
+            None
+        };
+
+        Ok(Sample { data, sample_info })
+    }
+
+    fn mark_samples_read(&mut self, indexed_sample_info_list: &[(usize, SampleInfo)]) {
+        for (index, _) in indexed_sample_info_list {
+            self.changes[*index].sample_state = SampleStateKind::Read;
+        }
+        self.mark_instances_viewed(indexed_sample_info_list);
+    }
+
+    fn remove_taken_samples(&mut self, indexed_sample_info_list: &[(usize, SampleInfo)]) {
+        // The indices are in increasing order so the changes are removed starting from the
+        // last one to keep the remaining indices valid
+        for (index, _) in indexed_sample_info_list.iter().rev() {
+            self.changes.remove(*index);
+        }
+        self.mark_instances_viewed(indexed_sample_info_list);
+    }
+
+    fn mark_instances_viewed(&mut self, indexed_sample_info_list: &[(usize, SampleInfo)]) {
+        for (_, sample_info) in indexed_sample_info_list {
+            self.instances
+                .get_mut(&sample_info.instance_handle)
+                .expect("Sample must exist on hash map")
+                .mark_viewed()
+        }
+    }
+
+    fn purge_not_alive_instances(&mut self, now: Time) {
+        let reader_data_lifecycle = &self.qos.reader_data_lifecycle;
+        let instances = &self.instances;
+        let purged_instances: Vec<InstanceHandle> = self
+            .instance_not_alive_time
+            .iter()
+            .filter(|(handle, not_alive_time)| {
+                let autopurge_delay = match instances[handle].instance_state {
+                    InstanceStateKind::NotAliveDisposed => {
+                        reader_data_lifecycle.autopurge_disposed_samples_delay
+                    }
+                    InstanceStateKind::NotAliveNoWriters => {
+                        reader_data_lifecycle.autopurge_nowriter_samples_delay
+                    }
+                    InstanceStateKind::Alive => DurationKind::Infinite,
+                };
+                DurationKind::Finite(now - **not_alive_time) > autopurge_delay
+            })
+            .map(|(&handle, _)| handle)
+            .collect();
+
+        for handle in purged_instances {
+            self.changes.retain(|cc| cc.instance_handle != handle);
+            self.instances.remove(&handle);
+            self.instance_serialized_key.remove(&handle);
+            self.instance_reception_time.remove(&handle);
+            self.instance_not_alive_time.remove(&handle);
+        }
+    }
+
     fn next_instance(&self, previous_handle: Option<InstanceHandle>) -> Option<InstanceHandle> {
         match previous_handle {
             Some(p) => self.instances.keys().filter(|&h| h > &p).min().cloned(),
@@ -1707,3 +2355,22 @@ impl DdsDataReader {
         }
     }
 }
+
+fn is_change_lifespan_expired(
+    change: &RtpsReaderCacheChange,
+    matched_publication_list: &HashMap<InstanceHandle, PublicationBuiltinTopicData>,
+    now: Time,
+) -> bool {
+    let writer_key: [u8; 16] = change.writer_guid.into();
+    match (
+        change.source_timestamp,
+        matched_publication_list
+            .values()
+            .find(|p| p.key().value == writer_key),
+    ) {
+        (Some(source_timestamp), Some(publication_data)) => {
+            DurationKind::Finite(now - source_timestamp) > publication_data.lifespan().duration
+        }
+        _ => false,
+    }
+}
@@ -3,8 +3,8 @@ use crate::{
     infrastructure::{
         error::DdsResult,
         status::{
-            RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleRejectedStatus,
-            SubscriptionMatchedStatus,
+            RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleLostStatus,
+            SampleRejectedStatus, SubscriptionMatchedStatus,
         },
     },
 };
@@ -140,4 +140,24 @@ impl ActorAddress<DdsDataReaderListener> {
 
         self.send_command(TriggerOnRequestedDeadlineMissed { reader, status })
     }
+
+    pub fn trigger_on_sample_lost(
+        &self,
+        reader: DataReaderNode,
+        status: SampleLostStatus,
+    ) -> DdsResult<()> {
+        struct TriggerOnSampleLost {
+            reader: DataReaderNode,
+            status: SampleLostStatus,
+        }
+
+        impl CommandHandler<TriggerOnSampleLost> for DdsDataReaderListener {
+            fn handle(&mut self, mail: TriggerOnSampleLost) {
+                self.listener
+                    .trigger_on_sample_lost(mail.reader, mail.status)
+            }
+        }
+
+        self.send_command(TriggerOnSampleLost { reader, status })
+    }
 }
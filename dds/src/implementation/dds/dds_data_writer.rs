@@ -11,7 +11,7 @@ use crate::{
             discovered_writer_data::{DiscoveredWriterData, WriterProxy},
         },
         data_representation_inline_qos::{
-            parameter_id_values::PID_STATUS_INFO,
+            parameter_id_values::{PID_KEY_HASH, PID_STATUS_INFO},
             types::{
                 STATUS_INFO_DISPOSED, STATUS_INFO_DISPOSED_UNREGISTERED, STATUS_INFO_UNREGISTERED,
             },
@@ -23,7 +23,7 @@ use crate::{
                     RtpsMessageHeader, RtpsMessageRead, RtpsMessageWrite, RtpsSubmessageReadKind,
                     RtpsSubmessageWriteKind,
                 },
-                submessage_elements::{Parameter, ParameterList, SequenceNumberSet},
+                submessage_elements::{Data, Parameter, ParameterList, SequenceNumberSet},
                 submessages::{
                     ack_nack::AckNackSubmessageRead, gap::GapSubmessageWrite,
                     info_destination::InfoDestinationSubmessageWrite,
@@ -35,7 +35,8 @@ use crate::{
             reader_proxy::RtpsReaderProxy,
             types::{
                 ChangeKind, EntityId, Guid, GuidPrefix, Locator, ReliabilityKind, SequenceNumber,
-                ENTITYID_UNKNOWN, GUID_UNKNOWN, USER_DEFINED_UNKNOWN,
+                BUILT_IN_WRITER_WITH_KEY, ENTITYID_UNKNOWN, GUID_UNKNOWN, USER_DEFINED_UNKNOWN,
+                USER_DEFINED_WRITER_WITH_KEY,
             },
             writer::RtpsWriter,
         },
@@ -49,10 +50,11 @@ use crate::{
         instance::{InstanceHandle, HANDLE_NIL},
         qos::{PublisherQos, TopicQos},
         qos_policy::{
-            DurabilityQosPolicyKind, QosPolicyId, ReliabilityQosPolicyKind, DEADLINE_QOS_POLICY_ID,
-            DESTINATIONORDER_QOS_POLICY_ID, DURABILITY_QOS_POLICY_ID, INVALID_QOS_POLICY_ID,
-            LATENCYBUDGET_QOS_POLICY_ID, LIVELINESS_QOS_POLICY_ID, PRESENTATION_QOS_POLICY_ID,
-            RELIABILITY_QOS_POLICY_ID,
+            is_data_representation_compatible, DurabilityQosPolicyKind, LivelinessQosPolicyKind,
+            QosPolicyId, ReliabilityQosPolicyKind, DATAREPRESENTATION_QOS_POLICY_ID,
+            DEADLINE_QOS_POLICY_ID, DESTINATIONORDER_QOS_POLICY_ID, DURABILITY_QOS_POLICY_ID,
+            INVALID_QOS_POLICY_ID, LATENCYBUDGET_QOS_POLICY_ID, LIVELINESS_QOS_POLICY_ID,
+            PRESENTATION_QOS_POLICY_ID, RELIABILITY_QOS_POLICY_ID,
         },
         status::{
             LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
@@ -60,9 +62,9 @@ use crate::{
         },
         time::DurationKind,
     },
-    topic_definition::type_support::{
-        dds_serialize_key, dds_set_key_fields_from_serialized_key, DdsSerializedKey,
-        DdsSetKeyFields,
+    topic_definition::{
+        type_object::{is_type_consistent, TypeInformation, TypeObject},
+        type_support::{dds_serialize_key, DdsSerializedKey},
     },
     {
         builtin_topics::SubscriptionBuiltinTopicData,
@@ -74,7 +76,10 @@ use crate::{
     },
 };
 use serde::Serialize;
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+};
 
 struct MatchedSubscriptions {
     matched_subscription_list: HashMap<InstanceHandle, SubscriptionBuiltinTopicData>,
@@ -185,6 +190,10 @@ impl IncompatibleSubscriptions {
         }
     }
 
+    fn remove_incompatible_subscription(&mut self, handle: InstanceHandle) {
+        self.incompatible_subscription_list.remove(&handle);
+    }
+
     fn get_incompatible_subscriptions(&self) -> Vec<InstanceHandle> {
         self.incompatible_subscription_list
             .iter()
@@ -206,14 +215,49 @@ impl IncompatibleSubscriptions {
     }
 }
 
+impl OfferedDeadlineMissedStatus {
+    fn increment(&mut self, instance_handle: InstanceHandle) {
+        self.total_count += 1;
+        self.total_count_change += 1;
+        self.last_instance_handle = instance_handle;
+    }
+
+    fn read_and_reset(&mut self) -> Self {
+        let status = self.clone();
+
+        self.total_count_change = 0;
+
+        status
+    }
+}
+
+impl LivelinessLostStatus {
+    fn increment(&mut self) {
+        self.total_count += 1;
+        self.total_count_change += 1;
+    }
+
+    fn read_and_reset(&mut self) -> Self {
+        let status = self.clone();
+
+        self.total_count_change = 0;
+
+        status
+    }
+}
+
 pub struct DdsDataWriter {
     rtps_writer: RtpsWriter,
     reader_locators: Vec<RtpsReaderLocator>,
     matched_readers: Vec<RtpsReaderProxy>,
     type_name: String,
+    type_information: Option<TypeInformation>,
+    type_objects: Vec<TypeObject>,
+    max_serialized_size: Option<usize>,
     topic_name: String,
     matched_subscriptions: MatchedSubscriptions,
     incompatible_subscriptions: IncompatibleSubscriptions,
+    inconsistent_subscription_list: HashSet<InstanceHandle>,
     enabled: bool,
     status_condition: DdsShared<DdsRwLock<StatusConditionImpl>>,
     listener: Option<Actor<DdsDataWriterListener>>,
@@ -221,6 +265,11 @@ pub struct DdsDataWriter {
     writer_cache: WriterHistoryCache,
     qos: DataWriterQos,
     registered_instance_list: HashMap<InstanceHandle, DdsSerializedKey>,
+    offered_deadline_missed_status: OfferedDeadlineMissedStatus,
+    instance_write_time: HashMap<InstanceHandle, Time>,
+    liveliness_lost_status: LivelinessLostStatus,
+    last_liveliness_assertion: Option<Time>,
+    is_alive: bool,
 }
 
 impl DdsDataWriter {
@@ -237,9 +286,13 @@ impl DdsDataWriter {
             reader_locators: Vec::new(),
             matched_readers: Vec::new(),
             type_name,
+            type_information: None,
+            type_objects: Vec::new(),
+            max_serialized_size: None,
             topic_name,
             matched_subscriptions: MatchedSubscriptions::new(),
             incompatible_subscriptions: IncompatibleSubscriptions::new(),
+            inconsistent_subscription_list: HashSet::new(),
             enabled: false,
             status_condition: DdsShared::new(DdsRwLock::new(StatusConditionImpl::default())),
             listener,
@@ -247,22 +300,25 @@ impl DdsDataWriter {
             writer_cache: WriterHistoryCache::new(),
             qos,
             registered_instance_list: HashMap::new(),
+            offered_deadline_missed_status: OfferedDeadlineMissedStatus::default(),
+            instance_write_time: HashMap::new(),
+            liveliness_lost_status: LivelinessLostStatus::default(),
+            last_liveliness_assertion: None,
+            is_alive: true,
         }
     }
 
-    pub fn get_key_value<Foo>(&self, key_holder: &mut Foo, handle: InstanceHandle) -> DdsResult<()>
-    where
-        Foo: DdsSetKeyFields,
-    {
-        if !self.enabled {
-            return Err(DdsError::NotEnabled);
-        }
+    pub fn set_type_information(
+        &mut self,
+        type_information: Option<TypeInformation>,
+        type_objects: Vec<TypeObject>,
+    ) {
+        self.type_information = type_information;
+        self.type_objects = type_objects;
+    }
 
-        let serialized_key = self
-            .registered_instance_list
-            .get(&handle)
-            .ok_or(DdsError::BadParameter)?;
-        dds_set_key_fields_from_serialized_key(key_holder, serialized_key.as_ref())
+    pub fn set_max_serialized_size(&mut self, max_serialized_size: Option<usize>) {
+        self.max_serialized_size = max_serialized_size;
     }
 
     pub fn reader_locator_list(&mut self) -> &[RtpsReaderLocator] {
@@ -289,6 +345,25 @@ impl DdsDataWriter {
     fn add_change(&mut self, change: RtpsWriterCacheChange) {
         self.writer_cache.add_change(change, &self.qos.history)
     }
+
+    // The key hash is sent with the changes of keyed topics so that the readers can identify the instance
+    // without deserializing the data
+    fn key_hash_parameter(&self, handle: InstanceHandle) -> Option<Parameter> {
+        match self.rtps_writer.guid().entity_id().entity_kind() {
+            USER_DEFINED_WRITER_WITH_KEY | BUILT_IN_WRITER_WITH_KEY => {
+                Some(Parameter::new(PID_KEY_HASH, handle.as_ref().to_vec()))
+            }
+            _ => None,
+        }
+    }
+
+    fn on_inconsistent_topic(&self, participant_address: &ActorAddress<DdsDomainParticipant>) {
+        for topic in participant_address.get_user_defined_topic_list().unwrap() {
+            if topic.get_name() == Ok(self.topic_name.clone()) {
+                topic.on_inconsistent_topic().ok();
+            }
+        }
+    }
 }
 
 actor_interface! {
@@ -341,16 +416,88 @@ impl DdsDataWriter {
     }
 
     pub fn get_offered_incompatible_qos_status(&mut self) -> OfferedIncompatibleQosStatus {
+        self.status_condition
+            .write_lock()
+            .remove_communication_state(StatusKind::OfferedIncompatibleQos);
         self.incompatible_subscriptions
             .get_offered_incompatible_qos_status()
     }
 
-    pub fn get_offered_deadline_missed_status(&self) -> OfferedDeadlineMissedStatus {
-        todo!()
+    pub fn get_offered_deadline_missed_status(&mut self) -> OfferedDeadlineMissedStatus {
+        self.status_condition
+            .write_lock()
+            .remove_communication_state(StatusKind::OfferedDeadlineMissed);
+        self.offered_deadline_missed_status.read_and_reset()
+    }
+
+    pub fn get_liveliness_lost_status(&mut self) -> LivelinessLostStatus {
+        self.status_condition
+            .write_lock()
+            .remove_communication_state(StatusKind::LivelinessLost);
+        self.liveliness_lost_status.read_and_reset()
+    }
+
+    pub fn assert_liveliness(&mut self, now: Time) {
+        self.last_liveliness_assertion = Some(now);
     }
 
-    pub fn get_liveliness_lost_status(&self) -> LivelinessLostStatus {
-        todo!()
+    pub fn on_instance_written(&mut self, instance_handle: InstanceHandle, now: Time) {
+        self.instance_write_time.insert(instance_handle, now);
+        self.assert_liveliness(now);
+    }
+
+    pub fn update_communication_status(
+        &mut self,
+        now: Time,
+        participant_liveliness_assertion: Option<Time>,
+        data_writer_address: ActorAddress<DdsDataWriter>,
+        publisher_address: ActorAddress<DdsPublisher>,
+        participant_address: ActorAddress<DdsDomainParticipant>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut missed_deadline_instances = Vec::new();
+        for (instance_handle, write_time) in self.instance_write_time.iter_mut() {
+            if DurationKind::Finite(now - *write_time) > self.qos.deadline.period {
+                // Restart the period so that every elapsed deadline period is counted
+                *write_time = now;
+                missed_deadline_instances.push(*instance_handle);
+            }
+        }
+        for instance_handle in &missed_deadline_instances {
+            self.offered_deadline_missed_status
+                .increment(*instance_handle);
+        }
+        if !missed_deadline_instances.is_empty() {
+            self.on_offered_deadline_missed(
+                data_writer_address.clone(),
+                publisher_address.clone(),
+                participant_address.clone(),
+            );
+        }
+
+        // The lease of a writer which never asserted its liveliness starts
+        // with the first status update after it is enabled
+        let writer_liveliness_assertion = *self.last_liveliness_assertion.get_or_insert(now);
+        let liveliness_assertion = match self.qos.liveliness.kind {
+            LivelinessQosPolicyKind::Automatic => None,
+            LivelinessQosPolicyKind::ManualByParticipant => Some(
+                participant_liveliness_assertion
+                    .map_or(writer_liveliness_assertion, |t| t.max(writer_liveliness_assertion)),
+            ),
+            LivelinessQosPolicyKind::ManualByTopic => Some(writer_liveliness_assertion),
+        };
+        if let Some(liveliness_assertion) = liveliness_assertion {
+            let is_alive = DurationKind::Finite(now - liveliness_assertion)
+                <= self.qos.liveliness.lease_duration;
+            if self.is_alive && !is_alive {
+                self.liveliness_lost_status.increment();
+                self.on_liveliness_lost(data_writer_address, publisher_address, participant_address);
+            }
+            self.is_alive = is_alive;
+        }
     }
 
     pub fn get_incompatible_subscriptions(&self) -> Vec<InstanceHandle> {
@@ -378,6 +525,15 @@ impl DdsDataWriter {
         self.status_condition.clone()
     }
 
+    pub fn set_listener(
+        &mut self,
+        listener: Option<Actor<DdsDataWriterListener>>,
+        status_kind: Vec<StatusKind>,
+    ) {
+        self.listener = listener;
+        self.status_kind = status_kind;
+    }
+
     pub fn guid(&self) -> Guid {
         self.rtps_writer.guid()
     }
@@ -409,8 +565,15 @@ impl DdsDataWriter {
         self.qos.clone()
     }
 
-    pub fn set_qos(&mut self, qos: DataWriterQos) {
+    pub fn set_qos(&mut self, qos: DataWriterQos) -> DdsResult<()> {
+        if self.enabled {
+            self.qos.check_immutability(&qos)?;
+        }
+
+        qos.is_consistent()?;
         self.qos = qos;
+
+        Ok(())
     }
 
     pub fn process_rtps_message(&mut self, message: RtpsMessageRead) {
@@ -435,14 +598,13 @@ impl DdsDataWriter {
     pub fn register_instance_w_timestamp(
         &mut self,
         instance_serialized_key: DdsSerializedKey,
+        instance_handle: InstanceHandle,
         _timestamp: Time,
     ) -> DdsResult<Option<InstanceHandle>> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
         }
 
-        let instance_handle = instance_serialized_key.clone().into();
-
         if !self.registered_instance_list.contains_key(&instance_handle) {
             if self.registered_instance_list.len() < self.qos.resource_limits.max_instances {
                 self.registered_instance_list
@@ -479,31 +641,40 @@ impl DdsDataWriter {
             STATUS_INFO_UNREGISTERED.serialize(&mut serializer).unwrap();
         }
 
-        let inline_qos = ParameterList::new(vec![Parameter::new(
-            PID_STATUS_INFO,
-            serialized_status_info,
-        )]);
+        let mut inline_qos = vec![Parameter::new(PID_STATUS_INFO, serialized_status_info)];
+        inline_qos.extend(self.key_hash_parameter(handle));
 
         let change: RtpsWriterCacheChange = self.rtps_writer.new_change(
             ChangeKind::NotAliveUnregistered,
-            instance_serialized_key,
-            inline_qos,
+            Data::new(instance_serialized_key),
+            ParameterList::new(inline_qos),
             handle,
             timestamp,
         );
 
         self.add_change(change);
+        self.instance_write_time.remove(&handle);
         Ok(())
     }
 
+    pub fn get_key_value(&self, handle: InstanceHandle) -> DdsResult<DdsSerializedKey> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+
+        self.registered_instance_list
+            .get(&handle)
+            .cloned()
+            .ok_or(DdsError::BadParameter)
+    }
+
     pub fn lookup_instance(
         &self,
-        instance_serialized_key: DdsSerializedKey,
+        instance_handle: InstanceHandle,
     ) -> DdsResult<Option<InstanceHandle>> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
         }
-        let instance_handle = instance_serialized_key.into();
 
         Ok(
         if self.registered_instance_list.contains_key(&instance_handle) {
@@ -515,18 +686,19 @@ impl DdsDataWriter {
 
     pub fn write_w_timestamp(
         &mut self,
-        serialized_data: Vec<u8>,
+        serialized_data: Data,
         instance_serialized_key: DdsSerializedKey,
+        instance_handle: InstanceHandle,
         _handle: Option<InstanceHandle>,
         timestamp: Time,
     ) -> DdsResult<()> {
         let handle = self
-            .register_instance_w_timestamp(instance_serialized_key, timestamp)?
+            .register_instance_w_timestamp(instance_serialized_key, instance_handle, timestamp)?
             .unwrap_or(HANDLE_NIL);
         let change = self.rtps_writer.new_change(
             ChangeKind::Alive,
             serialized_data,
-            ParameterList::empty(),
+            ParameterList::new(self.key_hash_parameter(handle).into_iter().collect()),
             handle,
             timestamp,
         );
@@ -551,15 +723,13 @@ impl DdsDataWriter {
             cdr::Serializer::<_, cdr::LittleEndian>::new(&mut serialized_status_info);
         STATUS_INFO_DISPOSED.serialize(&mut serializer).unwrap();
 
-        let inline_qos = ParameterList::new(vec![Parameter::new(
-            PID_STATUS_INFO,
-            serialized_status_info,
-        )]);
+        let mut inline_qos = vec![Parameter::new(PID_STATUS_INFO, serialized_status_info)];
+        inline_qos.extend(self.key_hash_parameter(handle));
 
         let change: RtpsWriterCacheChange = self.rtps_writer.new_change(
             ChangeKind::NotAliveDisposed,
-            instance_serialized_key,
-            inline_qos,
+            Data::new(instance_serialized_key),
+            ParameterList::new(inline_qos),
             handle,
             timestamp,
         );
@@ -619,14 +789,19 @@ impl DdsDataWriter {
                 publisher_qos.partition.clone(),
                 topic_qos.topic_data,
                 publisher_qos.group_data,
+                writer_qos.representation,
+                self.type_information.clone(),
             ),
             WriterProxy::new(
                 self.rtps_writer.guid(),
                 EntityId::new([0; 3], USER_DEFINED_UNKNOWN),
                 unicast_locator_list,
                 multicast_locator_list,
-                None,
+                // The serialized data includes the representation header
+                self.max_serialized_size
+                    .and_then(|size| i32::try_from(size + 4).ok()),
             ),
+            self.type_objects.clone(),
         )
     }
 
@@ -720,12 +895,36 @@ impl DdsDataWriter {
             == self.get_type_name();
 
         if is_matched_topic_name && is_matched_type_name {
+            let instance_handle = dds_serialize_key(&discovered_reader_data).unwrap().into();
+            let subscription_builtin_topic_data =
+                discovered_reader_data.subscription_builtin_topic_data();
+            if !is_type_consistent(
+                subscription_builtin_topic_data.type_information(),
+                discovered_reader_data.type_objects(),
+                self.type_information.as_ref(),
+                &self.type_objects,
+                subscription_builtin_topic_data.type_consistency(),
+            ) {
+                if self.get_matched_subscriptions().contains(&instance_handle) {
+                    self.remove_matched_reader(
+                        instance_handle,
+                        data_writer_address,
+                        publisher_address,
+                        participant_address.clone(),
+                    );
+                }
+                if self.inconsistent_subscription_list.insert(instance_handle) {
+                    self.on_inconsistent_topic(&participant_address);
+                }
+                return;
+            }
+            self.inconsistent_subscription_list.remove(&instance_handle);
+
             let incompatible_qos_policy_list = get_discovered_reader_incompatible_qos_policy_list(
                 &self.get_qos(),
                 discovered_reader_data.subscription_builtin_topic_data(),
                 &publisher_address.get_qos().unwrap(),
             );
-            let instance_handle = dds_serialize_key(&discovered_reader_data).unwrap().into();
 
             if incompatible_qos_policy_list.is_empty() {
                 let unicast_locator_list = if discovered_reader_data
@@ -790,6 +989,8 @@ impl DdsDataWriter {
                 );
 
                 self.matched_reader_add(reader_proxy);
+                self.incompatible_subscriptions
+                    .remove_incompatible_subscription(instance_handle);
 
                 if !self.get_matched_subscriptions().contains(&instance_handle)
                     || self.get_matched_subscription_data(instance_handle).as_ref()
@@ -814,13 +1015,27 @@ impl DdsDataWriter {
                     )
                 }
             } else {
-                self.incompatible_subscriptions
-                    .add_offered_incompatible_qos(instance_handle, incompatible_qos_policy_list);
-                self.on_offered_incompatible_qos(
-                    data_writer_address,
-                    publisher_address,
-                    participant_address,
-                );
+                if self.get_matched_subscriptions().contains(&instance_handle) {
+                    self.remove_matched_reader(
+                        instance_handle,
+                        data_writer_address.clone(),
+                        publisher_address.clone(),
+                        participant_address.clone(),
+                    );
+                }
+
+                if !self
+                    .get_incompatible_subscriptions()
+                    .contains(&instance_handle)
+                {
+                    self.incompatible_subscriptions
+                        .add_offered_incompatible_qos(instance_handle, incompatible_qos_policy_list);
+                    self.on_offered_incompatible_qos(
+                        data_writer_address,
+                        publisher_address,
+                        participant_address,
+                    );
+                }
             }
         }
     }
@@ -973,6 +1188,11 @@ impl DdsDataWriter {
         header: RtpsMessageHeader,
         udp_transport_write: &ActorAddress<UdpTransportWrite>,
     ) {
+        // The changes of the types whose serialized size can exceed the fragment size are all
+        // sent as DATAFRAG submessages, like the readers expect from the announced maximum size
+        let fragment_changes = self
+            .max_serialized_size
+            .is_some_and(|size| size + 4 > self.rtps_writer.data_max_size_serialized());
         for reader_proxy in &mut self.matched_readers {
             match (&self.qos.reliability.kind, reader_proxy.reliability()) {
                 (ReliabilityQosPolicyKind::BestEffort, ReliabilityKind::BestEffort)
@@ -981,6 +1201,7 @@ impl DdsDataWriter {
                         reader_proxy,
                         self.rtps_writer.guid().entity_id(),
                         &self.writer_cache,
+                        fragment_changes,
                         udp_transport_write,
                         header,
                     )
@@ -990,6 +1211,7 @@ impl DdsDataWriter {
                         reader_proxy,
                         self.rtps_writer.guid().entity_id(),
                         &self.writer_cache,
+                        fragment_changes,
                         self.rtps_writer.heartbeat_period(),
                         udp_transport_write,
                         header,
@@ -1126,6 +1348,102 @@ impl DdsDataWriter {
                 .expect("Should not fail to send message");
         }
     }
+
+    fn on_offered_deadline_missed(
+        &mut self,
+        data_writer_address: ActorAddress<DdsDataWriter>,
+        publisher_address: ActorAddress<DdsPublisher>,
+        participant_address: ActorAddress<DdsDomainParticipant>,
+    ) {
+        self.status_condition
+            .write_lock()
+            .add_communication_state(StatusKind::OfferedDeadlineMissed);
+        if self.listener.is_some()
+            && self
+                .status_kind
+                .contains(&StatusKind::OfferedDeadlineMissed)
+        {
+            let status = self.get_offered_deadline_missed_status();
+            let listener_address = self.listener.as_ref().unwrap().address();
+            let writer =
+                DataWriterNode::new(data_writer_address, publisher_address, participant_address);
+            listener_address
+                .trigger_on_offered_deadline_missed(writer, status)
+                .expect("Should not fail to send message");
+        } else if publisher_address.get_listener().unwrap().is_some()
+            && publisher_address
+                .status_kind()
+                .unwrap()
+                .contains(&StatusKind::OfferedDeadlineMissed)
+        {
+            let status = self.get_offered_deadline_missed_status();
+            let listener_address = publisher_address.get_listener().unwrap().unwrap();
+            let writer =
+                DataWriterNode::new(data_writer_address, publisher_address, participant_address);
+            listener_address
+                .trigger_on_offered_deadline_missed(writer, status)
+                .expect("Should not fail to send message");
+        } else if participant_address.get_listener().unwrap().is_some()
+            && participant_address
+                .status_kind()
+                .unwrap()
+                .contains(&StatusKind::OfferedDeadlineMissed)
+        {
+            let status = self.get_offered_deadline_missed_status();
+            let listener_address = participant_address.get_listener().unwrap().unwrap();
+            let writer =
+                DataWriterNode::new(data_writer_address, publisher_address, participant_address);
+            listener_address
+                .trigger_on_offered_deadline_missed(writer, status)
+                .expect("Should not fail to send message");
+        }
+    }
+
+    fn on_liveliness_lost(
+        &mut self,
+        data_writer_address: ActorAddress<DdsDataWriter>,
+        publisher_address: ActorAddress<DdsPublisher>,
+        participant_address: ActorAddress<DdsDomainParticipant>,
+    ) {
+        self.status_condition
+            .write_lock()
+            .add_communication_state(StatusKind::LivelinessLost);
+        if self.listener.is_some() && self.status_kind.contains(&StatusKind::LivelinessLost) {
+            let status = self.get_liveliness_lost_status();
+            let listener_address = self.listener.as_ref().unwrap().address();
+            let writer =
+                DataWriterNode::new(data_writer_address, publisher_address, participant_address);
+            listener_address
+                .trigger_on_liveliness_lost(writer, status)
+                .expect("Should not fail to send message");
+        } else if publisher_address.get_listener().unwrap().is_some()
+            && publisher_address
+                .status_kind()
+                .unwrap()
+                .contains(&StatusKind::LivelinessLost)
+        {
+            let status = self.get_liveliness_lost_status();
+            let listener_address = publisher_address.get_listener().unwrap().unwrap();
+            let writer =
+                DataWriterNode::new(data_writer_address, publisher_address, participant_address);
+            listener_address
+                .trigger_on_liveliness_lost(writer, status)
+                .expect("Should not fail to send message");
+        } else if participant_address.get_listener().unwrap().is_some()
+            && participant_address
+                .status_kind()
+                .unwrap()
+                .contains(&StatusKind::LivelinessLost)
+        {
+            let status = self.get_liveliness_lost_status();
+            let listener_address = participant_address.get_listener().unwrap().unwrap();
+            let writer =
+                DataWriterNode::new(data_writer_address, publisher_address, participant_address);
+            listener_address
+                .trigger_on_liveliness_lost(writer, status)
+                .expect("Should not fail to send message");
+        }
+    }
 }
 
 fn get_discovered_reader_incompatible_qos_policy_list(
@@ -1160,6 +1478,12 @@ fn get_discovered_reader_incompatible_qos_policy_list(
     if &writer_qos.destination_order < discovered_reader_data.destination_order() {
         incompatible_qos_policy_list.push(DESTINATIONORDER_QOS_POLICY_ID);
     }
+    if !is_data_representation_compatible(
+        &writer_qos.representation,
+        discovered_reader_data.representation(),
+    ) {
+        incompatible_qos_policy_list.push(DATAREPRESENTATION_QOS_POLICY_ID);
+    }
     incompatible_qos_policy_list
 }
 
@@ -1167,6 +1491,7 @@ fn send_message_to_reader_proxy_best_effort(
     reader_proxy: &mut RtpsReaderProxy,
     writer_id: EntityId,
     writer_cache: &WriterHistoryCache,
+    fragment_changes: bool,
     udp_transport_write: &ActorAddress<UdpTransportWrite>,
     header: RtpsMessageHeader,
 ) {
@@ -1210,13 +1535,15 @@ fn send_message_to_reader_proxy_best_effort(
                     reader_proxy.unicast_locator_list().to_vec(),
                 )
                 .expect("Should not fail cause actor always exists");
-            reader_proxy.set_highest_sent_seq_num(next_unsent_change_seq_num);
-        } else if let Some(cache_change) = writer_cache
+        }
+        // The change following the GAP is sent in the same pass since it would otherwise be
+        // considered as sent without the reader ever receiving it
+        if let Some(cache_change) = writer_cache
             .change_list()
             .find(|cc| cc.sequence_number() == next_unsent_change_seq_num)
         {
             // Either send a DATAFRAG submessages or send a single DATA submessage
-            if cache_change.data_value().len() > 1 {
+            if fragment_changes || cache_change.data_value().len() > 1 {
                 let cache_change_frag = DataFragSubmessages::new(
                     cache_change,
                     reader_proxy.remote_reader_guid().entity_id(),
@@ -1301,6 +1628,7 @@ fn send_message_to_reader_proxy_reliable(
     reader_proxy: &mut RtpsReaderProxy,
     writer_id: EntityId,
     writer_cache: &WriterHistoryCache,
+    fragment_changes: bool,
     heartbeat_period: Duration,
     udp_transport_write: &ActorAddress<UdpTransportWrite>,
     header: RtpsMessageHeader,
@@ -1323,16 +1651,18 @@ fn send_message_to_reader_proxy_reliable(
                         reader_proxy.unicast_locator_list().to_vec(),
                     )
                     .expect("Should not fail cause actor always exists");
-            } else {
-                send_change_message_reader_proxy_reliable(
-                    reader_proxy,
-                    writer_id,
-                    writer_cache,
-                    next_unsent_change_seq_num,
-                    udp_transport_write,
-                    header,
-                );
             }
+            // The change following the GAP is sent right away since the reader has no missing
+            // changes to request until the next HEARTBEAT
+            send_change_message_reader_proxy_reliable(
+                reader_proxy,
+                writer_id,
+                writer_cache,
+                fragment_changes,
+                next_unsent_change_seq_num,
+                udp_transport_write,
+                header,
+            );
             reader_proxy.set_highest_sent_seq_num(next_unsent_change_seq_num);
         }
     } else if !reader_proxy.unacked_changes(writer_cache) {
@@ -1374,6 +1704,7 @@ fn send_message_to_reader_proxy_reliable(
                 reader_proxy,
                 writer_id,
                 writer_cache,
+                fragment_changes,
                 next_requested_change_seq_num,
                 udp_transport_write,
                 header,
@@ -1386,6 +1717,7 @@ fn send_change_message_reader_proxy_reliable(
     reader_proxy: &mut RtpsReaderProxy,
     writer_id: EntityId,
     writer_cache: &WriterHistoryCache,
+    fragment_changes: bool,
     change_seq_num: SequenceNumber,
     udp_transport_write: &ActorAddress<UdpTransportWrite>,
     header: RtpsMessageHeader,
@@ -1396,7 +1728,7 @@ fn send_change_message_reader_proxy_reliable(
     {
         Some(cache_change) if change_seq_num > reader_proxy.first_relevant_sample_seq_num() => {
             // Either send a DATAFRAG submessages or send a single DATA submessage
-            if cache_change.data_value().len() > 1 {
+            if fragment_changes || cache_change.data_value().len() > 1 {
                 let cache_change_frag = DataFragSubmessages::new(
                     cache_change,
                     reader_proxy.remote_reader_guid().entity_id(),
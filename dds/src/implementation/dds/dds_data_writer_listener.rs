@@ -5,7 +5,10 @@ use crate::{
     },
     infrastructure::{
         error::DdsResult,
-        status::{OfferedIncompatibleQosStatus, PublicationMatchedStatus},
+        status::{
+            LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
+            PublicationMatchedStatus,
+        },
     },
 };
 
@@ -61,4 +64,44 @@ impl ActorAddress<DdsDataWriterListener> {
 
         self.send_command(OnPublicationMatched { the_writer, status })
     }
+
+    pub fn trigger_on_offered_deadline_missed(
+        &self,
+        the_writer: DataWriterNode,
+        status: OfferedDeadlineMissedStatus,
+    ) -> DdsResult<()> {
+        struct OnOfferedDeadlineMissed {
+            the_writer: DataWriterNode,
+            status: OfferedDeadlineMissedStatus,
+        }
+
+        impl CommandHandler<OnOfferedDeadlineMissed> for DdsDataWriterListener {
+            fn handle(&mut self, mail: OnOfferedDeadlineMissed) {
+                self.listener
+                    .trigger_on_offered_deadline_missed(mail.the_writer, mail.status)
+            }
+        }
+
+        self.send_command(OnOfferedDeadlineMissed { the_writer, status })
+    }
+
+    pub fn trigger_on_liveliness_lost(
+        &self,
+        the_writer: DataWriterNode,
+        status: LivelinessLostStatus,
+    ) -> DdsResult<()> {
+        struct OnLivelinessLost {
+            the_writer: DataWriterNode,
+            status: LivelinessLostStatus,
+        }
+
+        impl CommandHandler<OnLivelinessLost> for DdsDataWriterListener {
+            fn handle(&mut self, mail: OnLivelinessLost) {
+                self.listener
+                    .trigger_on_liveliness_lost(mail.the_writer, mail.status)
+            }
+        }
+
+        self.send_command(OnLivelinessLost { the_writer, status })
+    }
 }
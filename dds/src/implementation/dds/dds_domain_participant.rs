@@ -27,7 +27,10 @@ use crate::{
             writer::RtpsWriter,
         },
         rtps_udp_psm::udp_transport::UdpTransportWrite,
-        utils::actor::{actor_interface, spawn_actor, Actor, ActorAddress},
+        utils::{
+            actor::{actor_interface, spawn_actor, Actor, ActorAddress},
+            shared_object::{DdsRwLock, DdsShared},
+        },
     },
     infrastructure::{
         instance::InstanceHandle,
@@ -39,6 +42,7 @@ use crate::{
         status::StatusKind,
         time::{DurationKind, DURATION_ZERO},
     },
+    topic_definition::{dynamic_data::DynamicData, dynamic_type::DynamicType},
     {
         builtin_topics::TopicBuiltinTopicData,
         infrastructure::{
@@ -50,13 +54,14 @@ use crate::{
 };
 
 use std::{
+    any::TypeId,
     collections::{HashMap, HashSet},
     time::{SystemTime, UNIX_EPOCH},
 };
 
 use super::{
     dds_data_writer::DdsDataWriter, dds_domain_participant_listener::DdsDomainParticipantListener,
-    dds_publisher::DdsPublisher,
+    dds_publisher::DdsPublisher, status_condition_impl::StatusConditionImpl,
 };
 
 pub const ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER: EntityId =
@@ -104,17 +109,25 @@ pub struct DdsDomainParticipant {
     default_publisher_qos: PublisherQos,
     topic_list: Vec<Actor<DdsTopic>>,
     user_defined_topic_counter: u8,
+    multitopic_name_list: HashSet<String>,
+    registered_type_list: HashMap<String, TypeId>,
+    registered_dynamic_type_list: HashMap<String, DynamicType>,
     default_topic_qos: TopicQos,
     manual_liveliness_count: Count,
     lease_duration: Duration,
     discovered_participant_list: HashMap<InstanceHandle, SpdpDiscoveredParticipantData>,
     discovered_topic_list: HashMap<InstanceHandle, TopicBuiltinTopicData>,
+    discovered_writer_list: HashMap<InstanceHandle, DiscoveredWriterData>,
+    discovered_reader_list: HashMap<InstanceHandle, DiscoveredReaderData>,
     enabled: bool,
     ignored_participants: HashSet<InstanceHandle>,
     ignored_publications: HashSet<InstanceHandle>,
     ignored_subcriptions: HashSet<InstanceHandle>,
+    ignored_topic_list: HashSet<InstanceHandle>,
+    last_liveliness_assertion: Option<Time>,
     data_max_size_serialized: usize,
     udp_transport_write: Actor<UdpTransportWrite>,
+    status_condition: DdsShared<DdsRwLock<StatusConditionImpl>>,
     listener: Option<Actor<DdsDomainParticipantListener>>,
     status_kind: Vec<StatusKind>,
 }
@@ -411,14 +424,22 @@ impl DdsDomainParticipant {
             default_topic_qos: TopicQos::default(),
             manual_liveliness_count: 0,
             lease_duration,
+            registered_type_list: HashMap::new(),
+            registered_dynamic_type_list: HashMap::new(),
             discovered_participant_list: HashMap::new(),
             discovered_topic_list: HashMap::new(),
+            discovered_writer_list: HashMap::new(),
+            discovered_reader_list: HashMap::new(),
             enabled: false,
             ignored_participants: HashSet::new(),
             ignored_publications: HashSet::new(),
             ignored_subcriptions: HashSet::new(),
+            ignored_topic_list: HashSet::new(),
+            multitopic_name_list: HashSet::new(),
+            last_liveliness_assertion: None,
             data_max_size_serialized,
             udp_transport_write,
+            status_condition: DdsShared::new(DdsRwLock::new(StatusConditionImpl::default())),
             listener,
             status_kind,
         }
@@ -533,12 +554,32 @@ impl DdsDomainParticipant {
         self.ignored_publications.contains(&handle)
     }
 
-    pub fn ignore_topic(&self, _handle: InstanceHandle) {
-        todo!()
+    pub fn ignore_topic(&mut self, handle: InstanceHandle) {
+        self.ignored_topic_list.insert(handle);
+    }
+
+    pub fn is_topic_ignored(&self, handle: InstanceHandle) -> bool {
+        self.ignored_topic_list.contains(&handle)
+    }
+
+    pub fn add_multitopic(&mut self, name: String) {
+        self.multitopic_name_list.insert(name);
+    }
+
+    pub fn remove_multitopic(&mut self, name: String) -> bool {
+        self.multitopic_name_list.remove(&name)
+    }
+
+    pub fn has_multitopic(&self, name: String) -> bool {
+        self.multitopic_name_list.contains(&name)
+    }
+
+    pub fn assert_liveliness(&mut self, now: Time) {
+        self.last_liveliness_assertion = Some(now);
     }
 
-    pub fn is_topic_ignored(&self, _handle: InstanceHandle) -> bool {
-        todo!()
+    pub fn get_last_liveliness_assertion(&self) -> Option<Time> {
+        self.last_liveliness_assertion
     }
 
     pub fn discovered_participant_add(
@@ -554,7 +595,7 @@ impl DdsDomainParticipant {
         self.discovered_participant_list.get(&handle).cloned()
     }
 
-    pub fn _discovered_participant_remove(&mut self, handle: InstanceHandle) {
+    pub fn discovered_participant_remove(&mut self, handle: InstanceHandle) {
         self.discovered_participant_list.remove(&handle);
     }
 
@@ -650,6 +691,49 @@ impl DdsDomainParticipant {
                     });
     }
 
+    pub fn register_type(&mut self, type_name: String, type_id: TypeId) -> DdsResult<()> {
+        match self.registered_type_list.get(&type_name) {
+            Some(registered_type_id) if registered_type_id != &type_id => {
+                Err(DdsError::PreconditionNotMet(format!(
+                    "Type name {} is already registered for a different type",
+                    type_name
+                )))
+            }
+            _ => {
+                self.registered_type_list.insert(type_name, type_id);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn get_registered_type(&self, type_name: String) -> Option<TypeId> {
+        self.registered_type_list.get(&type_name).cloned()
+    }
+
+    pub fn register_dynamic_type(
+        &mut self,
+        type_name: String,
+        dynamic_type: DynamicType,
+    ) -> DdsResult<()> {
+        match self.registered_dynamic_type_list.get(&type_name) {
+            Some(registered_dynamic_type) if registered_dynamic_type != &dynamic_type => {
+                Err(DdsError::PreconditionNotMet(format!(
+                    "Type name {} is already registered for a different type",
+                    type_name
+                )))
+            }
+            _ => {
+                self.register_type(type_name.clone(), TypeId::of::<DynamicData>())?;
+                self.registered_dynamic_type_list.insert(type_name, dynamic_type);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn get_registered_dynamic_type(&self, type_name: String) -> Option<DynamicType> {
+        self.registered_dynamic_type_list.get(&type_name).cloned()
+    }
+
     pub fn get_qos(&self) -> DomainParticipantQos {
         self.qos.clone()
     }
@@ -767,6 +851,38 @@ impl DdsDomainParticipant {
             );
     }
 
+    pub fn discovered_topic_remove(&mut self, handle: InstanceHandle) {
+        self.discovered_topic_list.remove(&handle);
+    }
+
+    pub fn discovered_writer_add(&mut self, handle: InstanceHandle, writer_data: DiscoveredWriterData) {
+        self.discovered_writer_list.insert(handle, writer_data);
+    }
+
+    pub fn discovered_writer_remove(&mut self, handle: InstanceHandle) {
+        self.discovered_writer_list.remove(&handle);
+    }
+
+    pub fn discovered_writer_data_list(&self) -> Vec<DiscoveredWriterData> {
+        self.discovered_writer_list.values().cloned().collect()
+    }
+
+    pub fn discovered_reader_add(&mut self, handle: InstanceHandle, reader_data: DiscoveredReaderData) {
+        self.discovered_reader_list.insert(handle, reader_data);
+    }
+
+    pub fn discovered_reader_remove(&mut self, handle: InstanceHandle) {
+        self.discovered_reader_list.remove(&handle);
+    }
+
+    pub fn discovered_reader_data_list(&self) -> Vec<DiscoveredReaderData> {
+        self.discovered_reader_list.values().cloned().collect()
+    }
+
+    pub fn get_statuscondition(&self) -> DdsShared<DdsRwLock<StatusConditionImpl>> {
+        self.status_condition.clone()
+    }
+
     pub fn get_listener(&self) -> Option<ActorAddress<DdsDomainParticipantListener>> {
         self.listener.as_ref().map(|l| l.address().clone())
     }
@@ -774,6 +890,15 @@ impl DdsDomainParticipant {
     pub fn status_kind(&self) -> Vec<StatusKind> {
         self.status_kind.clone()
     }
+
+    pub fn set_listener(
+        &mut self,
+        listener: Option<Actor<DdsDomainParticipantListener>>,
+        status_kind: Vec<StatusKind>,
+    ) {
+        self.listener = listener;
+        self.status_kind = status_kind;
+    }
 }
 }
 
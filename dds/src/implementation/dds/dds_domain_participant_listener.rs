@@ -4,8 +4,10 @@ use crate::{
     infrastructure::{
         error::DdsResult,
         status::{
-            OfferedIncompatibleQosStatus, PublicationMatchedStatus, RequestedDeadlineMissedStatus,
-            RequestedIncompatibleQosStatus, SampleRejectedStatus, SubscriptionMatchedStatus,
+            LivelinessLostStatus, OfferedDeadlineMissedStatus, OfferedIncompatibleQosStatus,
+            PublicationMatchedStatus, RequestedDeadlineMissedStatus,
+            RequestedIncompatibleQosStatus, SampleLostStatus, SampleRejectedStatus,
+            SubscriptionMatchedStatus,
         },
     },
 };
@@ -123,6 +125,25 @@ impl ActorAddress<DdsDomainParticipantListener> {
         self.send_command(TriggerOnSubscriptionMatched { reader, status })
     }
 
+    pub fn trigger_on_sample_lost(
+        &self,
+        reader: DataReaderNode,
+        status: SampleLostStatus,
+    ) -> DdsResult<()> {
+        struct TriggerOnSampleLost {
+            reader: DataReaderNode,
+            status: SampleLostStatus,
+        }
+
+        impl CommandHandler<TriggerOnSampleLost> for DdsDomainParticipantListener {
+            fn handle(&mut self, mail: TriggerOnSampleLost) {
+                self.listener.on_sample_lost(&mail.reader, mail.status)
+            }
+        }
+
+        self.send_command(TriggerOnSampleLost { reader, status })
+    }
+
     pub fn trigger_on_publication_matched(
         &self,
         the_writer: DataWriterNode,
@@ -142,4 +163,44 @@ impl ActorAddress<DdsDomainParticipantListener> {
 
         self.send_command(OnPublicationMatched { the_writer, status })
     }
+
+    pub fn trigger_on_offered_deadline_missed(
+        &self,
+        the_writer: DataWriterNode,
+        status: OfferedDeadlineMissedStatus,
+    ) -> DdsResult<()> {
+        struct OnOfferedDeadlineMissed {
+            the_writer: DataWriterNode,
+            status: OfferedDeadlineMissedStatus,
+        }
+
+        impl CommandHandler<OnOfferedDeadlineMissed> for DdsDomainParticipantListener {
+            fn handle(&mut self, mail: OnOfferedDeadlineMissed) {
+                self.listener
+                    .on_offered_deadline_missed(&mail.the_writer, mail.status)
+            }
+        }
+
+        self.send_command(OnOfferedDeadlineMissed { the_writer, status })
+    }
+
+    pub fn trigger_on_liveliness_lost(
+        &self,
+        the_writer: DataWriterNode,
+        status: LivelinessLostStatus,
+    ) -> DdsResult<()> {
+        struct OnLivelinessLost {
+            the_writer: DataWriterNode,
+            status: LivelinessLostStatus,
+        }
+
+        impl CommandHandler<OnLivelinessLost> for DdsDomainParticipantListener {
+            fn handle(&mut self, mail: OnLivelinessLost) {
+                self.listener
+                    .on_liveliness_lost(&mail.the_writer, mail.status)
+            }
+        }
+
+        self.send_command(OnLivelinessLost { the_writer, status })
+    }
 }
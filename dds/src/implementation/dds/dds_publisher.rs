@@ -1,7 +1,10 @@
 use crate::{
     implementation::{
         rtps::{group::RtpsGroup, types::Guid},
-        utils::actor::{actor_interface, Actor, ActorAddress},
+        utils::{
+            actor::{actor_interface, Actor, ActorAddress},
+            shared_object::{DdsRwLock, DdsShared},
+        },
     },
     infrastructure::{
         error::DdsResult,
@@ -11,7 +14,10 @@ use crate::{
     },
 };
 
-use super::{dds_data_writer::DdsDataWriter, dds_publisher_listener::DdsPublisherListener};
+use super::{
+    dds_data_writer::DdsDataWriter, dds_publisher_listener::DdsPublisherListener,
+    status_condition_impl::StatusConditionImpl,
+};
 
 pub struct DdsPublisher {
     qos: PublisherQos,
@@ -20,6 +26,7 @@ pub struct DdsPublisher {
     enabled: bool,
     user_defined_data_writer_counter: u8,
     default_datawriter_qos: DataWriterQos,
+    status_condition: DdsShared<DdsRwLock<StatusConditionImpl>>,
     listener: Option<Actor<DdsPublisherListener>>,
     status_kind: Vec<StatusKind>,
 }
@@ -38,6 +45,7 @@ impl DdsPublisher {
             enabled: false,
             user_defined_data_writer_counter: 0,
             default_datawriter_qos: DataWriterQos::default(),
+            status_condition: DdsShared::new(DdsRwLock::new(StatusConditionImpl::default())),
             listener,
             status_kind,
         }
@@ -129,6 +137,10 @@ impl DdsPublisher {
         self.rtps_group.guid().into()
     }
 
+    pub fn get_statuscondition(&self) -> DdsShared<DdsRwLock<StatusConditionImpl>> {
+        self.status_condition.clone()
+    }
+
     pub fn get_listener(&self) -> Option<ActorAddress<DdsPublisherListener>> {
         self.listener.as_ref().map(|l| l.address().clone())
     }
@@ -136,5 +148,14 @@ impl DdsPublisher {
     pub fn status_kind(&self) -> Vec<StatusKind> {
         self.status_kind.clone()
     }
+
+    pub fn set_listener(
+        &mut self,
+        listener: Option<Actor<DdsPublisherListener>>,
+        status_kind: Vec<StatusKind>,
+    ) {
+        self.listener = listener;
+        self.status_kind = status_kind;
+    }
 }
 }
@@ -11,10 +11,12 @@ use crate::{
         },
     },
     infrastructure::{
-        error::DdsResult,
+        error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind, SubscriberQos},
-        status::StatusKind,
+        qos_policy::PresentationQosPolicyAccessScopeKind,
+        status::{SampleLostStatus, StatusKind},
+        time::Time,
     },
 };
 
@@ -28,6 +30,8 @@ pub struct DdsSubscriber {
     status_condition: DdsShared<DdsRwLock<StatusConditionImpl>>,
     listener: Option<Actor<DdsSubscriberListener>>,
     status_kind: Vec<StatusKind>,
+    sample_lost_status: SampleLostStatus,
+    access_count: i32,
 }
 
 impl DdsSubscriber {
@@ -47,6 +51,8 @@ impl DdsSubscriber {
             status_condition: DdsShared::new(DdsRwLock::new(StatusConditionImpl::default())),
             listener,
             status_kind,
+            sample_lost_status: SampleLostStatus::default(),
+            access_count: 0,
         }
     }
 }
@@ -101,6 +107,55 @@ impl DdsSubscriber {
                     });
     }
 
+    pub fn begin_access(&mut self) {
+        self.access_count += 1;
+    }
+
+    pub fn end_access(&mut self) -> DdsResult<()> {
+        if self.access_count == 0 {
+            return Err(DdsError::PreconditionNotMet(
+                "end_access called without matching begin_access".to_string(),
+            ));
+        }
+        self.access_count -= 1;
+        Ok(())
+    }
+
+    pub fn get_datareaders(
+        &self,
+        reader_sample_timestamp_list: Vec<(InstanceHandle, Vec<Time>)>,
+    ) -> DdsResult<Vec<InstanceHandle>> {
+        let is_group_access_scope =
+            self.qos.presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group;
+        if is_group_access_scope && self.access_count == 0 {
+            return Err(DdsError::PreconditionNotMet(
+                "Group access to the readers requires begin_access".to_string(),
+            ));
+        }
+
+        let mut sample_list = Vec::new();
+        for (handle, timestamp_list) in reader_sample_timestamp_list {
+            for timestamp in timestamp_list {
+                sample_list.push((timestamp, handle));
+            }
+        }
+
+        if is_group_access_scope && self.qos.presentation.ordered_access {
+            // Each reader is listed once per sample such that accessing the samples one at a time
+            // in the order of the list follows the order in which they were published.
+            sample_list.sort_by_key(|(timestamp, _)| *timestamp);
+            Ok(sample_list.into_iter().map(|(_, handle)| handle).collect())
+        } else {
+            let mut reader_list = Vec::new();
+            for (_, handle) in sample_list {
+                if !reader_list.contains(&handle) {
+                    reader_list.push(handle);
+                }
+            }
+            Ok(reader_list)
+        }
+    }
+
     pub fn set_default_datareader_qos(&mut self, qos: QosKind<DataReaderQos>) -> DdsResult<()> {
         match qos {
             QosKind::Default => self.default_data_reader_qos = DataReaderQos::default(),
@@ -150,4 +205,24 @@ impl DdsSubscriber {
     pub fn status_kind(&self) -> Vec<StatusKind> {
         self.status_kind.clone()
     }
+
+    pub fn set_listener(
+        &mut self,
+        listener: Option<Actor<DdsSubscriberListener>>,
+        status_kind: Vec<StatusKind>,
+    ) {
+        self.listener = listener;
+        self.status_kind = status_kind;
+    }
+
+    pub fn increment_sample_lost_status(&mut self, number_of_lost_samples: i32) {
+        self.sample_lost_status.total_count += number_of_lost_samples;
+        self.sample_lost_status.total_count_change += number_of_lost_samples;
+    }
+
+    pub fn get_sample_lost_status(&mut self) -> SampleLostStatus {
+        let status = self.sample_lost_status.clone();
+        self.sample_lost_status.total_count_change = 0;
+        status
+    }
 }}
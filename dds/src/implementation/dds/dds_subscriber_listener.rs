@@ -6,8 +6,8 @@ use crate::{
     infrastructure::{
         error::DdsResult,
         status::{
-            RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleRejectedStatus,
-            SubscriptionMatchedStatus,
+            RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus, SampleLostStatus,
+            SampleRejectedStatus, SubscriptionMatchedStatus,
         },
     },
     subscription::{subscriber::Subscriber, subscriber_listener::SubscriberListener},
@@ -120,4 +120,23 @@ impl ActorAddress<DdsSubscriberListener> {
 
         self.send_command(TriggerOnSubscriptionMatched { reader, status })
     }
+
+    pub fn trigger_on_sample_lost(
+        &self,
+        reader: DataReaderNode,
+        status: SampleLostStatus,
+    ) -> DdsResult<()> {
+        struct TriggerOnSampleLost {
+            reader: DataReaderNode,
+            status: SampleLostStatus,
+        }
+
+        impl CommandHandler<TriggerOnSampleLost> for DdsSubscriberListener {
+            fn handle(&mut self, mail: TriggerOnSampleLost) {
+                self.listener.on_sample_lost(&mail.reader, mail.status)
+            }
+        }
+
+        self.send_command(TriggerOnSampleLost { reader, status })
+    }
 }
@@ -118,6 +118,7 @@ impl DdsTopic {
             qos.resource_limits.clone(),
             qos.ownership.clone(),
             qos.topic_data.clone(),
+            qos.representation.clone(),
         ))
     }
 
@@ -133,8 +134,7 @@ impl DdsTopic {
             && discovered_topic_data.topic_builtin_topic_data().name() == self.get_name()
             && !is_discovered_topic_consistent(&self.qos, &discovered_topic_data)
         {
-            self.inconsistent_topic_status.increment();
-            self.status_condition.write_lock().add_communication_state(StatusKind::InconsistentTopic);
+            self.on_inconsistent_topic();
         //     listener_sender
         //         .try_send(ListenerTriggerKind::InconsistentTopic(TopicNode::new(
         //             self.guid(),
@@ -143,6 +143,11 @@ impl DdsTopic {
         //         .ok();
         }
     }
+
+    pub fn on_inconsistent_topic(&mut self) {
+        self.inconsistent_topic_status.increment();
+        self.status_condition.write_lock().add_communication_state(StatusKind::InconsistentTopic);
+    }
 }
 }
 
@@ -13,5 +13,6 @@ pub mod dds_subscriber;
 pub mod dds_subscriber_listener;
 pub mod dds_topic;
 pub mod message_receiver;
+pub mod multitopic_join;
 pub mod nodes;
 pub mod status_condition_impl;
@@ -0,0 +1,631 @@
+use std::collections::HashMap;
+
+use crate::{
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        instance::InstanceHandle,
+    },
+    topic_definition::type_support::{
+        dds_key_hash, dds_serialize_to_bytes, DdsGetKey, DdsRepresentation,
+    },
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FieldSelection {
+    All,
+    // Path of the field in the related topics and the name it gets in the resulting type
+    Fields(Vec<(String, String)>),
+}
+
+/// Subset of the subscription expression of a MultiTopic as defined in Annex B of the DDS standard.
+/// Only the selection of the fields and the natural join of the related topics is supported, filtering
+/// the samples using a WHERE clause is not.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SubscriptionExpression {
+    field_selection: FieldSelection,
+    related_topic_names: Vec<String>,
+}
+
+impl SubscriptionExpression {
+    pub fn parse(expression: &str) -> DdsResult<Self> {
+        let tokens = tokenize(expression);
+        let mut tokens = tokens.iter().map(String::as_str).peekable();
+
+        if !matches!(tokens.next(), Some(t) if is_keyword(t, "SELECT")) {
+            return Err(DdsError::BadParameter);
+        }
+
+        let field_selection = if tokens.peek() == Some(&"*") {
+            tokens.next();
+            FieldSelection::All
+        } else {
+            let mut fields = Vec::new();
+            loop {
+                let field = tokens
+                    .next()
+                    .filter(|t| is_identifier(t))
+                    .ok_or(DdsError::BadParameter)?;
+                let name = match tokens.peek() {
+                    Some(t) if is_keyword(t, "AS") => {
+                        tokens.next();
+                        tokens
+                            .next()
+                            .filter(|t| is_identifier(t))
+                            .ok_or(DdsError::BadParameter)?
+                    }
+                    Some(t) if is_identifier(t) && !is_keyword(t, "FROM") => {
+                        tokens.next().expect("Already peeked")
+                    }
+                    _ => field
+                        .rsplit('.')
+                        .next()
+                        .expect("Split has at least one item"),
+                };
+                fields.push((field.to_string(), name.to_string()));
+
+                if tokens.peek() == Some(&",") {
+                    tokens.next();
+                } else {
+                    break;
+                }
+            }
+            FieldSelection::Fields(fields)
+        };
+
+        if !matches!(tokens.next(), Some(t) if is_keyword(t, "FROM")) {
+            return Err(DdsError::BadParameter);
+        }
+
+        let mut related_topic_names = Vec::new();
+        loop {
+            while tokens.peek() == Some(&"(") {
+                tokens.next();
+            }
+            let topic_name = tokens
+                .next()
+                .filter(|t| is_identifier(t))
+                .ok_or(DdsError::BadParameter)?;
+            related_topic_names.push(topic_name.to_string());
+            while tokens.peek() == Some(&")") {
+                tokens.next();
+            }
+
+            match tokens.next() {
+                None => break,
+                Some(t) if is_keyword(t, "WHERE") => return Err(DdsError::Unsupported),
+                Some(t) if is_keyword(t, "NATURAL") => {
+                    if matches!(tokens.peek(), Some(t) if is_keyword(t, "INNER")) {
+                        tokens.next();
+                    }
+                }
+                Some(t) if is_keyword(t, "INNER") => {
+                    if !matches!(tokens.next(), Some(t) if is_keyword(t, "NATURAL")) {
+                        return Err(DdsError::BadParameter);
+                    }
+                }
+                _ => return Err(DdsError::BadParameter),
+            }
+            if !matches!(tokens.next(), Some(t) if is_keyword(t, "JOIN")) {
+                return Err(DdsError::BadParameter);
+            }
+        }
+
+        Ok(Self {
+            field_selection,
+            related_topic_names,
+        })
+    }
+
+    pub fn field_selection(&self) -> &FieldSelection {
+        &self.field_selection
+    }
+
+    pub fn related_topic_names(&self) -> &[String] {
+        &self.related_topic_names
+    }
+}
+
+fn tokenize(expression: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for c in expression.chars() {
+        if c.is_alphanumeric() || c == '_' || c == '.' {
+            current.push(c);
+        } else {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            if !c.is_whitespace() {
+                tokens.push(c.to_string());
+            }
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn is_keyword(token: &str, keyword: &str) -> bool {
+    token.eq_ignore_ascii_case(keyword)
+}
+
+fn is_identifier(token: &str) -> bool {
+    matches!(token.chars().next(), Some(c) if c.is_alphabetic() || c == '_')
+}
+
+/// Change of the joined samples of a MultiTopic resulting from a sample received on one of its
+/// related topics.
+#[derive(Debug, PartialEq, Eq)]
+pub enum JoinedChange {
+    Alive {
+        instance_handle: InstanceHandle,
+        data: Vec<u8>,
+    },
+    Disposed {
+        instance_handle: InstanceHandle,
+    },
+}
+
+/// State of the natural join of the related topics of a MultiTopic. The most recent sample of each
+/// instance of the related topics is kept and combined with the samples of the other related topics
+/// which have the same value on all the fields they have in common. Every combination results in a
+/// joined sample whose instance is disposed once no combination producing it is left.
+pub struct MultiTopicJoin {
+    field_selection: FieldSelection,
+    related_topic_samples: Vec<HashMap<InstanceHandle, serde_json::Value>>,
+    // Combinations of the instances of each of the related topics producing every joined instance
+    joined_instances: HashMap<InstanceHandle, Vec<Vec<InstanceHandle>>>,
+    serialize_joined_sample: fn(serde_json::Value) -> DdsResult<(InstanceHandle, Vec<u8>)>,
+}
+
+impl MultiTopicJoin {
+    pub fn new<Foo>(field_selection: FieldSelection, number_of_related_topics: usize) -> Self
+    where
+        Foo: serde::Serialize + for<'de> serde::Deserialize<'de> + DdsRepresentation + DdsGetKey,
+    {
+        fn serialize_joined_sample<Foo>(
+            value: serde_json::Value,
+        ) -> DdsResult<(InstanceHandle, Vec<u8>)>
+        where
+            Foo:
+                serde::Serialize + for<'de> serde::Deserialize<'de> + DdsRepresentation + DdsGetKey,
+        {
+            let sample: Foo = serde_json::from_value(value)
+                .map_err(|e| DdsError::PreconditionNotMet(e.to_string()))?;
+            Ok((dds_key_hash(&sample)?, dds_serialize_to_bytes(&sample)?))
+        }
+
+        Self {
+            field_selection,
+            related_topic_samples: vec![HashMap::new(); number_of_related_topics],
+            joined_instances: HashMap::new(),
+            serialize_joined_sample: serialize_joined_sample::<Foo>,
+        }
+    }
+
+    /// Update the sample of an instance of one of the related topics. A `None` value indicates
+    /// the instance is no longer alive on that topic. Returns the joined samples produced by the
+    /// new sample and the joined instances which can no longer be produced.
+    pub fn add_sample(
+        &mut self,
+        related_topic_index: usize,
+        instance_handle: InstanceHandle,
+        value: Option<serde_json::Value>,
+    ) -> DdsResult<Vec<JoinedChange>> {
+        // The joined samples are built before changing the state of the join so that it is left
+        // untouched if any of them fails
+        let mut joined_samples = Vec::new();
+        if let Some(value) = &value {
+            for combination in self.combinations(related_topic_index, instance_handle, value) {
+                let samples = combination.iter().enumerate().map(|(index, handle)| {
+                    if index == related_topic_index {
+                        value
+                    } else {
+                        &self.related_topic_samples[index][handle]
+                    }
+                });
+                let joined_sample = select_fields(&self.field_selection, samples)?;
+                let (joined_instance, data) = (self.serialize_joined_sample)(joined_sample)?;
+                joined_samples.push((joined_instance, data, combination));
+            }
+        }
+
+        let mut affected_instances = Vec::new();
+        for (joined_instance, combinations) in self.joined_instances.iter_mut() {
+            let number_of_combinations = combinations.len();
+            combinations.retain(|c| c[related_topic_index] != instance_handle);
+            if combinations.len() != number_of_combinations {
+                affected_instances.push(*joined_instance);
+            }
+        }
+
+        let mut changes = Vec::new();
+        for (joined_instance, data, combination) in joined_samples {
+            self.joined_instances
+                .entry(joined_instance)
+                .or_default()
+                .push(combination);
+            changes.push(JoinedChange::Alive {
+                instance_handle: joined_instance,
+                data,
+            });
+        }
+        match value {
+            Some(value) => {
+                self.related_topic_samples[related_topic_index].insert(instance_handle, value);
+            }
+            None => {
+                self.related_topic_samples[related_topic_index].remove(&instance_handle);
+            }
+        }
+
+        for joined_instance in affected_instances {
+            if self.joined_instances[&joined_instance].is_empty() {
+                self.joined_instances.remove(&joined_instance);
+                changes.push(JoinedChange::Disposed {
+                    instance_handle: joined_instance,
+                });
+            }
+        }
+
+        Ok(changes)
+    }
+
+    // Instances of all the related topics which can be joined with the given sample
+    fn combinations(
+        &self,
+        related_topic_index: usize,
+        instance_handle: InstanceHandle,
+        value: &serde_json::Value,
+    ) -> Vec<Vec<InstanceHandle>> {
+        let mut combinations: Vec<(Vec<InstanceHandle>, Vec<&serde_json::Value>)> =
+            vec![(Vec::new(), vec![value])];
+        for (index, samples) in self.related_topic_samples.iter().enumerate() {
+            if index == related_topic_index {
+                for (handles, _) in combinations.iter_mut() {
+                    handles.push(instance_handle);
+                }
+            } else {
+                let mut joined_combinations = Vec::new();
+                for (handles, values) in &combinations {
+                    for (handle, sample) in samples {
+                        if values.iter().all(|v| are_joinable(v, sample)) {
+                            let mut handles = handles.clone();
+                            handles.push(*handle);
+                            let mut values = values.clone();
+                            values.push(sample);
+                            joined_combinations.push((handles, values));
+                        }
+                    }
+                }
+                combinations = joined_combinations;
+            }
+        }
+        combinations
+            .into_iter()
+            .map(|(handles, _)| handles)
+            .collect()
+    }
+}
+
+// Samples are joined when all the fields they have in common have the same value
+fn are_joinable(sample: &serde_json::Value, other: &serde_json::Value) -> bool {
+    match (sample, other) {
+        (serde_json::Value::Object(fields), serde_json::Value::Object(other_fields)) => fields
+            .iter()
+            .all(|(name, value)| !matches!(other_fields.get(name), Some(v) if v != value)),
+        _ => true,
+    }
+}
+
+fn select_fields<'a>(
+    field_selection: &FieldSelection,
+    samples: impl Iterator<Item = &'a serde_json::Value> + Clone,
+) -> DdsResult<serde_json::Value> {
+    let mut joined_sample = serde_json::Map::new();
+    match field_selection {
+        FieldSelection::All => {
+            for sample in samples {
+                if let serde_json::Value::Object(fields) = sample {
+                    for (name, value) in fields {
+                        match joined_sample.get(name) {
+                            Some(v) if v != value => {
+                                return Err(DdsError::PreconditionNotMet(format!(
+                                    "Field {} has different values in the related topics",
+                                    name
+                                )))
+                            }
+                            Some(_) => (),
+                            None => {
+                                joined_sample.insert(name.clone(), value.clone());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        FieldSelection::Fields(fields) => {
+            for (path, name) in fields {
+                let mut values = samples.clone().filter_map(|sample| {
+                    path.split('.')
+                        .try_fold(sample, |value, field| value.get(field))
+                });
+                let value = values.next().ok_or_else(|| {
+                    DdsError::PreconditionNotMet(format!(
+                        "Field {} not found in any of the related topics",
+                        path
+                    ))
+                })?;
+                if values.any(|v| v != value) {
+                    return Err(DdsError::PreconditionNotMet(format!(
+                        "Field {} has different values in the related topics",
+                        path
+                    )));
+                }
+                joined_sample.insert(name.clone(), value.clone());
+            }
+        }
+    }
+    Ok(serde_json::Value::Object(joined_sample))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topic_definition::type_support::Representation;
+
+    #[test]
+    fn parse_select_all_natural_join() {
+        let expression =
+            SubscriptionExpression::parse("SELECT * FROM Gps NATURAL JOIN Imu").unwrap();
+        assert_eq!(expression.field_selection(), &FieldSelection::All);
+        assert_eq!(expression.related_topic_names(), &["Gps", "Imu"]);
+    }
+
+    #[test]
+    fn parse_selected_fields_with_names() {
+        let expression = SubscriptionExpression::parse(
+            "select id, position.x AS x, yaw heading from Gps inner natural join (Imu natural inner join Odometry)",
+        )
+        .unwrap();
+        assert_eq!(
+            expression.field_selection(),
+            &FieldSelection::Fields(vec![
+                ("id".to_string(), "id".to_string()),
+                ("position.x".to_string(), "x".to_string()),
+                ("yaw".to_string(), "heading".to_string()),
+            ])
+        );
+        assert_eq!(
+            expression.related_topic_names(),
+            &["Gps", "Imu", "Odometry"]
+        );
+    }
+
+    #[test]
+    fn parse_where_clause_is_unsupported() {
+        assert_eq!(
+            SubscriptionExpression::parse("SELECT * FROM Gps NATURAL JOIN Imu WHERE id > %0"),
+            Err(DdsError::Unsupported)
+        );
+    }
+
+    #[test]
+    fn parse_invalid_expression() {
+        assert_eq!(
+            SubscriptionExpression::parse("SELECT * Gps NATURAL JOIN Imu"),
+            Err(DdsError::BadParameter)
+        );
+        assert_eq!(
+            SubscriptionExpression::parse("SELECT * FROM Gps JOIN Imu"),
+            Err(DdsError::BadParameter)
+        );
+        assert_eq!(
+            SubscriptionExpression::parse("SELECT FROM Gps"),
+            Err(DdsError::BadParameter)
+        );
+    }
+
+    #[test]
+    fn select_fields_of_joined_samples() {
+        let samples = [
+            serde_json::json!({"id": 1, "position": {"x": 2.0, "y": 3.0}}),
+            serde_json::json!({"id": 1, "yaw": 4.0}),
+        ];
+        let field_selection = FieldSelection::Fields(vec![
+            ("id".to_string(), "id".to_string()),
+            ("position.x".to_string(), "x".to_string()),
+            ("yaw".to_string(), "heading".to_string()),
+        ]);
+
+        assert_eq!(
+            select_fields(&field_selection, samples.iter()).unwrap(),
+            serde_json::json!({"id": 1, "x": 2.0, "heading": 4.0})
+        );
+        assert_eq!(
+            select_fields(&FieldSelection::All, samples.iter()).unwrap(),
+            serde_json::json!({"id": 1, "position": {"x": 2.0, "y": 3.0}, "yaw": 4.0})
+        );
+    }
+
+    #[test]
+    fn select_fields_with_different_shared_values_fails() {
+        let samples = [
+            serde_json::json!({"id": 1, "x": 2.0}),
+            serde_json::json!({"id": 2, "yaw": 4.0}),
+        ];
+        let field_selection = FieldSelection::Fields(vec![("id".to_string(), "id".to_string())]);
+
+        assert!(matches!(
+            select_fields(&field_selection, samples.iter()),
+            Err(DdsError::PreconditionNotMet(_))
+        ));
+        assert!(matches!(
+            select_fields(&FieldSelection::All, samples.iter()),
+            Err(DdsError::PreconditionNotMet(_))
+        ));
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Joined {
+        id: u8,
+        x: i32,
+        yaw: i32,
+    }
+
+    impl DdsRepresentation for Joined {
+        const REPRESENTATION: Representation = Representation::CdrLe;
+    }
+
+    impl DdsGetKey for Joined {
+        type BorrowedKeyHolder<'a> = u8;
+
+        const MAX_KEY_SIZE: Option<usize> = Some(1);
+
+        fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
+            self.id
+        }
+    }
+
+    fn alive(id: u8, x: i32, yaw: i32) -> JoinedChange {
+        let sample = Joined { id, x, yaw };
+        JoinedChange::Alive {
+            instance_handle: dds_key_hash(&sample).unwrap(),
+            data: dds_serialize_to_bytes(&sample).unwrap(),
+        }
+    }
+
+    fn disposed(id: u8) -> JoinedChange {
+        JoinedChange::Disposed {
+            instance_handle: dds_key_hash(&Joined { id, x: 0, yaw: 0 }).unwrap(),
+        }
+    }
+
+    #[test]
+    fn join_samples_with_equal_common_fields() {
+        let mut join = MultiTopicJoin::new::<Joined>(FieldSelection::All, 2);
+        let position_handle = |id: u8| InstanceHandle::new([id; 16]);
+        let heading_handle = |id: u8| InstanceHandle::new([id + 100; 16]);
+
+        assert_eq!(
+            join.add_sample(
+                0,
+                position_handle(1),
+                Some(serde_json::json!({"id": 1, "x": 10}))
+            ),
+            Ok(vec![])
+        );
+        assert_eq!(
+            join.add_sample(
+                0,
+                position_handle(2),
+                Some(serde_json::json!({"id": 2, "x": 20}))
+            ),
+            Ok(vec![])
+        );
+        assert_eq!(
+            join.add_sample(
+                1,
+                heading_handle(2),
+                Some(serde_json::json!({"id": 2, "yaw": 90}))
+            ),
+            Ok(vec![alive(2, 20, 90)])
+        );
+        assert_eq!(
+            join.add_sample(
+                0,
+                position_handle(2),
+                Some(serde_json::json!({"id": 2, "x": 25}))
+            ),
+            Ok(vec![alive(2, 25, 90)])
+        );
+    }
+
+    #[test]
+    fn joined_instance_is_disposed_when_related_instance_is_not_alive() {
+        let mut join = MultiTopicJoin::new::<Joined>(FieldSelection::All, 2);
+        let position_handle = InstanceHandle::new([1; 16]);
+        let heading_handle = InstanceHandle::new([2; 16]);
+
+        join.add_sample(
+            0,
+            position_handle,
+            Some(serde_json::json!({"id": 1, "x": 10})),
+        )
+        .unwrap();
+        join.add_sample(
+            1,
+            heading_handle,
+            Some(serde_json::json!({"id": 1, "yaw": 90})),
+        )
+        .unwrap();
+
+        assert_eq!(
+            join.add_sample(1, heading_handle, None),
+            Ok(vec![disposed(1)])
+        );
+        assert_eq!(join.add_sample(0, position_handle, None), Ok(vec![]));
+    }
+
+    #[test]
+    fn joined_instance_is_disposed_when_common_field_changes() {
+        let mut join = MultiTopicJoin::new::<Joined>(FieldSelection::All, 2);
+        let position_handle = InstanceHandle::new([1; 16]);
+        let heading_handle = InstanceHandle::new([2; 16]);
+
+        join.add_sample(
+            0,
+            position_handle,
+            Some(serde_json::json!({"id": 1, "x": 10})),
+        )
+        .unwrap();
+        join.add_sample(
+            1,
+            heading_handle,
+            Some(serde_json::json!({"id": 1, "yaw": 90})),
+        )
+        .unwrap();
+
+        assert_eq!(
+            join.add_sample(
+                1,
+                heading_handle,
+                Some(serde_json::json!({"id": 2, "yaw": 90}))
+            ),
+            Ok(vec![disposed(1)])
+        );
+    }
+
+    #[test]
+    fn join_is_unchanged_when_joined_sample_fails() {
+        let mut join = MultiTopicJoin::new::<Joined>(FieldSelection::All, 2);
+        let position_handle = InstanceHandle::new([1; 16]);
+        let heading_handle = InstanceHandle::new([2; 16]);
+
+        join.add_sample(
+            0,
+            position_handle,
+            Some(serde_json::json!({"id": 1, "x": 10})),
+        )
+        .unwrap();
+        join.add_sample(
+            1,
+            heading_handle,
+            Some(serde_json::json!({"id": 1, "yaw": 90})),
+        )
+        .unwrap();
+
+        assert!(join
+            .add_sample(
+                1,
+                heading_handle,
+                Some(serde_json::json!({"id": 1, "yaw": "north"}))
+            )
+            .is_err());
+        assert_eq!(
+            join.add_sample(0, position_handle, None),
+            Ok(vec![disposed(1)])
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{
-    implementation::utils::actor::ActorAddress, publication::data_writer::AnyDataWriter,
+    implementation::utils::actor::ActorAddress,
+    infrastructure::error::{DdsError, DdsResult},
+    publication::data_writer::AnyDataWriter,
     subscription::data_reader::AnyDataReader,
 };
 
@@ -24,8 +26,8 @@ pub enum DataWriterNodeKind {
 
 #[derive(Clone, PartialEq, Eq)]
 pub enum DataReaderNodeKind {
-    _BuiltinStateful(DataReaderNode),
-    _BuiltinStateless(DataReaderNode),
+    BuiltinStateful(DataReaderNode),
+    BuiltinStateless(DataReaderNode),
     UserDefined(DataReaderNode),
     Listener(DataReaderNode),
 }
@@ -90,17 +92,16 @@ impl DataReaderNode {
         &self.parent_participant
     }
 
-    pub fn topic_address(&self) -> ActorAddress<DdsTopic> {
-        self.parent_participant
-            .get_user_defined_topic_list()
-            .expect("should never fail")
-            .iter()
-            .find(|t| {
-                t.get_type_name() == self.this.get_type_name()
-                    && t.get_name() == self.this.get_topic_name()
-            })
-            .expect("should always exist")
-            .clone()
+    pub fn topic_address(&self) -> DdsResult<ActorAddress<DdsTopic>> {
+        let type_name = self.this.get_type_name()?;
+        let topic_name = self.this.get_topic_name()?;
+        for topic in self.parent_participant.get_user_defined_topic_list()? {
+            if topic.get_type_name()? == type_name && topic.get_name()? == topic_name {
+                return Ok(topic);
+            }
+        }
+
+        Err(DdsError::AlreadyDeleted)
     }
 }
 
@@ -157,6 +158,18 @@ impl DataWriterNode {
     pub fn parent_participant(&self) -> &ActorAddress<DdsDomainParticipant> {
         &self.parent_participant
     }
+
+    pub fn topic_address(&self) -> DdsResult<ActorAddress<DdsTopic>> {
+        let type_name = self.this.get_type_name()?;
+        let topic_name = self.this.get_topic_name()?;
+        for topic in self.parent_participant.get_user_defined_topic_list()? {
+            if topic.get_type_name()? == type_name && topic.get_name()? == topic_name {
+                return Ok(topic);
+            }
+        }
+
+        Err(DdsError::AlreadyDeleted)
+    }
 }
 
 impl AnyDataWriter for DataWriterNode {}
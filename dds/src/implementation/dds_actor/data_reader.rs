@@ -8,17 +8,21 @@ use crate::{
             discovered_writer_data::DiscoveredWriterData,
         },
         dds::{
-            dds_data_reader::DdsDataReader, dds_domain_participant::DdsDomainParticipant,
-            dds_subscriber::DdsSubscriber, status_condition_impl::StatusConditionImpl,
+            dds_data_reader::DdsDataReader, dds_data_reader_listener::DdsDataReaderListener,
+            dds_domain_participant::DdsDomainParticipant, dds_subscriber::DdsSubscriber,
+            status_condition_impl::StatusConditionImpl,
         },
         rtps::{
-            messages::overall_structure::{RtpsMessageHeader, RtpsMessageRead},
-            types::Locator,
+            messages::{
+                overall_structure::{RtpsMessageHeader, RtpsMessageRead},
+                submessage_elements::Data,
+            },
+            types::{Guid, Locator},
             writer_proxy::RtpsWriterProxy,
         },
         rtps_udp_psm::udp_transport::UdpTransportWrite,
         utils::{
-            actor::{ActorAddress, Mail, MailHandler},
+            actor::{Actor, ActorAddress, Mail, MailHandler},
             shared_object::{DdsRwLock, DdsShared},
         },
     },
@@ -26,14 +30,20 @@ use crate::{
         error::DdsResult,
         instance::InstanceHandle,
         qos::{DataReaderQos, SubscriberQos, TopicQos},
-        status::SubscriptionMatchedStatus,
+        status::{
+            LivelinessChangedStatus, RequestedDeadlineMissedStatus, RequestedIncompatibleQosStatus,
+            SampleLostStatus, SampleRejectedStatus, StatusKind, SubscriptionMatchedStatus,
+        },
         time::Time,
     },
     subscription::{
         data_reader::Sample,
-        sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
+        sample_info::{InstanceStateKind, SampleInfo, SampleStateKind, ViewStateKind},
+    },
+    topic_definition::{
+        dynamic_type::DynamicType,
+        type_support::{DdsDeserialize, DdsRepresentation, DdsSerializedKey},
     },
-    topic_definition::type_support::DdsRepresentation,
 };
 
 impl ActorAddress<DdsDataReader> {
@@ -99,6 +109,22 @@ impl ActorAddress<DdsDataReader> {
         self.send_blocking(GetTopicName)
     }
 
+    pub fn get_dynamic_type(&self) -> DdsResult<Option<DynamicType>> {
+        struct GetDynamicType;
+
+        impl Mail for GetDynamicType {
+            type Result = Option<DynamicType>;
+        }
+
+        impl MailHandler<GetDynamicType> for DdsDataReader {
+            fn handle(&mut self, _mail: GetDynamicType) -> <GetDynamicType as Mail>::Result {
+                self.get_dynamic_type()
+            }
+        }
+
+        self.send_blocking(GetDynamicType)
+    }
+
     pub fn get_statuscondition(&self) -> DdsResult<DdsShared<DdsRwLock<StatusConditionImpl>>> {
         struct GetStatusConditions;
 
@@ -136,6 +162,103 @@ impl ActorAddress<DdsDataReader> {
         self.send_blocking(GetSubscriptionMatchedStatus)
     }
 
+    pub fn get_sample_lost_status(&self) -> DdsResult<SampleLostStatus> {
+        struct GetSampleLostStatus;
+
+        impl Mail for GetSampleLostStatus {
+            type Result = SampleLostStatus;
+        }
+
+        impl MailHandler<GetSampleLostStatus> for DdsDataReader {
+            fn handle(
+                &mut self,
+                _mail: GetSampleLostStatus,
+            ) -> <GetSampleLostStatus as Mail>::Result {
+                self.get_sample_lost_status()
+            }
+        }
+
+        self.send_blocking(GetSampleLostStatus)
+    }
+
+    pub fn get_liveliness_changed_status(&self) -> DdsResult<LivelinessChangedStatus> {
+        struct GetLivelinessChangedStatus;
+
+        impl Mail for GetLivelinessChangedStatus {
+            type Result = LivelinessChangedStatus;
+        }
+
+        impl MailHandler<GetLivelinessChangedStatus> for DdsDataReader {
+            fn handle(
+                &mut self,
+                _mail: GetLivelinessChangedStatus,
+            ) -> <GetLivelinessChangedStatus as Mail>::Result {
+                self.get_liveliness_changed_status()
+            }
+        }
+
+        self.send_blocking(GetLivelinessChangedStatus)
+    }
+
+    pub fn get_requested_deadline_missed_status(&self) -> DdsResult<RequestedDeadlineMissedStatus> {
+        struct GetRequestedDeadlineMissedStatus;
+
+        impl Mail for GetRequestedDeadlineMissedStatus {
+            type Result = RequestedDeadlineMissedStatus;
+        }
+
+        impl MailHandler<GetRequestedDeadlineMissedStatus> for DdsDataReader {
+            fn handle(
+                &mut self,
+                _mail: GetRequestedDeadlineMissedStatus,
+            ) -> <GetRequestedDeadlineMissedStatus as Mail>::Result {
+                self.get_requested_deadline_missed_status()
+            }
+        }
+
+        self.send_blocking(GetRequestedDeadlineMissedStatus)
+    }
+
+    pub fn get_requested_incompatible_qos_status(
+        &self,
+    ) -> DdsResult<RequestedIncompatibleQosStatus> {
+        struct GetRequestedIncompatibleQosStatus;
+
+        impl Mail for GetRequestedIncompatibleQosStatus {
+            type Result = RequestedIncompatibleQosStatus;
+        }
+
+        impl MailHandler<GetRequestedIncompatibleQosStatus> for DdsDataReader {
+            fn handle(
+                &mut self,
+                _mail: GetRequestedIncompatibleQosStatus,
+            ) -> <GetRequestedIncompatibleQosStatus as Mail>::Result {
+                self.get_requested_incompatible_qos_status()
+            }
+        }
+
+        self.send_blocking(GetRequestedIncompatibleQosStatus)
+    }
+
+    pub fn get_sample_rejected_status(&self) -> DdsResult<SampleRejectedStatus> {
+        struct GetSampleRejectedStatus;
+
+        impl Mail for GetSampleRejectedStatus {
+            type Result = SampleRejectedStatus;
+        }
+
+        impl MailHandler<GetSampleRejectedStatus> for DdsDataReader {
+            fn handle(
+                &mut self,
+                _mail: GetSampleRejectedStatus,
+            ) -> <GetSampleRejectedStatus as Mail>::Result {
+                self.get_sample_rejected_status()
+            }
+        }
+
+        self.send_blocking(GetSampleRejectedStatus)
+    }
+
     pub fn get_matched_publications(&self) -> DdsResult<Vec<InstanceHandle>> {
         struct GetMatchedPublications;
 
@@ -300,6 +423,134 @@ impl ActorAddress<DdsDataReader> {
         self.send_blocking(GetQos)
     }
 
+    pub fn set_listener(
+        &self,
+        listener: Option<Actor<DdsDataReaderListener>>,
+        status_kind: Vec<StatusKind>,
+    ) -> DdsResult<()> {
+        struct SetListener {
+            listener: Option<Actor<DdsDataReaderListener>>,
+            status_kind: Vec<StatusKind>,
+        }
+
+        impl Mail for SetListener {
+            type Result = ();
+        }
+
+        impl MailHandler<SetListener> for DdsDataReader {
+            fn handle(&mut self, mail: SetListener) -> <SetListener as Mail>::Result {
+                self.set_listener(mail.listener, mail.status_kind)
+            }
+        }
+
+        self.send_blocking(SetListener {
+            listener,
+            status_kind,
+        })
+    }
+
+    pub fn set_multitopic_source_readers(
+        &self,
+        multitopic_source_readers: Vec<ActorAddress<DdsDataReader>>,
+    ) -> DdsResult<()> {
+        struct SetMultitopicSourceReaders {
+            multitopic_source_readers: Vec<ActorAddress<DdsDataReader>>,
+        }
+
+        impl Mail for SetMultitopicSourceReaders {
+            type Result = ();
+        }
+
+        impl MailHandler<SetMultitopicSourceReaders> for DdsDataReader {
+            fn handle(
+                &mut self,
+                mail: SetMultitopicSourceReaders,
+            ) -> <SetMultitopicSourceReaders as Mail>::Result {
+                self.set_multitopic_source_readers(mail.multitopic_source_readers)
+            }
+        }
+
+        self.send_blocking(SetMultitopicSourceReaders {
+            multitopic_source_readers,
+        })
+    }
+
+    pub fn get_multitopic_source_readers(&self) -> DdsResult<Vec<ActorAddress<DdsDataReader>>> {
+        struct GetMultitopicSourceReaders;
+
+        impl Mail for GetMultitopicSourceReaders {
+            type Result = Vec<ActorAddress<DdsDataReader>>;
+        }
+
+        impl MailHandler<GetMultitopicSourceReaders> for DdsDataReader {
+            fn handle(
+                &mut self,
+                _mail: GetMultitopicSourceReaders,
+            ) -> <GetMultitopicSourceReaders as Mail>::Result {
+                self.get_multitopic_source_readers()
+            }
+        }
+
+        self.send_blocking(GetMultitopicSourceReaders)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_multitopic_source_sample(
+        &self,
+        related_topic_index: usize,
+        instance_handle: InstanceHandle,
+        value: Option<serde_json::Value>,
+        source_timestamp: Option<Time>,
+        reception_timestamp: Time,
+        data_reader_address: ActorAddress<DdsDataReader>,
+        subscriber_address: ActorAddress<DdsSubscriber>,
+        participant_address: ActorAddress<DdsDomainParticipant>,
+    ) -> DdsResult<()> {
+        struct AddMultitopicSourceSample {
+            related_topic_index: usize,
+            instance_handle: InstanceHandle,
+            value: Option<serde_json::Value>,
+            source_timestamp: Option<Time>,
+            reception_timestamp: Time,
+            data_reader_address: ActorAddress<DdsDataReader>,
+            subscriber_address: ActorAddress<DdsSubscriber>,
+            participant_address: ActorAddress<DdsDomainParticipant>,
+        }
+
+        impl Mail for AddMultitopicSourceSample {
+            type Result = DdsResult<()>;
+        }
+
+        impl MailHandler<AddMultitopicSourceSample> for DdsDataReader {
+            fn handle(
+                &mut self,
+                mail: AddMultitopicSourceSample,
+            ) -> <AddMultitopicSourceSample as Mail>::Result {
+                self.add_multitopic_source_sample(
+                    mail.related_topic_index,
+                    mail.instance_handle,
+                    mail.value,
+                    mail.source_timestamp,
+                    mail.reception_timestamp,
+                    mail.data_reader_address,
+                    mail.subscriber_address,
+                    mail.participant_address,
+                )
+            }
+        }
+
+        self.send_blocking(AddMultitopicSourceSample {
+            related_topic_index,
+            instance_handle,
+            value,
+            source_timestamp,
+            reception_timestamp,
+            data_reader_address,
+            subscriber_address,
+            participant_address,
+        })?
+    }
+
     pub fn send_message(
         &self,
         header: RtpsMessageHeader,
@@ -344,6 +595,27 @@ impl ActorAddress<DdsDataReader> {
         self.send_blocking(MatchedWriterAdd { a_writer_proxy })
     }
 
+    pub fn matched_writer_remove(&self, a_writer_guid: Guid) -> DdsResult<()> {
+        struct MatchedWriterRemove {
+            a_writer_guid: Guid,
+        }
+
+        impl Mail for MatchedWriterRemove {
+            type Result = ();
+        }
+
+        impl MailHandler<MatchedWriterRemove> for DdsDataReader {
+            fn handle(
+                &mut self,
+                mail: MatchedWriterRemove,
+            ) -> <MatchedWriterRemove as Mail>::Result {
+                self.matched_writer_remove(mail.a_writer_guid)
+            }
+        }
+
+        self.send_blocking(MatchedWriterRemove { a_writer_guid })
+    }
+
     pub fn get_instance_handle(&self) -> DdsResult<InstanceHandle> {
         struct GetInstanceHandle;
 
@@ -360,6 +632,45 @@ impl ActorAddress<DdsDataReader> {
         self.send_blocking(GetInstanceHandle)
     }
 
+    pub fn get_key_value(&self, handle: InstanceHandle) -> DdsResult<DdsSerializedKey> {
+        struct GetKeyValue {
+            handle: InstanceHandle,
+        }
+
+        impl Mail for GetKeyValue {
+            type Result = DdsResult<DdsSerializedKey>;
+        }
+
+        impl MailHandler<GetKeyValue> for DdsDataReader {
+            fn handle(&mut self, mail: GetKeyValue) -> <GetKeyValue as Mail>::Result {
+                self.get_key_value(mail.handle)
+            }
+        }
+
+        self.send_blocking(GetKeyValue { handle })?
+    }
+
+    pub fn lookup_instance(
+        &self,
+        instance_handle: InstanceHandle,
+    ) -> DdsResult<Option<InstanceHandle>> {
+        struct LookupInstance {
+            instance_handle: InstanceHandle,
+        }
+
+        impl Mail for LookupInstance {
+            type Result = DdsResult<Option<InstanceHandle>>;
+        }
+
+        impl MailHandler<LookupInstance> for DdsDataReader {
+            fn handle(&mut self, mail: LookupInstance) -> <LookupInstance as Mail>::Result {
+                self.lookup_instance(mail.instance_handle)
+            }
+        }
+
+        self.send_blocking(LookupInstance { instance_handle })?
+    }
+
     pub fn read_next_instance<Foo>(
         &self,
         max_samples: i32,
@@ -369,7 +680,7 @@ impl ActorAddress<DdsDataReader> {
         instance_states: &[InstanceStateKind],
     ) -> DdsResult<Vec<Sample<Foo>>>
     where
-        Foo: DdsRepresentation + for<'de> serde::Deserialize<'de> + Send + 'static,
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de> + Send + 'static,
     {
         struct ReadNextInstance<Foo> {
             phantom: PhantomData<Foo>,
@@ -386,7 +697,7 @@ impl ActorAddress<DdsDataReader> {
 
         impl<Foo> MailHandler<ReadNextInstance<Foo>> for DdsDataReader
         where
-            Foo: DdsRepresentation + for<'de> serde::Deserialize<'de>,
+            Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
         {
             fn handle(
                 &mut self,
@@ -421,7 +732,7 @@ impl ActorAddress<DdsDataReader> {
         specific_instance_handle: Option<InstanceHandle>,
     ) -> DdsResult<Vec<Sample<Foo>>>
     where
-        Foo: DdsRepresentation + for<'de> serde::Deserialize<'de> + Send + 'static,
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de> + Send + 'static,
     {
         struct Take<Foo> {
             phantom: PhantomData<Foo>,
@@ -438,7 +749,7 @@ impl ActorAddress<DdsDataReader> {
 
         impl<Foo> MailHandler<Take<Foo>> for DdsDataReader
         where
-            Foo: DdsRepresentation + for<'de> serde::Deserialize<'de>,
+            Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
         {
             fn handle(&mut self, mail: Take<Foo>) -> <Take<Foo> as Mail>::Result {
                 self.take(
@@ -470,7 +781,7 @@ impl ActorAddress<DdsDataReader> {
         instance_states: Vec<InstanceStateKind>,
     ) -> DdsResult<Vec<Sample<Foo>>>
     where
-        Foo: DdsRepresentation + for<'de> serde::Deserialize<'de> + Send + 'static,
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de> + Send + 'static,
     {
         struct TakeNextInstance<Foo> {
             phantom_data: PhantomData<Foo>,
@@ -487,7 +798,7 @@ impl ActorAddress<DdsDataReader> {
 
         impl<Foo> MailHandler<TakeNextInstance<Foo>> for DdsDataReader
         where
-            Foo: DdsRepresentation + for<'de> serde::Deserialize<'de>,
+            Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
         {
             fn handle(
                 &mut self,
@@ -623,7 +934,7 @@ impl ActorAddress<DdsDataReader> {
         specific_instance_handle: Option<InstanceHandle>,
     ) -> DdsResult<Vec<Sample<Foo>>>
     where
-        Foo: DdsRepresentation + for<'de> serde::Deserialize<'de> + Send + 'static,
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de> + Send + 'static,
     {
         struct Read<Foo> {
             phantom: PhantomData<Foo>,
@@ -640,7 +951,7 @@ impl ActorAddress<DdsDataReader> {
 
         impl<Foo> MailHandler<Read<Foo>> for DdsDataReader
         where
-            Foo: DdsRepresentation + for<'de> serde::Deserialize<'de>,
+            Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
         {
             fn handle(&mut self, mail: Read<Foo>) -> <Read<Foo> as Mail>::Result {
                 self.read(
@@ -663,6 +974,186 @@ impl ActorAddress<DdsDataReader> {
         })?
     }
 
+    pub fn read_loaned(
+        &self,
+        max_samples: i32,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        specific_instance_handle: Option<InstanceHandle>,
+    ) -> DdsResult<Vec<(Data, SampleInfo)>> {
+        struct ReadLoaned {
+            max_samples: i32,
+            sample_states: Vec<SampleStateKind>,
+            view_states: Vec<ViewStateKind>,
+            instance_states: Vec<InstanceStateKind>,
+            specific_instance_handle: Option<InstanceHandle>,
+        }
+
+        impl Mail for ReadLoaned {
+            type Result = DdsResult<Vec<(Data, SampleInfo)>>;
+        }
+
+        impl MailHandler<ReadLoaned> for DdsDataReader {
+            fn handle(&mut self, mail: ReadLoaned) -> <ReadLoaned as Mail>::Result {
+                self.read_loaned(
+                    mail.max_samples,
+                    &mail.sample_states,
+                    &mail.view_states,
+                    &mail.instance_states,
+                    mail.specific_instance_handle,
+                )
+            }
+        }
+
+        self.send_blocking(ReadLoaned {
+            max_samples,
+            sample_states: sample_states.to_vec(),
+            view_states: view_states.to_vec(),
+            instance_states: instance_states.to_vec(),
+            specific_instance_handle,
+        })?
+    }
+
+    pub fn take_loaned(
+        &self,
+        max_samples: i32,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        specific_instance_handle: Option<InstanceHandle>,
+    ) -> DdsResult<Vec<(Data, SampleInfo)>> {
+        struct TakeLoaned {
+            max_samples: i32,
+            sample_states: Vec<SampleStateKind>,
+            view_states: Vec<ViewStateKind>,
+            instance_states: Vec<InstanceStateKind>,
+            specific_instance_handle: Option<InstanceHandle>,
+        }
+
+        impl Mail for TakeLoaned {
+            type Result = DdsResult<Vec<(Data, SampleInfo)>>;
+        }
+
+        impl MailHandler<TakeLoaned> for DdsDataReader {
+            fn handle(&mut self, mail: TakeLoaned) -> <TakeLoaned as Mail>::Result {
+                self.take_loaned(
+                    mail.max_samples,
+                    &mail.sample_states,
+                    &mail.view_states,
+                    &mail.instance_states,
+                    mail.specific_instance_handle,
+                )
+            }
+        }
+
+        self.send_blocking(TakeLoaned {
+            max_samples,
+            sample_states: sample_states.to_vec(),
+            view_states: view_states.to_vec(),
+            instance_states: instance_states.to_vec(),
+            specific_instance_handle,
+        })?
+    }
+
+    pub fn get_sample_timestamp_list(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<Vec<Time>> {
+        struct GetSampleTimestampList {
+            sample_states: Vec<SampleStateKind>,
+            view_states: Vec<ViewStateKind>,
+            instance_states: Vec<InstanceStateKind>,
+        }
+
+        impl Mail for GetSampleTimestampList {
+            type Result = Vec<Time>;
+        }
+
+        impl MailHandler<GetSampleTimestampList> for DdsDataReader {
+            fn handle(
+                &mut self,
+                mail: GetSampleTimestampList,
+            ) -> <GetSampleTimestampList as Mail>::Result {
+                self.get_sample_timestamp_list(
+                    &mail.sample_states,
+                    &mail.view_states,
+                    &mail.instance_states,
+                )
+            }
+        }
+
+        self.send_blocking(GetSampleTimestampList {
+            sample_states: sample_states.to_vec(),
+            view_states: view_states.to_vec(),
+            instance_states: instance_states.to_vec(),
+        })
+    }
+
+    pub fn notify_data_available(
+        &self,
+        data_reader_address: ActorAddress<DdsDataReader>,
+        subscriber_address: ActorAddress<DdsSubscriber>,
+        participant_address: ActorAddress<DdsDomainParticipant>,
+    ) -> DdsResult<()> {
+        struct NotifyDataAvailable {
+            data_reader_address: ActorAddress<DdsDataReader>,
+            subscriber_address: ActorAddress<DdsSubscriber>,
+            participant_address: ActorAddress<DdsDomainParticipant>,
+        }
+
+        impl Mail for NotifyDataAvailable {
+            type Result = ();
+        }
+
+        impl MailHandler<NotifyDataAvailable> for DdsDataReader {
+            fn handle(
+                &mut self,
+                mail: NotifyDataAvailable,
+            ) -> <NotifyDataAvailable as Mail>::Result {
+                self.notify_data_available(
+                    mail.data_reader_address,
+                    mail.subscriber_address,
+                    mail.participant_address,
+                )
+            }
+        }
+
+        self.send_blocking(NotifyDataAvailable {
+            data_reader_address,
+            subscriber_address,
+            participant_address,
+        })
+    }
+
+    pub fn peek<Foo>(&self) -> DdsResult<Vec<Sample<Foo>>>
+    where
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de> + Send + 'static,
+    {
+        struct Peek<Foo> {
+            phantom: PhantomData<Foo>,
+        }
+
+        impl<Foo> Mail for Peek<Foo> {
+            type Result = DdsResult<Vec<Sample<Foo>>>;
+        }
+
+        impl<Foo> MailHandler<Peek<Foo>> for DdsDataReader
+        where
+            Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
+        {
+            fn handle(&mut self, _mail: Peek<Foo>) -> <Peek<Foo> as Mail>::Result {
+                self.peek()
+            }
+        }
+
+        self.send_blocking(Peek {
+            phantom: PhantomData,
+        })?
+    }
+
     pub fn process_rtps_message(
         &self,
         message: RtpsMessageRead,
@@ -703,4 +1194,56 @@ impl ActorAddress<DdsDataReader> {
             participant_address,
         })
     }
+
+    pub fn process_rtps_message_and_get_added_samples<Foo>(
+        &self,
+        message: RtpsMessageRead,
+        reception_timestamp: Time,
+        data_reader_address: ActorAddress<DdsDataReader>,
+        subscriber_address: ActorAddress<DdsSubscriber>,
+        participant_address: ActorAddress<DdsDomainParticipant>,
+    ) -> DdsResult<Vec<Sample<Foo>>>
+    where
+        Foo: DdsRepresentation + for<'de> DdsDeserialize<'de> + Send + 'static,
+    {
+        struct ProcessRtpsMessageAndGetAddedSamples<Foo> {
+            message: RtpsMessageRead,
+            reception_timestamp: Time,
+            data_reader_address: ActorAddress<DdsDataReader>,
+            subscriber_address: ActorAddress<DdsSubscriber>,
+            participant_address: ActorAddress<DdsDomainParticipant>,
+            phantom: PhantomData<Foo>,
+        }
+
+        impl<Foo> Mail for ProcessRtpsMessageAndGetAddedSamples<Foo> {
+            type Result = DdsResult<Vec<Sample<Foo>>>;
+        }
+
+        impl<Foo> MailHandler<ProcessRtpsMessageAndGetAddedSamples<Foo>> for DdsDataReader
+        where
+            Foo: DdsRepresentation + for<'de> DdsDeserialize<'de>,
+        {
+            fn handle(
+                &mut self,
+                mail: ProcessRtpsMessageAndGetAddedSamples<Foo>,
+            ) -> <ProcessRtpsMessageAndGetAddedSamples<Foo> as Mail>::Result {
+                self.process_rtps_message_and_get_added_samples(
+                    mail.message,
+                    mail.reception_timestamp,
+                    mail.data_reader_address,
+                    mail.subscriber_address,
+                    mail.participant_address,
+                )
+            }
+        }
+
+        self.send_blocking(ProcessRtpsMessageAndGetAddedSamples {
+            message,
+            reception_timestamp,
+            data_reader_address,
+            subscriber_address,
+            participant_address,
+            phantom: PhantomData,
+        })?
+    }
 }
@@ -7,3 +7,4 @@ pub mod parameter_list_serde;
 pub mod rtps;
 pub mod rtps_udp_psm;
 pub mod utils;
+pub mod xcdr2_serde;
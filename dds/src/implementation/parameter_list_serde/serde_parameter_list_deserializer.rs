@@ -5,6 +5,8 @@ use serde::de::{self};
 
 use cdr::Error;
 
+use crate::topic_definition::type_object::{xtypes_deserializer, XTYPES_DATA};
+
 pub struct ParameterListDeserializer<'a, E> {
     data: &'a [u8],
     endianness: PhantomData<E>,
@@ -327,14 +329,153 @@ where
     where
         V: serde::de::DeserializeSeed<'de>,
     {
-        let mut cdr_deserializer = cdr::Deserializer::<_, _, E>::new(self.data, cdr::Infinite);
-        let value = seed.deserialize(&mut cdr_deserializer);
+        let mut value_deserializer = ParameterValueDeserializer::<E>::new(self.data);
+        let value = seed.deserialize(&mut value_deserializer);
         self.data = &self.data[self.skip_length..];
         self.skip_length = 0;
         value
     }
 }
 
+// Deserializer of the parameter values, which are serialized in CDR except for the XTypes data.
+// This data is serialized in XCDR2 with the endianness of the parameter list and is given to the
+// newtype structures named XTYPES_DATA.
+struct ParameterValueDeserializer<'de, E> {
+    data: &'de [u8],
+    cdr_deserializer: cdr::Deserializer<&'de [u8], cdr::Infinite, E>,
+}
+
+impl<'de, E> ParameterValueDeserializer<'de, E>
+where
+    E: ByteOrder,
+{
+    fn new(data: &'de [u8]) -> Self {
+        Self {
+            data,
+            cdr_deserializer: cdr::Deserializer::new(data, cdr::Infinite),
+        }
+    }
+}
+
+macro_rules! forward_to_cdr_deserializer {
+    ($($method:ident),*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: de::Visitor<'de>,
+            {
+                de::Deserializer::$method(&mut self.cdr_deserializer, visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, E> de::Deserializer<'de> for &mut ParameterValueDeserializer<'de, E>
+where
+    E: ByteOrder,
+{
+    type Error = Error;
+
+    forward_to_cdr_deserializer!(
+        deserialize_any,
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_f32,
+        deserialize_f64,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_option,
+        deserialize_unit,
+        deserialize_seq,
+        deserialize_map,
+        deserialize_identifier,
+        deserialize_ignored_any
+    );
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_unit_struct(&mut self.cdr_deserializer, name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if name == XTYPES_DATA {
+            visitor.visit_newtype_struct(&mut xtypes_deserializer::<E>(self.data))
+        } else {
+            de::Deserializer::deserialize_newtype_struct(&mut self.cdr_deserializer, name, visitor)
+        }
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(&mut self.cdr_deserializer, len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple_struct(&mut self.cdr_deserializer, name, len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(&mut self.cdr_deserializer, name, fields, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_enum(&mut self.cdr_deserializer, name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
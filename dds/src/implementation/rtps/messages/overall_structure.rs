@@ -1,5 +1,5 @@
 use super::{
-    submessage_elements::SubmessageElement,
+    submessage_elements::{Data, SubmessageElement},
     submessages::{
         ack_nack::AckNackSubmessageWrite, data::DataSubmessageWrite,
         data_frag::DataFragSubmessageWrite, gap::GapSubmessageWrite,
@@ -27,10 +27,14 @@ use crate::implementation::rtps::{
     },
     types::{GuidPrefix, ProtocolVersion, VendorId},
 };
-use std::{io::BufRead, marker::PhantomData, sync::Arc};
+use bytes::Bytes;
+use std::{cell::RefCell, io::BufRead, marker::PhantomData, sync::Arc};
 
 pub(in crate::implementation::rtps) type WriteEndianness = byteorder::LittleEndian;
 const BUFFER_SIZE: usize = 65000;
+// Payloads up to this size are copied into the message instead of being sent as a
+// separate segment
+const MIN_SEGMENT_PAYLOAD_SIZE: usize = 1024;
 
 pub trait Submessage {
     fn submessage_header(&self, octets_to_next_header: u16) -> SubmessageHeaderWrite;
@@ -42,14 +46,39 @@ where
     T: Submessage,
 {
     fn write_bytes(&self, buf: &mut [u8]) -> usize {
-        let (header, body) = buf.split_at_mut(4);
-        let mut len = 0;
-        for submessage_element in self.submessage_elements() {
-            len += submessage_element.write_bytes(&mut body[len..]);
+        write_submessage_bytes(self, buf, |_, _| false)
+    }
+}
+
+// Write the submessage into the buffer. The serialized payloads for which `take_payload`
+// returns true are sent separately by the caller, so only their padding is written into
+// the buffer while their length is still accounted for in the submessage header.
+// `take_payload` receives the position in the buffer where the payload has to be inserted.
+fn write_submessage_bytes(
+    submessage: &(impl Submessage + ?Sized),
+    buf: &mut [u8],
+    mut take_payload: impl FnMut(&Data, usize) -> bool,
+) -> usize {
+    let (header, body) = buf.split_at_mut(4);
+    let mut len = 0;
+    let mut octets_to_next_header = 0;
+    for submessage_element in submessage.submessage_elements() {
+        match submessage_element {
+            SubmessageElement::SerializedData(data) if take_payload(data, 4 + len) => {
+                let padding = ((data.len() + 3) & !3) - data.len();
+                body[len..len + padding].fill(0);
+                len += padding;
+                octets_to_next_header += data.len() + padding;
+            }
+            _ => {
+                let element_length = submessage_element.write_bytes(&mut body[len..]);
+                len += element_length;
+                octets_to_next_header += element_length;
+            }
         }
-        let submessage_header = self.submessage_header(len as u16);
-        submessage_header.write_bytes(header) + len
     }
+    let submessage_header = submessage.submessage_header(octets_to_next_header as u16);
+    submessage_header.write_bytes(header) + len
 }
 
 pub trait FromBytes {
@@ -178,24 +207,71 @@ pub fn into_bytes_vec<T: WriteBytes>(value: T) -> Vec<u8> {
     Vec::from(&buf[0..len])
 }
 
+thread_local! {
+    // Buffer in which the messages of the calling thread are written. Only the written
+    // part is zeroed again after each message so the buffer is allocated once per thread.
+    static MESSAGE_BUFFER: RefCell<Vec<u8>> = RefCell::new(vec![0; BUFFER_SIZE]);
+}
+
+// The message is kept as a list of segments so that the serialized payloads
+// are sent directly from the history cache without being copied
 #[derive(Debug, PartialEq, Eq)]
 pub struct RtpsMessageWrite {
-    buffer: [u8; BUFFER_SIZE],
-    len: usize,
+    segments: Vec<Data>,
 }
 
 impl RtpsMessageWrite {
     pub fn new(header: RtpsMessageHeader, submessages: Vec<RtpsSubmessageWriteKind<'_>>) -> Self {
-        let mut buffer = [0; BUFFER_SIZE];
-        let mut len = header.write_bytes(&mut buffer[0..]);
-        for submessage in &submessages {
-            len += submessage.write_bytes(&mut buffer[len..]);
-        }
-        Self { buffer, len }
+        MESSAGE_BUFFER.with(|buffer| {
+            let mut buffer = buffer.borrow_mut();
+            let mut len = header.write_bytes(&mut buffer[0..]);
+            // Position in the buffer where each of the payloads has to be inserted. Small
+            // payloads are cheaper to copy into the buffer than to send as a separate segment
+            let mut payloads = Vec::new();
+            for submessage in &submessages {
+                let submessage_position = len;
+                len += write_submessage_bytes(
+                    submessage.as_submessage(),
+                    &mut buffer[len..],
+                    |data, payload_position| {
+                        let is_taken = data.len() > MIN_SEGMENT_PAYLOAD_SIZE;
+                        if is_taken {
+                            payloads.push((submessage_position + payload_position, data.clone()));
+                        }
+                        is_taken
+                    },
+                );
+            }
+            // The written part is copied out once and shared by all the segments between
+            // the payloads
+            let message_bytes = Bytes::copy_from_slice(&buffer[..len]);
+            buffer[..len].fill(0);
+
+            let mut segments = Vec::new();
+            let mut position = 0;
+            for (payload_position, payload) in payloads {
+                segments.push(message_bytes.slice(position..payload_position).into());
+                segments.push(payload);
+                position = payload_position;
+            }
+            segments.push(message_bytes.slice(position..).into());
+            segments.retain(|s: &Data| !s.as_ref().is_empty());
+
+            Self { segments }
+        })
+    }
+
+    pub fn segments(&self) -> &[Data] {
+        &self.segments
     }
 
-    pub fn buffer(&self) -> &[u8] {
-        &self.buffer[0..self.len]
+    #[allow(dead_code)] // Only used as convenience in tests
+    pub fn buffer(&self) -> Vec<u8> {
+        self.segments
+            .iter()
+            .flat_map(|s| s.as_ref())
+            .copied()
+            .collect()
     }
 }
 
@@ -231,6 +307,25 @@ pub enum RtpsSubmessageWriteKind<'a> {
     Pad(PadSubmessageWrite),
 }
 
+impl RtpsSubmessageWriteKind<'_> {
+    fn as_submessage(&self) -> &dyn Submessage {
+        match self {
+            RtpsSubmessageWriteKind::AckNack(s) => s,
+            RtpsSubmessageWriteKind::Data(s) => s,
+            RtpsSubmessageWriteKind::DataFrag(s) => s,
+            RtpsSubmessageWriteKind::Gap(s) => s,
+            RtpsSubmessageWriteKind::Heartbeat(s) => s,
+            RtpsSubmessageWriteKind::HeartbeatFrag(s) => s,
+            RtpsSubmessageWriteKind::InfoDestination(s) => s,
+            RtpsSubmessageWriteKind::InfoReply(s) => s,
+            RtpsSubmessageWriteKind::InfoSource(s) => s,
+            RtpsSubmessageWriteKind::InfoTimestamp(s) => s,
+            RtpsSubmessageWriteKind::NackFrag(s) => s,
+            RtpsSubmessageWriteKind::Pad(s) => s,
+        }
+    }
+}
+
 impl WriteBytes for RtpsSubmessageWriteKind<'_> {
     fn write_bytes(&self, buf: &mut [u8]) -> usize {
         match self {
@@ -404,6 +499,87 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn serialize_rtps_message_keeps_large_payload_as_separate_segment() {
+        let header = RtpsMessageHeader {
+            protocol: ProtocolId::PROTOCOL_RTPS,
+            version: ProtocolVersion::new(2, 3),
+            vendor_id: [9, 8],
+            guid_prefix: [3; 12],
+        };
+        let reader_id = EntityId::new([1, 2, 3], USER_DEFINED_READER_NO_KEY);
+        let writer_id = EntityId::new([6, 7, 8], USER_DEFINED_READER_GROUP);
+        let writer_sn = SequenceNumber::from(5);
+        let inline_qos = &ParameterList::empty();
+        let serialized_payload = &Data::new(vec![7; MIN_SEGMENT_PAYLOAD_SIZE + 3]);
+
+        let submessage = RtpsSubmessageWriteKind::Data(DataSubmessageWrite::new(
+            false,
+            true,
+            false,
+            false,
+            reader_id,
+            writer_id,
+            writer_sn,
+            inline_qos,
+            serialized_payload,
+        ));
+        let mut expected_submessage = vec![0; BUFFER_SIZE];
+        let submessage_length = submessage.write_bytes(&mut expected_submessage);
+        expected_submessage.truncate(submessage_length);
+        let value = RtpsMessageWrite::new(header, vec![submessage]);
+
+        assert_eq!(value.segments().len(), 3);
+        assert_eq!(&value.segments()[1], serialized_payload);
+        assert_eq!(value.segments()[2].as_ref(), &[0]);
+        assert_eq!(value.buffer()[20..], expected_submessage);
+    }
+
+    #[test]
+    fn serialize_rtps_message_copies_small_payload() {
+        let header = RtpsMessageHeader {
+            protocol: ProtocolId::PROTOCOL_RTPS,
+            version: ProtocolVersion::new(2, 3),
+            vendor_id: [9, 8],
+            guid_prefix: [3; 12],
+        };
+        let reader_id = EntityId::new([1, 2, 3], USER_DEFINED_READER_NO_KEY);
+        let writer_id = EntityId::new([6, 7, 8], USER_DEFINED_READER_GROUP);
+        let writer_sn = SequenceNumber::from(5);
+        let inline_qos = &ParameterList::empty();
+        let serialized_payload = &Data::new(vec![1, 2, 3]);
+
+        let submessage = RtpsSubmessageWriteKind::Data(DataSubmessageWrite::new(
+            false,
+            true,
+            false,
+            false,
+            reader_id,
+            writer_id,
+            writer_sn,
+            inline_qos,
+            serialized_payload,
+        ));
+        let value = RtpsMessageWrite::new(header, vec![submessage]);
+        #[rustfmt::skip]
+        assert_eq!(value.buffer(), vec![
+            b'R', b'T', b'P', b'S', // Protocol
+            2, 3, 9, 8, // ProtocolVersion | VendorId
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+            3, 3, 3, 3, // GuidPrefix
+            0x15, 0b_0000_0101, 24, 0, // Submessage header
+            0, 0, 16, 0, // extraFlags, octetsToInlineQos
+            1, 2, 3, 4, // readerId: value[4]
+            6, 7, 8, 9, // writerId: value[4]
+            0, 0, 0, 0, // writerSN: high
+            5, 0, 0, 0, // writerSN: low
+            1, 2, 3, 0, // serializedPayload
+        ]);
+        assert_eq!(value.segments().len(), 1);
+        assert!(MESSAGE_BUFFER.with(|buffer| buffer.borrow().iter().all(|&b| b == 0)));
+    }
+
     #[test]
     fn serialize_rtps_message() {
         let header = RtpsMessageHeader {
@@ -9,7 +9,8 @@ use crate::implementation::{
         types::{EntityId, GuidPrefix, Locator, ProtocolVersion, SequenceNumber, VendorId},
     },
 };
-use std::io::BufRead;
+use bytes::{BufMut, Bytes, BytesMut};
+use std::{cell::RefCell, io::BufRead, ops::Range};
 ///
 /// This files shall only contain the types as listed in the DDS-RTPS Version 2.3
 /// 8.3.5 RTPS SubmessageElements
@@ -206,7 +207,7 @@ impl Parameter {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ParameterList {
     parameter: Vec<Parameter>,
 }
@@ -287,16 +288,58 @@ impl WriteBytes for &ParameterList {
     }
 }
 
+// Initial capacity reserved in the payload pool of each thread. Payloads bigger
+// than this simply make the pool grow.
+const PAYLOAD_POOL_CAPACITY: usize = 64 * 1024;
+
+thread_local! {
+    static PAYLOAD_POOL: RefCell<BytesMut> = RefCell::new(BytesMut::new());
+}
+
+// The payload is reference counted so that the samples handed out by the
+// reader (e.g. on a loan), the fragments of a change and the messages being
+// sent all share the bytes stored in the history cache
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct Data(Vec<u8>);
+pub struct Data(Bytes);
 
 impl Data {
     pub fn new(data: Vec<u8>) -> Self {
-        Self(data)
+        Self(data.into())
+    }
+
+    /// Create the payload by writing it directly into a buffer taken from a pool
+    /// local to the calling thread. The payloads share the memory of the pool, which
+    /// is reclaimed once all of them have been dropped. A new block is only allocated
+    /// once the remaining capacity of the pool is used up.
+    pub fn from_writer<E>(
+        write: impl FnOnce(&mut dyn std::io::Write) -> Result<(), E>,
+    ) -> Result<Self, E> {
+        PAYLOAD_POOL.with(|pool| {
+            let mut buffer = std::mem::take(&mut *pool.borrow_mut());
+            if buffer.capacity() == 0 {
+                buffer.reserve(PAYLOAD_POOL_CAPACITY);
+            }
+            let result = write(&mut (&mut buffer).writer());
+            let data = buffer.split().freeze();
+            *pool.borrow_mut() = buffer;
+            result.map(|_| Self(data))
+        })
     }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    /// Get a part of the payload which shares the bytes of this one
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        Self(self.0.slice(range))
+    }
+}
+
+impl From<Bytes> for Data {
+    fn from(value: Bytes) -> Self {
+        Self(value)
+    }
 }
 
 impl AsRef<[u8]> for Data {
@@ -316,7 +359,7 @@ impl WriteBytes for &Data {
 
 impl FromBytes for Data {
     fn from_bytes<E: byteorder::ByteOrder>(v: &[u8]) -> Self {
-        Self::new(v.to_vec())
+        Self(Bytes::copy_from_slice(v))
     }
 }
 
@@ -487,6 +530,46 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn data_from_writer() {
+        let data = Data::from_writer(|writer| writer.write_all(&[1, 2, 3, 4])).unwrap();
+        assert_eq!(data, Data::new(vec![1, 2, 3, 4]));
+        assert_eq!(data.slice(1..3), Data::new(vec![2, 3]));
+    }
+
+    #[test]
+    fn data_from_writer_error_does_not_leave_bytes_in_pool() {
+        let result: Result<Data, std::io::Error> = Data::from_writer(|writer| {
+            writer.write_all(&[1, 2])?;
+            Err(std::io::ErrorKind::Other.into())
+        });
+        assert!(result.is_err());
+        let data = Data::from_writer(|writer| writer.write_all(&[3])).unwrap();
+        assert_eq!(data, Data::new(vec![3]));
+    }
+
+    #[test]
+    fn small_data_from_writer_shares_pool_memory() {
+        let first = Data::from_writer(|writer| writer.write_all(&[1, 2, 3])).unwrap();
+        let second = Data::from_writer(|writer| writer.write_all(&[4, 5, 6])).unwrap();
+
+        assert_eq!(first, Data::new(vec![1, 2, 3]));
+        assert_eq!(second, Data::new(vec![4, 5, 6]));
+        assert_eq!(second.as_ref().as_ptr(), first.as_ref().as_ptr_range().end);
+    }
+
+    #[test]
+    fn large_data_from_writer_shares_pool_memory() {
+        let payload = vec![7; PAYLOAD_POOL_CAPACITY * 2];
+        let data = Data::from_writer(|writer| writer.write_all(&payload)).unwrap();
+
+        assert_eq!(data, Data::new(payload));
+        assert_eq!(
+            PAYLOAD_POOL.with(|pool| pool.borrow().as_ptr()),
+            data.as_ref().as_ptr_range().end
+        );
+    }
+
     #[test]
     fn serialize_locator_list() {
         let locator_1 = Locator::new(1, 2, [3; 16]);
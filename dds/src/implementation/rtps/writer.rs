@@ -67,7 +67,7 @@ impl RtpsWriter {
     pub fn new_change(
         &mut self,
         kind: ChangeKind,
-        data: Vec<u8>,
+        data: Data,
         inline_qos: ParameterList,
         handle: InstanceHandle,
         timestamp: Time,
@@ -79,8 +79,11 @@ impl RtpsWriter {
             handle,
             self.last_change_sequence_number,
             timestamp,
-            data.chunks(self.data_max_size_serialized)
-                .map(|c| Data::new(c.to_vec()))
+            (0..data.len())
+                .step_by(self.data_max_size_serialized)
+                .map(|start| {
+                    data.slice(start..data.len().min(start + self.data_max_size_serialized))
+                })
                 .collect(),
             inline_qos,
         )
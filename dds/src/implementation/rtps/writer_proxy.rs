@@ -3,8 +3,9 @@ use std::{
     collections::HashMap,
 };
 
-use crate::implementation::{
-    rtps_udp_psm::udp_transport::UdpTransportWrite, utils::actor::ActorAddress,
+use crate::{
+    implementation::{rtps_udp_psm::udp_transport::UdpTransportWrite, utils::actor::ActorAddress},
+    infrastructure::time::{Duration, Time},
 };
 
 use super::{
@@ -65,6 +66,7 @@ pub struct RtpsWriterProxy {
     acknack_count: Count,
     nack_frag_count: Count,
     frag_buffer: HashMap<SequenceNumber, Vec<OwningDataFragSubmessage>>,
+    frag_buffer_reception_time: HashMap<SequenceNumber, Time>,
 }
 
 impl RtpsWriterProxy {
@@ -91,10 +93,22 @@ impl RtpsWriterProxy {
             acknack_count: 0,
             nack_frag_count: 0,
             frag_buffer: HashMap::new(),
+            frag_buffer_reception_time: HashMap::new(),
         }
     }
 
-    pub fn push_data_frag(&mut self, submessage: &DataFragSubmessageRead) {
+    pub fn push_data_frag(
+        &mut self,
+        submessage: &DataFragSubmessageRead,
+        reception_timestamp: Time,
+    ) {
+        // Fragments of a change which already expired are not going to be reassembled anymore
+        if self.is_irrelevant_change(submessage.writer_sn()) {
+            return;
+        }
+        self.frag_buffer_reception_time
+            .entry(submessage.writer_sn())
+            .or_insert(reception_timestamp);
         let owning_data_frag = submessage.into();
         let frag_bug_seq_num = self.frag_buffer.entry(submessage.writer_sn()).or_default();
         if !frag_bug_seq_num.contains(&owning_data_frag) {
@@ -113,6 +127,7 @@ impl RtpsWriterProxy {
 
             if total_fragments == total_fragments_expected {
                 let mut frag_seq_num_list = self.frag_buffer.remove(&seq_num).expect("Must exist");
+                self.frag_buffer_reception_time.remove(&seq_num);
                 frag_seq_num_list.sort_by_key(|k| k.fragment_starting_num);
 
                 let mut data = Vec::new();
@@ -125,6 +140,30 @@ impl RtpsWriterProxy {
         None
     }
 
+    pub fn remove_frags_before(&mut self, seq_num: SequenceNumber) {
+        self.frag_buffer.retain(|&sn, _| sn >= seq_num);
+        self.frag_buffer_reception_time
+            .retain(|&sn, _| sn >= seq_num);
+    }
+
+    pub fn remove_expired_frags(&mut self, now: Time, timeout: Duration) -> Vec<SequenceNumber> {
+        let expired_seq_num_list: Vec<SequenceNumber> = self
+            .frag_buffer_reception_time
+            .iter()
+            .filter(|(_, &reception_time)| now - reception_time > timeout)
+            .map(|(&sn, _)| sn)
+            .collect();
+
+        // The expired changes are marked as irrelevant so that they are only reported as lost once
+        for &seq_num in &expired_seq_num_list {
+            self.frag_buffer.remove(&seq_num);
+            self.frag_buffer_reception_time.remove(&seq_num);
+            self.irrelevant_change_set(seq_num);
+        }
+
+        expired_seq_num_list
+    }
+
     pub fn remote_writer_guid(&self) -> Guid {
         self.remote_writer_guid
     }
@@ -142,12 +181,22 @@ impl RtpsWriterProxy {
         if let Some(&minimum_missing_changes) = self.missing_changes().iter().min() {
             minimum_missing_changes - 1
         } else {
-            // If there are no missing changes then the highest received sequence number
-            // with a lower limit of the first_available_seq_num
+            // If there are no missing changes then the highest received or irrelevant sequence number
+            // with a lower limit of the first_available_seq_num. Irrelevant changes have status RECEIVED
+            // so a change following a GAP is available without waiting for the next HEARTBEAT.
             let minimum_available_changes_max =
                 min(self.first_available_seq_num, self.last_available_seq_num);
             let highest_received_seq_num = self.highest_received_change_sn;
-            max(highest_received_seq_num, minimum_available_changes_max)
+            let highest_irrelevant_seq_num = self
+                .irrelevant_changes
+                .iter()
+                .max()
+                .cloned()
+                .unwrap_or_else(|| SequenceNumber::from(0));
+            max(
+                max(highest_received_seq_num, highest_irrelevant_seq_num),
+                minimum_available_changes_max,
+            )
         }
     }
 
@@ -160,6 +209,26 @@ impl RtpsWriterProxy {
         self.irrelevant_changes.push(a_seq_num);
     }
 
+    pub fn is_irrelevant_change(&self, a_seq_num: SequenceNumber) -> bool {
+        self.irrelevant_changes.contains(&a_seq_num)
+    }
+
+    pub fn highest_received_change_sn(&self) -> SequenceNumber {
+        self.highest_received_change_sn
+    }
+
+    pub fn number_of_lost_changes(&self, a_seq_num: SequenceNumber) -> i32 {
+        // Number of changes which are skipped when a best-effort reader receives the change with
+        // SequenceNumber_t ‘a_seq_num’. As for the GAP, the changes skipped before anything is received
+        // from the writer are the ones which are not relevant for this reader (e.g. volatile durability).
+        if self.highest_received_change_sn == SequenceNumber::from(0) {
+            return 0;
+        }
+        (i64::from(self.available_changes_max() + 1)..i64::from(a_seq_num))
+            .filter(|&sn| !self.is_irrelevant_change(SequenceNumber::from(sn)))
+            .count() as i32
+    }
+
     pub fn lost_changes_update(&mut self, first_available_seq_num: SequenceNumber) {
         // FOREACH change IN this.changes_from_writer
         // SUCH-THAT ( change.status == UNKNOWN OR change.status == MISSING
@@ -327,3 +396,117 @@ impl RtpsWriterProxy {
         at_least_one_heartbeat_received && self.missing_changes().is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementation::rtps::types::{ENTITYID_UNKNOWN, GUID_UNKNOWN};
+
+    #[rustfmt::skip]
+    const FIRST_OF_TWO_FRAGMENTS_SEQ_NUM_5: [u8; 40] = [
+        0x16_u8, 0b_0000_0001, 36, 0, // Submessage header
+        0, 0, 28, 0, // extraFlags, octetsToInlineQos
+        1, 2, 3, 4, // readerId: value[4]
+        6, 7, 8, 9, // writerId: value[4]
+        0, 0, 0, 0, // writerSN: high
+        5, 0, 0, 0, // writerSN: low
+        1, 0, 0, 0, // fragmentStartingNum
+        1, 0, 4, 0, // fragmentsInSubmessage | fragmentSize
+        8, 0, 0, 0, // sampleSize
+        1, 2, 3, 4, // serializedPayload
+    ];
+
+    #[test]
+    fn incomplete_fragments_expire_after_timeout() {
+        let mut writer_proxy = RtpsWriterProxy::new(GUID_UNKNOWN, &[], &[], None, ENTITYID_UNKNOWN);
+        writer_proxy.push_data_frag(
+            &DataFragSubmessageRead::new(&FIRST_OF_TWO_FRAGMENTS_SEQ_NUM_5),
+            Time::new(10, 0),
+        );
+
+        assert!(writer_proxy
+            .remove_expired_frags(Time::new(10, 500_000_000), Duration::new(1, 0))
+            .is_empty());
+        assert_eq!(
+            writer_proxy.remove_expired_frags(Time::new(12, 0), Duration::new(1, 0)),
+            vec![SequenceNumber::from(5)]
+        );
+        assert_eq!(writer_proxy.extract_frag(SequenceNumber::from(5)), None);
+        assert!(writer_proxy.is_irrelevant_change(SequenceNumber::from(5)));
+    }
+
+    #[test]
+    fn change_following_irrelevant_changes_is_available() {
+        let mut writer_proxy = RtpsWriterProxy::new(GUID_UNKNOWN, &[], &[], None, ENTITYID_UNKNOWN);
+        writer_proxy.received_change_set(SequenceNumber::from(1));
+        writer_proxy.missing_changes_update(SequenceNumber::from(1));
+        writer_proxy.irrelevant_change_set(SequenceNumber::from(2));
+        writer_proxy.irrelevant_change_set(SequenceNumber::from(3));
+
+        assert_eq!(
+            writer_proxy.available_changes_max(),
+            SequenceNumber::from(3)
+        );
+    }
+
+    #[test]
+    fn skipped_changes_are_lost() {
+        let mut writer_proxy = RtpsWriterProxy::new(GUID_UNKNOWN, &[], &[], None, ENTITYID_UNKNOWN);
+        writer_proxy.received_change_set(SequenceNumber::from(1));
+        writer_proxy.irrelevant_change_set(SequenceNumber::from(3));
+
+        assert_eq!(
+            writer_proxy.number_of_lost_changes(SequenceNumber::from(2)),
+            0
+        );
+        assert_eq!(
+            writer_proxy.number_of_lost_changes(SequenceNumber::from(5)),
+            2
+        );
+    }
+
+    #[test]
+    fn changes_skipped_before_first_received_change_are_not_lost() {
+        let mut writer_proxy = RtpsWriterProxy::new(GUID_UNKNOWN, &[], &[], None, ENTITYID_UNKNOWN);
+        writer_proxy.irrelevant_change_set(SequenceNumber::from(1));
+
+        assert_eq!(
+            writer_proxy.number_of_lost_changes(SequenceNumber::from(5)),
+            0
+        );
+    }
+
+    #[test]
+    fn fragments_of_expired_change_are_ignored() {
+        let mut writer_proxy = RtpsWriterProxy::new(GUID_UNKNOWN, &[], &[], None, ENTITYID_UNKNOWN);
+        writer_proxy.push_data_frag(
+            &DataFragSubmessageRead::new(&FIRST_OF_TWO_FRAGMENTS_SEQ_NUM_5),
+            Time::new(10, 0),
+        );
+        writer_proxy.remove_expired_frags(Time::new(12, 0), Duration::new(1, 0));
+
+        writer_proxy.push_data_frag(
+            &DataFragSubmessageRead::new(&FIRST_OF_TWO_FRAGMENTS_SEQ_NUM_5),
+            Time::new(12, 0),
+        );
+
+        assert!(writer_proxy
+            .remove_expired_frags(Time::new(14, 0), Duration::new(1, 0))
+            .is_empty());
+    }
+
+    #[test]
+    fn fragments_before_sequence_number_are_removed() {
+        let mut writer_proxy = RtpsWriterProxy::new(GUID_UNKNOWN, &[], &[], None, ENTITYID_UNKNOWN);
+        writer_proxy.push_data_frag(
+            &DataFragSubmessageRead::new(&FIRST_OF_TWO_FRAGMENTS_SEQ_NUM_5),
+            Time::new(10, 0),
+        );
+
+        writer_proxy.remove_frags_before(SequenceNumber::from(6));
+
+        assert!(writer_proxy
+            .remove_expired_frags(Time::new(12, 0), Duration::new(1, 0))
+            .is_empty());
+    }
+}
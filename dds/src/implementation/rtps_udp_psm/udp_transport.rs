@@ -6,7 +6,10 @@ use crate::implementation::{
     utils::actor::actor_interface,
 };
 use network_interface::{Addr, NetworkInterface, NetworkInterfaceConfig};
-use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, ToSocketAddrs};
+use std::{
+    io::IoSlice,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, ToSocketAddrs},
+};
 
 pub struct UdpTransportRead {
     socket: tokio::net::UdpSocket,
@@ -43,12 +46,30 @@ impl UdpTransportWrite {
     pub fn new(socket: std::net::UdpSocket) -> Self {
         Self { socket }
     }
+
+    // The segments are gathered by the socket itself so that the payloads
+    // don't have to be copied into a single buffer before sending
+    fn send_to(&self, buf: &[IoSlice], destination_locator: Locator) {
+        if let Some(address) = UdpLocator(destination_locator)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut a| a.next())
+        {
+            socket2::SockRef::from(&self.socket)
+                .send_to_vectored(buf, &address.into())
+                .ok();
+        }
+    }
 }
 
 actor_interface! {
 impl UdpTransportWrite {
     pub fn write(&self, message: RtpsMessageWrite, destination_locator_list: Vec<Locator>) {
-        let buf = message.buffer();
+        let buf: Vec<_> = message
+            .segments()
+            .iter()
+            .map(|s| IoSlice::new(s.as_ref()))
+            .collect();
 
         for destination_locator in destination_locator_list {
             if UdpLocator(destination_locator).is_multicast() {
@@ -66,15 +87,11 @@ impl UdpTransportWrite {
                     .collect();
                 for address in interface_addresses {
                     if socket2.set_multicast_if_v4(&address).is_ok() {
-                        self.socket
-                            .send_to(buf, UdpLocator(destination_locator))
-                            .ok();
+                        self.send_to(&buf, destination_locator);
                     }
                 }
             } else {
-                self.socket
-                    .send_to(buf, UdpLocator(destination_locator))
-                    .ok();
+                self.send_to(&buf, destination_locator);
             }
         }
     }
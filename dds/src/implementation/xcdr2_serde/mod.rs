@@ -0,0 +1,2 @@
+pub mod serde_xcdr2_deserializer;
+pub mod serde_xcdr2_serializer;
@@ -0,0 +1,1385 @@
+use std::marker::PhantomData;
+
+use byteorder::ByteOrder;
+use cdr::Error;
+use serde::de::{self, IntoDeserializer};
+
+use crate::topic_definition::type_support::{ExtensibilityKind, StructDescriptor};
+
+// The primitive types bigger than 4 bytes are aligned to 4 in XCDR2
+const MAX_ALIGNMENT: usize = 4;
+const MUST_UNDERSTAND_FLAG: u32 = 0x8000_0000;
+const MEMBER_ID_MASK: u32 = 0x0fff_ffff;
+
+/// Deserialize a value of type `T` from the XCDR2 encoded `data`. The `data` must not include the
+/// encapsulation header.
+pub fn deserialize_from_bytes<'de, T, E>(
+    data: &'de [u8],
+    extensibility: ExtensibilityKind,
+    struct_descriptors: Vec<&'static StructDescriptor>,
+) -> Result<T, Error>
+where
+    T: de::Deserialize<'de>,
+    E: ByteOrder,
+{
+    let mut deserializer = Xcdr2Deserializer::<E>::new(data, extensibility, struct_descriptors);
+    T::deserialize(&mut deserializer)
+}
+
+pub struct Xcdr2Deserializer<'de, E> {
+    data: &'de [u8],
+    position: usize,
+    extensibility: ExtensibilityKind,
+    struct_descriptors: Vec<&'static StructDescriptor>,
+    // Set while deserializing a present optional member of a mutable structure which, unlike
+    // the other optional values, is not preceded by a presence flag
+    optional_member: bool,
+    // Value of DdsTypeDescriptor::DELIMITED_COLLECTIONS for the type of the value being deserialized
+    // if it is described by the member descriptor of the structure it belongs to. Otherwise a
+    // collection starts with a DHEADER only if its elements are not primitive, which is only known
+    // once the first element is deserialized.
+    delimited_collections: Option<u32>,
+    endianness: PhantomData<E>,
+}
+
+impl<'de, E> Xcdr2Deserializer<'de, E>
+where
+    E: ByteOrder,
+{
+    pub fn new(
+        data: &'de [u8],
+        extensibility: ExtensibilityKind,
+        struct_descriptors: Vec<&'static StructDescriptor>,
+    ) -> Self {
+        Self {
+            data,
+            position: 0,
+            extensibility,
+            struct_descriptors,
+            optional_member: false,
+            delimited_collections: None,
+            endianness: PhantomData,
+        }
+    }
+
+    fn align(&mut self, alignment: usize) {
+        let alignment = alignment.min(MAX_ALIGNMENT);
+        self.position += (alignment - self.position % alignment) % alignment;
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<&'de [u8], Error> {
+        let data = self.data;
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= data.len())
+            .ok_or_else(|| Error::Io(std::io::ErrorKind::UnexpectedEof.into()))?;
+        let bytes = &data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        self.align(4);
+        Ok(E::read_u32(self.read_bytes(4)?))
+    }
+
+    fn peek_u32(&mut self) -> Result<u32, Error> {
+        let position = self.position;
+        let value = self.read_u32();
+        self.position = position;
+        value
+    }
+
+    // Reads a DHEADER and returns the position where the data it delimits ends
+    fn read_dheader(&mut self) -> Result<usize, Error> {
+        let size = self.read_u32()? as usize;
+        let end = self.position + size;
+        if end > self.data.len() {
+            Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into()))
+        } else {
+            Ok(end)
+        }
+    }
+
+    fn struct_descriptor(&self, name: &str) -> Option<&'static StructDescriptor> {
+        self.struct_descriptors
+            .iter()
+            .find(|descriptor| descriptor.name == name)
+            .copied()
+    }
+
+    fn extensibility_of(&self, name: &str) -> ExtensibilityKind {
+        self.struct_descriptor(name)
+            .map_or(self.extensibility, |descriptor| descriptor.extensibility)
+    }
+
+    fn read_member_header(&mut self) -> Result<MemberHeader, Error> {
+        let emheader = self.read_u32()?;
+        let size = match (emheader >> 28) & 0x7 {
+            0 => 1,
+            1 => 2,
+            2 => 4,
+            3 => 8,
+            4 => self.read_u32()? as usize,
+            // For the length codes 5 to 7 the NEXTINT is also the first part of the member
+            5 => 4 + self.peek_u32()? as usize,
+            6 => 4 + 4 * self.peek_u32()? as usize,
+            _ => 4 + 8 * self.peek_u32()? as usize,
+        };
+        Ok(MemberHeader {
+            member_id: emheader & MEMBER_ID_MASK,
+            must_understand: emheader & MUST_UNDERSTAND_FLAG == MUST_UNDERSTAND_FLAG,
+            end: self.position + size,
+        })
+    }
+}
+
+struct MemberHeader {
+    member_id: u32,
+    must_understand: bool,
+    end: usize,
+}
+
+macro_rules! deserialize_primitive {
+    ($method:ident, $visit:ident, $read:ident, $size:literal) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            self.align($size);
+            visitor.$visit(E::$read(self.read_bytes($size)?))
+        }
+    };
+}
+
+impl<'de, E> de::Deserializer<'de> for &mut Xcdr2Deserializer<'de, E>
+where
+    E: ByteOrder,
+{
+    type Error = Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(Error::DeserializeAnyNotSupported)
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.read_bytes(1)?[0] {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            v => Err(Error::InvalidBoolEncoding(v)),
+        }
+    }
+
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_i8(self.read_bytes(1)?[0] as i8)
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_u8(self.read_bytes(1)?[0])
+    }
+
+    deserialize_primitive!(deserialize_i16, visit_i16, read_i16, 2);
+    deserialize_primitive!(deserialize_i32, visit_i32, read_i32, 4);
+    deserialize_primitive!(deserialize_i64, visit_i64, read_i64, 8);
+    deserialize_primitive!(deserialize_u16, visit_u16, read_u16, 2);
+    deserialize_primitive!(deserialize_u32, visit_u32, read_u32, 4);
+    deserialize_primitive!(deserialize_u64, visit_u64, read_u64, 8);
+    deserialize_primitive!(deserialize_f32, visit_f32, read_f32, 4);
+    deserialize_primitive!(deserialize_f64, visit_f64, read_f64, 8);
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let v = self.read_bytes(1)?[0];
+        if v.is_ascii() {
+            visitor.visit_char(v as char)
+        } else {
+            Err(Error::InvalidCharEncoding)
+        }
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let length = self.read_u32()? as usize;
+        let bytes = self.read_bytes(length)?;
+        let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+        visitor.visit_borrowed_str(std::str::from_utf8(bytes).map_err(Error::InvalidUtf8Encoding)?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let length = self.read_u32()? as usize;
+        visitor.visit_borrowed_bytes(self.read_bytes(length)?)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        if std::mem::take(&mut self.optional_member) {
+            visitor.visit_some(self)
+        } else {
+            match self.read_bytes(1)?[0] {
+                0 => visitor.visit_none(),
+                1 => visitor.visit_some(self),
+                v => Err(Error::InvalidBoolEncoding(v)),
+            }
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        // The descriptor of the member refers to the wrapper and not to the wrapped value
+        self.delimited_collections = None;
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.delimited_collections.take() {
+            Some(delimited_collections) => {
+                let end = if delimited_collections & 1 == 1 {
+                    Some(self.read_dheader()?)
+                } else {
+                    None
+                };
+                let length = self.read_u32()? as usize;
+                let value = visitor.visit_seq(Elements {
+                    de: &mut *self,
+                    remaining: length,
+                    element_delimited_collections: Some(delimited_collections >> 1),
+                    pending_header: None,
+                })?;
+                if let Some(end) = end {
+                    self.position = end;
+                }
+                Ok(value)
+            }
+            // Without the description of the elements an empty sequence is serialized without
+            // DHEADER, so that its first 4 bytes are the only case in which they are 0
+            None if self.peek_u32()? == 0 => {
+                self.read_u32()?;
+                visitor.visit_seq(Elements {
+                    de: self,
+                    remaining: 0,
+                    element_delimited_collections: None,
+                    pending_header: None,
+                })
+            }
+            None => visitor.visit_seq(Elements {
+                de: self,
+                remaining: 1,
+                element_delimited_collections: None,
+                pending_header: Some(CollectionKind::Sequence),
+            }),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.delimited_collections.take() {
+            Some(delimited_collections) => {
+                let end = if delimited_collections & 1 == 1 {
+                    Some(self.read_dheader()?)
+                } else {
+                    None
+                };
+                let value = visitor.visit_seq(Elements {
+                    de: &mut *self,
+                    remaining: len,
+                    element_delimited_collections: Some(delimited_collections >> 1),
+                    pending_header: None,
+                })?;
+                if let Some(end) = end {
+                    self.position = end;
+                }
+                Ok(value)
+            }
+            None => visitor.visit_seq(Elements {
+                de: self,
+                remaining: len,
+                element_delimited_collections: None,
+                pending_header: Some(CollectionKind::Array),
+            }),
+        }
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let descriptor = self.struct_descriptor(name);
+        let extensibility = self.extensibility_of(name);
+        match extensibility {
+            ExtensibilityKind::Final => visitor.visit_seq(Members {
+                de: self,
+                descriptor,
+                index: 0,
+                remaining: len,
+                has_member_header: false,
+            }),
+            ExtensibilityKind::Appendable | ExtensibilityKind::Mutable => {
+                let end = self.read_dheader()?;
+                let value = visitor.visit_seq(Members {
+                    has_member_header: extensibility == ExtensibilityKind::Mutable,
+                    de: &mut *self,
+                    descriptor,
+                    index: 0,
+                    remaining: len,
+                })?;
+                self.position = end;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(Error::TypeNotSupported)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        let descriptor = self.struct_descriptor(name);
+        match descriptor.map_or(self.extensibility, |d| d.extensibility) {
+            ExtensibilityKind::Final => visitor.visit_seq(Members {
+                de: self,
+                descriptor,
+                index: 0,
+                remaining: fields.len(),
+                has_member_header: false,
+            }),
+            ExtensibilityKind::Appendable => {
+                let end = self.read_dheader()?;
+                let value = visitor.visit_map(DelimitedMembers {
+                    de: &mut *self,
+                    descriptor,
+                    fields,
+                    index: 0,
+                    end,
+                })?;
+                self.position = end;
+                Ok(value)
+            }
+            ExtensibilityKind::Mutable => {
+                let end = self.read_dheader()?;
+                let value = visitor.visit_map(ParameterListMembers {
+                    de: &mut *self,
+                    descriptor,
+                    fields,
+                    end,
+                    member_end: end,
+                })?;
+                self.position = end;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(Error::TypeNotSupported)
+    }
+
+    fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        Err(Error::DeserializeAnyNotSupported)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<'de, E> de::EnumAccess<'de> for &mut Xcdr2Deserializer<'de, E>
+where
+    E: ByteOrder,
+{
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant_index = self.read_u32()?;
+        // The members of the variants are not described
+        self.delimited_collections = None;
+        let value =
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(variant_index))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, E> de::VariantAccess<'de> for &mut Xcdr2Deserializer<'de, E>
+where
+    E: ByteOrder,
+{
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(Members {
+            de: self,
+            descriptor: None,
+            index: 0,
+            remaining: len,
+            has_member_header: false,
+        })
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(Members {
+            de: self,
+            descriptor: None,
+            index: 0,
+            remaining: fields.len(),
+            has_member_header: false,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CollectionKind {
+    Sequence,
+    Array,
+}
+
+struct Elements<'a, 'de, E> {
+    de: &'a mut Xcdr2Deserializer<'de, E>,
+    remaining: usize,
+    element_delimited_collections: Option<u32>,
+    // Header of the collection which can only be read once the kind of the first element is known
+    pending_header: Option<CollectionKind>,
+}
+
+impl<'a, 'de, E> de::SeqAccess<'de> for Elements<'a, 'de, E>
+where
+    E: ByteOrder,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        self.de.delimited_collections = self.element_delimited_collections;
+
+        match self.pending_header.take() {
+            Some(collection_kind) => {
+                let mut sequence_length = None;
+                let value = seed.deserialize(FirstElementDeserializer {
+                    de: &mut *self.de,
+                    collection_kind,
+                    sequence_length: &mut sequence_length,
+                })?;
+                if let Some(sequence_length) = sequence_length {
+                    self.remaining = sequence_length - 1;
+                }
+                Ok(Some(value))
+            }
+            None => seed.deserialize(&mut *self.de).map(Some),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        match self.pending_header {
+            Some(CollectionKind::Sequence) => None,
+            _ => Some(self.remaining),
+        }
+    }
+}
+
+// Deserializer of the first element of a collection which reads the collection header
+// depending on whether the element is of primitive type
+struct FirstElementDeserializer<'a, 'b, 'de, E> {
+    de: &'a mut Xcdr2Deserializer<'de, E>,
+    collection_kind: CollectionKind,
+    sequence_length: &'b mut Option<usize>,
+}
+
+impl<'a, 'b, 'de, E> FirstElementDeserializer<'a, 'b, 'de, E>
+where
+    E: ByteOrder,
+{
+    fn read_header(&mut self, is_primitive: bool) -> Result<(), Error> {
+        match (self.collection_kind, is_primitive) {
+            (CollectionKind::Sequence, true) => {
+                *self.sequence_length = Some(self.de.read_u32()? as usize);
+            }
+            (CollectionKind::Sequence, false) => {
+                self.de.read_u32()?;
+                let length = self.de.read_u32()? as usize;
+                if length == 0 {
+                    return Err(Error::Message(
+                        "Empty sequence of non primitive elements of undescribed type".to_string(),
+                    ));
+                }
+                *self.sequence_length = Some(length);
+            }
+            (CollectionKind::Array, true) => (),
+            (CollectionKind::Array, false) => {
+                self.de.read_u32()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+macro_rules! forward_first_element {
+    ($is_primitive:literal => $($method:ident),*) => {
+        $(
+            fn $method<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: de::Visitor<'de>,
+            {
+                self.read_header($is_primitive)?;
+                self.de.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'a, 'b, 'de, E> de::Deserializer<'de> for FirstElementDeserializer<'a, 'b, 'de, E>
+where
+    E: ByteOrder,
+{
+    type Error = Error;
+
+    forward_first_element!(true =>
+        deserialize_any, deserialize_bool, deserialize_i8, deserialize_i16, deserialize_i32,
+        deserialize_i64, deserialize_u8, deserialize_u16, deserialize_u32, deserialize_u64,
+        deserialize_f32, deserialize_f64, deserialize_char, deserialize_unit,
+        deserialize_identifier, deserialize_ignored_any);
+
+    forward_first_element!(false =>
+        deserialize_str, deserialize_string, deserialize_bytes, deserialize_byte_buf,
+        deserialize_option, deserialize_seq, deserialize_map);
+
+    fn deserialize_unit_struct<V>(
+        mut self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.read_header(true)?;
+        self.de.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V>(mut self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.read_header(false)?;
+        self.de.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        mut self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.read_header(false)?;
+        self.de.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        mut self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.read_header(false)?;
+        self.de.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        mut self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.read_header(true)?;
+        self.de.deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+// Members of the aggregated types which are deserialized in order
+struct Members<'a, 'de, E> {
+    de: &'a mut Xcdr2Deserializer<'de, E>,
+    descriptor: Option<&'static StructDescriptor>,
+    index: usize,
+    remaining: usize,
+    has_member_header: bool,
+}
+
+impl<'a, 'de, E> de::SeqAccess<'de> for Members<'a, 'de, E>
+where
+    E: ByteOrder,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        self.de.delimited_collections = self
+            .descriptor
+            .and_then(|d| d.members.get(self.index))
+            .and_then(|m| m.delimited_collections);
+        self.index += 1;
+
+        if self.has_member_header {
+            let member_header = self.de.read_member_header()?;
+            let value = seed.deserialize(&mut *self.de)?;
+            self.de.position = member_header.end;
+            Ok(Some(value))
+        } else {
+            seed.deserialize(&mut *self.de).map(Some)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+// Members of an appendable structure. The members which are not known by the type
+// are at the end and are skipped.
+struct DelimitedMembers<'a, 'de, E> {
+    de: &'a mut Xcdr2Deserializer<'de, E>,
+    descriptor: Option<&'static StructDescriptor>,
+    fields: &'static [&'static str],
+    index: usize,
+    end: usize,
+}
+
+impl<'a, 'de, E> de::MapAccess<'de> for DelimitedMembers<'a, 'de, E>
+where
+    E: ByteOrder,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.fields.get(self.index) {
+            Some(field) if self.de.position < self.end => seed
+                .deserialize(IntoDeserializer::<Error>::into_deserializer(*field))
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let field = self.fields.get(self.index).copied();
+        self.de.delimited_collections = self
+            .descriptor
+            .and_then(|d| d.members.iter().find(|m| Some(m.name) == field))
+            .and_then(|m| m.delimited_collections);
+        self.index += 1;
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+// Members of a mutable structure. The members are identified by their member header so they
+// can be in any order and the members which are not known by the type are skipped.
+struct ParameterListMembers<'a, 'de, E> {
+    de: &'a mut Xcdr2Deserializer<'de, E>,
+    descriptor: Option<&'static StructDescriptor>,
+    fields: &'static [&'static str],
+    end: usize,
+    member_end: usize,
+}
+
+impl<'a, 'de, E> de::MapAccess<'de> for ParameterListMembers<'a, 'de, E>
+where
+    E: ByteOrder,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        loop {
+            self.de.align(4);
+            if self.de.position >= self.end {
+                return Ok(None);
+            }
+            let member_header = self.de.read_member_header()?;
+            let member = match self.descriptor {
+                Some(descriptor) => descriptor
+                    .members
+                    .iter()
+                    .find(|m| m.id == member_header.member_id)
+                    .map(|m| (m.name, m.optional, m.delimited_collections)),
+                None => self
+                    .fields
+                    .get(member_header.member_id as usize)
+                    .map(|field| (*field, false, None)),
+            };
+            match member {
+                Some((field, optional, delimited_collections)) => {
+                    self.member_end = member_header.end;
+                    self.de.optional_member = optional;
+                    self.de.delimited_collections = delimited_collections;
+                    return seed
+                        .deserialize(IntoDeserializer::<Error>::into_deserializer(field))
+                        .map(Some);
+                }
+                None if member_header.must_understand => {
+                    return Err(Error::Message(format!(
+                        "Unknown member with id {} must be understood",
+                        member_header.member_id
+                    )))
+                }
+                None => self.de.position = member_header.end,
+            }
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let value = seed.deserialize(&mut *self.de)?;
+        self.de.optional_member = false;
+        self.de.position = self.member_end;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        implementation::xcdr2_serde::serde_xcdr2_serializer::Xcdr2Serializer,
+        topic_definition::type_support::MemberDescriptor,
+    };
+    use byteorder::{BigEndian, LittleEndian};
+
+    fn serialize<T: serde::Serialize, E: ByteOrder>(
+        value: &T,
+        extensibility: ExtensibilityKind,
+    ) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        serde::Serialize::serialize(
+            value,
+            &mut Xcdr2Serializer::<E>::new(&mut buffer, extensibility, Vec::new()),
+        )
+        .unwrap();
+        buffer
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Primitives {
+        a: u8,
+        b: i64,
+        c: u16,
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Collections {
+        id: u8,
+        names: Vec<String>,
+        values: Vec<Primitives>,
+        bytes: Vec<u8>,
+        array: [Primitives; 2],
+        name: String,
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct PrimitivesV1 {
+        a: u8,
+        b: i64,
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    enum Kind {
+        First,
+        Second,
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Enumerated {
+        kind: Kind,
+        kinds: Vec<Kind>,
+    }
+
+    // The collections of non primitive elements are delimited by a DHEADER
+    static COLLECTIONS: StructDescriptor = StructDescriptor {
+        name: "Collections",
+        extensibility: ExtensibilityKind::Final,
+        members: &[
+            MemberDescriptor {
+                name: "id",
+                id: 0,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "names",
+                id: 1,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(1),
+            },
+            MemberDescriptor {
+                name: "values",
+                id: 2,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(1),
+            },
+            MemberDescriptor {
+                name: "bytes",
+                id: 3,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "array",
+                id: 4,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(1),
+            },
+            MemberDescriptor {
+                name: "name",
+                id: 5,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+        ],
+    };
+
+    fn collections() -> Collections {
+        Collections {
+            id: 1,
+            names: vec!["one".to_string(), "two".to_string()],
+            values: vec![Primitives { a: 1, b: -2, c: 3 }],
+            bytes: vec![1, 2, 3],
+            array: [
+                Primitives { a: 4, b: 5, c: 6 },
+                Primitives { a: 7, b: 8, c: 9 },
+            ],
+            name: "collections".to_string(),
+        }
+    }
+
+    #[test]
+    fn deserialize_final_struct() {
+        let data = [
+            1, 0, 0, 0, // a | padding (3 bytes)
+            0, 0, 0, 0, // b
+            0, 0, 0, 2, // b
+            0, 3, // c
+        ];
+        assert_eq!(
+            deserialize_from_bytes::<Primitives, BigEndian>(
+                &data,
+                ExtensibilityKind::Final,
+                Vec::new()
+            )
+            .unwrap(),
+            Primitives { a: 1, b: 2, c: 3 }
+        );
+    }
+
+    #[test]
+    fn deserialize_collections_with_and_without_dheader() {
+        for extensibility in [
+            ExtensibilityKind::Final,
+            ExtensibilityKind::Appendable,
+            ExtensibilityKind::Mutable,
+        ] {
+            let data = serialize::<_, LittleEndian>(&collections(), extensibility);
+            assert_eq!(
+                deserialize_from_bytes::<Collections, LittleEndian>(
+                    &data,
+                    extensibility,
+                    Vec::new()
+                )
+                .unwrap(),
+                collections()
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_empty_collections() {
+        let value = Collections {
+            id: 1,
+            names: vec![],
+            values: vec![],
+            bytes: vec![],
+            array: [
+                Primitives { a: 4, b: 5, c: 6 },
+                Primitives { a: 7, b: 8, c: 9 },
+            ],
+            name: "".to_string(),
+        };
+        let data = [
+            1, 0, 0, 0, // id | padding (3 bytes)
+            0, 0, 0, 4, // names DHEADER
+            0, 0, 0, 0, // names length
+            0, 0, 0, 4, // values DHEADER
+            0, 0, 0, 0, // values length
+            0, 0, 0, 0, // bytes length
+            0, 0, 0, 26, // array DHEADER
+            4, 0, 0, 0, // a | padding (3 bytes)
+            0, 0, 0, 0, // b
+            0, 0, 0, 5, // b
+            0, 6, 7, 0, // c | a | padding (1 byte)
+            0, 0, 0, 0, // b
+            0, 0, 0, 8, // b
+            0, 9, 0, 0, // c | padding (2 bytes)
+            0, 0, 0, 1, // name length
+            0, // name
+        ];
+        assert_eq!(
+            deserialize_from_bytes::<Collections, BigEndian>(
+                &data,
+                ExtensibilityKind::Final,
+                vec![&COLLECTIONS]
+            )
+            .unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn deserialize_serialized_empty_collections() {
+        let value = Collections {
+            names: vec![],
+            values: vec![],
+            ..collections()
+        };
+        let mut data = Vec::new();
+        serde::Serialize::serialize(
+            &value,
+            &mut Xcdr2Serializer::<LittleEndian>::new(
+                &mut data,
+                ExtensibilityKind::Final,
+                vec![&COLLECTIONS],
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            deserialize_from_bytes::<Collections, LittleEndian>(
+                &data,
+                ExtensibilityKind::Final,
+                vec![&COLLECTIONS]
+            )
+            .unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn deserialize_undescribed_empty_sequence_of_non_primitive_elements_fails() {
+        let data = [
+            1, 0, 0, 0, // id | padding (3 bytes)
+            0, 0, 0, 4, // names DHEADER
+            0, 0, 0, 0, // names length
+        ];
+        assert!(deserialize_from_bytes::<(u8, Vec<String>), BigEndian>(
+            &data,
+            ExtensibilityKind::Final,
+            Vec::new()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn deserialize_enumerated() {
+        let value = Enumerated {
+            kind: Kind::Second,
+            kinds: vec![Kind::First, Kind::Second],
+        };
+        let data = serialize::<_, BigEndian>(&value, ExtensibilityKind::Mutable);
+        assert_eq!(
+            deserialize_from_bytes::<Enumerated, BigEndian>(
+                &data,
+                ExtensibilityKind::Mutable,
+                Vec::new()
+            )
+            .unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn deserialize_appendable_struct_with_additional_members() {
+        let data = serialize::<_, LittleEndian>(
+            &Primitives { a: 1, b: 2, c: 3 },
+            ExtensibilityKind::Appendable,
+        );
+        assert_eq!(
+            deserialize_from_bytes::<PrimitivesV1, LittleEndian>(
+                &data,
+                ExtensibilityKind::Appendable,
+                Vec::new()
+            )
+            .unwrap(),
+            PrimitivesV1 { a: 1, b: 2 }
+        );
+    }
+
+    #[test]
+    fn deserialize_mutable_struct_with_members_in_any_order() {
+        let data = [
+            17, 0, 0, 0, // DHEADER
+            1, 0, 0, 0x30, // EMHEADER (LC 3, id 1)
+            2, 0, 0, 0, // b
+            0, 0, 0, 0, // b
+            0, 0, 0, 0x00, // EMHEADER (LC 0, id 0)
+            1,    // a
+        ];
+        assert_eq!(
+            deserialize_from_bytes::<PrimitivesV1, LittleEndian>(
+                &data,
+                ExtensibilityKind::Mutable,
+                Vec::new()
+            )
+            .unwrap(),
+            PrimitivesV1 { a: 1, b: 2 }
+        );
+    }
+
+    #[test]
+    fn deserialize_mutable_struct_skips_unknown_members() {
+        let data = serialize::<_, LittleEndian>(
+            &Primitives { a: 1, b: 2, c: 3 },
+            ExtensibilityKind::Mutable,
+        );
+        assert_eq!(
+            deserialize_from_bytes::<PrimitivesV1, LittleEndian>(
+                &data,
+                ExtensibilityKind::Mutable,
+                Vec::new()
+            )
+            .unwrap(),
+            PrimitivesV1 { a: 1, b: 2 }
+        );
+
+        let data = [
+            8, 0, 0, 0x00, // DHEADER
+            5, 0, 0, 0x80, // EMHEADER (M_FLAG, LC 0, id 5)
+            1, 0, 0, 0, // unknown member
+        ];
+        assert!(deserialize_from_bytes::<PrimitivesV1, LittleEndian>(
+            &data,
+            ExtensibilityKind::Mutable,
+            Vec::new()
+        )
+        .is_err());
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Described {
+        id: u8,
+        name: Option<String>,
+        value: Option<u16>,
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct DescribedV1 {
+        value: Option<u16>,
+        id: u8,
+    }
+
+    static DESCRIBED: StructDescriptor = StructDescriptor {
+        name: "Described",
+        extensibility: ExtensibilityKind::Mutable,
+        members: &[
+            MemberDescriptor {
+                name: "id",
+                id: 10,
+                optional: false,
+                must_understand: true,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "name",
+                id: 20,
+                optional: true,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "value",
+                id: 30,
+                optional: true,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+        ],
+    };
+
+    static DESCRIBED_V1: StructDescriptor = StructDescriptor {
+        name: "DescribedV1",
+        extensibility: ExtensibilityKind::Mutable,
+        members: &[
+            MemberDescriptor {
+                name: "value",
+                id: 30,
+                optional: true,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "id",
+                id: 10,
+                optional: false,
+                must_understand: true,
+                key: false,
+                delimited_collections: Some(0),
+            },
+        ],
+    };
+
+    fn serialize_described(value: &Described) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        serde::Serialize::serialize(
+            value,
+            &mut Xcdr2Serializer::<LittleEndian>::new(
+                &mut buffer,
+                ExtensibilityKind::Final,
+                vec![&DESCRIBED],
+            ),
+        )
+        .unwrap();
+        buffer
+    }
+
+    #[test]
+    fn deserialize_described_struct_with_optional_members() {
+        for value in [
+            Described {
+                id: 7,
+                name: None,
+                value: Some(3),
+            },
+            Described {
+                id: 7,
+                name: Some("a".to_string()),
+                value: None,
+            },
+        ] {
+            let data = serialize_described(&value);
+            assert_eq!(
+                deserialize_from_bytes::<Described, LittleEndian>(
+                    &data,
+                    ExtensibilityKind::Final,
+                    vec![&DESCRIBED]
+                )
+                .unwrap(),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_described_struct_matches_members_by_id() {
+        let data = serialize_described(&Described {
+            id: 7,
+            name: Some("a".to_string()),
+            value: Some(3),
+        });
+        assert_eq!(
+            deserialize_from_bytes::<DescribedV1, LittleEndian>(
+                &data,
+                ExtensibilityKind::Final,
+                vec![&DESCRIBED_V1]
+            )
+            .unwrap(),
+            DescribedV1 {
+                value: Some(3),
+                id: 7
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_optional_values_with_presence_flag() {
+        let value = Described {
+            id: 7,
+            name: Some("a".to_string()),
+            value: None,
+        };
+        let data = serialize::<_, BigEndian>(&value, ExtensibilityKind::Appendable);
+        assert_eq!(
+            deserialize_from_bytes::<Described, BigEndian>(
+                &data,
+                ExtensibilityKind::Appendable,
+                Vec::new()
+            )
+            .unwrap(),
+            value
+        );
+    }
+}
@@ -0,0 +1,988 @@
+use std::{convert::TryFrom, marker::PhantomData};
+
+use byteorder::{ByteOrder, WriteBytesExt};
+use cdr::Error;
+use serde::ser::{self, Impossible};
+
+use crate::topic_definition::type_support::{
+    ExtensibilityKind, MemberDescriptor, StructDescriptor,
+};
+
+// The primitive types bigger than 4 bytes are aligned to 4 in XCDR2
+const MAX_ALIGNMENT: usize = 4;
+const LENGTH_CODE_NEXTINT: u32 = 4;
+const MUST_UNDERSTAND_FLAG: u32 = 0x8000_0000;
+
+pub struct Xcdr2Serializer<'a, E> {
+    buffer: &'a mut Vec<u8>,
+    extensibility: ExtensibilityKind,
+    // Descriptors of the structures annotated with an extensibility kind and member attributes
+    struct_descriptors: Vec<&'static StructDescriptor>,
+    // Counter of the serialized values which are not of primitive type. The elements of a
+    // collection are preceded by a DHEADER only if they are not primitive. When the type of the
+    // elements is not described this is only known once the first element is serialized.
+    non_primitive_count: usize,
+    // Value of DdsTypeDescriptor::DELIMITED_COLLECTIONS for the type of the value being serialized
+    // if it is described by the member descriptor of the structure it belongs to
+    delimited_collections: Option<u32>,
+    // An optional member of a mutable structure is omitted when absent instead of being preceded
+    // by a presence flag. These are set while serializing such a member.
+    optional_member: bool,
+    absent_member: bool,
+    endianness: PhantomData<E>,
+}
+
+impl<'a, E> Xcdr2Serializer<'a, E>
+where
+    E: ByteOrder,
+{
+    pub fn new(
+        buffer: &'a mut Vec<u8>,
+        extensibility: ExtensibilityKind,
+        struct_descriptors: Vec<&'static StructDescriptor>,
+    ) -> Self {
+        Self {
+            buffer,
+            extensibility,
+            struct_descriptors,
+            non_primitive_count: 0,
+            delimited_collections: None,
+            optional_member: false,
+            absent_member: false,
+            endianness: PhantomData,
+        }
+    }
+
+    fn align(&mut self, alignment: usize) {
+        let alignment = alignment.min(MAX_ALIGNMENT);
+        let padding = (alignment - self.buffer.len() % alignment) % alignment;
+        self.buffer.resize(self.buffer.len() + padding, 0);
+    }
+
+    fn write_u32(&mut self, v: u32) -> Result<(), Error> {
+        self.align(4);
+        self.buffer.write_u32::<E>(v).map_err(Into::into)
+    }
+
+    fn write_usize_as_u32(&mut self, v: usize) -> Result<(), Error> {
+        let v = u32::try_from(v).map_err(|_| Error::NumberOutOfRange)?;
+        self.write_u32(v)
+    }
+
+    // Reserves the space of a DHEADER which is written once the size of the data following it is known
+    fn reserve_dheader(&mut self) -> usize {
+        self.align(4);
+        let dheader_position = self.buffer.len();
+        self.buffer.extend_from_slice(&[0; 4]);
+        dheader_position
+    }
+
+    fn write_dheader(&mut self, dheader_position: usize) -> Result<(), Error> {
+        let size = self.buffer.len() - dheader_position - 4;
+        let size = u32::try_from(size).map_err(|_| Error::NumberOutOfRange)?;
+        E::write_u32(
+            &mut self.buffer[dheader_position..dheader_position + 4],
+            size,
+        );
+        Ok(())
+    }
+
+    fn serialize_member<T>(
+        &mut self,
+        member_id: u32,
+        must_understand: bool,
+        optional: bool,
+        value: &T,
+    ) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        // The EMHEADER is followed by a NEXTINT with the size of the member unless the member
+        // is a primitive whose size is given by the length code
+        let start = self.buffer.len();
+        self.align(4);
+        let emheader_position = self.buffer.len();
+        self.buffer.extend_from_slice(&[0; 8]);
+        let non_primitive_count = self.non_primitive_count;
+        self.optional_member = optional;
+        value.serialize(&mut *self)?;
+        self.optional_member = false;
+        if self.absent_member {
+            self.absent_member = false;
+            self.buffer.truncate(start);
+            return Ok(());
+        }
+        let member_id = if must_understand {
+            MUST_UNDERSTAND_FLAG | member_id
+        } else {
+            member_id
+        };
+        let size = self.buffer.len() - emheader_position - 8;
+
+        let primitive_length_code = match size {
+            1 => Some(0),
+            2 => Some(1),
+            4 => Some(2),
+            8 => Some(3),
+            _ => None,
+        }
+        .filter(|_| self.non_primitive_count == non_primitive_count);
+
+        match primitive_length_code {
+            Some(length_code) => {
+                self.buffer
+                    .drain(emheader_position + 4..emheader_position + 8);
+                E::write_u32(
+                    &mut self.buffer[emheader_position..emheader_position + 4],
+                    length_code << 28 | member_id,
+                );
+            }
+            None => {
+                let size = u32::try_from(size).map_err(|_| Error::NumberOutOfRange)?;
+                E::write_u32(
+                    &mut self.buffer[emheader_position..emheader_position + 4],
+                    LENGTH_CODE_NEXTINT << 28 | member_id,
+                );
+                E::write_u32(
+                    &mut self.buffer[emheader_position + 4..emheader_position + 8],
+                    size,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize_collection<'b>(
+        &'b mut self,
+        length: Option<u32>,
+    ) -> Result<Collection<'a, 'b, E>, Error> {
+        self.non_primitive_count += 1;
+        let start = self.buffer.len();
+        match self.delimited_collections.take() {
+            Some(delimited_collections) => {
+                let dheader_position = if delimited_collections & 1 == 1 {
+                    Some(self.reserve_dheader())
+                } else {
+                    None
+                };
+                if let Some(length) = length {
+                    self.write_u32(length)?;
+                }
+                Ok(Collection {
+                    ser: self,
+                    start,
+                    length,
+                    dheader_position,
+                    element_delimited_collections: Some(delimited_collections >> 1),
+                    is_first_element: false,
+                })
+            }
+            None => {
+                if let Some(length) = length {
+                    self.write_u32(length)?;
+                }
+                Ok(Collection {
+                    ser: self,
+                    start,
+                    length,
+                    dheader_position: None,
+                    element_delimited_collections: None,
+                    is_first_element: true,
+                })
+            }
+        }
+    }
+
+    fn serialize_aggregated<'b>(&'b mut self, name: &'static str) -> Aggregated<'a, 'b, E> {
+        self.non_primitive_count += 1;
+        let descriptor = self
+            .struct_descriptors
+            .iter()
+            .find(|descriptor| descriptor.name == name)
+            .copied();
+        let extensibility = descriptor.map_or(self.extensibility, |d| d.extensibility);
+        let dheader_position = match extensibility {
+            ExtensibilityKind::Final => None,
+            ExtensibilityKind::Appendable | ExtensibilityKind::Mutable => {
+                Some(self.reserve_dheader())
+            }
+        };
+        Aggregated {
+            ser: self,
+            dheader_position,
+            descriptor,
+            is_mutable: extensibility == ExtensibilityKind::Mutable,
+            member_index: 0,
+        }
+    }
+}
+
+impl<'a, 'b, E> ser::Serializer for &'b mut Xcdr2Serializer<'a, E>
+where
+    E: ByteOrder,
+{
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Collection<'a, 'b, E>;
+    type SerializeTuple = Collection<'a, 'b, E>;
+    type SerializeTupleStruct = Aggregated<'a, 'b, E>;
+    type SerializeTupleVariant = Aggregated<'a, 'b, E>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Aggregated<'a, 'b, E>;
+    type SerializeStructVariant = Aggregated<'a, 'b, E>;
+
+    fn serialize_bool(self, v: bool) -> std::result::Result<Self::Ok, Self::Error> {
+        self.serialize_u8(v as u8)
+    }
+
+    fn serialize_i8(self, v: i8) -> std::result::Result<Self::Ok, Self::Error> {
+        self.buffer.write_i8(v).map_err(Into::into)
+    }
+
+    fn serialize_i16(self, v: i16) -> std::result::Result<Self::Ok, Self::Error> {
+        self.align(2);
+        self.buffer.write_i16::<E>(v).map_err(Into::into)
+    }
+
+    fn serialize_i32(self, v: i32) -> std::result::Result<Self::Ok, Self::Error> {
+        self.align(4);
+        self.buffer.write_i32::<E>(v).map_err(Into::into)
+    }
+
+    fn serialize_i64(self, v: i64) -> std::result::Result<Self::Ok, Self::Error> {
+        self.align(8);
+        self.buffer.write_i64::<E>(v).map_err(Into::into)
+    }
+
+    fn serialize_u8(self, v: u8) -> std::result::Result<Self::Ok, Self::Error> {
+        self.buffer.write_u8(v).map_err(Into::into)
+    }
+
+    fn serialize_u16(self, v: u16) -> std::result::Result<Self::Ok, Self::Error> {
+        self.align(2);
+        self.buffer.write_u16::<E>(v).map_err(Into::into)
+    }
+
+    fn serialize_u32(self, v: u32) -> std::result::Result<Self::Ok, Self::Error> {
+        self.write_u32(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> std::result::Result<Self::Ok, Self::Error> {
+        self.align(8);
+        self.buffer.write_u64::<E>(v).map_err(Into::into)
+    }
+
+    fn serialize_f32(self, v: f32) -> std::result::Result<Self::Ok, Self::Error> {
+        self.align(4);
+        self.buffer.write_f32::<E>(v).map_err(Into::into)
+    }
+
+    fn serialize_f64(self, v: f64) -> std::result::Result<Self::Ok, Self::Error> {
+        self.align(8);
+        self.buffer.write_f64::<E>(v).map_err(Into::into)
+    }
+
+    fn serialize_char(self, v: char) -> std::result::Result<Self::Ok, Self::Error> {
+        if v.len_utf8() != 1 {
+            Err(Error::InvalidChar(v))
+        } else {
+            self.serialize_u8(v as u8)
+        }
+    }
+
+    fn serialize_str(self, v: &str) -> std::result::Result<Self::Ok, Self::Error> {
+        self.non_primitive_count += 1;
+        self.write_usize_as_u32(v.len() + 1)?;
+        self.buffer.extend_from_slice(v.as_bytes());
+        self.buffer.push(0);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> std::result::Result<Self::Ok, Self::Error> {
+        self.non_primitive_count += 1;
+        self.write_usize_as_u32(v.len())?;
+        self.buffer.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> std::result::Result<Self::Ok, Self::Error> {
+        if std::mem::take(&mut self.optional_member) {
+            self.absent_member = true;
+            Ok(())
+        } else {
+            // Optional values which are not members of a mutable structure are preceded by a
+            // presence flag
+            self.non_primitive_count += 1;
+            self.serialize_u8(0)
+        }
+    }
+
+    fn serialize_some<T>(self, value: &T) -> std::result::Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        if !std::mem::take(&mut self.optional_member) {
+            self.non_primitive_count += 1;
+            self.buffer.write_u8(1)?;
+        }
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> std::result::Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(
+        self,
+        _name: &'static str,
+    ) -> std::result::Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> std::result::Result<Self::Ok, Self::Error> {
+        self.write_u32(variant_index)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> std::result::Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        // The descriptor of the member refers to the wrapper and not to the wrapped value
+        self.delimited_collections = None;
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> std::result::Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.write_u32(variant_index)?;
+        // The members of the variants are not described
+        self.delimited_collections = None;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(
+        self,
+        len: Option<usize>,
+    ) -> std::result::Result<Self::SerializeSeq, Self::Error> {
+        let len = len.ok_or(Error::SequenceMustHaveLength)?;
+        let length = u32::try_from(len).map_err(|_| Error::NumberOutOfRange)?;
+        self.serialize_collection(Some(length))
+    }
+
+    fn serialize_tuple(
+        self,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_collection(None)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(self.serialize_aggregated(name))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeTupleVariant, Self::Error> {
+        self.write_u32(variant_index)?;
+        self.delimited_collections = None;
+        Ok(Aggregated {
+            ser: self,
+            dheader_position: None,
+            descriptor: None,
+            is_mutable: false,
+            member_index: 0,
+        })
+    }
+
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> std::result::Result<Self::SerializeMap, Self::Error> {
+        Err(Error::TypeNotSupported)
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeStruct, Self::Error> {
+        Ok(self.serialize_aggregated(name))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeStructVariant, Self::Error> {
+        self.write_u32(variant_index)?;
+        self.delimited_collections = None;
+        Ok(Aggregated {
+            ser: self,
+            dheader_position: None,
+            descriptor: None,
+            is_mutable: false,
+            member_index: 0,
+        })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+pub struct Collection<'a, 'b, E> {
+    ser: &'b mut Xcdr2Serializer<'a, E>,
+    start: usize,
+    length: Option<u32>,
+    dheader_position: Option<usize>,
+    element_delimited_collections: Option<u32>,
+    // Whether the DHEADER is still to be determined from the first element
+    is_first_element: bool,
+}
+
+impl<'a, 'b, E> Collection<'a, 'b, E>
+where
+    E: ByteOrder,
+{
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.ser.delimited_collections = self.element_delimited_collections;
+        if self.is_first_element {
+            self.is_first_element = false;
+            let non_primitive_count = self.ser.non_primitive_count;
+            value.serialize(&mut *self.ser)?;
+            if self.ser.non_primitive_count != non_primitive_count {
+                // The collection elements are not primitive so it has to start with a DHEADER.
+                // The first element is serialized again since its alignment might change.
+                self.ser.buffer.truncate(self.start);
+                self.dheader_position = Some(self.ser.reserve_dheader());
+                if let Some(length) = self.length {
+                    self.ser.write_u32(length)?;
+                }
+                self.ser.delimited_collections = self.element_delimited_collections;
+                value.serialize(&mut *self.ser)?;
+            }
+            Ok(())
+        } else {
+            value.serialize(&mut *self.ser)
+        }
+    }
+
+    fn end(self) -> Result<(), Error> {
+        match self.dheader_position {
+            Some(dheader_position) => self.ser.write_dheader(dheader_position),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a, 'b, E> ser::SerializeSeq for Collection<'a, 'b, E>
+where
+    E: ByteOrder,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> std::result::Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        Collection::serialize_element(self, value)
+    }
+
+    fn end(self) -> std::result::Result<Self::Ok, Self::Error> {
+        Collection::end(self)
+    }
+}
+
+impl<'a, 'b, E> ser::SerializeTuple for Collection<'a, 'b, E>
+where
+    E: ByteOrder,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> std::result::Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        Collection::serialize_element(self, value)
+    }
+
+    fn end(self) -> std::result::Result<Self::Ok, Self::Error> {
+        Collection::end(self)
+    }
+}
+
+pub struct Aggregated<'a, 'b, E> {
+    ser: &'b mut Xcdr2Serializer<'a, E>,
+    dheader_position: Option<usize>,
+    descriptor: Option<&'static StructDescriptor>,
+    // Members are only identified in the mutable types. Without a descriptor they take sequential ids.
+    is_mutable: bool,
+    member_index: u32,
+}
+
+impl<'a, 'b, E> Aggregated<'a, 'b, E>
+where
+    E: ByteOrder,
+{
+    fn member_descriptor(
+        &self,
+        key: Option<&'static str>,
+    ) -> Result<Option<&'static MemberDescriptor>, Error> {
+        match self.descriptor {
+            Some(descriptor) => {
+                let member = match key {
+                    Some(key) => descriptor.members.iter().find(|m| m.name == key),
+                    None => descriptor.members.get(self.member_index as usize),
+                };
+                member.map(Some).ok_or_else(|| {
+                    Error::Message(format!(
+                        "Member {} not found in the descriptor of {}",
+                        key.map_or_else(|| self.member_index.to_string(), String::from),
+                        descriptor.name
+                    ))
+                })
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn serialize_member<T>(&mut self, key: Option<&'static str>, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let member_index = self.member_index;
+        if self.is_mutable {
+            let member_descriptor = self.member_descriptor(key)?;
+            self.member_index += 1;
+            self.ser.delimited_collections =
+                member_descriptor.and_then(|m| m.delimited_collections);
+            match member_descriptor {
+                Some(member) => self.ser.serialize_member(
+                    member.id,
+                    member.must_understand,
+                    member.optional,
+                    value,
+                ),
+                None => self.ser.serialize_member(member_index, false, false, value),
+            }
+        } else {
+            // The members of final and appendable structures are only identified by their order
+            // so the descriptor is only used for the collections they contain
+            self.ser.delimited_collections = self
+                .member_descriptor(key)
+                .ok()
+                .flatten()
+                .and_then(|m| m.delimited_collections);
+            self.member_index += 1;
+            value.serialize(&mut *self.ser)
+        }
+    }
+
+    fn skip_member(&mut self) {
+        self.member_index += 1;
+    }
+
+    fn end(self) -> Result<(), Error> {
+        match self.dheader_position {
+            Some(dheader_position) => self.ser.write_dheader(dheader_position),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a, 'b, E> ser::SerializeTupleStruct for Aggregated<'a, 'b, E>
+where
+    E: ByteOrder,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> std::result::Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.serialize_member(None, value)
+    }
+
+    fn end(self) -> std::result::Result<Self::Ok, Self::Error> {
+        Aggregated::end(self)
+    }
+}
+
+impl<'a, 'b, E> ser::SerializeTupleVariant for Aggregated<'a, 'b, E>
+where
+    E: ByteOrder,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> std::result::Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.serialize_member(None, value)
+    }
+
+    fn end(self) -> std::result::Result<Self::Ok, Self::Error> {
+        Aggregated::end(self)
+    }
+}
+
+impl<'a, 'b, E> ser::SerializeStruct for Aggregated<'a, 'b, E>
+where
+    E: ByteOrder,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> std::result::Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.serialize_member(Some(key), value)
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> std::result::Result<(), Self::Error> {
+        self.skip_member();
+        Ok(())
+    }
+
+    fn end(self) -> std::result::Result<Self::Ok, Self::Error> {
+        Aggregated::end(self)
+    }
+}
+
+impl<'a, 'b, E> ser::SerializeStructVariant for Aggregated<'a, 'b, E>
+where
+    E: ByteOrder,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> std::result::Result<(), Self::Error>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        self.serialize_member(Some(key), value)
+    }
+
+    fn end(self) -> std::result::Result<Self::Ok, Self::Error> {
+        Aggregated::end(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use byteorder::{BigEndian, LittleEndian};
+
+    fn serialize<T: ser::Serialize, E: ByteOrder>(
+        value: &T,
+        extensibility: ExtensibilityKind,
+    ) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        value
+            .serialize(&mut Xcdr2Serializer::<E>::new(
+                &mut buffer,
+                extensibility,
+                Vec::new(),
+            ))
+            .unwrap();
+        buffer
+    }
+
+    #[derive(serde::Serialize)]
+    struct Primitives {
+        a: u8,
+        b: i64,
+        c: u16,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Nested {
+        id: u8,
+        inner: Primitives,
+    }
+
+    #[test]
+    fn serialize_primitives_have_maximum_alignment_of_4() {
+        let value = Primitives { a: 1, b: 2, c: 3 };
+        assert_eq!(
+            serialize::<_, LittleEndian>(&value, ExtensibilityKind::Final),
+            vec![
+                1, 0, 0, 0, // a | padding (3 bytes)
+                2, 0, 0, 0, // b
+                0, 0, 0, 0, // b
+                3, 0, // c
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_appendable_struct() {
+        let value = Nested {
+            id: 7,
+            inner: Primitives { a: 1, b: 2, c: 3 },
+        };
+        assert_eq!(
+            serialize::<_, BigEndian>(&value, ExtensibilityKind::Appendable),
+            vec![
+                0, 0, 0, 22, // DHEADER
+                7, 0, 0, 0, // id | padding (3 bytes)
+                0, 0, 0, 14, // inner DHEADER
+                1, 0, 0, 0, // a | padding (3 bytes)
+                0, 0, 0, 0, // b
+                0, 0, 0, 2, // b
+                0, 3, // c
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_mutable_struct() {
+        let value = Nested {
+            id: 7,
+            inner: Primitives { a: 1, b: 2, c: 3 },
+        };
+        assert_eq!(
+            serialize::<_, LittleEndian>(&value, ExtensibilityKind::Mutable),
+            vec![
+                46, 0, 0, 0, // DHEADER
+                0, 0, 0, 0x00, // EMHEADER (LC 0, id 0)
+                7, 0, 0, 0, // id | padding (3 bytes)
+                1, 0, 0, 0x40, // EMHEADER (LC 4, id 1)
+                30, 0, 0, 0, // NEXTINT
+                26, 0, 0, 0, // inner DHEADER
+                0, 0, 0, 0x00, // EMHEADER (LC 0, id 0)
+                1, 0, 0, 0, // a | padding (3 bytes)
+                1, 0, 0, 0x30, // EMHEADER (LC 3, id 1)
+                2, 0, 0, 0, // b
+                0, 0, 0, 0, // b
+                2, 0, 0, 0x10, // EMHEADER (LC 1, id 2)
+                3, 0, // c
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_sequence_of_primitives_has_no_dheader() {
+        let value = vec![1u16, 2];
+        assert_eq!(
+            serialize::<_, BigEndian>(&value, ExtensibilityKind::Final),
+            vec![
+                0, 0, 0, 2, // length
+                0, 1, 0, 2, // elements
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_sequence_of_strings_has_dheader() {
+        let value = (1u8, vec!["a".to_string(), "bc".to_string()]);
+        assert_eq!(
+            serialize::<_, BigEndian>(&value, ExtensibilityKind::Final),
+            vec![
+                1, 0, 0, 0, // tuple element | padding (3 bytes)
+                0, 0, 0, 19, // DHEADER
+                0, 0, 0, 2, // length
+                0, 0, 0, 2, // first string length
+                b'a', 0, 0, 0, // first string | padding (2 bytes)
+                0, 0, 0, 3, // second string length
+                b'b', b'c', 0, // second string
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_array_of_structs_has_dheader() {
+        let value = [Primitives { a: 1, b: 2, c: 3 }];
+        assert_eq!(
+            serialize::<_, LittleEndian>(&value, ExtensibilityKind::Final),
+            vec![
+                14, 0, 0, 0, // DHEADER
+                1, 0, 0, 0, // a | padding (3 bytes)
+                2, 0, 0, 0, // b
+                0, 0, 0, 0, // b
+                3, 0, // c
+            ]
+        );
+    }
+
+    #[derive(serde::Serialize)]
+    struct Sequences {
+        id: u8,
+        values: Vec<Primitives>,
+    }
+
+    static SEQUENCES: StructDescriptor = StructDescriptor {
+        name: "Sequences",
+        extensibility: ExtensibilityKind::Final,
+        members: &[
+            MemberDescriptor {
+                name: "id",
+                id: 0,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "values",
+                id: 1,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(1),
+            },
+        ],
+    };
+
+    #[test]
+    fn serialize_described_empty_sequence_of_structs_has_dheader() {
+        let value = Sequences {
+            id: 7,
+            values: vec![],
+        };
+        let mut buffer = Vec::new();
+        ser::Serialize::serialize(
+            &value,
+            &mut Xcdr2Serializer::<BigEndian>::new(
+                &mut buffer,
+                ExtensibilityKind::Final,
+                vec![&SEQUENCES],
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            buffer,
+            vec![
+                7, 0, 0, 0, // id | padding (3 bytes)
+                0, 0, 0, 4, // DHEADER
+                0, 0, 0, 0, // length
+            ]
+        );
+    }
+
+    #[derive(serde::Serialize)]
+    struct Described {
+        id: u8,
+        name: Option<u16>,
+        value: Option<u16>,
+    }
+
+    static DESCRIBED: StructDescriptor = StructDescriptor {
+        name: "Described",
+        extensibility: ExtensibilityKind::Mutable,
+        members: &[
+            MemberDescriptor {
+                name: "id",
+                id: 10,
+                optional: false,
+                must_understand: true,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "name",
+                id: 20,
+                optional: true,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "value",
+                id: 30,
+                optional: true,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+        ],
+    };
+
+    #[test]
+    fn serialize_described_struct_uses_member_ids_and_omits_absent_optional_members() {
+        let value = Described {
+            id: 7,
+            name: None,
+            value: Some(3),
+        };
+        let mut buffer = Vec::new();
+        ser::Serialize::serialize(
+            &value,
+            &mut Xcdr2Serializer::<LittleEndian>::new(
+                &mut buffer,
+                ExtensibilityKind::Final,
+                vec![&DESCRIBED],
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            buffer,
+            vec![
+                14, 0, 0, 0, // DHEADER
+                10, 0, 0, 0x80, // EMHEADER (M flag, LC 0, id 10)
+                7, 0, 0, 0, // id | padding (3 bytes)
+                30, 0, 0, 0x10, // EMHEADER (LC 1, id 30)
+                3, 0, // value
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_optional_values_of_final_struct_have_presence_flag() {
+        let value = Described {
+            id: 7,
+            name: None,
+            value: Some(3),
+        };
+        assert_eq!(
+            serialize::<_, LittleEndian>(&value, ExtensibilityKind::Final),
+            vec![
+                7, 0, 1, 0, // id | name flag | value flag | padding (1 byte)
+                3, 0, // value
+            ]
+        );
+    }
+}
@@ -1,7 +1,13 @@
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
-        qos::{DataReaderQos, DataWriterQos, PublisherQos, QosKind, SubscriberQos},
+        error::DdsError,
+        instance::InstanceHandle,
+        owned::Owned,
+        qos::{
+            DataReaderQos, DataWriterQos, DomainParticipantQos, PublisherQos, QosKind,
+            SubscriberQos,
+        },
         qos_policy::{PartitionQosPolicy, UserDataQosPolicy},
         status::{StatusKind, NO_STATUS},
         time::Duration,
@@ -218,6 +224,52 @@ fn deleted_writers_are_disposed_from_reader() {
     assert_eq!(data_reader.get_matched_publications().unwrap().len(), 0);
 }
 
+#[test]
+fn dropped_owned_writers_are_disposed_from_reader() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let dp = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = dp
+        .create_topic("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = dp
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = Owned::new(
+        publisher
+            .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+            .unwrap(),
+    );
+    let subscriber = dp
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let cond = data_reader.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    data_reader.get_subscription_matched_status().unwrap();
+
+    let data_writer_clone = data_writer.clone();
+    drop(data_writer);
+    assert_eq!(data_reader.get_matched_publications().unwrap().len(), 1);
+
+    drop(data_writer_clone);
+
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    assert_eq!(data_reader.get_matched_publications().unwrap().len(), 0);
+}
+
 #[test]
 fn updated_writers_are_announced_to_reader() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -767,3 +819,218 @@ fn participant_removed_after_lease_duration() {
 
     assert_eq!(discovered_participant.len(), 1);
 }
+
+#[test]
+fn subscriber_partition_change_unmatches_writer() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let dp = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = dp
+        .create_topic("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher_qos = PublisherQos {
+        partition: PartitionQosPolicy {
+            name: "A".to_string(),
+        },
+        ..Default::default()
+    };
+    let publisher = dp
+        .create_publisher(QosKind::Specific(publisher_qos), None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let cond_data_writer = data_writer.get_statuscondition().unwrap();
+    cond_data_writer
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let subscriber_qos = SubscriberQos {
+        partition: PartitionQosPolicy {
+            name: "A".to_string(),
+        },
+        ..Default::default()
+    };
+    let subscriber = dp
+        .create_subscriber(QosKind::Specific(subscriber_qos), None, NO_STATUS)
+        .unwrap();
+    let _data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let mut wait_set_data_writer = WaitSet::new();
+    wait_set_data_writer
+        .attach_condition(Condition::StatusCondition(cond_data_writer))
+        .unwrap();
+    wait_set_data_writer.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(
+        data_writer
+            .get_publication_matched_status()
+            .unwrap()
+            .current_count,
+        1
+    );
+
+    let subscriber_qos = SubscriberQos {
+        partition: PartitionQosPolicy {
+            name: "B".to_string(),
+        },
+        ..Default::default()
+    };
+    subscriber
+        .set_qos(QosKind::Specific(subscriber_qos))
+        .unwrap();
+
+    wait_set_data_writer.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(
+        data_writer
+            .get_publication_matched_status()
+            .unwrap()
+            .current_count,
+        0
+    );
+}
+
+#[test]
+fn publisher_partition_change_matches_reader() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let dp = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = dp
+        .create_topic("topic_name", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher_qos = PublisherQos {
+        partition: PartitionQosPolicy {
+            name: "A".to_string(),
+        },
+        ..Default::default()
+    };
+    let publisher = dp
+        .create_publisher(QosKind::Specific(publisher_qos), None, NO_STATUS)
+        .unwrap();
+    let _data_writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber_qos = SubscriberQos {
+        partition: PartitionQosPolicy {
+            name: "B".to_string(),
+        },
+        ..Default::default()
+    };
+    let subscriber = dp
+        .create_subscriber(QosKind::Specific(subscriber_qos), None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let cond_data_reader = data_reader.get_statuscondition().unwrap();
+    cond_data_reader
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+
+    let mut wait_set_data_reader = WaitSet::new();
+    wait_set_data_reader
+        .attach_condition(Condition::StatusCondition(cond_data_reader))
+        .unwrap();
+    assert!(wait_set_data_reader.wait(Duration::new(2, 0)).is_err());
+
+    let publisher_qos = PublisherQos {
+        partition: PartitionQosPolicy {
+            name: "B".to_string(),
+        },
+        ..Default::default()
+    };
+    publisher.set_qos(QosKind::Specific(publisher_qos)).unwrap();
+
+    assert!(wait_set_data_reader.wait(Duration::new(10, 0)).is_ok());
+}
+
+#[test]
+fn deleted_participant_is_removed_from_discovered_participants() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2_handle = participant2.get_instance_handle().unwrap();
+
+    let start_time = std::time::Instant::now();
+    while !participant1
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&participant2_handle)
+    {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    domain_participant_factory
+        .delete_participant(&participant2)
+        .unwrap();
+
+    let start_time = std::time::Instant::now();
+    while participant1
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&participant2_handle)
+    {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn discovered_participant_data() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let user_data = UserDataQosPolicy {
+        value: vec![1, 2, 3],
+    };
+    let participant2_qos = DomainParticipantQos {
+        user_data: user_data.clone(),
+        ..Default::default()
+    };
+    let participant2 = domain_participant_factory
+        .create_participant(
+            domain_id,
+            QosKind::Specific(participant2_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let participant2_handle = participant2.get_instance_handle().unwrap();
+
+    let start_time = std::time::Instant::now();
+    while !participant1
+        .get_discovered_participants()
+        .unwrap()
+        .contains(&participant2_handle)
+    {
+        assert!(start_time.elapsed() < std::time::Duration::from_secs(10));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    let participant2_data = participant1
+        .get_discovered_participant_data(participant2_handle)
+        .unwrap();
+    assert_eq!(participant2_data.user_data(), &user_data);
+    assert!(matches!(
+        participant1.get_discovered_participant_data(InstanceHandle::default()),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
@@ -6,6 +6,7 @@ use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
         error::DdsError,
+        owned::Owned,
         qos::{
             DataReaderQos, DataWriterQos, DomainParticipantQos, PublisherQos, QosKind,
             SubscriberQos, TopicQos,
@@ -18,8 +19,14 @@ use dust_dds::{
         time::{Duration, DurationKind},
         wait_set::{Condition, WaitSet},
     },
-    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
-    topic_definition::type_support::DdsType,
+    subscription::sample_info::{
+        InstanceStateKind, ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE,
+    },
+    topic_definition::{
+        dynamic_data::DynamicData,
+        dynamic_type::{DynamicType, DynamicTypeBuilder, TypeKind},
+        type_support::{DdsType, TypeSupport},
+    },
 };
 
 mod utils;
@@ -95,6 +102,368 @@ fn create_delete_topic() {
     );
 }
 
+#[test]
+fn lookup_topicdescription() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("abc", "TestType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let found_topic = participant.lookup_topicdescription("abc").unwrap().unwrap();
+    assert_eq!(
+        found_topic.get_instance_handle(),
+        topic.get_instance_handle()
+    );
+    assert_eq!(found_topic.get_type_name(), Ok("TestType".to_string()));
+    assert!(participant
+        .lookup_topicdescription("def")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn create_typed_topic() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_typed_topic::<MyData>("abc", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(MyData::get_type_name(), "MyData");
+    assert_eq!(topic.get_type_name(), Ok("MyData".to_string()));
+    assert_eq!(
+        writer.get_topic().unwrap().get_instance_handle(),
+        topic.get_instance_handle()
+    );
+    assert_eq!(publisher.delete_datawriter(&writer), Ok(()));
+    assert_eq!(participant.delete_topic(&topic), Ok(()));
+}
+
+#[test]
+fn not_allowed_to_register_type_name_of_different_type() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(TestType::register_type(&participant, "TestType"), Ok(()));
+    assert_eq!(TestType::register_type(&participant, "TestType"), Ok(()));
+    assert_eq!(TestType::register_type(&participant, "OtherType"), Ok(()));
+    assert!(matches!(
+        MyData::register_type(&participant, "TestType"),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert!(participant
+        .create_typed_topic::<MyData>("abc", QosKind::Default, None, NO_STATUS)
+        .is_ok());
+    assert!(matches!(
+        TestType::register_type(&participant, "MyData"),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[test]
+fn not_allowed_to_create_reader_and_writer_of_different_type_than_registered() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let typed_topic = participant
+        .create_typed_topic::<MyData>("abc", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let untyped_topic = participant
+        .create_topic("def", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert!(publisher
+        .create_datawriter(&typed_topic, QosKind::Default, None, NO_STATUS)
+        .is_ok());
+    assert!(matches!(
+        subscriber.create_datareader::<TestType>(&untyped_topic, QosKind::Default, None, NO_STATUS),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert!(subscriber
+        .create_datareader::<MyData>(&untyped_topic, QosKind::Default, None, NO_STATUS)
+        .is_ok());
+}
+
+#[test]
+fn unregistered_type_name_is_bound_to_type_of_first_reader_or_writer() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("abc", "UnregisteredType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert!(publisher
+        .create_datawriter::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .is_ok());
+    assert!(matches!(
+        subscriber.create_datareader::<TestType>(&topic, QosKind::Default, None, NO_STATUS),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert!(matches!(
+        TestType::register_type(&participant, "UnregisteredType"),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert!(subscriber
+        .create_datareader::<MyData>(&topic, QosKind::Default, None, NO_STATUS)
+        .is_ok());
+}
+
+#[test]
+fn dynamic_data_reader_and_writer_require_registered_dynamic_type() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let dynamic_type = DynamicTypeBuilder::new_struct("DynamicType")
+        .add_key_member("id", DynamicType::primitive(TypeKind::UInt8).unwrap())
+        .build()
+        .unwrap();
+    let other_dynamic_type = DynamicTypeBuilder::new_struct("DynamicType")
+        .add_member("id", DynamicType::primitive(TypeKind::UInt8).unwrap())
+        .build()
+        .unwrap();
+    assert_eq!(
+        dynamic_type.register_type(&participant, "DynamicType"),
+        Ok(())
+    );
+    assert_eq!(
+        dynamic_type.register_type(&participant, "DynamicType"),
+        Ok(())
+    );
+    assert!(matches!(
+        other_dynamic_type.register_type(&participant, "DynamicType"),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert!(matches!(
+        TestType::register_type(&participant, "DynamicType"),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    let dynamic_topic = participant
+        .create_topic("abc", "DynamicType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let unregistered_topic = participant
+        .create_topic("def", "UnregisteredType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert!(matches!(
+        publisher.create_datawriter::<DynamicData>(
+            &unregistered_topic,
+            QosKind::Default,
+            None,
+            NO_STATUS
+        ),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert!(matches!(
+        subscriber.create_datareader::<DynamicData>(
+            &unregistered_topic,
+            QosKind::Default,
+            None,
+            NO_STATUS
+        ),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert!(publisher
+        .create_datawriter::<DynamicData>(&dynamic_topic, QosKind::Default, None, NO_STATUS)
+        .is_ok());
+    assert!(subscriber
+        .create_datareader::<DynamicData>(&dynamic_topic, QosKind::Default, None, NO_STATUS)
+        .is_ok());
+}
+
+#[test]
+fn contains_entity() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let other_participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("abc", "TestType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<TestType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<TestType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let other_publisher = other_participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(
+        participant.contains_entity(topic.get_instance_handle().unwrap()),
+        Ok(true)
+    );
+    assert_eq!(
+        participant.contains_entity(publisher.get_instance_handle().unwrap()),
+        Ok(true)
+    );
+    assert_eq!(
+        participant.contains_entity(subscriber.get_instance_handle().unwrap()),
+        Ok(true)
+    );
+    assert_eq!(
+        participant.contains_entity(writer.get_instance_handle().unwrap()),
+        Ok(true)
+    );
+    assert_eq!(
+        participant.contains_entity(reader.get_instance_handle().unwrap()),
+        Ok(true)
+    );
+    assert_eq!(
+        participant.contains_entity(other_publisher.get_instance_handle().unwrap()),
+        Ok(false)
+    );
+}
+
+#[test]
+fn owned_entities_are_deleted_when_dropped() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = Owned::new(
+        participant
+            .create_topic("abc", "TestType", QosKind::Default, None, NO_STATUS)
+            .unwrap(),
+    );
+    let publisher = Owned::new(
+        participant
+            .create_publisher(QosKind::Default, None, NO_STATUS)
+            .unwrap(),
+    );
+    let subscriber = Owned::new(
+        participant
+            .create_subscriber(QosKind::Default, None, NO_STATUS)
+            .unwrap(),
+    );
+    let writer = Owned::new(
+        publisher
+            .create_datawriter::<TestType>(&topic, QosKind::Default, None, NO_STATUS)
+            .unwrap(),
+    );
+    let reader = Owned::new(
+        subscriber
+            .create_datareader::<TestType>(&topic, QosKind::Default, None, NO_STATUS)
+            .unwrap(),
+    );
+    let reader_clone = reader.clone();
+
+    let topic_handle = topic.get_instance_handle().unwrap();
+    let publisher_handle = publisher.get_instance_handle().unwrap();
+    let subscriber_handle = subscriber.get_instance_handle().unwrap();
+    let writer_handle = writer.get_instance_handle().unwrap();
+    let reader_handle = reader.get_instance_handle().unwrap();
+
+    drop(writer);
+    assert_eq!(participant.contains_entity(writer_handle), Ok(false));
+    assert_eq!(participant.contains_entity(publisher_handle), Ok(true));
+
+    drop(reader);
+    assert_eq!(participant.contains_entity(reader_handle), Ok(true));
+    drop(reader_clone);
+    assert_eq!(participant.contains_entity(reader_handle), Ok(false));
+
+    drop(publisher);
+    drop(subscriber);
+    drop(topic);
+    assert_eq!(participant.contains_entity(publisher_handle), Ok(false));
+    assert_eq!(participant.contains_entity(subscriber_handle), Ok(false));
+    assert_eq!(participant.contains_entity(topic_handle), Ok(false));
+}
+
+#[test]
+fn owned_entity_delete_returns_deletion_error() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = Owned::new(
+        participant
+            .create_publisher(QosKind::Default, None, NO_STATUS)
+            .unwrap(),
+    );
+    let topic = participant
+        .create_topic("abc", "TestType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<TestType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher_handle = publisher.get_instance_handle().unwrap();
+
+    assert!(matches!(
+        publisher.delete(),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert_eq!(participant.contains_entity(publisher_handle), Ok(true));
+
+    publisher.delete_datawriter(&writer).unwrap();
+    assert_eq!(publisher.delete(), Ok(()));
+    assert_eq!(participant.contains_entity(publisher_handle), Ok(false));
+    drop(publisher);
+}
+
 #[test]
 fn not_allowed_to_delete_publisher_from_different_participant() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -469,7 +838,6 @@ fn default_topic_qos() {
 }
 
 #[test]
-#[ignore = "Broken after refactor"]
 fn builtin_reader_access() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
@@ -481,23 +849,26 @@ fn builtin_reader_access() {
 
     assert!(builtin_subscriber
         .lookup_datareader::<ParticipantBuiltinTopicData>("DCPSParticipant")
-        .is_ok());
+        .unwrap()
+        .is_some());
 
     assert!(builtin_subscriber
         .lookup_datareader::<TopicBuiltinTopicData>("DCPSTopic")
-        .is_ok());
+        .unwrap()
+        .is_some());
 
     assert!(builtin_subscriber
         .lookup_datareader::<PublicationBuiltinTopicData>("DCPSPublication")
-        .is_ok());
+        .unwrap()
+        .is_some());
 
     assert!(builtin_subscriber
         .lookup_datareader::<SubscriptionBuiltinTopicData>("DCPSSubscription")
-        .is_ok());
+        .unwrap()
+        .is_some());
 }
 
 #[test]
-#[ignore = "Broken after refactor"]
 fn get_discovery_data_from_builtin_reader() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
     let participant_user_data = vec![1, 2];
@@ -680,6 +1051,87 @@ fn get_discovery_data_from_builtin_reader() {
     );
 }
 
+#[test]
+fn take_discovery_data_from_builtin_reader() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+    let participant1 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let participant2 = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic1 = participant1
+        .create_topic("topic_name", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_writer = publisher
+        .create_datawriter::<MyData>(&topic1, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publications_reader = participant2
+        .get_builtin_subscriber()
+        .unwrap()
+        .lookup_datareader::<PublicationBuiltinTopicData>("DCPSPublication")
+        .unwrap()
+        .unwrap();
+    let publications_reader_cond = publications_reader.get_statuscondition().unwrap();
+    publications_reader_cond
+        .set_enabled_statuses(&[StatusKind::DataAvailable])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(publications_reader_cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let publication_samples = publications_reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        publication_samples[0].sample_info.instance_state,
+        InstanceStateKind::Alive
+    );
+    assert_eq!(
+        publication_samples[0].data.as_ref().unwrap().topic_name(),
+        "topic_name"
+    );
+
+    // Taking the discovery data must not prevent the matching of the discovered writer
+    let topic2 = participant2
+        .create_topic("topic_name", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader = subscriber
+        .create_datareader::<MyData>(&topic2, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let data_reader_cond = data_reader.get_statuscondition().unwrap();
+    data_reader_cond
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set_reader = WaitSet::new();
+    wait_set_reader
+        .attach_condition(Condition::StatusCondition(data_reader_cond))
+        .unwrap();
+    wait_set_reader.wait(Duration::new(10, 0)).unwrap();
+
+    publisher.delete_datawriter(&data_writer).unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let publication_samples = publications_reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        publication_samples[0].sample_info.instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
+}
+
 #[test]
 fn ignore_publication() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
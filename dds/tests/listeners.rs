@@ -6,15 +6,15 @@ use dust_dds::{
     infrastructure::{
         qos::{DataReaderQos, DataWriterQos, QosKind},
         qos_policy::{
-            DeadlineQosPolicy, HistoryQosPolicy, HistoryQosPolicyKind, Length,
+            DeadlineQosPolicy, HistoryQosPolicy, HistoryQosPolicyKind, Length, LifespanQosPolicy,
             ReliabilityQosPolicy, ReliabilityQosPolicyKind, ResourceLimitsQosPolicy,
         },
         status::{
             OfferedIncompatibleQosStatus, PublicationMatchedStatus, RequestedDeadlineMissedStatus,
-            RequestedIncompatibleQosStatus, SampleRejectedStatus, SampleRejectedStatusKind,
-            StatusKind, SubscriptionMatchedStatus, NO_STATUS,
+            RequestedIncompatibleQosStatus, SampleLostStatus, SampleRejectedStatus,
+            SampleRejectedStatusKind, StatusKind, SubscriptionMatchedStatus, NO_STATUS,
         },
-        time::{Duration, DurationKind},
+        time::{Duration, DurationKind, Time},
         wait_set::{Condition, WaitSet},
     },
     publication::{
@@ -25,6 +25,7 @@ use dust_dds::{
     subscription::{
         data_reader::{AnyDataReader, DataReader},
         data_reader_listener::DataReaderListener,
+        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
         subscriber::Subscriber,
         subscriber_listener::SubscriberListener,
     },
@@ -275,6 +276,123 @@ fn sample_rejected_listener() {
         .unwrap();
 }
 
+#[test]
+fn sample_lost_listener() {
+    mock! {
+        SampleLostListener{}
+
+        impl DataReaderListener for SampleLostListener {
+            type Foo = MyData;
+
+            fn on_sample_lost(
+                &mut self,
+                _the_reader: &DataReader<MyData>,
+                _status: SampleLostStatus,
+            );
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic(
+            "SampleLostListenerTopic",
+            "MyData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        lifespan: LifespanQosPolicy {
+            duration: DurationKind::Finite(Duration::new(10, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let mut reader_listener = MockSampleLostListener::new();
+    reader_listener
+        .expect_on_sample_lost()
+        .times(1)
+        .withf(|_, status| status.total_count == 1 && status.total_count_change == 1)
+        .return_const(());
+
+    let reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Specific(reader_qos),
+            Some(Box::new(reader_listener)),
+            &[StatusKind::SampleLost],
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    writer.write(&MyData { id: 1, value: 1 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    // The second sample expires in the writer before it is sent so it is announced with a GAP
+    writer
+        .write_w_timestamp(&MyData { id: 1, value: 2 }, None, Time::new(0, 0))
+        .unwrap();
+    writer.write(&MyData { id: 1, value: 3 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    // Delete all entities to make sure listeners are dropped and missed functions
+    // calls are detected by the mocking framework
+    subscriber.delete_datareader(&reader).unwrap();
+    publisher.delete_datawriter(&writer).unwrap();
+    participant.delete_publisher(&publisher).unwrap();
+    participant.delete_subscriber(&subscriber).unwrap();
+    participant.delete_topic(&topic).unwrap();
+    THE_PARTICIPANT_FACTORY
+        .delete_participant(&participant)
+        .unwrap();
+}
+
 #[test]
 fn subscription_matched_listener() {
     mock! {
@@ -995,6 +1113,275 @@ fn data_available_listener_not_called_when_data_on_readers_listener() {
         .unwrap();
 }
 
+#[test]
+fn notify_datareaders_calls_data_available_listener() {
+    mock! {
+        DataOnReadersListener{}
+
+        impl SubscriberListener for DataOnReadersListener {
+            fn on_data_on_readers(&mut self, _the_subscriber: &Subscriber);
+        }
+    }
+    mock! {
+        DataAvailableListener{}
+
+        impl DataReaderListener for DataAvailableListener {
+            type Foo = MyData;
+
+            fn on_data_available(
+                &mut self,
+                _the_reader: &DataReader<MyData>,
+            );
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let mut subscriber_listener = MockDataOnReadersListener::new();
+    subscriber_listener
+        .expect_on_data_on_readers()
+        .times(1..)
+        .returning(|the_subscriber| the_subscriber.notify_datareaders().unwrap());
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Default,
+            Some(Box::new(subscriber_listener)),
+            &[StatusKind::DataOnReaders],
+        )
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+
+    let (sender, receiver) = std::sync::mpsc::sync_channel(10);
+    let mut reader_listener = MockDataAvailableListener::new();
+    reader_listener
+        .expect_on_data_available()
+        .times(1..)
+        .returning(move |_| {
+            sender.send(()).ok();
+        });
+
+    let reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Specific(reader_qos),
+            Some(Box::new(reader_listener)),
+            &[StatusKind::DataAvailable],
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = MyData { id: 1, value: 1 };
+    writer.write(&data1, None).unwrap();
+
+    receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .unwrap();
+
+    // Delete all entities to make sure listeners are dropped and missed functions
+    // calls are detected by the mocking framework
+    subscriber.delete_datareader(&reader).unwrap();
+    publisher.delete_datawriter(&writer).unwrap();
+    participant.delete_publisher(&publisher).unwrap();
+    participant.delete_subscriber(&subscriber).unwrap();
+    participant.delete_topic(&topic).unwrap();
+    THE_PARTICIPANT_FACTORY
+        .delete_participant(&participant)
+        .unwrap();
+}
+
+#[test]
+fn entity_operations_from_data_available_listener() {
+    mock! {
+        DataAvailableListener{}
+
+        impl DataReaderListener for DataAvailableListener {
+            type Foo = MyData;
+
+            fn on_data_available(
+                &mut self,
+                _the_reader: &DataReader<MyData>,
+            );
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let request_topic = participant
+        .create_topic("RequestTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reply_topic = participant
+        .create_topic("ReplyTopic", "MyData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let request_writer = publisher
+        .create_datawriter(
+            &request_topic,
+            QosKind::Specific(writer_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reply_writer = publisher
+        .create_datawriter(&reply_topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+
+    let listener_reply_writer = publisher
+        .lookup_datawriter::<MyData>("ReplyTopic")
+        .unwrap()
+        .unwrap();
+    let mut request_listener = MockDataAvailableListener::new();
+    request_listener
+        .expect_on_data_available()
+        .times(1..)
+        .returning(move |the_reader| {
+            let samples = the_reader
+                .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+                .unwrap();
+            assert_eq!(the_reader.get_matched_publications().unwrap().len(), 1);
+            assert!(the_reader.get_status_changes().is_ok());
+            assert_eq!(
+                the_reader
+                    .get_topicdescription()
+                    .unwrap()
+                    .get_name()
+                    .unwrap(),
+                "RequestTopic"
+            );
+            the_reader.get_subscriber().unwrap().get_qos().unwrap();
+
+            let request = samples[0].data.as_ref().unwrap();
+            let reply = MyData {
+                id: request.id,
+                value: request.value + 1,
+            };
+            listener_reply_writer.write(&reply, None).unwrap();
+            listener_reply_writer
+                .wait_for_acknowledgments(Duration::new(10, 0))
+                .unwrap();
+        });
+    let request_reader = subscriber
+        .create_datareader(
+            &request_topic,
+            QosKind::Specific(reader_qos.clone()),
+            Some(Box::new(request_listener)),
+            &[StatusKind::DataAvailable],
+        )
+        .unwrap();
+    let reply_reader = subscriber
+        .create_datareader::<MyData>(&reply_topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    for writer in [&request_writer, &reply_writer] {
+        let cond = writer.get_statuscondition().unwrap();
+        cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(cond))
+            .unwrap();
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    let reply_cond = reply_reader.get_statuscondition().unwrap();
+    reply_cond
+        .set_enabled_statuses(&[StatusKind::DataAvailable])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(reply_cond))
+        .unwrap();
+
+    request_writer
+        .write(&MyData { id: 1, value: 1 }, None)
+        .unwrap();
+
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    let samples = reply_reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].data, Some(MyData { id: 1, value: 2 }));
+
+    // Delete all entities to make sure listeners are dropped and missed functions
+    // calls are detected by the mocking framework
+    subscriber.delete_datareader(&request_reader).unwrap();
+    subscriber.delete_datareader(&reply_reader).unwrap();
+    publisher.delete_datawriter(&request_writer).unwrap();
+    publisher.delete_datawriter(&reply_writer).unwrap();
+    participant.delete_publisher(&publisher).unwrap();
+    participant.delete_subscriber(&subscriber).unwrap();
+    participant.delete_topic(&request_topic).unwrap();
+    participant.delete_topic(&reply_topic).unwrap();
+    THE_PARTICIPANT_FACTORY
+        .delete_participant(&participant)
+        .unwrap();
+}
+
 #[test]
 fn participant_deadline_missed_listener() {
     mock! {
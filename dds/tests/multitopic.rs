@@ -0,0 +1,590 @@
+use dust_dds::{
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        error::DdsError,
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
+        wait_set::{Condition, WaitSet},
+    },
+    subscription::sample_info::{
+        InstanceStateKind, ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE,
+    },
+    topic_definition::type_support::DdsType,
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+struct Gps {
+    #[key]
+    vehicle_id: u8,
+    latitude: f64,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+struct Imu {
+    #[key]
+    vehicle_id: u8,
+    yaw: f64,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+struct VehicleState {
+    #[key]
+    vehicle_id: u8,
+    latitude: f64,
+    yaw: f64,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+struct VehicleHeading {
+    #[key]
+    id: u8,
+    heading: f64,
+}
+
+#[test]
+fn multitopic_reader_receives_joined_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let gps_topic = participant
+        .create_topic("Gps", "Gps", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let imu_topic = participant
+        .create_topic("Imu", "Imu", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let multitopic = participant
+        .create_multitopic::<(Gps, Imu)>(
+            "VehicleState",
+            "VehicleState",
+            "SELECT * FROM Gps NATURAL JOIN Imu",
+            &[],
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let gps_writer = publisher
+        .create_datawriter(
+            &gps_topic,
+            QosKind::Specific(writer_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let imu_writer = publisher
+        .create_datawriter(&imu_topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_multitopic_datareader::<VehicleState>(
+            &multitopic,
+            QosKind::Specific(reader_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    for writer_cond in [
+        gps_writer.get_statuscondition().unwrap(),
+        imu_writer.get_statuscondition().unwrap(),
+    ] {
+        writer_cond
+            .set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(writer_cond))
+            .unwrap();
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    let reader_cond = reader.get_statuscondition().unwrap();
+    reader_cond
+        .set_enabled_statuses(&[StatusKind::DataAvailable])
+        .unwrap();
+    let mut reader_wait_set = WaitSet::new();
+    reader_wait_set
+        .attach_condition(Condition::StatusCondition(reader_cond))
+        .unwrap();
+
+    gps_writer
+        .write(
+            &Gps {
+                vehicle_id: 1,
+                latitude: 52.0,
+            },
+            None,
+        )
+        .unwrap();
+    imu_writer
+        .write(
+            &Imu {
+                vehicle_id: 1,
+                yaw: 90.0,
+            },
+            None,
+        )
+        .unwrap();
+
+    reader_wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        samples[0].data.as_ref().unwrap(),
+        &VehicleState {
+            vehicle_id: 1,
+            latitude: 52.0,
+            yaw: 90.0,
+        }
+    );
+    assert_eq!(
+        reader.get_topicdescription().err(),
+        Some(DdsError::IllegalOperation)
+    );
+    assert_eq!(subscriber.delete_datareader(&reader), Ok(()));
+    assert!(subscriber
+        .lookup_datareader::<Gps>("Gps")
+        .unwrap()
+        .is_none());
+    assert_eq!(participant.delete_multitopic(&multitopic), Ok(()));
+}
+
+#[test]
+fn multitopic_reader_receives_selected_fields() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let gps_topic = participant
+        .create_topic("Gps", "Gps", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let imu_topic = participant
+        .create_topic("Imu", "Imu", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let multitopic = participant
+        .create_multitopic::<(Gps, Imu)>(
+            "VehicleHeading",
+            "VehicleHeading",
+            "SELECT vehicle_id AS id, yaw heading FROM Gps NATURAL JOIN Imu",
+            &[],
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let imu_writer = publisher
+        .create_datawriter(&imu_topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let gps_writer = publisher
+        .create_datawriter(&gps_topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_multitopic_datareader::<VehicleHeading>(
+            &multitopic,
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    for writer_cond in [
+        gps_writer.get_statuscondition().unwrap(),
+        imu_writer.get_statuscondition().unwrap(),
+    ] {
+        writer_cond
+            .set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(writer_cond))
+            .unwrap();
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    let reader_cond = reader.get_statuscondition().unwrap();
+    reader_cond
+        .set_enabled_statuses(&[StatusKind::DataAvailable])
+        .unwrap();
+    let mut reader_wait_set = WaitSet::new();
+    reader_wait_set
+        .attach_condition(Condition::StatusCondition(reader_cond))
+        .unwrap();
+
+    imu_writer
+        .write(
+            &Imu {
+                vehicle_id: 3,
+                yaw: 45.0,
+            },
+            None,
+        )
+        .unwrap();
+    gps_writer
+        .write(
+            &Gps {
+                vehicle_id: 3,
+                latitude: 10.0,
+            },
+            None,
+        )
+        .unwrap();
+
+    reader_wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        samples[0].data.as_ref().unwrap(),
+        &VehicleHeading {
+            id: 3,
+            heading: 45.0,
+        }
+    );
+}
+
+#[test]
+fn create_multitopic_with_unknown_topic_fails() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    participant
+        .create_topic("Gps", "Gps", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert!(matches!(
+        participant.create_multitopic::<(Gps, Imu)>(
+            "VehicleState",
+            "VehicleState",
+            "SELECT * FROM Gps NATURAL JOIN Imu",
+            &[],
+        ),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+    assert_eq!(
+        participant
+            .create_multitopic::<(Gps,)>(
+                "VehicleState",
+                "VehicleState",
+                "SELECT * FROM Gps WHERE latitude > 0",
+                &[]
+            )
+            .err(),
+        Some(DdsError::Unsupported)
+    );
+}
+
+#[test]
+fn multitopic_reader_joins_samples_with_equal_common_fields() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let gps_topic = participant
+        .create_topic("Gps", "Gps", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let imu_topic = participant
+        .create_topic("Imu", "Imu", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let multitopic = participant
+        .create_multitopic::<(Gps, Imu)>(
+            "VehicleState",
+            "VehicleState",
+            "SELECT * FROM Gps NATURAL JOIN Imu",
+            &[],
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let gps_writer = publisher
+        .create_datawriter(
+            &gps_topic,
+            QosKind::Specific(writer_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let imu_writer = publisher
+        .create_datawriter(&imu_topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_multitopic_datareader::<VehicleState>(
+            &multitopic,
+            QosKind::Specific(reader_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    for writer_cond in [
+        gps_writer.get_statuscondition().unwrap(),
+        imu_writer.get_statuscondition().unwrap(),
+    ] {
+        writer_cond
+            .set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(writer_cond))
+            .unwrap();
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    let reader_cond = reader.get_statuscondition().unwrap();
+    reader_cond
+        .set_enabled_statuses(&[StatusKind::DataAvailable])
+        .unwrap();
+    let mut reader_wait_set = WaitSet::new();
+    reader_wait_set
+        .attach_condition(Condition::StatusCondition(reader_cond))
+        .unwrap();
+
+    gps_writer
+        .write(
+            &Gps {
+                vehicle_id: 1,
+                latitude: 52.0,
+            },
+            None,
+        )
+        .unwrap();
+    gps_writer
+        .write(
+            &Gps {
+                vehicle_id: 2,
+                latitude: 48.0,
+            },
+            None,
+        )
+        .unwrap();
+    let imu = Imu {
+        vehicle_id: 2,
+        yaw: 180.0,
+    };
+    imu_writer.write(&imu, None).unwrap();
+
+    reader_wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(
+        samples[0].data.as_ref().unwrap(),
+        &VehicleState {
+            vehicle_id: 2,
+            latitude: 48.0,
+            yaw: 180.0,
+        }
+    );
+
+    imu_writer.dispose(&imu, None).unwrap();
+
+    reader_wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+    assert!(!samples[0].sample_info.valid_data);
+    assert_eq!(
+        samples[0].sample_info.instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
+}
+
+#[test]
+fn multitopic_source_readers_are_internal() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    participant
+        .create_topic("Gps", "Gps", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    participant
+        .create_topic("Imu", "Imu", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let multitopic = participant
+        .create_multitopic::<(Gps, Imu)>(
+            "VehicleState",
+            "VehicleState",
+            "SELECT * FROM Gps NATURAL JOIN Imu",
+            &[],
+        )
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_multitopic_datareader::<VehicleState>(
+            &multitopic,
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    assert!(subscriber
+        .lookup_datareader::<Gps>("Gps")
+        .unwrap()
+        .is_none());
+    assert!(subscriber
+        .lookup_datareader::<Imu>("Imu")
+        .unwrap()
+        .is_none());
+    assert!(matches!(
+        participant.delete_multitopic(&multitopic),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    assert_eq!(subscriber.delete_contained_entities(), Ok(()));
+    assert!(subscriber
+        .lookup_datareader::<VehicleState>("VehicleState")
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        reader.get_instance_handle().err(),
+        Some(DdsError::AlreadyDeleted)
+    );
+    assert_eq!(participant.delete_subscriber(&subscriber), Ok(()));
+}
+
+#[test]
+fn deleted_multitopic_cannot_be_used() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    participant
+        .create_topic("Gps", "Gps", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let multitopic = participant
+        .create_multitopic::<(Gps,)>("VehicleState", "VehicleState", "SELECT * FROM Gps", &[])
+        .unwrap();
+    assert!(matches!(
+        participant.create_multitopic::<(Gps,)>(
+            "VehicleState",
+            "VehicleState",
+            "SELECT * FROM Gps",
+            &[]
+        ),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    assert_eq!(participant.delete_multitopic(&multitopic), Ok(()));
+    assert_eq!(
+        participant.delete_multitopic(&multitopic),
+        Err(DdsError::AlreadyDeleted)
+    );
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    assert_eq!(
+        subscriber
+            .create_multitopic_datareader::<VehicleState>(
+                &multitopic,
+                QosKind::Default,
+                None,
+                NO_STATUS,
+            )
+            .err(),
+        Some(DdsError::AlreadyDeleted)
+    );
+}
+
+#[test]
+fn multitopic_expression_parameters_are_not_supported() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    participant
+        .create_topic("Gps", "Gps", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(
+        participant
+            .create_multitopic::<(Gps,)>(
+                "VehicleState",
+                "VehicleState",
+                "SELECT * FROM Gps",
+                &["1".to_string()]
+            )
+            .err(),
+        Some(DdsError::BadParameter)
+    );
+
+    let mut multitopic = participant
+        .create_multitopic::<(Gps,)>("VehicleState", "VehicleState", "SELECT * FROM Gps", &[])
+        .unwrap();
+    assert_eq!(
+        multitopic.set_expression_parameters(&["1".to_string()]),
+        Err(DdsError::BadParameter)
+    );
+    assert_eq!(multitopic.get_expression_parameters(), Ok(vec![]));
+}
@@ -1,9 +1,14 @@
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
-        qos::{DataWriterQos, QosKind},
-        qos_policy::UserDataQosPolicy,
+        error::DdsError,
+        qos::{DataWriterQos, QosKind, TopicQos},
+        qos_policy::{
+            DeadlineQosPolicy, LivelinessQosPolicy, LivelinessQosPolicyKind, ReliabilityQosPolicy,
+            ReliabilityQosPolicyKind, UserDataQosPolicy,
+        },
         status::NO_STATUS,
+        time::{Duration, DurationKind},
     },
     topic_definition::type_support::DdsType,
 };
@@ -14,6 +19,9 @@ use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
 #[derive(serde::Serialize, serde::Deserialize, DdsType)]
 struct UserType(i32);
 
+#[derive(serde::Serialize, serde::Deserialize, DdsType)]
+struct OtherUserType(i32);
+
 #[test]
 fn get_publisher_parent_participant() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -135,3 +143,322 @@ fn different_writers_have_different_instance_handles() {
         writer2_2.get_instance_handle()
     );
 }
+
+#[test]
+fn lookup_datawriter_by_topic_name() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic(
+            "lookup_datawriter_by_topic_name",
+            "UserType",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let found_writer = publisher
+        .lookup_datawriter::<UserType>("lookup_datawriter_by_topic_name")
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        found_writer.get_instance_handle(),
+        writer.get_instance_handle()
+    );
+    assert!(publisher
+        .lookup_datawriter::<UserType>("UnknownTopic")
+        .unwrap()
+        .is_none());
+    assert!(matches!(
+        publisher.lookup_datawriter::<OtherUserType>("lookup_datawriter_by_topic_name"),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[test]
+fn writer_offered_deadline_missed_status() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic(
+            "writer_offered_deadline_missed_status",
+            "UserType",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        deadline: DeadlineQosPolicy {
+            period: DurationKind::Finite(Duration::new(0, 100_000_000)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(
+        writer
+            .get_offered_deadline_missed_status()
+            .unwrap()
+            .total_count,
+        0
+    );
+
+    writer.write(&UserType(1), None).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let status = writer.get_offered_deadline_missed_status().unwrap();
+    assert!(status.total_count >= 1);
+    assert_eq!(status.total_count_change, status.total_count);
+    assert_eq!(
+        writer
+            .get_offered_deadline_missed_status()
+            .unwrap()
+            .total_count_change,
+        0
+    );
+}
+
+#[test]
+fn writer_liveliness_lost_status() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic(
+            "writer_liveliness_lost_status",
+            "UserType",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        liveliness: LivelinessQosPolicy {
+            kind: LivelinessQosPolicyKind::ManualByTopic,
+            lease_duration: DurationKind::Finite(Duration::new(0, 200_000_000)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    writer.write(&UserType(1), None).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    assert_eq!(writer.get_liveliness_lost_status().unwrap().total_count, 1);
+
+    // Staying not alive does not count as losing the liveliness again
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    assert_eq!(writer.get_liveliness_lost_status().unwrap().total_count, 1);
+
+    writer.assert_liveliness().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    let status = writer.get_liveliness_lost_status().unwrap();
+    assert_eq!(status.total_count, 2);
+    assert_eq!(status.total_count_change, 1);
+}
+
+#[test]
+fn writer_liveliness_asserted_by_participant() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic(
+            "writer_liveliness_asserted_by_participant",
+            "UserType",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        liveliness: LivelinessQosPolicy {
+            kind: LivelinessQosPolicyKind::ManualByParticipant,
+            lease_duration: DurationKind::Finite(Duration::new(0, 300_000_000)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    for _ in 0..6 {
+        participant.assert_liveliness().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    assert_eq!(writer.get_liveliness_lost_status().unwrap().total_count, 0);
+
+    std::thread::sleep(std::time::Duration::from_millis(700));
+    assert_eq!(writer.get_liveliness_lost_status().unwrap().total_count, 1);
+}
+
+#[test]
+fn writer_with_automatic_liveliness_does_not_lose_liveliness() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic(
+            "writer_with_automatic_liveliness_does_not_lose_liveliness",
+            "UserType",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        liveliness: LivelinessQosPolicy {
+            kind: LivelinessQosPolicyKind::Automatic,
+            lease_duration: DurationKind::Finite(Duration::new(0, 100_000_000)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    assert_eq!(writer.get_liveliness_lost_status().unwrap().total_count, 0);
+}
+
+#[test]
+fn publisher_delete_contained_entities() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic(
+            "publisher_delete_contained_entities",
+            "UserType",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let _writer = publisher
+        .create_datawriter::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    publisher.delete_contained_entities().unwrap();
+
+    assert!(publisher
+        .lookup_datawriter::<UserType>("publisher_delete_contained_entities")
+        .unwrap()
+        .is_none());
+    assert_eq!(participant.delete_publisher(&publisher), Ok(()));
+}
+
+#[test]
+fn publisher_copy_from_topic_qos() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic_qos = TopicQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let user_data = UserDataQosPolicy {
+        value: vec![1, 2, 3],
+    };
+    let mut writer_qos = DataWriterQos {
+        user_data: user_data.clone(),
+        ..Default::default()
+    };
+
+    publisher
+        .copy_from_topic_qos(&mut writer_qos, &topic_qos)
+        .unwrap();
+
+    assert_eq!(writer_qos.reliability, topic_qos.reliability);
+    assert_eq!(writer_qos.user_data, user_data);
+}
+
+#[test]
+fn publisher_unsupported_operations() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(publisher.suspend_publications(), Err(DdsError::Unsupported));
+    assert_eq!(publisher.resume_publications(), Err(DdsError::Unsupported));
+    assert_eq!(
+        publisher.begin_coherent_changes(),
+        Err(DdsError::Unsupported)
+    );
+    assert_eq!(publisher.end_coherent_changes(), Err(DdsError::Unsupported));
+    assert_eq!(publisher.get_status_changes(), Ok(vec![]));
+}
@@ -1,19 +1,31 @@
 use dust_dds::{
+    builtin_topics::{ParticipantBuiltinTopicData, TopicBuiltinTopicData},
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
-        qos::{DataReaderQos, QosKind},
-        qos_policy::UserDataQosPolicy,
-        status::NO_STATUS,
+        error::DdsError,
+        qos::{DataReaderQos, DataWriterQos, PublisherQos, QosKind, SubscriberQos},
+        qos_policy::{
+            HistoryQosPolicy, HistoryQosPolicyKind, PresentationQosPolicy,
+            PresentationQosPolicyAccessScopeKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+            UserDataQosPolicy,
+        },
+        status::{StatusKind, NO_STATUS},
+        time::{Duration, DurationKind, Time},
+        wait_set::{Condition, WaitSet},
     },
+    subscription::sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
     topic_definition::type_support::DdsType,
 };
 
 mod utils;
 use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
 
-#[derive(serde::Serialize, serde::Deserialize, DdsType)]
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
 struct UserType(i32);
 
+#[derive(serde::Serialize, serde::Deserialize, DdsType)]
+struct OtherUserType(i32);
+
 #[test]
 fn get_subscriber_parent_participant() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -164,3 +176,208 @@ fn data_reader_get_topicdescription() {
 
     assert!(reader.get_topicdescription().unwrap() == topic);
 }
+
+#[test]
+fn lookup_datareader_by_topic_name() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic(
+            "lookup_datareader_by_topic_name",
+            "UserType",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let reader = subscriber
+        .create_datareader::<UserType>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let found_reader = subscriber
+        .lookup_datareader::<UserType>("lookup_datareader_by_topic_name")
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        found_reader.get_instance_handle(),
+        reader.get_instance_handle()
+    );
+    assert!(subscriber
+        .lookup_datareader::<UserType>("UnknownTopic")
+        .unwrap()
+        .is_none());
+    assert!(matches!(
+        subscriber.lookup_datareader::<OtherUserType>("lookup_datareader_by_topic_name"),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[test]
+fn lookup_builtin_datareader_of_different_type_fails() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let builtin_subscriber = participant.get_builtin_subscriber().unwrap();
+
+    assert!(builtin_subscriber
+        .lookup_datareader::<ParticipantBuiltinTopicData>("DCPSParticipant")
+        .unwrap()
+        .is_some());
+    assert!(matches!(
+        builtin_subscriber.lookup_datareader::<TopicBuiltinTopicData>("DCPSParticipant"),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
+
+#[test]
+fn get_datareaders_with_group_ordered_access() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory = DomainParticipantFactory::get_instance();
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic1 = participant
+        .create_topic("Topic1", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic2 = participant
+        .create_topic("Topic2", "UserType", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let presentation = PresentationQosPolicy {
+        access_scope: PresentationQosPolicyAccessScopeKind::Group,
+        coherent_access: false,
+        ordered_access: true,
+    };
+    let reliability = ReliabilityQosPolicy {
+        kind: ReliabilityQosPolicyKind::Reliable,
+        max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+    };
+    let history = HistoryQosPolicy {
+        kind: HistoryQosPolicyKind::KeepAll,
+    };
+
+    let publisher = participant
+        .create_publisher(
+            QosKind::Specific(PublisherQos {
+                presentation: presentation.clone(),
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: reliability.clone(),
+        history: history.clone(),
+        ..Default::default()
+    };
+    let writer1 = publisher
+        .create_datawriter::<UserType>(
+            &topic1,
+            QosKind::Specific(writer_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer2 = publisher
+        .create_datawriter::<UserType>(&topic2, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Specific(SubscriberQos {
+                presentation,
+                ..Default::default()
+            }),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability,
+        history,
+        ..Default::default()
+    };
+    let reader1 = subscriber
+        .create_datareader::<UserType>(
+            &topic1,
+            QosKind::Specific(reader_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader2 = subscriber
+        .create_datareader::<UserType>(&topic2, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    for writer in [&writer1, &writer2] {
+        let cond = writer.get_statuscondition().unwrap();
+        cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(cond))
+            .unwrap();
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    writer1
+        .write_w_timestamp(&UserType(1), None, Time::new(1, 0))
+        .unwrap();
+    writer2
+        .write_w_timestamp(&UserType(2), None, Time::new(2, 0))
+        .unwrap();
+    writer1
+        .write_w_timestamp(&UserType(3), None, Time::new(3, 0))
+        .unwrap();
+
+    assert!(matches!(
+        subscriber.get_datareaders(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    let start_time = std::time::Instant::now();
+    let mut reader_list = Vec::new();
+    while reader_list.len() < 3 && start_time.elapsed() < std::time::Duration::from_secs(10) {
+        subscriber.begin_access().unwrap();
+        reader_list = subscriber
+            .get_datareaders(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            .unwrap();
+        subscriber.end_access().unwrap();
+    }
+
+    let reader1_handle = reader1.get_instance_handle().unwrap();
+    let reader2_handle = reader2.get_instance_handle().unwrap();
+    assert_eq!(
+        reader_list
+            .iter()
+            .map(|r| r.get_instance_handle().unwrap())
+            .collect::<Vec<_>>(),
+        vec![reader1_handle, reader2_handle, reader1_handle]
+    );
+
+    let mut data_list = Vec::new();
+    for reader in reader_list {
+        let reader = reader.into_typed::<UserType>().unwrap();
+        data_list.push(reader.take_next_sample().unwrap().data.unwrap());
+    }
+    assert_eq!(data_list, vec![UserType(1), UserType(2), UserType(3)]);
+    assert!(matches!(
+        subscriber.end_access(),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+}
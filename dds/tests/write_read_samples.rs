@@ -1,27 +1,40 @@
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
-        error::DdsError,
+        error::{DdsError, DdsResult},
+        instance::InstanceHandle,
         qos::{DataReaderQos, DataWriterQos, QosKind, TopicQos},
         qos_policy::{
-            DestinationOrderQosPolicy, DestinationOrderQosPolicyKind, DurabilityQosPolicy,
-            DurabilityQosPolicyKind, HistoryQosPolicy, HistoryQosPolicyKind, Length,
-            LifespanQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
-            ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, WriterDataLifecycleQosPolicy,
+            DataRepresentationQosPolicy, DestinationOrderQosPolicy, DestinationOrderQosPolicyKind,
+            DurabilityQosPolicy, DurabilityQosPolicyKind, HistoryQosPolicy, HistoryQosPolicyKind,
+            Length, LifespanQosPolicy, ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy,
+            ReliabilityQosPolicyKind, ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy,
+            WriterDataLifecycleQosPolicy, DATAREPRESENTATION_QOS_POLICY_ID,
+            XCDR2_DATA_REPRESENTATION, XCDR_DATA_REPRESENTATION,
         },
         status::{StatusKind, NO_STATUS},
         time::{Duration, DurationKind, Time},
         wait_set::{Condition, WaitSet},
     },
     subscription::{
-        data_reader::DataReader,
+        data_reader::{DataReader, Sample},
         data_reader_listener::DataReaderListener,
         sample_info::{
             InstanceStateKind, SampleStateKind, ViewStateKind, ANY_INSTANCE_STATE,
             ANY_SAMPLE_STATE, ANY_VIEW_STATE,
         },
     },
-    topic_definition::type_support::{dds_serialize_key, dds_serialize_key_to_bytes, DdsType},
+    topic_definition::{
+        bounded::BoundedVec,
+        builtin_types::{KeyedBytes, KeyedString},
+        dynamic_data::{DynamicData, DynamicValue},
+        dynamic_type::{DynamicType, DynamicTypeBuilder, TypeKind},
+        json::from_json_value,
+        type_support::{
+            dds_key_hash, dds_serialize_key_to_bytes, DdsDeserialize, DdsGetKey, DdsHasKey,
+            DdsRepresentation, DdsSerialize, DdsSetKeyFields, DdsType, Representation, TypeSupport,
+        },
+    },
 };
 
 mod utils;
@@ -199,6 +212,106 @@ fn large_data_should_be_fragmented_reliable() {
     assert_eq!(samples[0].data.as_ref().unwrap(), &data);
 }
 
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+struct LargeBoundedData {
+    #[key]
+    id: u8,
+    value: BoundedVec<u8, 2000>,
+}
+
+#[test]
+fn bounded_data_larger_than_fragment_size_should_be_fragmented() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic(
+            "LargeBoundedDataTopic",
+            "LargeBoundedData",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<LargeBoundedData>(
+            &topic,
+            QosKind::Specific(reader_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    // The maximum size of the type exceeds the fragment size so both samples are fragmented,
+    // the small one in a single fragment
+    let data1 = LargeBoundedData {
+        id: 1,
+        value: BoundedVec::new(vec![8; 2000]).unwrap(),
+    };
+    let data2 = LargeBoundedData {
+        id: 2,
+        value: BoundedVec::new(vec![8; 10]).unwrap(),
+    };
+
+    let cond = reader.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::DataAvailable])
+        .unwrap();
+    let mut reader_wait_set = WaitSet::new();
+    reader_wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+
+    for data in [data1, data2] {
+        writer.write(&data, None).unwrap();
+        reader_wait_set.wait(Duration::new(10, 0)).unwrap();
+
+        let samples = reader
+            .take(3, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            .unwrap();
+
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].data.as_ref().unwrap(), &data);
+    }
+}
+
 #[test]
 fn writer_with_keep_last_1_should_send_only_last_sample_to_reader() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -462,7 +575,7 @@ fn samples_are_taken() {
 }
 
 #[test]
-fn read_only_unread_samples() {
+fn loaned_samples_are_read_and_taken() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let participant = DomainParticipantFactory::get_instance()
@@ -513,59 +626,74 @@ fn read_only_unread_samples() {
 
     let data1 = KeyedData { id: 1, value: 1 };
     let data2 = KeyedData { id: 2, value: 10 };
-    let data3 = KeyedData { id: 3, value: 20 };
-    let data4 = KeyedData { id: 4, value: 30 };
-    let data5 = KeyedData { id: 5, value: 40 };
 
     writer.write(&data1, None).unwrap();
     writer.write(&data2, None).unwrap();
-    writer.write(&data3, None).unwrap();
-    writer.write(&data4, None).unwrap();
-    writer.write(&data5, None).unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
-    let samples1 = reader
-        .read(
-            3,
-            &[SampleStateKind::NotRead],
-            ANY_VIEW_STATE,
-            ANY_INSTANCE_STATE,
-        )
+    let loaned_samples = reader
+        .read_loaned(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
         .unwrap();
+    assert_eq!(loaned_samples.len(), 2);
+    assert_eq!(loaned_samples[0].data().unwrap(), Some(data1.clone()));
+    assert_eq!(
+        loaned_samples[0].sample_info().sample_state,
+        SampleStateKind::NotRead
+    );
+    assert_eq!(loaned_samples[1].data().unwrap(), Some(data2.clone()));
 
-    let samples2 = reader
+    let samples = reader
         .read(
-            3,
+            10,
             &[SampleStateKind::NotRead],
             ANY_VIEW_STATE,
             ANY_INSTANCE_STATE,
         )
-        .unwrap();
+        .unwrap_err();
+    assert_eq!(samples, DdsError::NoData);
 
-    let samples3 = reader.read(
-        3,
-        &[SampleStateKind::NotRead],
-        ANY_VIEW_STATE,
-        ANY_INSTANCE_STATE,
+    let taken_samples = reader
+        .take_loaned(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        reader.read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+    assert_eq!(taken_samples.len(), 2);
+    assert_eq!(
+        taken_samples[0].sample_info().sample_state,
+        SampleStateKind::Read
     );
+    assert_eq!(taken_samples[1].data().unwrap(), Some(data2));
 
-    assert_eq!(samples1.len(), 3);
-    assert_eq!(samples1[0].data.as_ref().unwrap(), &data1);
-    assert_eq!(samples1[1].data.as_ref().unwrap(), &data2);
-    assert_eq!(samples1[2].data.as_ref().unwrap(), &data3);
+    // The samples loaned before they were taken still give access to their data
+    assert_eq!(loaned_samples[0].data().unwrap(), Some(data1));
+}
 
-    assert_eq!(samples2.len(), 2);
-    assert_eq!(samples2[0].data.as_ref().unwrap(), &data4);
-    assert_eq!(samples2[1].data.as_ref().unwrap(), &data5);
+#[derive(
+    Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsHasKey, DdsGetKey, DdsSetKeyFields,
+)]
+struct RawData(Vec<u8>);
 
-    assert_eq!(samples3, Err(DdsError::NoData));
+impl DdsRepresentation for RawData {
+    const REPRESENTATION: Representation = Representation::Custom;
+
+    fn to_bytes(&self, mut writer: impl std::io::Write) -> DdsResult<()> {
+        writer
+            .write_all(&self.0)
+            .map_err(|e| DdsError::PreconditionNotMet(e.to_string()))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> DdsResult<Self> {
+        Ok(RawData(bytes.to_vec()))
+    }
 }
 
 #[test]
-fn read_next_sample() {
+fn loaned_samples_give_access_to_custom_serialized_data() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let participant = DomainParticipantFactory::get_instance()
@@ -573,7 +701,7 @@ fn read_next_sample() {
         .unwrap();
 
     let topic = participant
-        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .create_topic("MyTopic", "RawData", QosKind::Default, None, NO_STATUS)
         .unwrap();
 
     let publisher = participant
@@ -601,7 +729,7 @@ fn read_next_sample() {
         ..Default::default()
     };
     let reader = subscriber
-        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .create_datareader::<RawData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
     let cond = writer.get_statuscondition().unwrap();
@@ -614,26 +742,224 @@ fn read_next_sample() {
         .unwrap();
     wait_set.wait(Duration::new(10, 0)).unwrap();
 
-    let data1 = KeyedData { id: 1, value: 1 };
-    let data2 = KeyedData { id: 2, value: 10 };
-    let data3 = KeyedData { id: 3, value: 20 };
-
-    writer.write(&data1, None).unwrap();
-    writer.write(&data2, None).unwrap();
-    writer.write(&data3, None).unwrap();
+    writer.write(&RawData(vec![1, 2, 3, 4]), None).unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
-    assert_eq!(reader.read_next_sample().unwrap().data.unwrap(), data1);
-    assert_eq!(reader.read_next_sample().unwrap().data.unwrap(), data2);
-    assert_eq!(reader.read_next_sample().unwrap().data.unwrap(), data3);
-    assert_eq!(reader.read_next_sample(), Err(DdsError::NoData));
+    let loaned_samples = reader
+        .take_loaned(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(loaned_samples[0].serialized_data(), Some(&[1, 2, 3, 4][..]));
+    assert_eq!(
+        loaned_samples[0].data().unwrap(),
+        Some(RawData(vec![1, 2, 3, 4]))
+    );
+}
+
+// Type with a custom representation which does not implement serde. The length of the payload
+// is serialized since the serialized data is padded to a multiple of 4 bytes
+#[derive(Debug, PartialEq, DdsHasKey, DdsGetKey, DdsSetKeyFields)]
+struct Frame {
+    #[key]
+    id: u8,
+    payload: Vec<u8>,
 }
 
+impl DdsRepresentation for Frame {
+    const REPRESENTATION: Representation = Representation::Custom;
+
+    fn to_bytes(&self, mut writer: impl std::io::Write) -> DdsResult<()> {
+        writer
+            .write_all(&[self.id, self.payload.len() as u8])
+            .and_then(|_| writer.write_all(&self.payload))
+            .map_err(|e| DdsError::PreconditionNotMet(e.to_string()))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> DdsResult<Self> {
+        match bytes {
+            [id, length, payload @ ..] if payload.len() >= *length as usize => Ok(Frame {
+                id: *id,
+                payload: payload[..*length as usize].to_vec(),
+            }),
+            _ => Err(DdsError::PreconditionNotMet("Invalid frame".to_string())),
+        }
+    }
+}
+
+impl DdsSerialize for Frame {}
+
+impl DdsDeserialize<'_> for Frame {}
+
 #[test]
-fn take_next_sample() {
+fn custom_representation_samples_without_serde_are_written_and_read() {
+    let samples = [
+        Frame {
+            id: 1,
+            payload: vec![1, 2, 3],
+        },
+        Frame {
+            id: 2,
+            payload: vec![4],
+        },
+    ];
+
+    let received = write_and_read_samples("Frame", &samples);
+
+    assert_eq!(received.len(), 2);
+    assert_eq!(received[0].data.as_ref(), Some(&samples[0]));
+    assert_eq!(received[1].data.as_ref(), Some(&samples[1]));
+    assert_eq!(
+        received[0].sample_info.instance_handle,
+        dds_key_hash(&samples[0]).unwrap()
+    );
+    assert_ne!(
+        received[0].sample_info.instance_handle,
+        received[1].sample_info.instance_handle
+    );
+}
+
+// Identifier which does not implement serde
+#[derive(Debug, PartialEq)]
+struct PacketId([u8; 2]);
+
+// Type with a custom representation which serializes its key without serde
+#[derive(Debug, PartialEq)]
+struct Packet {
+    id: PacketId,
+    payload: u8,
+}
+
+impl DdsHasKey for Packet {
+    const HAS_KEY: bool = true;
+}
+
+impl DdsGetKey for Packet {
+    type BorrowedKeyHolder<'a> = ();
+
+    const MAX_KEY_SIZE: Option<usize> = Some(2);
+
+    fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {}
+
+    fn serialize_key(
+        &self,
+        _representation: &Representation,
+        mut writer: impl std::io::Write,
+    ) -> DdsResult<()> {
+        writer
+            .write_all(&self.id.0)
+            .map_err(|e| DdsError::PreconditionNotMet(e.to_string()))
+    }
+}
+
+impl DdsSetKeyFields for Packet {
+    type OwningKeyHolder = ();
+
+    fn set_key_from_holder(&mut self, _key_holder: Self::OwningKeyHolder) {}
+}
+
+impl DdsRepresentation for Packet {
+    const REPRESENTATION: Representation = Representation::Custom;
+
+    fn to_bytes(&self, mut writer: impl std::io::Write) -> DdsResult<()> {
+        writer
+            .write_all(&[self.id.0[0], self.id.0[1], self.payload])
+            .map_err(|e| DdsError::PreconditionNotMet(e.to_string()))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> DdsResult<Self> {
+        match bytes {
+            [id0, id1, payload, ..] => Ok(Packet {
+                id: PacketId([*id0, *id1]),
+                payload: *payload,
+            }),
+            _ => Err(DdsError::PreconditionNotMet("Invalid packet".to_string())),
+        }
+    }
+}
+
+impl DdsSerialize for Packet {}
+
+impl DdsDeserialize<'_> for Packet {}
+
+#[test]
+fn custom_representation_samples_serialize_their_own_key() {
+    let samples = [
+        Packet {
+            id: PacketId([1, 2]),
+            payload: 3,
+        },
+        Packet {
+            id: PacketId([4, 5]),
+            payload: 6,
+        },
+    ];
+
+    let received = write_and_read_samples("Packet", &samples);
+
+    assert_eq!(received.len(), 2);
+    assert_eq!(received[0].data.as_ref(), Some(&samples[0]));
+    assert_eq!(
+        received[0].sample_info.instance_handle,
+        InstanceHandle::new([1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])
+    );
+    assert_eq!(
+        received[1].sample_info.instance_handle,
+        dds_key_hash(&samples[1]).unwrap()
+    );
+    assert_eq!(
+        dds_serialize_key_to_bytes(&samples[1]).unwrap().as_ref(),
+        &[0, 1, 0, 0, 4, 5][..]
+    );
+}
+
+// Type with a custom representation which does not implement its conversion to bytes
+#[derive(DdsHasKey, DdsGetKey, DdsSetKeyFields)]
+struct Unconvertible {
+    _value: u8,
+}
+
+impl DdsRepresentation for Unconvertible {
+    const REPRESENTATION: Representation = Representation::Custom;
+}
+
+impl DdsSerialize for Unconvertible {}
+
+#[test]
+fn custom_representation_samples_without_conversion_are_not_written() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic(
+            "MyTopic",
+            "Unconvertible",
+            QosKind::Default,
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(
+        writer.write(&Unconvertible { _value: 1 }, None),
+        Err(DdsError::Unsupported)
+    );
+}
+
+#[test]
+fn read_only_unread_samples() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let participant = DomainParticipantFactory::get_instance()
@@ -680,29 +1006,200 @@ fn take_next_sample() {
     wait_set
         .attach_condition(Condition::StatusCondition(cond))
         .unwrap();
-    wait_set.wait(Duration::new(5, 0)).unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
 
     let data1 = KeyedData { id: 1, value: 1 };
     let data2 = KeyedData { id: 2, value: 10 };
     let data3 = KeyedData { id: 3, value: 20 };
+    let data4 = KeyedData { id: 4, value: 30 };
+    let data5 = KeyedData { id: 5, value: 40 };
 
     writer.write(&data1, None).unwrap();
     writer.write(&data2, None).unwrap();
     writer.write(&data3, None).unwrap();
+    writer.write(&data4, None).unwrap();
+    writer.write(&data5, None).unwrap();
 
     writer
-        .wait_for_acknowledgments(Duration::new(1, 0))
+        .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
-    assert_eq!(reader.take_next_sample().unwrap().data.unwrap(), data1);
-    assert_eq!(reader.take_next_sample().unwrap().data.unwrap(), data2);
-    assert_eq!(reader.take_next_sample().unwrap().data.unwrap(), data3);
-    assert_eq!(reader.take_next_sample(), Err(DdsError::NoData));
-}
+    let samples1 = reader
+        .read(
+            3,
+            &[SampleStateKind::NotRead],
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
 
-#[test]
-fn each_key_sample_is_read() {
-    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let samples2 = reader
+        .read(
+            3,
+            &[SampleStateKind::NotRead],
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+
+    let samples3 = reader.read(
+        3,
+        &[SampleStateKind::NotRead],
+        ANY_VIEW_STATE,
+        ANY_INSTANCE_STATE,
+    );
+
+    assert_eq!(samples1.len(), 3);
+    assert_eq!(samples1[0].data.as_ref().unwrap(), &data1);
+    assert_eq!(samples1[1].data.as_ref().unwrap(), &data2);
+    assert_eq!(samples1[2].data.as_ref().unwrap(), &data3);
+
+    assert_eq!(samples2.len(), 2);
+    assert_eq!(samples2[0].data.as_ref().unwrap(), &data4);
+    assert_eq!(samples2[1].data.as_ref().unwrap(), &data5);
+
+    assert_eq!(samples3, Err(DdsError::NoData));
+}
+
+#[test]
+fn read_next_sample() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 10 };
+    let data3 = KeyedData { id: 3, value: 20 };
+
+    writer.write(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
+    writer.write(&data3, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    assert_eq!(reader.read_next_sample().unwrap().data.unwrap(), data1);
+    assert_eq!(reader.read_next_sample().unwrap().data.unwrap(), data2);
+    assert_eq!(reader.read_next_sample().unwrap().data.unwrap(), data3);
+    assert_eq!(reader.read_next_sample(), Err(DdsError::NoData));
+}
+
+#[test]
+fn take_next_sample() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 10 };
+    let data3 = KeyedData { id: 3, value: 20 };
+
+    writer.write(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
+    writer.write(&data3, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(1, 0))
+        .unwrap();
+
+    assert_eq!(reader.take_next_sample().unwrap().data.unwrap(), data1);
+    assert_eq!(reader.take_next_sample().unwrap().data.unwrap(), data2);
+    assert_eq!(reader.take_next_sample().unwrap().data.unwrap(), data3);
+    assert_eq!(reader.take_next_sample(), Err(DdsError::NoData));
+}
+
+#[test]
+fn each_key_sample_is_read() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
 
     let participant = DomainParticipantFactory::get_instance()
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
@@ -770,19 +1267,19 @@ fn each_key_sample_is_read() {
     assert_eq!(samples[0].data.as_ref().unwrap(), &data1);
     assert_eq!(
         samples[0].sample_info.instance_handle,
-        dds_serialize_key(&data1).unwrap().into(),
+        dds_key_hash(&data1).unwrap(),
     );
 
     assert_eq!(samples[1].data.as_ref().unwrap(), &data2);
     assert_eq!(
         samples[1].sample_info.instance_handle,
-        dds_serialize_key(&data2).unwrap().into(),
+        dds_key_hash(&data2).unwrap(),
     );
 
     assert_eq!(samples[2].data.as_ref().unwrap(), &data3);
     assert_eq!(
         samples[2].sample_info.instance_handle,
-        dds_serialize_key(&data3).unwrap().into(),
+        dds_key_hash(&data3).unwrap(),
     );
 }
 
@@ -851,7 +1348,7 @@ fn read_specific_instance() {
     let samples = reader
         .read_instance(
             3,
-            dds_serialize_key(&data1).unwrap().into(),
+            dds_key_hash(&data1).unwrap(),
             ANY_SAMPLE_STATE,
             ANY_VIEW_STATE,
             ANY_INSTANCE_STATE,
@@ -1139,7 +1636,7 @@ fn take_specific_instance() {
     let samples = reader
         .take_instance(
             3,
-            dds_serialize_key(&data1).unwrap().into(),
+            dds_key_hash(&data1).unwrap(),
             ANY_SAMPLE_STATE,
             ANY_VIEW_STATE,
             ANY_INSTANCE_STATE,
@@ -1150,6 +1647,118 @@ fn take_specific_instance() {
     assert_eq!(samples[0].data.as_ref().unwrap(), &data1);
 }
 
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+struct ShortKeyData {
+    #[key]
+    id: u32,
+    #[key]
+    index: u8,
+    value: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+struct LongKeyData {
+    #[key]
+    id: u8,
+    #[key]
+    index: u64,
+    #[key]
+    sub_index: u32,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+struct StringKeyData {
+    #[key]
+    name: String,
+}
+
+#[test]
+fn key_hash_is_padded_or_hashed_big_endian_key() {
+    assert_eq!(ShortKeyData::MAX_KEY_SIZE, Some(5));
+    assert_eq!(
+        <[u8; 16]>::from(
+            dds_key_hash(&ShortKeyData {
+                id: 1,
+                index: 2,
+                value: vec![1; 20],
+            })
+            .unwrap()
+        ),
+        [0, 0, 0, 1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+    );
+
+    // The maximum size of the key is larger than 16 bytes even if the actual key is not
+    assert_eq!(LongKeyData::MAX_KEY_SIZE, Some(20));
+    assert_eq!(
+        <[u8; 16]>::from(
+            dds_key_hash(&LongKeyData {
+                id: 1,
+                index: 2,
+                sub_index: 3,
+            })
+            .unwrap()
+        ),
+        <[u8; 16]>::from(md5::compute([
+            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 3
+        ]))
+    );
+
+    assert_eq!(StringKeyData::MAX_KEY_SIZE, None);
+    assert_eq!(
+        <[u8; 16]>::from(
+            dds_key_hash(&StringKeyData {
+                name: String::from("a"),
+            })
+            .unwrap()
+        ),
+        <[u8; 16]>::from(md5::compute([0, 0, 0, 2, b'a', 0]))
+    );
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+struct InnerKey {
+    #[key]
+    index: u8,
+    #[key]
+    sub_index: u64,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+struct NestedKeyData {
+    #[key]
+    id: u8,
+    #[key]
+    inner: InnerKey,
+    value: u32,
+}
+
+#[test]
+fn nested_key_is_padded_from_its_offset() {
+    let data = NestedKeyData {
+        id: 1,
+        inner: InnerKey {
+            index: 2,
+            sub_index: 3,
+        },
+        value: 4,
+    };
+
+    // The nested key starts at offset 1 so its u64 ends at offset 16
+    assert_eq!(NestedKeyData::MAX_KEY_SIZE, Some(16));
+    assert_eq!(
+        <[u8; 16]>::from(dds_key_hash(&data).unwrap()),
+        [1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3]
+    );
+    // The disposed samples received without key hash carry the little-endian serialized key
+    assert_eq!(
+        NestedKeyData::serialized_key_hash(
+            dds_serialize_key_to_bytes(&data).unwrap().as_ref(),
+            None
+        ),
+        dds_key_hash(&data)
+    );
+}
+
 #[test]
 fn take_specific_unknown_instance() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -1227,11 +1836,10 @@ fn take_specific_unknown_instance() {
 }
 
 #[test]
-fn write_read_disposed_samples() {
+fn reader_lookup_instance_and_get_key_value() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
-    let participant_factory = DomainParticipantFactory::get_instance();
 
-    let participant = participant_factory
+    let participant = DomainParticipantFactory::get_instance()
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
 
@@ -1247,9 +1855,6 @@ fn write_read_disposed_samples() {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         ..Default::default()
     };
     let writer = publisher
@@ -1264,12 +1869,8 @@ fn write_read_disposed_samples() {
             kind: ReliabilityQosPolicyKind::Reliable,
             max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
         },
-        history: HistoryQosPolicy {
-            kind: HistoryQosPolicyKind::KeepAll,
-        },
         ..Default::default()
     };
-
     let reader = subscriber
         .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
@@ -1285,31 +1886,41 @@ fn write_read_disposed_samples() {
     wait_set.wait(Duration::new(10, 0)).unwrap();
 
     let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 10 };
 
     writer.write(&data1, None).unwrap();
-    writer.dispose(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
 
     writer
         .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
 
     let samples = reader
-        .read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
         .unwrap();
-
-    assert_eq!(samples.len(), 2);
-    assert_eq!(
-        samples[0].sample_info.instance_state,
-        InstanceStateKind::NotAliveDisposed
-    );
+    let handle = reader.lookup_instance(&data1).unwrap().unwrap();
+    assert_eq!(handle, samples[0].sample_info.instance_handle);
     assert_eq!(
-        samples[1].sample_info.instance_state,
-        InstanceStateKind::NotAliveDisposed
+        reader
+            .lookup_instance(&KeyedData { id: 99, value: 1 })
+            .unwrap(),
+        None
     );
+
+    let mut key_holder = KeyedData { id: 0, value: 50 };
+    reader.get_key_value(&mut key_holder, handle).unwrap();
+    assert_eq!(key_holder, KeyedData { id: 1, value: 50 });
+
+    let writer_handle = writer.lookup_instance(&data2).unwrap().unwrap();
+    let mut key_holder = KeyedData { id: 0, value: 50 };
+    writer
+        .get_key_value(&mut key_holder, writer_handle)
+        .unwrap();
+    assert_eq!(key_holder, KeyedData { id: 2, value: 50 });
 }
 
 #[test]
-fn write_read_sample_view_state() {
+fn write_read_disposed_samples() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
     let participant_factory = DomainParticipantFactory::get_instance();
 
@@ -1318,7 +1929,198 @@ fn write_read_sample_view_state() {
         .unwrap();
 
     let topic = participant
-        .create_topic("OtherTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = KeyedData { id: 1, value: 1 };
+
+    writer.write(&data1, None).unwrap();
+    writer.dispose(&data1, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(
+        samples[0].sample_info.instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
+    assert_eq!(
+        samples[1].sample_info.instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
+}
+
+#[test]
+fn reader_autopurges_disposed_instances() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reader_data_lifecycle: ReaderDataLifecycleQosPolicy {
+            autopurge_disposed_samples_delay: DurationKind::Finite(Duration::new(0, 500_000_000)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 2 };
+
+    writer.write(&data1, None).unwrap();
+    writer.write(&data2, None).unwrap();
+    writer.dispose(&data1, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 3);
+
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    let samples = reader
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data.as_ref().unwrap(), &data2);
+
+    let next_instance_samples = reader
+        .read_next_instance(
+            10,
+            None,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+    assert_eq!(next_instance_samples.len(), 1);
+    assert_eq!(next_instance_samples[0].data.as_ref().unwrap(), &data2);
+    assert_eq!(
+        reader.read_next_instance(
+            10,
+            Some(next_instance_samples[0].sample_info.instance_handle),
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        ),
+        Err(DdsError::NoData)
+    );
+}
+
+#[test]
+fn write_read_sample_view_state() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("OtherTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
         .unwrap();
 
     let publisher = participant
@@ -2002,7 +2804,7 @@ fn data_reader_publication_handle_sample_info() {
         ..Default::default()
     };
     let reader = subscriber
-        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .create_datareader::<UserData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
         .unwrap();
 
     let cond = writer.get_statuscondition().unwrap();
@@ -2304,77 +3106,1250 @@ fn transient_local_writer_does_not_deliver_lifespan_expired_data() {
 }
 
 #[test]
-#[ignore = "Listener calls are async and don't guarantee order"]
-fn best_effort_should_receive_all_samples_in_order_if_perfect_wire() {
-    struct Listener {
-        sender: std::sync::mpsc::SyncSender<()>,
-        data_sample_list: std::vec::IntoIter<KeyedData>,
-    }
-    impl DataReaderListener for Listener {
-        type Foo = KeyedData;
-
-        fn on_data_available(&mut self, the_reader: &DataReader<Self::Foo>) {
-            if let Ok(samples) =
-                the_reader.take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
-            {
-                let sample = samples[0].data.as_ref().unwrap();
-                let sample_expected = self.data_sample_list.next().unwrap();
-                assert_eq!(sample, &sample_expected);
-                if self.data_sample_list.len() == 0 {
-                    self.sender.send(()).unwrap();
-                }
-            }
-        }
-    }
-
+fn reader_removes_lifespan_expired_data() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
-    let participant_factory = DomainParticipantFactory::get_instance();
-    let participant = participant_factory
+
+    let participant = DomainParticipantFactory::get_instance()
         .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
         .unwrap();
+
     let topic = participant
-        .create_topic("TestTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
-        .unwrap();
-    let subscriber = participant
-        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
         .unwrap();
 
-    let (sender, receiver) = std::sync::mpsc::sync_channel(0);
-
-    let data_sample_list = (1..=10)
-        .map(|value| KeyedData { id: 1, value })
-        .collect::<Vec<_>>();
-    let listener = Box::new(Listener {
-        sender,
-        data_sample_list: data_sample_list.clone().into_iter(),
-    });
-    let _reader = subscriber
-        .create_datareader(
-            &topic,
-            QosKind::Default,
-            Some(listener),
-            &[StatusKind::DataAvailable],
-        )
-        .unwrap();
     let publisher = participant
         .create_publisher(QosKind::Default, None, NO_STATUS)
         .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        lifespan: LifespanQosPolicy {
+            duration: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
     let writer = publisher
-        .create_datawriter(&topic, QosKind::Default, None, NO_STATUS)
-        .unwrap();
-    let writer_cond = writer.get_statuscondition().unwrap();
-    writer_cond
-        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
         .unwrap();
-    let mut wait_set = WaitSet::new();
-    wait_set
-        .attach_condition(Condition::StatusCondition(writer_cond))
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
         .unwrap();
-    wait_set.wait(Duration::new(60, 0)).unwrap();
-    for data_sample in data_sample_list {
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    writer.write(&data1, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(1, 0))
+        .unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(1500));
+
+    assert_eq!(
+        reader.read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+}
+
+#[test]
+fn reliable_writer_sends_change_following_lifespan_expired_change() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        lifespan: LifespanQosPolicy {
+            duration: DurationKind::Finite(Duration::new(10, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 2, value: 2 };
+    let data3 = KeyedData { id: 3, value: 3 };
+    writer.write(&data1, None).unwrap();
+    writer
+        .write_w_timestamp(&data2, None, Time::new(0, 0))
+        .unwrap();
+    writer.write(&data3, None).unwrap();
+
+    // No further write triggers the writer so the change following the GAP must be sent with it
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data.as_ref().unwrap(), &data1);
+    assert_eq!(samples[1].data.as_ref().unwrap(), &data3);
+}
+
+#[test]
+fn reliable_reader_reports_changes_skipped_by_gap_as_lost() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        lifespan: LifespanQosPolicy {
+            duration: DurationKind::Finite(Duration::new(10, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 1, value: 2 };
+    let data3 = KeyedData { id: 1, value: 3 };
+    writer.write(&data1, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+    // The second sample expires in the writer before it is sent so it is announced with a GAP
+    writer
+        .write_w_timestamp(&data2, None, Time::new(0, 0))
+        .unwrap();
+    writer.write(&data3, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(5, 0))
+        .unwrap();
+
+    let cond = reader.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::SampleLost])
+        .unwrap();
+    let mut reader_wait_set = WaitSet::new();
+    reader_wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    reader_wait_set.wait(Duration::new(5, 0)).unwrap();
+
+    let status = reader.get_sample_lost_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(status.total_count_change, 1);
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data.as_ref().unwrap(), &data1);
+    assert_eq!(samples[1].data.as_ref().unwrap(), &data3);
+}
+
+#[test]
+fn best_effort_reader_reports_skipped_changes_as_lost() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        lifespan: LifespanQosPolicy {
+            duration: DurationKind::Finite(Duration::new(10, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::BestEffort,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let writer_cond = writer.get_statuscondition().unwrap();
+    writer_cond
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let reader_cond = reader.get_statuscondition().unwrap();
+    reader_cond
+        .set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    for cond in [writer_cond, reader_cond] {
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(cond))
+            .unwrap();
+        wait_set.wait(Duration::new(5, 0)).unwrap();
+    }
+
+    let data1 = KeyedData { id: 1, value: 1 };
+    let data2 = KeyedData { id: 1, value: 2 };
+    let data3 = KeyedData { id: 1, value: 3 };
+    let data4 = KeyedData { id: 1, value: 4 };
+    writer.write(&data1, None).unwrap();
+    writer
+        .write_w_timestamp(&data2, None, Time::new(0, 0))
+        .unwrap();
+    writer.write(&data3, None).unwrap();
+    writer.write(&data4, None).unwrap();
+
+    let cond = reader.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::DataAvailable])
+        .unwrap();
+    let mut reader_wait_set = WaitSet::new();
+    reader_wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+
+    let mut samples = Vec::new();
+    while samples.len() < 3 {
+        reader_wait_set.wait(Duration::new(5, 0)).unwrap();
+        if let Ok(taken) = reader.take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE) {
+            samples.extend(taken.into_iter().map(|s| s.data.unwrap()));
+        }
+    }
+
+    // The expired change is skipped with a GAP and the change following it is still delivered
+    assert_eq!(samples, vec![data1, data3, data4]);
+    assert_eq!(reader.get_sample_lost_status().unwrap().total_count, 1);
+}
+
+#[test]
+#[ignore = "Listener calls are async and don't guarantee order"]
+fn best_effort_should_receive_all_samples_in_order_if_perfect_wire() {
+    struct Listener {
+        sender: std::sync::mpsc::SyncSender<()>,
+        data_sample_list: std::vec::IntoIter<KeyedData>,
+    }
+    impl DataReaderListener for Listener {
+        type Foo = KeyedData;
+
+        fn on_data_available(&mut self, the_reader: &DataReader<Self::Foo>) {
+            if let Ok(samples) =
+                the_reader.take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            {
+                let sample = samples[0].data.as_ref().unwrap();
+                let sample_expected = self.data_sample_list.next().unwrap();
+                assert_eq!(sample, &sample_expected);
+                if self.data_sample_list.len() == 0 {
+                    self.sender.send(()).unwrap();
+                }
+            }
+        }
+    }
+
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant_factory = DomainParticipantFactory::get_instance();
+    let participant = participant_factory
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic("TestTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let (sender, receiver) = std::sync::mpsc::sync_channel(0);
+
+    let data_sample_list = (1..=10)
+        .map(|value| KeyedData { id: 1, value })
+        .collect::<Vec<_>>();
+    let listener = Box::new(Listener {
+        sender,
+        data_sample_list: data_sample_list.clone().into_iter(),
+    });
+    let _reader = subscriber
+        .create_datareader(
+            &topic,
+            QosKind::Default,
+            Some(listener),
+            &[StatusKind::DataAvailable],
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_cond = writer.get_statuscondition().unwrap();
+    writer_cond
+        .set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(writer_cond))
+        .unwrap();
+    wait_set.wait(Duration::new(60, 0)).unwrap();
+    for data_sample in data_sample_list {
         writer.write(&data_sample, None).unwrap();
     }
-    receiver
-        .recv_timeout(std::time::Duration::from_secs(10))
+    receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .unwrap();
+}
+
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    serde::Serialize,
+    serde::Deserialize,
+    DdsHasKey,
+    DdsGetKey,
+    DdsSetKeyFields,
+    DdsRepresentation,
+)]
+#[dust_dds(extensibility = "mutable")]
+struct MutableData {
+    #[key]
+    id: u8,
+    name: String,
+    values: Vec<KeyedData>,
+}
+
+#[test]
+fn xcdr2_samples_are_written_and_read() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "MutableData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<MutableData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    assert_eq!(
+        writer.get_qos().unwrap().representation,
+        DataRepresentationQosPolicy {
+            value: vec![XCDR2_DATA_REPRESENTATION]
+        }
+    );
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data = MutableData {
+        id: 1,
+        name: String::from("xcdr2"),
+        values: vec![KeyedData { id: 2, value: 3 }, KeyedData { id: 4, value: 5 }],
+    };
+    writer.write(&data, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples[0].data.as_ref().unwrap(), &data);
+}
+
+#[test]
+fn writer_with_data_representation_not_accepted_by_reader_does_not_match() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        representation: DataRepresentationQosPolicy {
+            value: vec![XCDR2_DATA_REPRESENTATION],
+        },
+        ..Default::default()
+    };
+    let _writer = publisher
+        .create_datawriter::<KeyedData>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        representation: DataRepresentationQosPolicy {
+            value: vec![XCDR_DATA_REPRESENTATION],
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = reader.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::RequestedIncompatibleQos])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let status = reader.get_requested_incompatible_qos_status().unwrap();
+    assert_eq!(status.total_count, 1);
+    assert_eq!(status.last_policy_id, DATAREPRESENTATION_QOS_POLICY_ID);
+    assert!(reader.get_matched_publications().unwrap().is_empty());
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+#[dust_dds(extensibility = "mutable")]
+struct ShapeV1 {
+    #[key]
+    id: u8,
+    #[dust_dds(id = 10)]
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+#[dust_dds(extensibility = "mutable")]
+struct ShapeV2 {
+    #[key]
+    id: u8,
+    #[dust_dds(id = 20, optional)]
+    color: Option<String>,
+    #[dust_dds(id = 10)]
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn samples_of_newer_mutable_type_are_read_by_older_type() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "Shape", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    // Each participant binds the "Shape" type name to a single type
+    let reader_participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_topic = reader_participant
+        .create_topic("MyTopic", "Shape", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<ShapeV2>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = reader_participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<ShapeV1>(
+            &reader_topic,
+            QosKind::Specific(reader_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data = ShapeV2 {
+        id: 1,
+        color: Some(String::from("BLUE")),
+        x: 2,
+        y: 3,
+    };
+    writer.write(&data, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(
+        samples[0].data.as_ref().unwrap(),
+        &ShapeV1 { id: 1, x: 2, y: 3 }
+    );
+}
+
+#[test]
+fn samples_of_older_mutable_type_are_read_by_newer_type() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "Shape", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    // Each participant binds the "Shape" type name to a single type
+    let reader_participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_topic = reader_participant
+        .create_topic("MyTopic", "Shape", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<ShapeV1>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = reader_participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<ShapeV2>(
+            &reader_topic,
+            QosKind::Specific(reader_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    writer.write(&ShapeV1 { id: 1, x: 2, y: 3 }, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(
+        samples[0].data.as_ref().unwrap(),
+        &ShapeV2 {
+            id: 1,
+            color: None,
+            x: 2,
+            y: 3,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+#[dust_dds(extensibility = "mutable")]
+struct ShapeWithTextCoordinates {
+    #[key]
+    id: u8,
+    #[dust_dds(id = 10)]
+    x: String,
+    y: String,
+}
+
+#[test]
+fn writer_with_type_not_assignable_to_reader_type_does_not_match() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "Shape", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    // Each participant binds the "Shape" type name to a single type
+    let reader_participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_topic = reader_participant
+        .create_topic("MyTopic", "Shape", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter::<ShapeWithTextCoordinates>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = reader_participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<ShapeV1>(&reader_topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let cond = reader_topic.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::InconsistentTopic])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    assert!(
+        reader_topic
+            .get_inconsistent_topic_status()
+            .unwrap()
+            .total_count
+            > 0
+    );
+    assert!(reader.get_matched_publications().unwrap().is_empty());
+    assert!(writer.get_matched_subscriptions().unwrap().is_empty());
+}
+
+#[test]
+fn dynamic_data_samples_are_exchanged_with_compiled_type() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let dynamic_participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let typed_participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let keyed_data_type = DynamicTypeBuilder::new_struct("KeyedData")
+        .add_key_member("id", DynamicType::primitive(TypeKind::UInt8).unwrap())
+        .add_member("value", DynamicType::primitive(TypeKind::UInt8).unwrap())
+        .build()
+        .unwrap();
+    keyed_data_type
+        .register_type(&dynamic_participant, "KeyedData")
+        .unwrap();
+
+    let dynamic_topic = dynamic_participant
+        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let typed_topic = typed_participant
+        .create_topic("MyTopic", "KeyedData", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = dynamic_participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datawriter::<DynamicData>(
+            &dynamic_topic,
+            QosKind::Specific(writer_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let dynamic_reader = dynamic_participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datareader::<DynamicData>(
+            &dynamic_topic,
+            QosKind::Specific(reader_qos.clone()),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+    let typed_reader = typed_participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datareader::<KeyedData>(
+            &typed_topic,
+            QosKind::Specific(reader_qos),
+            None,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    while writer.get_matched_subscriptions().unwrap().len() < 2 {
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    let mut data = DynamicData::new(&keyed_data_type).unwrap();
+    data.set_value("id", 1_u8).unwrap();
+    data.set_value("value", 10_u8).unwrap();
+    writer.write(&data, None).unwrap();
+    data.set_value("id", 2_u8).unwrap();
+    data.set_value("value", 20_u8).unwrap();
+    writer.write(&data, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let typed_samples = typed_reader
+        .take(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        typed_samples[0].data.as_ref().unwrap(),
+        &KeyedData { id: 1, value: 10 }
+    );
+    assert_eq!(
+        typed_samples[1].data.as_ref().unwrap(),
+        &KeyedData { id: 2, value: 20 }
+    );
+    assert_ne!(
+        typed_samples[0].sample_info.instance_handle,
+        typed_samples[1].sample_info.instance_handle
+    );
+
+    let dynamic_samples = dynamic_reader
+        .take(2, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(dynamic_samples[1].data.as_ref().unwrap(), &data);
+    assert_eq!(
+        dynamic_samples[0].data.as_ref().unwrap().get_value("value"),
+        Ok(&DynamicValue::UInt8(10))
+    );
+    assert_eq!(
+        dynamic_samples[0].sample_info.instance_handle,
+        typed_samples[0].sample_info.instance_handle
+    );
+}
+
+#[test]
+fn dynamic_data_writer_of_type_without_key_members_has_no_key() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let counter_type = DynamicTypeBuilder::new_struct("Counter")
+        .add_member("value", DynamicType::primitive(TypeKind::UInt8).unwrap())
+        .build()
+        .unwrap();
+    counter_type.register_type(&participant, "Counter").unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "Counter", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datawriter::<DynamicData>(&topic, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let mut data = DynamicData::new(&counter_type).unwrap();
+    data.set_value("value", 1_u8).unwrap();
+    writer.write(&data, None).unwrap();
+
+    assert_eq!(
+        writer.unregister_instance(&data, None),
+        Err(DdsError::IllegalOperation)
+    );
+}
+
+#[test]
+fn dynamic_data_loaned_samples_are_read_with_xcdr2_representation() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let point_type = DynamicTypeBuilder::new_struct("Point")
+        .add_member("x", DynamicType::primitive(TypeKind::Int32).unwrap())
+        .add_member("y", DynamicType::primitive(TypeKind::Int32).unwrap())
+        .build()
+        .unwrap();
+    let line_type = DynamicTypeBuilder::new_struct("Line")
+        .add_member("start", point_type.clone())
+        .add_member("end", point_type.clone())
+        .add_key_member("id", DynamicType::primitive(TypeKind::UInt8).unwrap())
+        .build()
+        .unwrap();
+    line_type.register_type(&participant, "Line").unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", "Line", QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        representation: DataRepresentationQosPolicy {
+            value: vec![XCDR2_DATA_REPRESENTATION],
+        },
+        ..Default::default()
+    };
+    let writer = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datawriter::<DynamicData>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        representation: DataRepresentationQosPolicy {
+            value: vec![XCDR2_DATA_REPRESENTATION],
+        },
+        ..Default::default()
+    };
+    let reader = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap()
+        .create_datareader::<DynamicData>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let mut end = DynamicData::new(&point_type).unwrap();
+    end.set_value("x", 3).unwrap();
+    end.set_value("y", -4).unwrap();
+    let mut data = DynamicData::new(&line_type).unwrap();
+    data.set_value("end", end).unwrap();
+    data.set_value("id", 1_u8).unwrap();
+    writer.write(&data, None).unwrap();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take_loaned(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(&samples[0].serialized_data().unwrap()[0..2], &[0x00, 0x07]);
+    assert_eq!(samples[0].data().unwrap(), Some(data));
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+#[key]
+#[dust_dds(extensibility = "final")]
+enum UnionData {
+    Value(u32),
+    Text { text: String, count: u16 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, DdsType)]
+#[dust_dds(serialize_as_discriminant)]
+enum Color {
+    Red = 1,
+    Blue = 10,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsType)]
+struct ColorData {
+    #[key]
+    color: Color,
+    value: u8,
+}
+
+fn write_and_read_samples<Foo>(type_name: &str, samples: &[Foo]) -> Vec<Sample<Foo>>
+where
+    Foo: DdsHasKey
+        + DdsGetKey
+        + DdsSetKeyFields
+        + DdsSerialize
+        + for<'de> DdsDeserialize<'de>
+        + Send
+        + 'static,
+{
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic("MyTopic", type_name, QosKind::Default, None, NO_STATUS)
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<Foo>(&topic, QosKind::Specific(writer_qos), None, NO_STATUS)
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, None, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<Foo>(&topic, QosKind::Specific(reader_qos), None, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition().unwrap();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    for sample in samples {
+        writer.write(sample, None).unwrap();
+    }
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
         .unwrap();
+
+    reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()
+}
+
+#[test]
+fn union_samples_are_written_and_read() {
+    let samples = [
+        UnionData::Value(7),
+        UnionData::Text {
+            text: String::from("seven"),
+            count: 7,
+        },
+        UnionData::Value(8),
+    ];
+
+    let received = write_and_read_samples("UnionData", &samples);
+
+    assert_eq!(received.len(), 3);
+    assert_eq!(received[0].data.as_ref(), Some(&samples[0]));
+    assert_eq!(received[1].data.as_ref(), Some(&samples[1]));
+    assert_eq!(received[2].data.as_ref(), Some(&samples[2]));
+    // The discriminator is the key so both values are samples of the same instance
+    assert_eq!(
+        received[0].sample_info.instance_handle,
+        received[2].sample_info.instance_handle
+    );
+    assert_ne!(
+        received[0].sample_info.instance_handle,
+        received[1].sample_info.instance_handle
+    );
+}
+
+#[test]
+fn samples_with_enum_key_are_written_and_read() {
+    let samples = [
+        ColorData {
+            color: Color::Blue,
+            value: 1,
+        },
+        ColorData {
+            color: Color::Red,
+            value: 2,
+        },
+    ];
+
+    let received = write_and_read_samples("ColorData", &samples);
+
+    assert_eq!(received.len(), 2);
+    assert_eq!(received[0].data.as_ref(), Some(&samples[0]));
+    assert_eq!(received[1].data.as_ref(), Some(&samples[1]));
+    assert_ne!(
+        received[0].sample_info.instance_handle,
+        received[1].sample_info.instance_handle
+    );
+    assert_eq!(Color::Blue.get_key(), 10);
+}
+
+#[test]
+fn built_in_type_samples_are_written_and_read() {
+    let strings = [
+        KeyedString::new("first", "one"),
+        KeyedString::new("second", "two"),
+        KeyedString::new("first", "three"),
+    ];
+    let bytes = [KeyedBytes::new("first", vec![1, 2, 3])];
+
+    let received_strings = write_and_read_samples(KeyedString::get_type_name(), &strings);
+    let received_bytes = write_and_read_samples(KeyedBytes::get_type_name(), &bytes);
+
+    assert_eq!(received_strings.len(), 3);
+    assert_eq!(received_strings[0].data.as_ref(), Some(&strings[0]));
+    assert_eq!(received_strings[1].data.as_ref(), Some(&strings[1]));
+    assert_eq!(received_strings[2].data.as_ref(), Some(&strings[2]));
+    assert_eq!(
+        received_strings[0].sample_info.instance_handle,
+        received_strings[2].sample_info.instance_handle
+    );
+    assert_eq!(received_bytes.len(), 1);
+    assert_eq!(received_bytes[0].data.as_ref(), Some(&bytes[0]));
+}
+
+#[test]
+fn samples_are_rendered_as_json() {
+    let samples = [from_json_value::<KeyedData>(serde_json::json!({"id": 1, "value": 7})).unwrap()];
+
+    let received = write_and_read_samples("KeyedData", &samples);
+    let value = received[0].to_json_value().unwrap();
+
+    assert_eq!(value["data"], serde_json::json!({"id": 1, "value": 7}));
+    assert_eq!(value["key"], serde_json::json!({"id": 1}));
+    assert_eq!(value["sample_info"]["sample_state"], "NotRead");
+    assert_eq!(value["sample_info"]["instance_state"], "Alive");
+    assert_eq!(value["sample_info"]["valid_data"], true);
 }
@@ -1,6 +1,9 @@
 use proc_macro::TokenStream;
-use quote::{quote, quote_spanned};
-use syn::{parse_macro_input, spanned::Spanned, DeriveInput, Field};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Attribute, DataEnum, DataStruct, DeriveInput,
+    Expr, ExprLit, ExprUnary, Field, Fields, Lit, Meta, NestedMeta, Type, UnOp,
+};
 
 #[proc_macro_derive(DdsHasKey, attributes(key))]
 pub fn derive_dds_has_key(input: TokenStream) -> TokenStream {
@@ -12,13 +15,24 @@ pub fn derive_dds_has_key(input: TokenStream) -> TokenStream {
 
         let has_key = struct_data.fields.iter().any(field_has_key_attribute);
 
+        quote! {
+            impl #impl_generics dust_dds::topic_definition::type_support::DdsHasKey for #ident #type_generics #where_clause {
+                const HAS_KEY: bool = #has_key;
+            }
+        }
+    } else if let syn::Data::Enum(_) = &input.data {
+        let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+        let ident = &input.ident;
+
+        let has_key = has_key_attribute(&input.attrs);
+
         quote! {
             impl #impl_generics dust_dds::topic_definition::type_support::DdsHasKey for #ident #type_generics #where_clause {
                 const HAS_KEY: bool = #has_key;
             }
         }
     } else {
-        quote_spanned!{input.span() => compile_error!("DdsHasKey can only be derived for structs");}
+        quote_spanned!{input.span() => compile_error!("DdsHasKey can only be derived for structs and enums");}
     }
     .into()
 }
@@ -39,13 +53,27 @@ pub fn derive_dds_get_key(input: TokenStream) -> TokenStream {
 
                 let mut borrowed_key_holder_fields = quote!{};
                 let mut borrowed_key_holder_field_assignment = quote!{};
+                let mut max_key_end = quote!{let end = Some(offset);};
+                let mut key_alignment = quote!{let alignment = 1;};
 
                 for key_field in key_fields {
                     let field_ident = &key_field.ident;
                     let field_type = &key_field.ty;
                     borrowed_key_holder_fields.extend(quote!{#field_ident: <#field_type as dust_dds::topic_definition::type_support::DdsGetKey>::BorrowedKeyHolder<'a>,});
                     borrowed_key_holder_field_assignment.extend(quote!{#field_ident: self.#field_ident.get_key(),});
-
+                    max_key_end.extend(quote!{
+                        let end = dust_dds::topic_definition::type_support::append_max_key_end(
+                            end,
+                            <#field_type as dust_dds::topic_definition::type_support::DdsGetKey>::MAX_KEY_ENDS,
+                        );
+                    });
+                    key_alignment.extend(quote!{
+                        let alignment = if <#field_type as dust_dds::topic_definition::type_support::DdsGetKey>::KEY_ALIGNMENT > alignment {
+                            <#field_type as dust_dds::topic_definition::type_support::DdsGetKey>::KEY_ALIGNMENT
+                        } else {
+                            alignment
+                        };
+                    });
                 }
 
 
@@ -60,6 +88,23 @@ pub fn derive_dds_get_key(input: TokenStream) -> TokenStream {
                         impl #impl_generics dust_dds::topic_definition::type_support::DdsGetKey for #ident #type_generics #where_clause {
                             type BorrowedKeyHolder<'a> = BorrowedKeyHolder<'a>;
 
+                            const MAX_KEY_SIZE: Option<usize> = <Self as dust_dds::topic_definition::type_support::DdsGetKey>::MAX_KEY_ENDS[0];
+                            // The padding of the nested keys depends on the offset at which this key starts
+                            const MAX_KEY_ENDS: [Option<usize>; 8] = {
+                                let mut ends = [None; 8];
+                                let mut offset = 0;
+                                while offset < 8 {
+                                    #max_key_end
+                                    ends[offset] = end;
+                                    offset += 1;
+                                }
+                                ends
+                            };
+                            const KEY_ALIGNMENT: usize = {
+                                #key_alignment
+                                alignment
+                            };
+
                             fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
                                 BorrowedKeyHolder {
                                     #borrowed_key_holder_field_assignment
@@ -74,13 +119,17 @@ pub fn derive_dds_get_key(input: TokenStream) -> TokenStream {
                     impl #impl_generics dust_dds::topic_definition::type_support::DdsGetKey for #ident #type_generics #where_clause {
                         type BorrowedKeyHolder<'a> = ();
 
+                        const MAX_KEY_SIZE: Option<usize> = Some(0);
+
                         fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {}
                     }
                 }
             }
         }
+    } else if let syn::Data::Enum(enum_data) = &input.data {
+        enum_get_key(&input, enum_data).unwrap_or_else(|err| err.to_compile_error())
     } else {
-        quote_spanned! {input.span() => compile_error!("DdsGetKey can only be derived for structs");}
+        quote_spanned! {input.span() => compile_error!("DdsGetKey can only be derived for structs and enums");}
     }
     .into()
 }
@@ -114,7 +163,7 @@ pub fn derive_dds_set_key_fields(input: TokenStream) -> TokenStream {
                 // Create the new structs and implementation inside a const to avoid name conflicts
                 quote! {
                     const _ : () = {
-                        #[derive(serde::Deserialize)]
+                        #[derive(serde::Serialize, serde::Deserialize)]
                         pub struct OwningKeyHolder {
                             #owning_key_holder_fields
                         }
@@ -139,35 +188,49 @@ pub fn derive_dds_set_key_fields(input: TokenStream) -> TokenStream {
                 }
             }
         }
+    } else if let syn::Data::Enum(enum_data) = &input.data {
+        enum_set_key_fields(&input, enum_data)
     } else {
-        quote_spanned! {input.span() => compile_error!("DdsGetKey can only be derived for structs");}
+        quote_spanned! {input.span() => compile_error!("DdsSetKeyFields can only be derived for structs and enums");}
     }
     .into()
 }
 
-#[proc_macro_derive(DdsRepresentation, attributes(key))]
+#[proc_macro_derive(DdsRepresentation, attributes(key, dust_dds))]
 pub fn derive_dds_representation(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input);
 
-    if let syn::Data::Struct(_) = &input.data {
-        let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
-        let ident = input.ident;
+    if let syn::Data::Struct(struct_data) = &input.data {
+        dds_representation(&input, struct_data).unwrap_or_else(|err| err.to_compile_error())
+    } else if let syn::Data::Enum(enum_data) = &input.data {
+        enum_dds_representation(&input, enum_data).unwrap_or_else(|err| err.to_compile_error())
+    } else {
+        quote_spanned! {input.span() => compile_error!("DdsRepresentation can only be derived for structs and enums");}
+    }.into()
+}
 
-        quote! {
-            impl #impl_generics dust_dds::topic_definition::type_support::DdsRepresentation for #ident #type_generics #where_clause {
-                const REPRESENTATION: dust_dds::topic_definition::type_support::Representation
-                    = dust_dds::topic_definition::type_support::Representation::CdrLe;
+#[proc_macro_derive(TypeSupport)]
+pub fn derive_type_support(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let ident = input.ident;
+    let type_name = ident.to_string();
+
+    quote! {
+        impl #impl_generics dust_dds::topic_definition::type_support::TypeSupport for #ident #type_generics #where_clause {
+            fn get_type_name() -> &'static str {
+                #type_name
             }
         }
-    }else {
-        quote_spanned! {input.span() => compile_error!("DdsRepresentation can only be derived for structs");}
-    }.into()
+    }
+    .into()
 }
 
-#[proc_macro_derive(DdsType, attributes(key))]
+#[proc_macro_derive(DdsType, attributes(key, dust_dds))]
 pub fn derive_dds_type(input: TokenStream) -> TokenStream {
     let mut output = TokenStream::new();
 
+    output.extend(derive_type_support(input.clone()));
     output.extend(derive_dds_representation(input.clone()));
     output.extend(derive_dds_has_key(input.clone()));
     output.extend(derive_dds_get_key(input.clone()));
@@ -177,7 +240,11 @@ pub fn derive_dds_type(input: TokenStream) -> TokenStream {
 }
 
 fn field_has_key_attribute(field: &Field) -> bool {
-    field.attrs.iter().any(|attr| {
+    has_key_attribute(&field.attrs)
+}
+
+fn has_key_attribute(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
         attr.parse_meta()
             .ok()
             .and_then(|meta| meta.path().get_ident().cloned())
@@ -185,3 +252,881 @@ fn field_has_key_attribute(field: &Field) -> bool {
             .unwrap_or(false)
     })
 }
+
+// Without the extensibility attribute the type keeps the classic CDR representation. With it the
+// type uses the XCDR2 encoding of its extensibility kind and describes its members so that they
+// are identified by the serializer with their ids and flags.
+fn dds_representation(
+    input: &DeriveInput,
+    struct_data: &DataStruct,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let extensibility = struct_extensibility(&input.attrs)?;
+    let members = member_attributes(struct_data)?;
+    let field_types: Vec<_> = struct_data.fields.iter().map(|f| &f.ty).collect();
+
+    // Only the field types depending on the type parameters are bounded since the bounds on
+    // concrete types would not be resolved for recursive types
+    let generics = bounded_generics(input, &field_types);
+
+    let extensibility = match extensibility {
+        Some(extensibility) => extensibility,
+        None => {
+            if let Some(member) = members.iter().find(|m| m.has_attributes) {
+                return Err(syn::Error::new(
+                    member.span,
+                    "member attributes require the struct extensibility to be specified with #[dust_dds(extensibility = \"...\")]",
+                ));
+            }
+            let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+            let (descriptor_impl_generics, _, descriptor_where_clause) = generics.split_for_impl();
+            // The fields are not required to implement DdsTypeDescriptor, those which don't leave the
+            // structure unbounded. The type is sized and described as a final structure. The descriptor
+            // is not collected with the struct descriptors so that the serialization of the type is not
+            // affected.
+            let descriptors = field_type_descriptors(&field_types);
+            let max_serialized_size = max_serialized_size(&descriptors, 0, 0);
+            let alignment = alignment(&descriptors, 1);
+            let type_name = ident.to_string();
+            let member_descriptors = members.iter().map(|m| {
+                let MemberAttributes { name, id, key, .. } = m;
+                quote! {
+                    dust_dds::topic_definition::type_support::MemberDescriptor {
+                        name: #name,
+                        id: #id,
+                        optional: false,
+                        must_understand: #key,
+                        key: #key,
+                        delimited_collections: None,
+                    }
+                }
+            });
+            return Ok(quote! {
+                impl #impl_generics dust_dds::topic_definition::type_support::DdsRepresentation for #ident #type_generics #where_clause {
+                    const REPRESENTATION: dust_dds::topic_definition::type_support::Representation
+                        = dust_dds::topic_definition::type_support::Representation::CdrLe;
+
+                    fn max_serialized_size() -> Option<usize> {
+                        <Self as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::MAX_SERIALIZED_SIZE
+                    }
+
+                    fn type_information() -> Option<dust_dds::topic_definition::type_object::TypeInformation> {
+                        dust_dds::topic_definition::type_object::type_information::<Self>()
+                    }
+
+                    fn minimal_type_objects() -> Vec<dust_dds::topic_definition::type_object::TypeObject> {
+                        dust_dds::topic_definition::type_object::type_objects::<Self>(
+                            dust_dds::topic_definition::type_object::EquivalenceKind::Minimal,
+                        )
+                    }
+                }
+
+                impl #descriptor_impl_generics dust_dds::topic_definition::type_support::DdsTypeDescriptor for #ident #type_generics #descriptor_where_clause {
+                    const MAX_SERIALIZED_SIZE: Option<usize> = {
+                        use dust_dds::topic_definition::type_support::UndescribedFieldType as _;
+                        #max_serialized_size
+                    };
+                    const ALIGNMENT: usize = {
+                        use dust_dds::topic_definition::type_support::UndescribedFieldType as _;
+                        #alignment
+                    };
+
+                    fn struct_descriptor() -> Option<&'static dust_dds::topic_definition::type_support::StructDescriptor> {
+                        static STRUCT_DESCRIPTOR: dust_dds::topic_definition::type_support::StructDescriptor =
+                            dust_dds::topic_definition::type_support::StructDescriptor {
+                                name: #type_name,
+                                extensibility: dust_dds::topic_definition::type_support::ExtensibilityKind::Final,
+                                members: &[#(#member_descriptors,)*],
+                            };
+                        Some(&STRUCT_DESCRIPTOR)
+                    }
+
+                    fn type_identifier(
+                        builder: &mut dust_dds::topic_definition::type_object::TypeObjectBuilder,
+                    ) -> Option<dust_dds::topic_definition::type_object::TypeIdentifier> {
+                        use dust_dds::topic_definition::type_support::{DescribedFieldType as _, UndescribedFieldType as _};
+                        let member_type_identifiers: &[fn(&mut dust_dds::topic_definition::type_object::TypeObjectBuilder) -> Option<dust_dds::topic_definition::type_object::TypeIdentifier>] = &[
+                            #((&&dust_dds::topic_definition::type_support::FieldTypeDescriptor::<#field_types>(std::marker::PhantomData)).type_identifier_fn(),)*
+                        ];
+                        builder.struct_type_identifier(
+                            <Self as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::struct_descriptor()?,
+                            member_type_identifiers,
+                        )
+                    }
+                }
+            });
+        }
+    };
+
+    let representation = match extensibility {
+        Extensibility::Final => format_ident!("Cdr2Le"),
+        Extensibility::Appendable => format_ident!("DCdr2Le"),
+        Extensibility::Mutable => format_ident!("PlCdr2Le"),
+    };
+    let extensibility_kind = match extensibility {
+        Extensibility::Final => format_ident!("Final"),
+        Extensibility::Appendable => format_ident!("Appendable"),
+        Extensibility::Mutable => format_ident!("Mutable"),
+    };
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    // The members of appendable and mutable structures are preceded by a DHEADER and the members
+    // of mutable structures by an EMHEADER and a NEXTINT
+    let descriptors = type_descriptors(&field_types);
+    let (max_serialized_size, alignment) = match extensibility {
+        Extensibility::Final => (
+            max_serialized_size(&descriptors, 0, 0),
+            alignment(&descriptors, 1),
+        ),
+        Extensibility::Appendable => (
+            max_serialized_size(&descriptors, 4, 0),
+            alignment(&descriptors, 4),
+        ),
+        Extensibility::Mutable => (
+            max_serialized_size(&descriptors, 4, 8),
+            alignment(&descriptors, 4),
+        ),
+    };
+
+    let type_name = ident.to_string();
+    let type_parameters: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect();
+    let member_descriptors = members.iter().zip(&field_types).map(|(m, field_type)| {
+        let MemberAttributes {
+            name,
+            id,
+            optional,
+            must_understand,
+            key,
+            ..
+        } = m;
+        // The descriptor is a static which can not depend on the type parameters
+        let delimited_collections = if mentions_any_ident(quote!(#field_type), &type_parameters) {
+            quote! {None}
+        } else {
+            quote! {Some(<#field_type as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::DELIMITED_COLLECTIONS)}
+        };
+        quote! {
+            dust_dds::topic_definition::type_support::MemberDescriptor {
+                name: #name,
+                id: #id,
+                optional: #optional,
+                must_understand: #must_understand,
+                key: #key,
+                delimited_collections: #delimited_collections,
+            }
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics dust_dds::topic_definition::type_support::DdsRepresentation for #ident #type_generics #where_clause {
+            const REPRESENTATION: dust_dds::topic_definition::type_support::Representation
+                = dust_dds::topic_definition::type_support::Representation::#representation;
+
+            fn struct_descriptors() -> Vec<&'static dust_dds::topic_definition::type_support::StructDescriptor> {
+                let mut descriptors = Vec::new();
+                <Self as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::collect_struct_descriptors(&mut descriptors);
+                descriptors
+            }
+
+            fn max_serialized_size() -> Option<usize> {
+                <Self as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::MAX_SERIALIZED_SIZE
+            }
+
+            fn type_information() -> Option<dust_dds::topic_definition::type_object::TypeInformation> {
+                dust_dds::topic_definition::type_object::type_information::<Self>()
+            }
+
+            fn minimal_type_objects() -> Vec<dust_dds::topic_definition::type_object::TypeObject> {
+                dust_dds::topic_definition::type_object::type_objects::<Self>(
+                    dust_dds::topic_definition::type_object::EquivalenceKind::Minimal,
+                )
+            }
+        }
+
+        impl #impl_generics dust_dds::topic_definition::type_support::DdsTypeDescriptor for #ident #type_generics #where_clause {
+            const MAX_SERIALIZED_SIZE: Option<usize> = #max_serialized_size;
+            const ALIGNMENT: usize = #alignment;
+
+            fn collect_struct_descriptors(descriptors: &mut Vec<&'static dust_dds::topic_definition::type_support::StructDescriptor>) {
+                let struct_descriptor = <Self as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::struct_descriptor()
+                    .expect("Struct descriptor is always defined");
+
+                // The name is checked first so that recursive types are only described once
+                if !descriptors.iter().any(|d| d.name == struct_descriptor.name) {
+                    descriptors.push(struct_descriptor);
+                    #(<#field_types as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::collect_struct_descriptors(descriptors);)*
+                }
+            }
+
+            fn struct_descriptor() -> Option<&'static dust_dds::topic_definition::type_support::StructDescriptor> {
+                static STRUCT_DESCRIPTOR: dust_dds::topic_definition::type_support::StructDescriptor =
+                    dust_dds::topic_definition::type_support::StructDescriptor {
+                        name: #type_name,
+                        extensibility: dust_dds::topic_definition::type_support::ExtensibilityKind::#extensibility_kind,
+                        members: &[#(#member_descriptors,)*],
+                    };
+                Some(&STRUCT_DESCRIPTOR)
+            }
+
+            fn type_identifier(
+                builder: &mut dust_dds::topic_definition::type_object::TypeObjectBuilder,
+            ) -> Option<dust_dds::topic_definition::type_object::TypeIdentifier> {
+                let member_type_identifiers: &[fn(&mut dust_dds::topic_definition::type_object::TypeObjectBuilder) -> Option<dust_dds::topic_definition::type_object::TypeIdentifier>] = &[
+                    #(<#field_types as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::type_identifier,)*
+                ];
+                builder.struct_type_identifier(
+                    <Self as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::struct_descriptor()?,
+                    member_type_identifiers,
+                )
+            }
+        }
+    })
+}
+
+// The key of an enum is its discriminator. C-like enums are keyed by their explicit 32-bit
+// discriminant so that they can be used as key fields of other types. Enums with data only have a
+// key when #[key] is given on the enum itself and are then keyed by the index of their variant,
+// which is also the discriminator serialized in front of the active member.
+fn enum_get_key(
+    input: &DeriveInput,
+    enum_data: &DataEnum,
+) -> syn::Result<proc_macro2::TokenStream> {
+    validate_enum_key(input, enum_data)?;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let ident = &input.ident;
+
+    if is_c_like_enum(enum_data) {
+        let variant_idents = enum_data.variants.iter().map(|v| &v.ident);
+        Ok(quote! {
+            impl #impl_generics dust_dds::topic_definition::type_support::DdsGetKey for #ident #type_generics #where_clause {
+                type BorrowedKeyHolder<'a> = i32;
+
+                const MAX_KEY_SIZE: Option<usize> = Some(4);
+                const KEY_ALIGNMENT: usize = 4;
+
+                fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
+                    match self {
+                        #(Self::#variant_idents => Self::#variant_idents as i32,)*
+                    }
+                }
+            }
+        })
+    } else if has_key_attribute(&input.attrs) {
+        let variant_idents = enum_data.variants.iter().map(|v| &v.ident);
+        let variant_indices = 0..enum_data.variants.len() as u32;
+        Ok(quote! {
+            impl #impl_generics dust_dds::topic_definition::type_support::DdsGetKey for #ident #type_generics #where_clause {
+                type BorrowedKeyHolder<'a> = u32;
+
+                const MAX_KEY_SIZE: Option<usize> = Some(4);
+                const KEY_ALIGNMENT: usize = 4;
+
+                fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {
+                    match self {
+                        #(Self::#variant_idents { .. } => #variant_indices,)*
+                    }
+                }
+            }
+        })
+    } else {
+        Ok(quote! {
+            impl #impl_generics dust_dds::topic_definition::type_support::DdsGetKey for #ident #type_generics #where_clause {
+                type BorrowedKeyHolder<'a> = ();
+
+                const MAX_KEY_SIZE: Option<usize> = Some(0);
+
+                fn get_key(&self) -> Self::BorrowedKeyHolder<'_> {}
+            }
+        })
+    }
+}
+
+// Setting the key of an enum with data selects the variant of the discriminator. The members of a
+// newly selected variant are created with their Default implementation.
+fn enum_set_key_fields(input: &DeriveInput, enum_data: &DataEnum) -> proc_macro2::TokenStream {
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let ident = &input.ident;
+
+    if is_c_like_enum(enum_data) {
+        let variant_idents: Vec<_> = enum_data.variants.iter().map(|v| &v.ident).collect();
+        quote! {
+            impl #impl_generics dust_dds::topic_definition::type_support::DdsSetKeyFields for #ident #type_generics #where_clause {
+                type OwningKeyHolder = i32;
+
+                fn set_key_from_holder(&mut self, key_holder: Self::OwningKeyHolder) {
+                    #(if key_holder == Self::#variant_idents as i32 {
+                        *self = Self::#variant_idents;
+                    })*
+                }
+            }
+        }
+    } else if has_key_attribute(&input.attrs) {
+        let variant_indices = 0..enum_data.variants.len() as u32;
+        let default_variants = enum_data.variants.iter().map(|v| {
+            let variant_ident = &v.ident;
+            match &v.fields {
+                Fields::Named(fields) => {
+                    let field_idents = fields.named.iter().map(|f| &f.ident);
+                    quote! {Self::#variant_ident { #(#field_idents: Default::default(),)* }}
+                }
+                Fields::Unnamed(fields) => {
+                    let defaults = fields.unnamed.iter().map(|_| quote! {Default::default()});
+                    quote! {Self::#variant_ident(#(#defaults,)*)}
+                }
+                Fields::Unit => quote! {Self::#variant_ident},
+            }
+        });
+        quote! {
+            impl #impl_generics dust_dds::topic_definition::type_support::DdsSetKeyFields for #ident #type_generics #where_clause {
+                type OwningKeyHolder = u32;
+
+                fn set_key_from_holder(&mut self, key_holder: Self::OwningKeyHolder) {
+                    if dust_dds::topic_definition::type_support::DdsGetKey::get_key(self) != key_holder {
+                        match key_holder {
+                            #(#variant_indices => *self = #default_variants,)*
+                            _ => (),
+                        }
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl #impl_generics dust_dds::topic_definition::type_support::DdsSetKeyFields for #ident #type_generics #where_clause {
+                type OwningKeyHolder = ();
+
+                fn set_key_from_holder(&mut self, key_holder: Self::OwningKeyHolder) {}
+            }
+        }
+    }
+}
+
+fn is_c_like_enum(enum_data: &DataEnum) -> bool {
+    enum_data
+        .variants
+        .iter()
+        .all(|v| matches!(v.fields, Fields::Unit))
+}
+
+fn validate_enum_key(input: &DeriveInput, enum_data: &DataEnum) -> syn::Result<()> {
+    if enum_data.variants.is_empty() {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "enums without variants can not be used as DDS types",
+        ));
+    }
+
+    for variant in enum_data.variants.iter() {
+        if has_key_attribute(&variant.attrs) {
+            return Err(syn::Error::new(
+                variant.span(),
+                "only the discriminator of an enum can be a key, use #[key] on the enum instead",
+            ));
+        }
+        if let Some(field) = variant.fields.iter().find(|&f| field_has_key_attribute(f)) {
+            return Err(syn::Error::new(
+                field.span(),
+                "only the discriminator of an enum can be a key, use #[key] on the enum instead",
+            ));
+        }
+    }
+
+    if is_c_like_enum(enum_data) {
+        for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+            if let Ok(Meta::List(list)) = attr.parse_meta() {
+                for item in list.nested.iter() {
+                    if let NestedMeta::Meta(Meta::Path(path)) = item {
+                        if ["i64", "u64", "i128", "u128", "isize", "usize"]
+                            .iter()
+                            .any(|repr| path.is_ident(repr))
+                        {
+                            return Err(syn::Error::new(
+                                path.span(),
+                                "the discriminant of an enum must fit in 32 bits",
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        for variant in enum_data.variants.iter() {
+            if let Some((_, discriminant)) = &variant.discriminant {
+                let value = match discriminant {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Int(value),
+                        ..
+                    }) => Some(value.base10_parse::<i128>()?),
+                    Expr::Unary(ExprUnary {
+                        op: UnOp::Neg(_),
+                        expr,
+                        ..
+                    }) => match expr.as_ref() {
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Int(value),
+                            ..
+                        }) => Some(-value.base10_parse::<i128>()?),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let Some(value) = value {
+                    if value < i32::MIN as i128 || value > u32::MAX as i128 {
+                        return Err(syn::Error::new(
+                            discriminant.span(),
+                            "the discriminant of an enum must fit in 32 bits",
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Enums are encoded as IDL unions: the discriminator followed by the active member. Since unions
+// are always final only the final extensibility can be given.
+fn enum_dds_representation(
+    input: &DeriveInput,
+    enum_data: &DataEnum,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let (extensibility, serialize_as_discriminant) = enum_attributes(&input.attrs)?;
+
+    for field in enum_data.variants.iter().flat_map(|v| v.fields.iter()) {
+        if let Some(item) = dust_dds_attribute_items(&field.attrs)?.first() {
+            return Err(syn::Error::new(
+                item.span(),
+                "member attributes are not supported in enums",
+            ));
+        }
+    }
+
+    let field_types: Vec<_> = enum_data
+        .variants
+        .iter()
+        .flat_map(|v| v.fields.iter().map(|f| &f.ty))
+        .collect();
+    let generics = bounded_generics(input, &field_types);
+
+    // The members of the active variant follow the 32-bit discriminator
+    let union_max_serialized_size = |descriptors: fn(&[&Type]) -> Vec<proc_macro2::TokenStream>| {
+        let variant_max_serialized_sizes = enum_data.variants.iter().map(|v| {
+            let variant_field_types: Vec<_> = v.fields.iter().map(|f| &f.ty).collect();
+            max_serialized_size(&descriptors(&variant_field_types), 4, 0)
+        });
+        quote! {{
+            let size = Some(4);
+            #(let size = dust_dds::topic_definition::type_support::max_serialized_size(size, #variant_max_serialized_sizes);)*
+            size
+        }}
+    };
+    // The enumerations are serialized as their 32-bit discriminant
+    let is_primitive = is_c_like_enum(enum_data);
+    let c_like_enum_serde = match (serialize_as_discriminant, is_primitive) {
+        (true, true) => c_like_enum_serde(input, enum_data),
+        (true, false) => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "serialize_as_discriminant is only supported by C-like enums",
+            ))
+        }
+        (false, _) => quote! {},
+    };
+
+    match extensibility {
+        None => {
+            let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+            // Like for the structures, the fields of the variants are not required to implement
+            // DdsTypeDescriptor, those which don't leave the union unbounded. The union is sized as
+            // a final union.
+            let max_serialized_size = union_max_serialized_size(field_type_descriptors);
+            let alignment = alignment(&field_type_descriptors(&field_types), 4);
+            Ok(quote! {
+                impl #impl_generics dust_dds::topic_definition::type_support::DdsRepresentation for #ident #type_generics #where_clause {
+                    const REPRESENTATION: dust_dds::topic_definition::type_support::Representation
+                        = dust_dds::topic_definition::type_support::Representation::CdrLe;
+
+                    fn max_serialized_size() -> Option<usize> {
+                        <Self as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::MAX_SERIALIZED_SIZE
+                    }
+                }
+
+                impl #impl_generics dust_dds::topic_definition::type_support::DdsTypeDescriptor for #ident #type_generics #where_clause {
+                    const MAX_SERIALIZED_SIZE: Option<usize> = {
+                        use dust_dds::topic_definition::type_support::UndescribedFieldType as _;
+                        #max_serialized_size
+                    };
+                    const ALIGNMENT: usize = {
+                        use dust_dds::topic_definition::type_support::UndescribedFieldType as _;
+                        #alignment
+                    };
+                    const IS_PRIMITIVE: bool = #is_primitive;
+                }
+
+                #c_like_enum_serde
+            })
+        }
+        Some(Extensibility::Final) => {
+            let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+            let type_name = ident.to_string();
+            let max_serialized_size = union_max_serialized_size(type_descriptors);
+            let alignment = alignment(&type_descriptors(&field_types), 4);
+
+            Ok(quote! {
+                impl #impl_generics dust_dds::topic_definition::type_support::DdsRepresentation for #ident #type_generics #where_clause {
+                    const REPRESENTATION: dust_dds::topic_definition::type_support::Representation
+                        = dust_dds::topic_definition::type_support::Representation::Cdr2Le;
+
+                    fn struct_descriptors() -> Vec<&'static dust_dds::topic_definition::type_support::StructDescriptor> {
+                        let mut descriptors = Vec::new();
+                        <Self as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::collect_struct_descriptors(&mut descriptors);
+                        descriptors
+                    }
+
+                    fn max_serialized_size() -> Option<usize> {
+                        <Self as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::MAX_SERIALIZED_SIZE
+                    }
+                }
+
+                impl #impl_generics dust_dds::topic_definition::type_support::DdsTypeDescriptor for #ident #type_generics #where_clause {
+                    const MAX_SERIALIZED_SIZE: Option<usize> = #max_serialized_size;
+                    const ALIGNMENT: usize = #alignment;
+                    const IS_PRIMITIVE: bool = #is_primitive;
+
+                    fn collect_struct_descriptors(descriptors: &mut Vec<&'static dust_dds::topic_definition::type_support::StructDescriptor>) {
+                        // The union has no members of its own to describe. Its descriptor is only
+                        // collected so that recursive types are described once.
+                        static UNION_DESCRIPTOR: dust_dds::topic_definition::type_support::StructDescriptor =
+                            dust_dds::topic_definition::type_support::StructDescriptor {
+                                name: #type_name,
+                                extensibility: dust_dds::topic_definition::type_support::ExtensibilityKind::Final,
+                                members: &[],
+                            };
+
+                        if !descriptors.iter().any(|d| d.name == UNION_DESCRIPTOR.name) {
+                            descriptors.push(&UNION_DESCRIPTOR);
+                            #(<#field_types as dust_dds::topic_definition::type_support::DdsTypeDescriptor>::collect_struct_descriptors(descriptors);)*
+                        }
+                    }
+                }
+
+                #c_like_enum_serde
+            })
+        }
+        Some(Extensibility::Appendable | Extensibility::Mutable) => Err(syn::Error::new(
+            ident.span(),
+            "enums only support the final extensibility",
+        )),
+    }
+}
+
+// C-like enums are serialized as their 32-bit discriminant, which is also their key, like the IDL
+// enumerations. Since serde serializes the index of the variant instead, the serde traits of the
+// enums with #[dust_dds(serialize_as_discriminant)] are implemented here and must not be derived.
+fn c_like_enum_serde(input: &DeriveInput, enum_data: &DataEnum) -> proc_macro2::TokenStream {
+    let ident = &input.ident;
+    let type_name = ident.to_string();
+    let variant_idents: Vec<_> = enum_data.variants.iter().map(|v| &v.ident).collect();
+    quote! {
+        impl serde::Serialize for #ident {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                match self {
+                    #(Self::#variant_idents => serializer.serialize_i32(Self::#variant_idents as i32),)*
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for #ident {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let discriminant = <i32 as serde::Deserialize>::deserialize(deserializer)?;
+                #(if discriminant == Self::#variant_idents as i32 {
+                    return Ok(Self::#variant_idents);
+                })*
+                Err(serde::de::Error::custom(format!(
+                    "{} is not a discriminant of {}",
+                    discriminant, #type_name
+                )))
+            }
+        }
+    }
+}
+
+enum Extensibility {
+    Final,
+    Appendable,
+    Mutable,
+}
+
+struct MemberAttributes {
+    name: String,
+    id: u32,
+    optional: bool,
+    must_understand: bool,
+    key: bool,
+    has_attributes: bool,
+    span: proc_macro2::Span,
+}
+
+// Returns the items given in the #[dust_dds(...)] attributes
+fn dust_dds_attribute_items(attrs: &[Attribute]) -> syn::Result<Vec<NestedMeta>> {
+    let mut items = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("dust_dds")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => items.extend(list.nested),
+            meta => {
+                return Err(syn::Error::new(
+                    meta.span(),
+                    "expected attribute of the form #[dust_dds(...)]",
+                ))
+            }
+        }
+    }
+    Ok(items)
+}
+
+fn struct_extensibility(attrs: &[Attribute]) -> syn::Result<Option<Extensibility>> {
+    let mut extensibility = None;
+    for item in dust_dds_attribute_items(attrs)? {
+        match &item {
+            NestedMeta::Meta(Meta::NameValue(name_value))
+                if name_value.path.is_ident("extensibility") =>
+            {
+                extensibility = Some(extensibility_kind(&name_value.lit)?);
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    item.span(),
+                    "unknown dust_dds struct attribute",
+                ))
+            }
+        }
+    }
+    Ok(extensibility)
+}
+
+// Besides the extensibility, the enums accept `serialize_as_discriminant` to have the serde traits
+// of a C-like enum implemented with its discriminant
+fn enum_attributes(attrs: &[Attribute]) -> syn::Result<(Option<Extensibility>, bool)> {
+    let mut extensibility = None;
+    let mut serialize_as_discriminant = false;
+    for item in dust_dds_attribute_items(attrs)? {
+        match &item {
+            NestedMeta::Meta(Meta::NameValue(name_value))
+                if name_value.path.is_ident("extensibility") =>
+            {
+                extensibility = Some(extensibility_kind(&name_value.lit)?);
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("serialize_as_discriminant") => {
+                serialize_as_discriminant = true;
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    item.span(),
+                    "unknown dust_dds enum attribute",
+                ))
+            }
+        }
+    }
+    Ok((extensibility, serialize_as_discriminant))
+}
+
+fn extensibility_kind(lit: &Lit) -> syn::Result<Extensibility> {
+    match lit {
+        Lit::Str(kind) if kind.value() == "final" => Ok(Extensibility::Final),
+        Lit::Str(kind) if kind.value() == "appendable" => Ok(Extensibility::Appendable),
+        Lit::Str(kind) if kind.value() == "mutable" => Ok(Extensibility::Mutable),
+        lit => Err(syn::Error::new(
+            lit.span(),
+            "extensibility must be one of \"final\", \"appendable\" or \"mutable\"",
+        )),
+    }
+}
+
+const MEMBER_ID_MAX: u32 = 0x0FFF_FFFF;
+
+fn member_attributes(struct_data: &DataStruct) -> syn::Result<Vec<MemberAttributes>> {
+    let mut members: Vec<MemberAttributes> = Vec::new();
+    // Members without an explicit id take the id following the one of the previous member
+    let mut next_id = 0;
+    for (index, field) in struct_data.fields.iter().enumerate() {
+        let mut member = MemberAttributes {
+            name: field
+                .ident
+                .as_ref()
+                .map_or_else(|| index.to_string(), |ident| ident.to_string()),
+            id: next_id,
+            optional: false,
+            must_understand: field_has_key_attribute(field),
+            key: field_has_key_attribute(field),
+            has_attributes: false,
+            span: field.span(),
+        };
+
+        for item in dust_dds_attribute_items(&field.attrs)? {
+            member.has_attributes = true;
+            match &item {
+                NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("id") => {
+                    member.id = match &name_value.lit {
+                        Lit::Int(id) => id.base10_parse()?,
+                        lit => return Err(syn::Error::new(lit.span(), "id must be an integer")),
+                    };
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("optional") => {
+                    if field.ident.is_none() {
+                        return Err(syn::Error::new(
+                            path.span(),
+                            "optional members are only supported in structs with named fields",
+                        ));
+                    }
+                    if !is_option_type(&field.ty) {
+                        return Err(syn::Error::new(
+                            field.ty.span(),
+                            "optional members must be of type Option<T>",
+                        ));
+                    }
+                    member.optional = true;
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("must_understand") => {
+                    member.must_understand = true;
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        item.span(),
+                        "unknown dust_dds member attribute",
+                    ))
+                }
+            }
+        }
+
+        if member.id > MEMBER_ID_MAX {
+            return Err(syn::Error::new(
+                member.span,
+                "member id must not be greater than 0x0FFFFFFF",
+            ));
+        }
+        if members.iter().any(|m| m.id == member.id) {
+            return Err(syn::Error::new(
+                member.span,
+                format!("duplicate member id {}", member.id),
+            ));
+        }
+        next_id = member.id + 1;
+        members.push(member);
+    }
+    Ok(members)
+}
+
+// Bounds the field types depending on the type parameters with DdsTypeDescriptor
+fn bounded_generics(input: &DeriveInput, field_types: &[&Type]) -> syn::Generics {
+    let type_parameters: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect();
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field_type in field_types {
+        if mentions_any_ident(quote!(#field_type), &type_parameters) {
+            where_clause.predicates.push(parse_quote! {
+                #field_type: dust_dds::topic_definition::type_support::DdsTypeDescriptor
+            });
+        }
+    }
+    generics
+}
+
+// Paths to the descriptors of the given types, which must implement DdsTypeDescriptor
+fn type_descriptors(field_types: &[&Type]) -> Vec<proc_macro2::TokenStream> {
+    field_types
+        .iter()
+        .map(|t| quote! {<#t as dust_dds::topic_definition::type_support::DdsTypeDescriptor>})
+        .collect()
+}
+
+// Paths to the descriptors of the given types, which are unbounded if they don't implement
+// DdsTypeDescriptor
+fn field_type_descriptors(field_types: &[&Type]) -> Vec<proc_macro2::TokenStream> {
+    field_types
+        .iter()
+        .map(|t| quote! {dust_dds::topic_definition::type_support::FieldTypeDescriptor::<#t>})
+        .collect()
+}
+
+// Expression of the maximum serialized size of the members with the given descriptors starting
+// at `initial_size`, each of them preceded by a header of `member_header_size` bytes
+fn max_serialized_size(
+    descriptors: &[proc_macro2::TokenStream],
+    initial_size: usize,
+    member_header_size: usize,
+) -> proc_macro2::TokenStream {
+    let mut max_serialized_size = quote! {let size = Some(#initial_size);};
+    for descriptor in descriptors {
+        if member_header_size > 0 {
+            max_serialized_size.extend(quote! {
+                let size = dust_dds::topic_definition::type_support::append_max_serialized_size(size, Some(#member_header_size), 4);
+            });
+        }
+        max_serialized_size.extend(quote! {
+            let size = dust_dds::topic_definition::type_support::append_max_serialized_size(
+                size,
+                #descriptor::MAX_SERIALIZED_SIZE,
+                #descriptor::ALIGNMENT,
+            );
+        });
+    }
+    quote! {{
+        #max_serialized_size
+        size
+    }}
+}
+
+// Expression of the largest alignment of the members with the given descriptors and of
+// `initial_alignment`
+fn alignment(
+    descriptors: &[proc_macro2::TokenStream],
+    initial_alignment: usize,
+) -> proc_macro2::TokenStream {
+    let mut alignment = quote! {let alignment = #initial_alignment;};
+    for descriptor in descriptors {
+        alignment.extend(quote! {
+            let alignment = if #descriptor::ALIGNMENT > alignment {
+                #descriptor::ALIGNMENT
+            } else {
+                alignment
+            };
+        });
+    }
+    quote! {{
+        #alignment
+        alignment
+    }}
+}
+
+fn mentions_any_ident(tokens: proc_macro2::TokenStream, idents: &[proc_macro2::Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => idents.contains(&ident),
+        proc_macro2::TokenTree::Group(group) => mentions_any_ident(group.stream(), idents),
+        _ => false,
+    })
+}
+
+fn is_option_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => {
+            matches!(type_path.path.segments.last(), Some(segment) if segment.ident == "Option")
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,362 @@
+use dust_dds::topic_definition::{
+    bounded::{BoundedString, BoundedVec},
+    type_support::{
+        dds_deserialize_from_bytes, dds_serialize_to_bytes, DdsRepresentation, DdsTypeDescriptor,
+        ExtensibilityKind, MemberDescriptor, Representation,
+    },
+};
+
+#[derive(DdsRepresentation)]
+struct StructNoExtensibility {
+    _a: i32,
+}
+
+#[test]
+fn struct_no_extensibility() {
+    assert_eq!(StructNoExtensibility::REPRESENTATION, Representation::CdrLe);
+    assert!(StructNoExtensibility::struct_descriptors().is_empty());
+}
+
+#[test]
+fn struct_no_extensibility_is_described_as_final() {
+    assert!(StructNoExtensibility::type_information().is_some());
+    assert_eq!(
+        StructNoExtensibility::minimal_type_objects(),
+        FinalStruct::minimal_type_objects()
+    );
+}
+
+#[derive(DdsRepresentation)]
+#[dust_dds(extensibility = "final")]
+struct FinalStruct {
+    _a: i32,
+}
+
+#[derive(DdsRepresentation)]
+#[dust_dds(extensibility = "appendable")]
+struct AppendableStruct {
+    _a: i32,
+}
+
+#[test]
+fn struct_extensibility() {
+    assert_eq!(FinalStruct::REPRESENTATION, Representation::Cdr2Le);
+    assert_eq!(AppendableStruct::REPRESENTATION, Representation::DCdr2Le);
+    assert_eq!(
+        FinalStruct::struct_descriptors()[0].extensibility,
+        ExtensibilityKind::Final
+    );
+}
+
+#[derive(DdsRepresentation)]
+#[dust_dds(extensibility = "mutable")]
+struct MutableStruct {
+    #[key]
+    _a: i32,
+    #[dust_dds(id = 10)]
+    _b: i32,
+    _c: i32,
+    #[dust_dds(id = 2, optional, must_understand)]
+    _d: Option<i32>,
+    _e: Vec<AppendableStruct>,
+}
+
+#[test]
+fn mutable_struct_members() {
+    assert_eq!(MutableStruct::REPRESENTATION, Representation::PlCdr2Le);
+
+    let descriptors = MutableStruct::struct_descriptors();
+    assert_eq!(descriptors.len(), 2);
+    assert_eq!(descriptors[0].name, "MutableStruct");
+    assert_eq!(descriptors[0].extensibility, ExtensibilityKind::Mutable);
+    assert_eq!(
+        descriptors[0].members,
+        &[
+            MemberDescriptor {
+                name: "_a",
+                id: 0,
+                optional: false,
+                must_understand: true,
+                key: true,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "_b",
+                id: 10,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "_c",
+                id: 11,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "_d",
+                id: 2,
+                optional: true,
+                must_understand: true,
+                key: false,
+                delimited_collections: Some(0),
+            },
+            MemberDescriptor {
+                name: "_e",
+                id: 3,
+                optional: false,
+                must_understand: false,
+                key: false,
+                delimited_collections: Some(1),
+            },
+        ]
+    );
+    assert_eq!(descriptors[1].name, "AppendableStruct");
+}
+
+#[allow(dead_code)]
+#[derive(DdsRepresentation)]
+#[dust_dds(extensibility = "mutable")]
+struct MutableTuple(i32, #[dust_dds(id = 5)] i32);
+
+#[test]
+fn mutable_tuple_members() {
+    let descriptors = MutableTuple::struct_descriptors();
+    assert_eq!(descriptors[0].members[0].name, "0");
+    assert_eq!(descriptors[0].members[1].name, "1");
+    assert_eq!(descriptors[0].members[1].id, 5);
+}
+
+#[derive(DdsRepresentation)]
+#[dust_dds(extensibility = "appendable")]
+struct RecursiveStruct {
+    _children: Vec<RecursiveStruct>,
+}
+
+#[test]
+fn recursive_struct_is_described_once() {
+    assert_eq!(RecursiveStruct::struct_descriptors().len(), 1);
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsRepresentation)]
+#[dust_dds(extensibility = "appendable")]
+struct AppendableElement {
+    a: i32,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsRepresentation)]
+#[dust_dds(extensibility = "final")]
+struct FinalSequences {
+    id: u8,
+    elements: Vec<AppendableElement>,
+    nested: Vec<Vec<AppendableElement>>,
+}
+
+#[test]
+fn empty_sequence_of_non_primitive_elements_has_dheader() {
+    let value = FinalSequences {
+        id: 1,
+        elements: vec![],
+        nested: vec![vec![]],
+    };
+    let data = dds_serialize_to_bytes(&value).unwrap();
+    assert_eq!(
+        &data[4..],
+        &[
+            1, 0, 0, 0, // id | padding (3 bytes)
+            4, 0, 0, 0, // elements DHEADER
+            0, 0, 0, 0, // elements length
+            12, 0, 0, 0, // nested DHEADER
+            1, 0, 0, 0, // nested length
+            4, 0, 0, 0, // nested element DHEADER
+            0, 0, 0, 0, // nested element length
+        ]
+    );
+    assert_eq!(
+        dds_deserialize_from_bytes::<FinalSequences>(&data).unwrap(),
+        value
+    );
+}
+
+#[allow(dead_code)]
+#[derive(DdsRepresentation)]
+enum UnionNoExtensibility {
+    A(i32),
+    B,
+}
+
+#[test]
+fn union_no_extensibility() {
+    assert_eq!(UnionNoExtensibility::REPRESENTATION, Representation::CdrLe);
+    assert!(UnionNoExtensibility::struct_descriptors().is_empty());
+}
+
+#[allow(dead_code)]
+#[derive(DdsRepresentation)]
+#[dust_dds(extensibility = "final")]
+enum FinalUnion {
+    A(AppendableStruct),
+    B { children: Vec<FinalUnion> },
+}
+
+#[test]
+fn final_union_describes_member_types() {
+    assert_eq!(FinalUnion::REPRESENTATION, Representation::Cdr2Le);
+    let descriptors = FinalUnion::struct_descriptors();
+    assert_eq!(descriptors.len(), 2);
+    assert_eq!(descriptors[0].name, "FinalUnion");
+    assert_eq!(descriptors[1].name, "AppendableStruct");
+    assert_eq!(descriptors[1].extensibility, ExtensibilityKind::Appendable);
+}
+
+#[derive(DdsRepresentation)]
+#[dust_dds(extensibility = "final")]
+struct BoundedStruct {
+    _a: u8,
+    _b: BoundedString<4>,
+    _c: BoundedVec<u16, 2>,
+    _d: [f64; 2],
+}
+
+#[derive(DdsRepresentation)]
+#[dust_dds(extensibility = "mutable")]
+struct MutableBoundedStruct {
+    _a: u8,
+    _b: i64,
+}
+
+#[allow(dead_code)]
+#[derive(DdsRepresentation)]
+enum Color {
+    Red,
+    Green,
+}
+
+#[derive(Debug, PartialEq, DdsRepresentation)]
+#[dust_dds(serialize_as_discriminant)]
+enum ColorWithDiscriminants {
+    Red = 1,
+    Blue = 10,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsRepresentation)]
+struct ColorData {
+    color: ColorWithDiscriminants,
+    value: u8,
+}
+
+#[test]
+fn c_like_enum_is_serialized_as_discriminant() {
+    let value = ColorData {
+        color: ColorWithDiscriminants::Blue,
+        value: 5,
+    };
+    let data = dds_serialize_to_bytes(&value).unwrap();
+    assert_eq!(
+        &data[4..],
+        &[
+            10, 0, 0, 0, // color
+            5, // value
+        ]
+    );
+    assert_eq!(
+        dds_deserialize_from_bytes::<ColorData>(&data).unwrap(),
+        value
+    );
+    assert!(
+        dds_deserialize_from_bytes::<ColorData>(&[0, 1, 0, 0, 2, 0, 0, 0, 5, 0, 0, 0]).is_err()
+    );
+}
+
+struct Plain {
+    _a: i32,
+}
+
+// The fields of the structures without the extensibility attribute are not required to implement
+// DdsTypeDescriptor
+#[derive(DdsRepresentation)]
+struct WithMap {
+    #[key]
+    _id: u8,
+    _m: std::collections::HashMap<u8, u8>,
+    _p: Plain,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsRepresentation)]
+enum ColorWithSerde {
+    Red,
+    Green,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize, DdsRepresentation)]
+struct ColorWithSerdeData {
+    color: ColorWithSerde,
+    value: u8,
+}
+
+#[test]
+fn c_like_enum_with_serde_derive_is_serialized_by_serde() {
+    let value = ColorWithSerdeData {
+        color: ColorWithSerde::Green,
+        value: 5,
+    };
+    let data = dds_serialize_to_bytes(&value).unwrap();
+    assert_eq!(
+        &data[4..],
+        &[
+            1, 0, 0, 0, // color
+            5, // value
+        ]
+    );
+    assert_eq!(
+        dds_deserialize_from_bytes::<ColorWithSerdeData>(&data).unwrap(),
+        value
+    );
+}
+
+#[derive(DdsRepresentation)]
+struct StructNoExtensibilityWithPlain {
+    _a: i32,
+    _p: Plain,
+}
+
+#[derive(DdsRepresentation)]
+enum UnionNoExtensibilityWithPlain {
+    _A(i32),
+    _B(Plain),
+}
+
+#[test]
+fn max_serialized_size_of_structs() {
+    assert_eq!(FinalStruct::MAX_SERIALIZED_SIZE, Some(4));
+    assert_eq!(AppendableStruct::MAX_SERIALIZED_SIZE, Some(8));
+    assert_eq!(BoundedStruct::MAX_SERIALIZED_SIZE, Some(48));
+    assert_eq!(MutableBoundedStruct::MAX_SERIALIZED_SIZE, Some(32));
+    assert_eq!(MutableStruct::MAX_SERIALIZED_SIZE, None);
+    assert_eq!(StructNoExtensibility::MAX_SERIALIZED_SIZE, Some(4));
+    assert_eq!(StructNoExtensibilityWithPlain::MAX_SERIALIZED_SIZE, None);
+    assert_eq!(WithMap::MAX_SERIALIZED_SIZE, None);
+    assert_eq!(
+        BoundedStruct::max_serialized_size(),
+        BoundedStruct::MAX_SERIALIZED_SIZE
+    );
+    assert_eq!(
+        StructNoExtensibility::max_serialized_size(),
+        StructNoExtensibility::MAX_SERIALIZED_SIZE
+    );
+    assert_eq!(
+        [BoundedStruct::IS_BOUNDED, RecursiveStruct::IS_BOUNDED],
+        [true, false]
+    );
+}
+
+#[test]
+fn max_serialized_size_of_unions() {
+    assert_eq!(UnionNoExtensibility::MAX_SERIALIZED_SIZE, Some(8));
+    assert_eq!(UnionNoExtensibilityWithPlain::MAX_SERIALIZED_SIZE, None);
+    assert_eq!(Color::MAX_SERIALIZED_SIZE, Some(4));
+    assert_eq!(FinalUnion::MAX_SERIALIZED_SIZE, None);
+}
@@ -1,4 +1,4 @@
-use dust_dds::topic_definition::type_support::DdsHasKey;
+use dust_dds::topic_definition::type_support::{DdsGetKey, DdsHasKey, DdsSetKeyFields};
 
 #[derive(DdsHasKey)]
 struct StructNoKey {
@@ -71,3 +71,59 @@ struct TupleWithKeys(i32, #[key] i32, #[key] bool, char);
 fn tuple_with_keys() {
     assert_eq!(TupleWithKeys::HAS_KEY, true);
 }
+
+#[allow(dead_code)]
+#[derive(DdsHasKey, DdsGetKey, DdsSetKeyFields)]
+enum UnionNoKey {
+    A(i32),
+    B { b: u8 },
+}
+
+#[test]
+fn union_no_key() {
+    assert_eq!(UnionNoKey::HAS_KEY, false);
+}
+
+#[derive(Debug, PartialEq, DdsHasKey, DdsGetKey, DdsSetKeyFields)]
+#[key]
+enum UnionWithKey {
+    A(i32),
+    B { b: u8, c: bool },
+    C,
+}
+
+#[test]
+fn union_with_key() {
+    assert_eq!(UnionWithKey::HAS_KEY, true);
+    assert_eq!(UnionWithKey::A(10).get_key(), 0);
+    assert_eq!(UnionWithKey::B { b: 1, c: true }.get_key(), 1);
+    assert_eq!(UnionWithKey::C.get_key(), 2);
+}
+
+#[test]
+fn union_set_key_selects_variant() {
+    let mut data = UnionWithKey::A(10);
+    data.set_key_from_holder(0);
+    assert_eq!(data, UnionWithKey::A(10));
+    data.set_key_from_holder(1);
+    assert_eq!(data, UnionWithKey::B { b: 0, c: false });
+}
+
+#[derive(Debug, PartialEq, DdsHasKey, DdsGetKey, DdsSetKeyFields)]
+enum EnumWithDiscriminants {
+    A = -1,
+    B = 7,
+    C = 0x7FFF_FFFF,
+}
+
+#[test]
+fn enum_key_is_discriminant() {
+    assert_eq!(EnumWithDiscriminants::HAS_KEY, false);
+    assert_eq!(EnumWithDiscriminants::A.get_key(), -1);
+    assert_eq!(EnumWithDiscriminants::B.get_key(), 7);
+    assert_eq!(EnumWithDiscriminants::C.get_key(), i32::MAX);
+
+    let mut data = EnumWithDiscriminants::A;
+    data.set_key_from_holder(7);
+    assert_eq!(data, EnumWithDiscriminants::B);
+}
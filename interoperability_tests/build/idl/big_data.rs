@@ -0,0 +1,4 @@
+#[derive(Debug, serde::Deserialize, serde::Serialize, dust_dds::topic_definition::type_support::DdsType)]
+pub struct BigDataType {
+    pub msg: Vec<u8>,
+}
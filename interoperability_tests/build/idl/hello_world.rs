@@ -0,0 +1,5 @@
+#[derive(Debug, serde::Deserialize, serde::Serialize, dust_dds::topic_definition::type_support::DdsType)]
+pub struct HelloWorldType {
+    pub id: u8,
+    pub msg: char,
+}